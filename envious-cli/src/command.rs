@@ -1,108 +1,1076 @@
-use std::{error::Error, path::PathBuf, time::Instant};
+use std::{
+    collections::HashMap,
+    env,
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{self, Receiver},
+    },
+    thread,
+    time::{Duration, Instant},
+};
 
 use clap::ArgMatches;
-use envyc::{error::reporter::ErrorReporter, interner::Interner};
+use envyc::{
+    check_target,
+    database::{cache::BuildCache, Database},
+    environment::Environment,
+    error::{
+        explanations::explain,
+        fix::{apply_fixes, Applicability},
+        reporter::{ErrorFormat, ErrorReporter, Reporter},
+        warning::{Lint, LintLevel, LintLevels},
+    },
+    filter_tokens, format_source,
+    formatter::FormatConfig,
+    function_table::FunctionTable,
+    interner::Interner,
+    lex, lint, parse_with_recovery, type_check, Emit, OptLevel, TargetOptions,
+};
+use notify::{watcher, DebouncedEvent, RecursiveMode, Watcher};
 
 use crate::{
     compile_code,
-    utils::{clean_file, error, get_source, get_stem, path_to_str, replace_last},
+    manifest::{create_project, Project, DEFAULT_PROFILE, MANIFEST_FILE},
+    utils::{clean_files, error, get_source, get_stem, path_to_str, replace_last},
 };
 
 #[derive(Debug)]
 pub enum Command {
     Tui,
-    Compile { files: Vec<PathBuf> },
-    Build { files: Vec<PathBuf> },
-    Run { files: Vec<PathBuf> },
+    Compile {
+        files: Option<Vec<PathBuf>>,
+        profile: Option<String>,
+        error_format: ErrorFormat,
+        lint_arguments: Vec<LintArgument>,
+        jobs: usize,
+        artifacts: Artifacts,
+        opt_level: Option<OptLevel>,
+        debug_info: bool,
+        target: TargetOptions,
+    },
+    Build {
+        files: Option<Vec<PathBuf>>,
+        profile: Option<String>,
+        library: Option<LibraryKind>,
+        error_format: ErrorFormat,
+        lint_arguments: Vec<LintArgument>,
+        jobs: usize,
+        opt_level: Option<OptLevel>,
+        debug_info: bool,
+        lto: bool,
+    },
+    Run {
+        files: Option<Vec<PathBuf>>,
+        profile: Option<String>,
+        error_format: ErrorFormat,
+        lint_arguments: Vec<LintArgument>,
+        jobs: usize,
+        opt_level: Option<OptLevel>,
+        debug_info: bool,
+        lto: bool,
+    },
+    Check {
+        files: Option<Vec<PathBuf>>,
+        profile: Option<String>,
+        error_format: ErrorFormat,
+        lint_arguments: Vec<LintArgument>,
+        watch: bool,
+    },
+    New {
+        name: String,
+    },
+    Explain {
+        code: String,
+    },
+    Fix {
+        files: Vec<PathBuf>,
+    },
+    Fmt {
+        files: Vec<PathBuf>,
+        check: bool,
+        config: FormatConfig,
+    },
     Unknown,
 }
 
+/// A `-A`, `-W` or `-D` argument, which changes the level of a lint, or of
+/// every lint if the lint is `warnings`.
+pub type LintArgument = (LintLevel, Option<Lint>);
+
+/// The kinds of libraries that can be built from Envious files.
+#[derive(Debug, Clone, Copy)]
+pub enum LibraryKind {
+    Static,
+    Shared,
+}
+
 impl From<ArgMatches<'_>> for Command {
     fn from(matches: ArgMatches) -> Self {
         let start_tui = matches.is_present("tui");
         if start_tui {
             Self::Tui
         } else if let Some(compile_matches) = matches.subcommand_matches("compile") {
-            let files = compile_matches.values_of("files").unwrap();
-            let mut file_paths = vec![];
-            for file in files {
-                file_paths.push(PathBuf::from(file));
+            Self::Compile {
+                files: get_files(compile_matches),
+                profile: get_profile(compile_matches),
+                error_format: get_error_format(compile_matches),
+                lint_arguments: get_lint_arguments(compile_matches),
+                jobs: get_jobs(compile_matches),
+                artifacts: get_artifacts(compile_matches),
+                opt_level: get_opt_level(compile_matches),
+                debug_info: compile_matches.is_present("debug-info"),
+                target: get_target(compile_matches),
             }
-
-            Self::Compile { files: file_paths }
         } else if let Some(compile_matches) = matches.subcommand_matches("build") {
-            let files = compile_matches.values_of("files").unwrap();
-            let mut file_paths = vec![];
-            for file in files {
-                file_paths.push(PathBuf::from(file));
-            }
+            let library = match compile_matches.value_of("lib") {
+                Some("static") => Some(LibraryKind::Static),
+                Some("shared") => Some(LibraryKind::Shared),
+                _ => None,
+            };
 
-            Self::Build { files: file_paths }
+            Self::Build {
+                files: get_files(compile_matches),
+                profile: get_profile(compile_matches),
+                library,
+                error_format: get_error_format(compile_matches),
+                lint_arguments: get_lint_arguments(compile_matches),
+                jobs: get_jobs(compile_matches),
+                opt_level: get_opt_level(compile_matches),
+                debug_info: compile_matches.is_present("debug-info"),
+                lto: compile_matches.is_present("lto"),
+            }
         } else if let Some(compile_matches) = matches.subcommand_matches("run") {
-            let files = compile_matches.values_of("files").unwrap();
-            let mut file_paths = vec![];
-            for file in files {
-                file_paths.push(PathBuf::from(file));
+            Self::Run {
+                files: get_files(compile_matches),
+                profile: get_profile(compile_matches),
+                error_format: get_error_format(compile_matches),
+                lint_arguments: get_lint_arguments(compile_matches),
+                jobs: get_jobs(compile_matches),
+                opt_level: get_opt_level(compile_matches),
+                debug_info: compile_matches.is_present("debug-info"),
+                lto: compile_matches.is_present("lto"),
+            }
+        } else if let Some(check_matches) = matches.subcommand_matches("check") {
+            Self::Check {
+                files: get_files(check_matches),
+                profile: get_profile(check_matches),
+                error_format: get_error_format(check_matches),
+                lint_arguments: get_lint_arguments(check_matches),
+                watch: check_matches.is_present("watch"),
+            }
+        } else if let Some(new_matches) = matches.subcommand_matches("new") {
+            Self::New {
+                name: new_matches.value_of("name").unwrap().to_string(),
+            }
+        } else if let Some(explain_matches) = matches.subcommand_matches("explain") {
+            Self::Explain {
+                code: explain_matches.value_of("code").unwrap().to_uppercase(),
+            }
+        } else if let Some(fix_matches) = matches.subcommand_matches("fix") {
+            let files = fix_matches.values_of("files").unwrap();
+            Self::Fix {
+                files: files.map(PathBuf::from).collect(),
+            }
+        } else if let Some(fmt_matches) = matches.subcommand_matches("fmt") {
+            let files = fmt_matches.values_of("files").unwrap();
+            let mut config = FormatConfig::default();
+            if let Some(indent_width) = fmt_matches.value_of("indent-width") {
+                // The value was already validated by clap.
+                config.indent_width = indent_width.parse().unwrap();
             }
 
-            Self::Run { files: file_paths }
+            Self::Fmt {
+                files: files.map(PathBuf::from).collect(),
+                check: fmt_matches.is_present("check"),
+                config,
+            }
         } else {
             Self::Unknown
         }
     }
 }
 
+/// Reads the files to compile from the arguments of a subcommand. Without
+/// `--files`, the files are found through the manifest of the package.
+///
+/// # Arguments
+/// * `matches` - The matches of the subcommand.
+fn get_files(matches: &ArgMatches) -> Option<Vec<PathBuf>> {
+    matches
+        .values_of("files")
+        .map(|files| files.map(PathBuf::from).collect())
+}
+
+/// Reads the name of the profile from the arguments of a subcommand.
+///
+/// # Arguments
+/// * `matches` - The matches of the subcommand.
+fn get_profile(matches: &ArgMatches) -> Option<String> {
+    matches.value_of("profile").map(str::to_string)
+}
+
+/// Reads the format of the errors from the arguments of a subcommand.
+///
+/// # Arguments
+/// * `matches` - The matches of the subcommand.
+fn get_error_format(matches: &ArgMatches) -> ErrorFormat {
+    match matches.value_of("error-format") {
+        Some("json") => ErrorFormat::Json,
+        Some("sarif") => ErrorFormat::Sarif,
+        _ => ErrorFormat::Human,
+    }
+}
+
+/// Reads the number of files compiled at the same time from the arguments of a
+/// subcommand. Without `-j`, as many files are compiled as there are CPUs.
+///
+/// # Arguments
+/// * `matches` - The matches of the subcommand.
+fn get_jobs(matches: &ArgMatches) -> usize {
+    match matches.value_of("jobs") {
+        // The value was already validated by clap.
+        Some(jobs) => jobs.parse().unwrap(),
+        None => thread::available_parallelism().map_or(1, |jobs| jobs.get()),
+    }
+}
+
+/// Reads the optimization level from the arguments of a subcommand.
+///
+/// # Arguments
+/// * `matches` - The matches of the subcommand.
+fn get_opt_level(matches: &ArgMatches) -> Option<OptLevel> {
+    // The value was already validated by clap.
+    matches
+        .value_of("opt-level")
+        .map(|name| OptLevel::from_name(name).unwrap())
+}
+
+/// Reads the machine the code is generated for from the arguments of a
+/// subcommand. Without `--target`, the code is generated for the host.
+///
+/// # Arguments
+/// * `matches` - The matches of the subcommand.
+fn get_target(matches: &ArgMatches) -> TargetOptions {
+    let host = TargetOptions::default();
+    TargetOptions {
+        triple: matches.value_of("target").map(str::to_string),
+        cpu: matches
+            .value_of("target-cpu")
+            .map_or(host.cpu, str::to_string),
+        features: matches
+            .value_of("target-features")
+            .map_or(host.features, str::to_string),
+    }
+}
+
+/// Reads the artifacts to write and the directory they are written to from the
+/// arguments of a subcommand. Without `--emit`, only the object files are written.
+///
+/// # Arguments
+/// * `matches` - The matches of the subcommand.
+fn get_artifacts(matches: &ArgMatches) -> Artifacts {
+    let mut artifacts = Artifacts::default();
+    if let Some(emit) = matches.values_of("emit") {
+        // The values were already validated by clap.
+        artifacts.emit = emit.map(|name| Emit::from_name(name).unwrap()).collect();
+        artifacts
+            .emit
+            .sort_by_key(|emit| Emit::ALL.iter().position(|other| other == emit));
+        artifacts.emit.dedup();
+    }
+
+    artifacts.out_dir = matches.value_of("out-dir").map(PathBuf::from);
+    artifacts
+}
+
+/// Reads the `-A`, `-W` and `-D` arguments of a subcommand in the order they were given.
+///
+/// # Arguments
+/// * `matches` - The matches of the subcommand.
+fn get_lint_arguments(matches: &ArgMatches) -> Vec<LintArgument> {
+    let mut arguments = vec![];
+    for (name, level) in &[
+        ("allow", LintLevel::Allow),
+        ("warn", LintLevel::Warn),
+        ("deny", LintLevel::Deny),
+    ] {
+        if let (Some(indices), Some(values)) = (matches.indices_of(name), matches.values_of(name)) {
+            arguments.extend(
+                indices
+                    .zip(values)
+                    .map(|(index, lint)| (index, *level, Lint::from_name(lint))),
+            );
+        }
+    }
+
+    arguments.sort_by_key(|(index, _, _)| *index);
+    arguments
+        .into_iter()
+        .map(|(_, level, lint)| (level, lint))
+        .collect()
+}
+
+/// Struct that describes the files of a compilation, the levels of the lints
+/// they are compiled with and how they are optimized.
+pub struct Sources {
+    pub files: Vec<PathBuf>,
+    pub lint_levels: LintLevels,
+    pub codegen: Codegen,
+    // Whether the files are optimized together when they are built.
+    pub lto: bool,
+    // The package whose manifest listed the files, if they were not given on the command line.
+    pub project: Option<Project>,
+}
+
+impl Sources {
+    /// Finds the files of a compilation. The files given on the command line are
+    /// compiled on their own, and otherwise the package that contains the current
+    /// directory is compiled with one of its profiles. The arguments override the
+    /// settings of the profile, and later lint arguments override earlier ones.
+    ///
+    /// # Arguments
+    /// * `files` - The files and directories given on the command line, if any.
+    /// * `profile` - The name of the profile of the package.
+    /// * `lint_arguments` - The `-A`, `-W` and `-D` arguments.
+    /// * `opt_level` - The `-O` argument, if any.
+    /// * `debug_info` - Whether `-g` was given.
+    /// * `lto` - Whether `--lto` was given.
+    pub fn find(
+        files: Option<Vec<PathBuf>>,
+        profile: Option<&str>,
+        lint_arguments: &[LintArgument],
+        opt_level: Option<OptLevel>,
+        debug_info: bool,
+        lto: bool,
+    ) -> Result<Self, Box<dyn Error>> {
+        let (files, mut lint_levels, profile_opt_level, profile_debug, profile_lto, project) =
+            match files {
+                Some(files) => (
+                    clean_files(files)?,
+                    LintLevels::default(),
+                    OptLevel::default(),
+                    false,
+                    false,
+                    None,
+                ),
+                None => {
+                    let project = Project::find(&env::current_dir()?)?;
+                    let profile = profile.unwrap_or(DEFAULT_PROFILE);
+                    let lint_levels = project.lint_levels(profile)?;
+                    (
+                        project.sources()?,
+                        lint_levels,
+                        project.opt_level(profile),
+                        project.debug(profile),
+                        project.lto(profile),
+                        Some(project),
+                    )
+                }
+            };
+
+        for (level, lint) in lint_arguments {
+            match lint {
+                Some(lint) => lint_levels.set(*lint, *level),
+                None => lint_levels.set_all(*level),
+            }
+        }
+
+        Ok(Self {
+            files,
+            lint_levels,
+            codegen: Codegen {
+                opt_level: opt_level.unwrap_or(profile_opt_level),
+                target: TargetOptions::default(),
+                debug_info: debug_info || profile_debug,
+            },
+            lto: lto || profile_lto,
+            project,
+        })
+    }
+
+    /// Returns the artifacts that are written for every file before the files
+    /// are linked. With link time optimization, the files are compiled into
+    /// bitcode, which is optimized together once every file is compiled.
+    pub fn artifacts(&self) -> Artifacts {
+        if self.lto {
+            Artifacts {
+                emit: vec![Emit::LlvmBc],
+                out_dir: None,
+            }
+        } else {
+            Artifacts::default()
+        }
+    }
+
+    /// Returns the directory and the name of the executable or library that is
+    /// built. A package is built into its root and named after the package, and
+    /// otherwise the output is placed next to a file and named after it.
+    ///
+    /// # Arguments
+    /// * `file` - The file that defines `main`, or the first file of a library.
+    pub fn output(&self, file: &Path) -> Result<(PathBuf, String), Box<dyn Error>> {
+        match &self.project {
+            Some(project) => Ok((project.root.clone(), project.manifest.package.name.clone())),
+            None => Ok((replace_last(file, "")?, get_stem(file)?.to_string())),
+        }
+    }
+
+    /// Returns the path of the executable that is built from the file that
+    /// defines `main`, which must be the entry file of a package.
+    ///
+    /// # Arguments
+    /// * `main_file` - The file that defines `main`, if any.
+    pub fn executable(&self, main_file: Option<&PathBuf>) -> Result<PathBuf, Box<dyn Error>> {
+        let main_file = main_file.ok_or_else(|| error("No main method could be found."))?;
+        if let Some(project) = &self.project {
+            let entry = project.entry()?;
+            if *main_file != entry {
+                return Err(error(format!(
+                    "The main method must be defined in the entry file `{}`, but it was found in `{}`.",
+                    entry.display(),
+                    main_file.display()
+                )));
+            }
+        }
+
+        let (directory, name) = self.output(main_file)?;
+        Ok(directory.join(name))
+    }
+}
+
+/// Struct that describes how the machine code of the files is generated.
+#[derive(Debug, Clone, Default, Hash)]
+pub struct Codegen {
+    pub opt_level: OptLevel,
+    pub target: TargetOptions,
+    // Whether the files are compiled with debug information.
+    pub debug_info: bool,
+}
+
+/// Struct that describes the artifacts that are written for every compiled file.
+#[derive(Debug, Clone)]
+pub struct Artifacts {
+    pub emit: Vec<Emit>,
+    // The directory the artifacts are written to, instead of the
+    // directory that contains the file they are compiled from.
+    pub out_dir: Option<PathBuf>,
+}
+
+impl Default for Artifacts {
+    fn default() -> Self {
+        Self {
+            emit: vec![Emit::Obj],
+            out_dir: None,
+        }
+    }
+}
+
+impl Artifacts {
+    /// Returns the path an artifact of a file is written to, which is named
+    /// after the file and has the extension of the artifact.
+    ///
+    /// # Arguments
+    /// * `file` - The file the artifact is compiled from.
+    /// * `emit` - The artifact.
+    pub fn path(&self, file: &Path, emit: Emit) -> Result<PathBuf, Box<dyn Error>> {
+        let name = format!("{}.{}", get_stem(file)?, emit.extension());
+        match &self.out_dir {
+            Some(out_dir) => Ok(out_dir.join(name)),
+            None => replace_last(file, name),
+        }
+    }
+}
+
+/// A file that is compiled by one of the threads of `compile_command`.
+pub struct Job<'a> {
+    pub file_path: &'a str,
+    pub file_stem: &'a str,
+    pub source: &'a str,
+    // The build of a file is cached in the directory that contains it.
+    pub cache: BuildCache,
+}
+
+/// Compiles the files on up to `jobs` threads at the same time. Every file is
+/// compiled with its own `Database` and reported through its own buffered
+/// `ErrorReporter`, whose output is written in the order of the files once the
+/// threads are done. As in a serial build, nothing is reported after the first
+/// file that failed to compile, so the output does not depend on the number of threads.
+///
+/// # Arguments
+/// * `files` - The canonical paths of the files to compile.
+/// * `library` - The kind of library the files are compiled into, if any.
+/// * `error_format` - The format of the reported errors.
+/// * `lint_levels` - The levels of the lints.
+/// * `jobs` - The number of files compiled at the same time.
+/// * `artifacts` - The artifacts written for every file.
+/// * `codegen` - How the machine code of the files is generated.
 pub fn compile_command(
     files: Vec<PathBuf>,
+    library: Option<LibraryKind>,
+    error_format: ErrorFormat,
+    lint_levels: &LintLevels,
+    jobs: usize,
+    artifacts: &Artifacts,
+    codegen: &Codegen,
 ) -> Result<(Vec<PathBuf>, Option<PathBuf>), Box<dyn Error>> {
-    let mut error_reporter = ErrorReporter::new(vec![]);
-    let mut interner = Interner::default();
-    let mut clean_files = vec![];
+    let error_reporter = ErrorReporter::new(vec![]).with_format(error_format);
+    if artifacts.emit.iter().any(Emit::is_llvm)
+        && check_target(&codegen.target)
+            .report(&error_reporter, true)
+            .is_none()
+    {
+        if error_format == ErrorFormat::Sarif {
+            println!("{}", error_reporter.sarif_log());
+        }
+
+        return Err(error("Could not compile the files for the target."));
+    }
+
     let mut sources = vec![];
-    for file in files {
-        let file = clean_file(file)?;
-        let source = get_source(&file)?;
-        clean_files.push(file);
-        sources.push(source);
+    for file in &files {
+        sources.push(get_source(file)?);
     }
 
-    let mut main_file = None;
-    for (file, source) in clean_files.iter().zip(sources.iter()) {
-        error_reporter.add(path_to_str(file)?, source);
-        let file_stem = get_stem(file)?;
-        let file_path = path_to_str(file)?;
-        let output_file = replace_last(file, format!("{}.o", file_stem))?;
-        let output_file_path = path_to_str(&output_file)?;
-        let bytes = source.as_bytes();
-        let compilation_start = Instant::now();
-        let result = compile_code(
-            &error_reporter,
-            &mut interner,
-            file_stem,
-            file_path,
-            output_file_path,
-            bytes,
-        );
+    if let Some(out_dir) = &artifacts.out_dir {
+        // The artifacts are named after their files, so two files with
+        // the same name would overwrite each other's artifacts.
+        let mut stems = HashMap::new();
+        for file in &files {
+            if let Some(other) = stems.insert(get_stem(file)?, file) {
+                return Err(error(format!(
+                    "The files `{}` and `{}` have the same name, so their artifacts cannot both be written to `{}`.",
+                    other.display(),
+                    file.display(),
+                    out_dir.display()
+                )));
+            }
+        }
+
+        fs::create_dir_all(out_dir)?;
+    }
+
+    let mut queue = vec![];
+    for (file, source) in files.iter().zip(sources.iter()) {
+        queue.push(Job {
+            file_path: path_to_str(file)?,
+            file_stem: get_stem(file)?,
+            source,
+            cache: BuildCache::new(&replace_last(file, "")?),
+        });
+    }
+
+    let next_job = AtomicUsize::new(0);
+    let first_failure = AtomicUsize::new(usize::MAX);
+    let mut compilations = thread::scope(|scope| {
+        let workers = (0..jobs.min(queue.len()))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut compilations = vec![];
+                    loop {
+                        // The files after a file that failed to compile are not
+                        // compiled, since their output would not be reported.
+                        let index = next_job.fetch_add(1, Ordering::SeqCst);
+                        if index >= queue.len() || index > first_failure.load(Ordering::SeqCst) {
+                            break;
+                        }
+
+                        let job = &queue[index];
+                        let error_reporter = ErrorReporter::new(vec![(job.file_path, job.source)])
+                            .with_format(error_format)
+                            .buffered();
+                        let mut database = Database::default();
+                        database.set_source(job.file_path, job.source);
+                        let compilation_start = Instant::now();
+                        let result = compile_code(
+                            &error_reporter,
+                            &mut database,
+                            job,
+                            artifacts,
+                            codegen,
+                            library,
+                            lint_levels,
+                        );
+
+                        if result.is_none() {
+                            first_failure.fetch_min(index, Ordering::SeqCst);
+                        }
+
+                        let elapsed = compilation_start.elapsed().as_secs_f64();
+                        compilations.push((index, error_reporter, result, elapsed));
+                    }
+
+                    compilations
+                })
+            })
+            .collect::<Vec<_>>();
 
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().unwrap())
+            .collect::<Vec<_>>()
+    });
+    compilations.sort_by_key(|(index, _, _, _)| *index);
+
+    let mut main_file = None;
+    let mut multiple_mains = false;
+    for (index, file_reporter, result, elapsed) in compilations {
+        error_reporter.append(file_reporter)?;
+        let file_path = queue[index].file_path;
         if let Some(found_main) = result {
             match main_file {
-                Some(_) if found_main => return Err(error("Found multiple main methods.")),
-                None if found_main => main_file = Some(file.clone()),
+                Some(_) if found_main => {
+                    multiple_mains = true;
+                    break;
+                }
+                None if found_main => main_file = Some(files[index].clone()),
                 _ => {}
             }
         }
 
+        // The progress is only printed for people, so that the
+        // output of the other formats can be parsed by tools.
+        let human = error_format == ErrorFormat::Human;
         if result.is_none() {
-            println!("Failed to compile file `{}`.", file_path);
-            return Ok((clean_files, main_file));
-        } else {
+            if human {
+                println!("Failed to compile file `{}`.", file_path);
+                println!("For more information about an error, try `envious explain <code>`.");
+            }
+
+            break;
+        } else if human {
             println!(
                 "Finished full compilation process for file `{}` after {} seconds.",
+                file_path, elapsed
+            );
+        }
+    }
+
+    // The log is printed before any error is returned, so that tools
+    // always receive the errors that were reported.
+    if error_format == ErrorFormat::Sarif {
+        println!("{}", error_reporter.sarif_log());
+    }
+
+    if multiple_mains {
+        return Err(error("Found multiple main methods."));
+    }
+
+    Ok((files, main_file))
+}
+
+/// The time that the watcher of `envious check --watch` waits for more changes
+/// before checking again, so that saving several files only checks them once.
+const WATCH_DELAY: Duration = Duration::from_millis(200);
+
+/// Checks the files for errors and warnings without generating any code, and
+/// prints how many were found. When watching, the files are checked again
+/// whenever an `.envy` file or the manifest changes, until the process is stopped.
+///
+/// # Arguments
+/// * `files` - The files and directories given on the command line, if any.
+/// * `profile` - The name of the profile of the package.
+/// * `error_format` - The format of the reported errors.
+/// * `lint_arguments` - The `-A`, `-W` and `-D` arguments.
+/// * `watch` - Whether to check the files again after every change.
+pub fn check_command(
+    files: Option<Vec<PathBuf>>,
+    profile: Option<&str>,
+    error_format: ErrorFormat,
+    lint_arguments: &[LintArgument],
+    watch: bool,
+) -> Result<(), Box<dyn Error>> {
+    let sources = Sources::find(files.clone(), profile, lint_arguments, None, false, false)?;
+    // The database and the sources it borrows are kept between the checks, so
+    // that only the files and functions that changed are checked again.
+    let mut database = Database::default();
+    let mut loaded = HashMap::new();
+    if !watch {
+        return check_files(
+            &mut database,
+            &mut loaded,
+            &sources.files,
+            error_format,
+            &sources.lint_levels,
+        );
+    }
+
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = watcher(sender, WATCH_DELAY)?;
+    match (&sources.project, &files) {
+        (Some(project), _) => watcher.watch(&project.root, RecursiveMode::Recursive)?,
+        (None, Some(files)) => {
+            // The directory of a file is watched instead of the file itself,
+            // since many editors save a file by replacing it.
+            for file in files {
+                let file = file.canonicalize()?;
+                if file.is_dir() {
+                    watcher.watch(&file, RecursiveMode::Recursive)?;
+                } else {
+                    watcher.watch(replace_last(&file, "")?, RecursiveMode::NonRecursive)?;
+                }
+            }
+        }
+        (None, None) => unreachable!("The files are either given or found through a manifest."),
+    }
+
+    let mut sources = Ok(sources);
+    loop {
+        // Errors are printed instead of returned, so that they can be fixed
+        // while the watcher keeps running.
+        let checked = sources.and_then(|sources| {
+            check_files(
+                &mut database,
+                &mut loaded,
+                &sources.files,
+                error_format,
+                &sources.lint_levels,
+            )
+        });
+        if let Err(error) = checked {
+            println!("{}", error);
+        }
+
+        if error_format == ErrorFormat::Human {
+            println!("Watching for changes...");
+        }
+
+        wait_for_change(&receiver)?;
+        sources = Sources::find(files.clone(), profile, lint_arguments, None, false, false);
+    }
+}
+
+/// Blocks until an `.envy` file or a manifest changes, and skips the other
+/// changes that were made at the same time.
+///
+/// # Arguments
+/// * `receiver` - The receiver of the events of the watcher.
+fn wait_for_change(receiver: &Receiver<DebouncedEvent>) -> Result<(), Box<dyn Error>> {
+    let is_watched = |path: &Path| {
+        path.extension()
+            .is_some_and(|extension| extension == "envy")
+            || path.file_name().is_some_and(|name| name == MANIFEST_FILE)
+    };
+
+    loop {
+        let changed = match receiver.recv()? {
+            DebouncedEvent::Create(path)
+            | DebouncedEvent::Write(path)
+            | DebouncedEvent::Remove(path) => is_watched(&path),
+            DebouncedEvent::Rename(from, to) => is_watched(&from) || is_watched(&to),
+            DebouncedEvent::Rescan => true,
+            DebouncedEvent::Error(error, _) => return Err(Box::new(error)),
+            _ => false,
+        };
+
+        if changed {
+            while receiver.try_recv().is_ok() {}
+            return Ok(());
+        }
+    }
+}
+
+/// Checks every file and prints a summary of the errors and warnings. All the
+/// files are checked, even after a file with errors, and an error is returned
+/// if any file contains an error.
+///
+/// # Arguments
+/// * `database` - The `Database` that is kept between the checks.
+/// * `loaded` - The path and the source of every file that was read before.
+/// * `files` - The canonical paths of the files to check.
+/// * `error_format` - The format of the reported errors.
+/// * `lint_levels` - The levels of the lints.
+fn check_files(
+    database: &mut Database<'static>,
+    loaded: &mut HashMap<PathBuf, (&'static str, &'static str)>,
+    files: &[PathBuf],
+    error_format: ErrorFormat,
+    lint_levels: &LintLevels,
+) -> Result<(), Box<dyn Error>> {
+    let start = Instant::now();
+    let mut file_sources = vec![];
+    for file in files {
+        file_sources.push(load_source(loaded, file)?);
+    }
+
+    let error_reporter = ErrorReporter::new(file_sources.to_vec()).with_format(error_format);
+    let (mut errors, mut warnings) = (0, 0);
+    for (file_path, source) in file_sources {
+        database.set_source(file_path, source);
+        let (file_errors, file_warnings) =
+            check_file(&error_reporter, database, file_path, lint_levels);
+        errors += file_errors;
+        warnings += file_warnings;
+    }
+
+    if error_format == ErrorFormat::Sarif {
+        println!("{}", error_reporter.sarif_log());
+    } else if error_format == ErrorFormat::Human {
+        println!(
+            "Checked {} file{} after {} seconds with {} error{} and {} warning{}.",
+            files.len(),
+            plural(files.len()),
+            start.elapsed().as_secs_f64(),
+            errors,
+            plural(errors),
+            warnings,
+            plural(warnings)
+        );
+    }
+
+    if errors > 0 {
+        return Err(error(format!(
+            "Could not check the files because of {} error{}.",
+            errors,
+            plural(errors)
+        )));
+    }
+
+    Ok(())
+}
+
+/// Reads a file and returns its path and its source. The source is only read
+/// into a new string if it changed since the file was last read, and the strings
+/// are never freed, since the functions that the `Database` reuses borrow from
+/// the source they were checked in.
+///
+/// # Arguments
+/// * `loaded` - The path and the source of every file that was read before.
+/// * `file` - The canonical path of the file.
+fn load_source(
+    loaded: &mut HashMap<PathBuf, (&'static str, &'static str)>,
+    file: &Path,
+) -> Result<(&'static str, &'static str), Box<dyn Error>> {
+    let source = get_source(file)?;
+    match loaded.get_mut(file) {
+        Some((_, loaded_source)) if *loaded_source == source => {}
+        Some((_, loaded_source)) => *loaded_source = Box::leak(source.into_boxed_str()),
+        None => {
+            let file_path = Box::leak(path_to_str(file)?.to_string().into_boxed_str());
+            let source = Box::leak(source.into_boxed_str());
+            loaded.insert(file.to_path_buf(), (file_path, source));
+        }
+    }
+
+    Ok(loaded[file])
+}
+
+/// Reports the errors and warnings of a file and returns how many of each
+/// were found. The file is lexed, parsed, checked and linted, but no code is
+/// generated, so LLVM is never initialized. Denied warnings count as errors.
+///
+/// # Arguments
+/// * `error_reporter` - The `ErrorReporter` to report to.
+/// * `database` - The `Database` that contains the source of the file.
+/// * `file_path` - The path of the file.
+/// * `lint_levels` - The levels of the lints.
+fn check_file<'a>(
+    error_reporter: &ErrorReporter,
+    database: &mut Database<'a>,
+    file_path: &'a str,
+    lint_levels: &LintLevels,
+) -> (usize, usize) {
+    // The lexing errors are returned together with the syntax errors.
+    let (_, syntax_errors) = database.parse(file_path);
+    let mut errors = syntax_errors.len();
+    syntax_errors.report(error_reporter, true);
+
+    let typed_program = match database.type_check(file_path) {
+        Ok(typed_program) => typed_program,
+        Err(type_errors) => {
+            errors += type_errors.len();
+            type_errors.report(error_reporter, true);
+            return (errors, 0);
+        }
+    };
+
+    // As when compiling, a program with syntax errors is not linted.
+    if errors > 0 {
+        return (errors, 0);
+    }
+
+    match lint(&typed_program, database.interner(), lint_levels) {
+        Ok(warnings) => {
+            let denied = warnings
+                .iter()
+                .filter(|(_, level)| *level == LintLevel::Deny)
+                .count();
+            let warned = warnings.len() - denied;
+            warnings.report(error_reporter, true);
+            (denied, warned)
+        }
+        Err(lint_errors) => {
+            let errors = lint_errors.len();
+            lint_errors.report(error_reporter, true);
+            (errors, 0)
+        }
+    }
+}
+
+/// Returns the suffix of a word that is repeated `count` times.
+///
+/// # Arguments
+/// * `count` - The number of times the word is repeated.
+fn plural(count: usize) -> &'static str {
+    if count == 1 {
+        ""
+    } else {
+        "s"
+    }
+}
+
+/// Creates a new package in a directory with the same name.
+///
+/// # Arguments
+/// * `name` - The name of the package.
+pub fn new_command(name: &str) -> Result<(), Box<dyn Error>> {
+    create_project(Path::new(name), name)?;
+    println!("Created package `{}`.", name);
+    Ok(())
+}
+
+/// Prints the long-form explanation of an error code.
+///
+/// # Arguments
+/// * `code` - The code of the error, such as `E0001`.
+pub fn explain_command(code: &str) -> Result<(), Box<dyn Error>> {
+    let explanation =
+        explain(code).ok_or_else(|| error(format!("`{}` is not a valid error code.", code)))?;
+    print!("{}", explanation);
+    Ok(())
+}
+
+/// The number of times a file is checked again after its fixes were applied.
+/// The type checker stops at the first error of a function, so fixing an error
+/// can reveal another one.
+const MAX_FIX_PASSES: usize = 10;
+
+/// Applies the machine-applicable fixes of the errors in every file and writes
+/// the fixed source back to the file. Only the errors found before code generation
+/// are fixed.
+///
+/// # Arguments
+/// * `files` - The files to fix.
+pub fn fix_command(files: Vec<PathBuf>) -> Result<(), Box<dyn Error>> {
+    for file in clean_files(files)? {
+        let file_path = path_to_str(&file)?;
+        let mut source = get_source(&file)?;
+        let mut applied = 0;
+        for _ in 0..MAX_FIX_PASSES {
+            match fix_source(file_path, &source) {
+                Some((fixed_source, fixes)) => {
+                    source = fixed_source;
+                    applied += fixes;
+                }
+                None => break,
+            }
+        }
+
+        if applied == 0 {
+            println!("No fixes could be applied to file `{}`.", file_path);
+            continue;
+        }
+
+        fs::write(&file, source)?;
+        println!(
+            "Applied {} fix{} to file `{}`.",
+            applied,
+            if applied == 1 { "" } else { "es" },
+            file_path
+        );
+    }
+
+    Ok(())
+}
+
+/// Checks the source of a file and applies the fixes of its errors that are
+/// certainly correct. Returns the fixed source and the number of fixes that
+/// were applied, or `None` if there was nothing to fix.
+///
+/// # Arguments
+/// * `file_path` - The path of the file.
+/// * `source` - The source of the file.
+fn fix_source(file_path: &str, source: &str) -> Option<(String, usize)> {
+    let mut interner = Interner::default();
+    let errors = match lex(file_path, source.as_bytes(), &mut interner) {
+        Ok(tokens) => {
+            let (program, mut errors) = parse_with_recovery(filter_tokens(tokens), &interner);
+            let mut type_env = Environment::default();
+            let mut function_table = FunctionTable::default();
+            if let Err(type_errors) =
+                type_check(program, &mut type_env, &mut function_table, &interner)
+            {
+                errors.extend(type_errors);
+            }
+
+            errors
+        }
+        Err(errors) => errors,
+    };
+
+    let fixes = errors
+        .iter()
+        .filter_map(|error| error.fix())
+        .filter(|fix| fix.applicability == Applicability::MachineApplicable)
+        .collect::<Vec<_>>();
+    match apply_fixes(source, &fixes) {
+        (_, 0) => None,
+        fixed => Some(fixed),
+    }
+}
+
+/// Formats the files in the canonical layout and writes them back in place.
+/// When only checking, the files are left untouched and an error is returned
+/// if any of them is not formatted.
+///
+/// # Arguments
+/// * `files` - The files to format.
+/// * `check` - Whether to only check that the files are formatted.
+/// * `config` - The `FormatConfig` to follow.
+pub fn fmt_command(
+    files: Vec<PathBuf>,
+    check: bool,
+    config: FormatConfig,
+) -> Result<(), Box<dyn Error>> {
+    let mut unformatted = 0;
+    for file in clean_files(files)? {
+        let file_path = path_to_str(&file)?;
+        let source = get_source(&file)?;
+        let error_reporter = ErrorReporter::new(vec![(file_path, &source)]);
+        let formatted = format_source(file_path, &source, config)
+            .report(&error_reporter, true)
+            .ok_or_else(|| error(format!("Failed to format file `{}`.", file_path)))?;
+        if formatted == source {
+            continue;
+        }
+
+        if check {
+            let line = source
+                .lines()
+                .zip(formatted.lines())
+                .position(|(line, formatted_line)| line != formatted_line)
+                .unwrap_or_else(|| source.lines().count().min(formatted.lines().count()));
+            println!(
+                "File `{}` is not formatted, starting at line {}.",
                 file_path,
-                compilation_start.elapsed().as_secs_f64()
+                line + 1
             );
+            unformatted += 1;
+        } else {
+            fs::write(&file, formatted)?;
+            println!("Formatted file `{}`.", file_path);
         }
     }
 
-    Ok((clean_files, main_file))
+    if unformatted > 0 {
+        return Err(error(format!(
+            "{} file{} not formatted.",
+            unformatted,
+            if unformatted == 1 { " is" } else { "s are" }
+        )));
+    }
+
+    Ok(())
 }
@@ -1,32 +1,39 @@
 use std::{
+    env,
     error::Error,
+    fs,
     path::{Path, PathBuf},
     process,
     time::Instant,
 };
 
 use clap::{App, AppSettings, Arg, SubCommand};
-use command::Command;
+use command::{Artifacts, Codegen, Command, Job, LibraryKind, Sources};
 use envious_tui::run_tui;
 use envyc::{
     compile,
-    environment::Environment,
-    error::reporter::{ErrorReporter, Reporter},
-    filter_tokens,
-    function_table::FunctionTable,
-    interner::Interner,
-    lex, parse,
+    database::{fingerprint::Fingerprint, Database},
+    error::{
+        reporter::{ErrorFormat, ErrorReporter, Reporter},
+        warning::{Lint, LintLevels},
+        Error as CompilerError,
+    },
+    generate_header, link_time_optimize, lint,
+    printer::{print_tokens, Printer},
     semantic_analyzer::types::Type,
-    type_check, Config,
+    Config, Emit,
 };
 use home::home_dir;
 
 use crate::{
-    command::compile_command,
+    command::{
+        check_command, compile_command, explain_command, fix_command, fmt_command, new_command,
+    },
     utils::{error, get_stem, path_to_str, replace_last},
 };
 
 pub mod command;
+pub mod manifest;
 pub mod utils;
 
 pub fn main() -> Result<(), Box<dyn Error>> {
@@ -49,9 +56,52 @@ pub fn main() -> Result<(), Box<dyn Error>> {
                         .long("files")
                         .min_values(1)
                         .value_delimiter(";")
-                        .required(true)
-                        .help("The files to compile"),
-                ),
+                        .help("The files and directories to compile, or the sources of the package if omitted"),
+                )
+                .arg(profile_arg())
+                .arg(error_format_arg())
+                .arg(jobs_arg())
+                .arg(opt_level_arg())
+                .arg(debug_info_arg())
+                .arg(
+                    Arg::with_name("target")
+                        .long("target")
+                        .takes_value(true)
+                        .value_name("TRIPLE")
+                        .help("The target triple to compile for, such as `aarch64-unknown-linux-gnu`, which defaults to the host"),
+                )
+                .arg(
+                    Arg::with_name("target-cpu")
+                        .long("target-cpu")
+                        .takes_value(true)
+                        .value_name("CPU")
+                        .help("The CPU to compile for, or `native` for the CPU of the host, which defaults to `generic`"),
+                )
+                .arg(
+                    Arg::with_name("target-features")
+                        .long("target-features")
+                        .takes_value(true)
+                        .value_name("FEATURES")
+                        .help("The features of the CPU to enable or disable, such as `+avx2,-sse4.1`"),
+                )
+                .arg(
+                    Arg::with_name("emit")
+                        .long("emit")
+                        .takes_value(true)
+                        .min_values(1)
+                        .value_delimiter(",")
+                        .value_name("KIND")
+                        .possible_values(&Emit::ALL.iter().map(Emit::name).collect::<Vec<_>>())
+                        .help("The artifacts to write for every file, which defaults to `obj`"),
+                )
+                .arg(
+                    Arg::with_name("out-dir")
+                        .long("out-dir")
+                        .takes_value(true)
+                        .value_name("DIR")
+                        .help("The directory the artifacts are written to, instead of next to their files"),
+                )
+                .args(&lint_level_args()),
         )
         .subcommand(
             SubCommand::with_name("build")
@@ -62,13 +112,84 @@ pub fn main() -> Result<(), Box<dyn Error>> {
                         .long("files")
                         .min_values(1)
                         .value_delimiter(";")
-                        .required(true)
-                        .help("The files to compile and link"),
-                ),
+                        .help("The files and directories to compile and link, or the sources of the package if omitted"),
+                )
+                .arg(profile_arg())
+                .arg(
+                    Arg::with_name("lib")
+                        .long("lib")
+                        .takes_value(true)
+                        .possible_values(&["static", "shared"])
+                        .help("Builds a library with a C header instead of an executable"),
+                )
+                .arg(error_format_arg())
+                .arg(jobs_arg())
+                .arg(opt_level_arg())
+                .arg(debug_info_arg())
+                .arg(lto_arg())
+                .args(&lint_level_args()),
         )
         .subcommand(
             SubCommand::with_name("run")
                 .about("Run the main file after compiling and linking")
+                .arg(
+                    Arg::with_name("files")
+                        .short("f")
+                        .long("files")
+                        .min_values(1)
+                        .value_delimiter(";")
+                        .help("The files and directories to run after compiling and linking, or the sources of the package if omitted"),
+                )
+                .arg(profile_arg())
+                .arg(error_format_arg())
+                .arg(jobs_arg())
+                .arg(opt_level_arg())
+                .arg(debug_info_arg())
+                .arg(lto_arg())
+                .args(&lint_level_args()),
+        )
+        .subcommand(
+            SubCommand::with_name("check")
+                .about("Checks the files for errors without compiling them")
+                .arg(
+                    Arg::with_name("files")
+                        .short("f")
+                        .long("files")
+                        .min_values(1)
+                        .value_delimiter(";")
+                        .help("The files and directories to check, or the sources of the package if omitted"),
+                )
+                .arg(profile_arg())
+                .arg(
+                    Arg::with_name("watch")
+                        .short("w")
+                        .long("watch")
+                        .help("Checks the files again whenever they change"),
+                )
+                .arg(error_format_arg())
+                .args(&lint_level_args()),
+        )
+        .subcommand(
+            SubCommand::with_name("new")
+                .about("Creates a new package in a directory with the same name")
+                .arg(
+                    Arg::with_name("name")
+                        .required(true)
+                        .help("The name of the package"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("explain")
+                .about("Explains an error code, such as E0001, in detail")
+                .arg(
+                    Arg::with_name("code")
+                        .required(true)
+                        .help("The code of the error to explain"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("fix")
+                .about("Applies the fixes that are certainly correct to the files")
                 .arg(
                     Arg::with_name("files")
                         .short("f")
@@ -76,7 +197,39 @@ pub fn main() -> Result<(), Box<dyn Error>> {
                         .min_values(1)
                         .value_delimiter(";")
                         .required(true)
-                        .help("The files to run after compiling and linking"),
+                        .help("The files to fix"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("fmt")
+                .about("Formats the files in the canonical layout")
+                .arg(
+                    Arg::with_name("files")
+                        .short("f")
+                        .long("files")
+                        .min_values(1)
+                        .value_delimiter(";")
+                        .required(true)
+                        .help("The files to format"),
+                )
+                .arg(
+                    Arg::with_name("check")
+                        .long("check")
+                        .help("Checks that the files are formatted without changing them"),
+                )
+                .arg(
+                    Arg::with_name("indent-width")
+                        .long("indent-width")
+                        .takes_value(true)
+                        .value_name("WIDTH")
+                        .default_value("4")
+                        .validator(|width: String| {
+                            width
+                                .parse::<usize>()
+                                .map(|_| ())
+                                .map_err(|_| format!("`{}` is not a number", width))
+                        })
+                        .help("The number of spaces used for every level of indentation"),
                 ),
         )
         .settings(&[AppSettings::ArgRequiredElseHelp, AppSettings::ColorAlways])
@@ -85,74 +238,323 @@ pub fn main() -> Result<(), Box<dyn Error>> {
     let command = Command::from(matches);
     match command {
         Command::Tui => run_tui()?,
-        Command::Compile { files } => {
-            compile_command(files)?;
+        Command::Compile {
+            files,
+            profile,
+            error_format,
+            lint_arguments,
+            jobs,
+            artifacts,
+            opt_level,
+            debug_info,
+            target,
+        } => {
+            let mut sources = Sources::find(
+                files,
+                profile.as_deref(),
+                &lint_arguments,
+                opt_level,
+                debug_info,
+                false,
+            )?;
+            sources.codegen.target = target;
+            compile_command(
+                sources.files,
+                None,
+                error_format,
+                &sources.lint_levels,
+                jobs,
+                &artifacts,
+                &sources.codegen,
+            )?;
+        }
+        Command::Build {
+            files,
+            profile,
+            library: Some(library),
+            error_format,
+            lint_arguments,
+            jobs,
+            opt_level,
+            debug_info,
+            lto,
+        } => {
+            let sources = Sources::find(
+                files,
+                profile.as_deref(),
+                &lint_arguments,
+                opt_level,
+                debug_info,
+                lto,
+            )?;
+            let (files, _) = compile_command(
+                sources.files.clone(),
+                Some(library),
+                error_format,
+                &sources.lint_levels,
+                jobs,
+                &sources.artifacts(),
+                &sources.codegen,
+            )?;
+            let first_file = files
+                .first()
+                .ok_or_else(|| error("No files were given to build the library."))?;
+            let (directory, name) = sources.output(first_file)?;
+            let objects = object_files(
+                &files,
+                &sources,
+                &directory.join(&name),
+                Some(library),
+                error_format,
+            )?;
+            build_library(&objects, library, &directory, &name)?;
         }
-        Command::Build { files } => {
-            let (files, main_file) = compile_command(files)?;
-            if let Some(ref main_file) = main_file {
-                build_static_files(&files, main_file)?;
-            } else {
-                return Err(error("No main method could be found."));
-            }
+        Command::Build {
+            files,
+            profile,
+            library: None,
+            error_format,
+            lint_arguments,
+            jobs,
+            opt_level,
+            debug_info,
+            lto,
+        } => {
+            let sources = Sources::find(
+                files,
+                profile.as_deref(),
+                &lint_arguments,
+                opt_level,
+                debug_info,
+                lto,
+            )?;
+            let (files, main_file) = compile_command(
+                sources.files.clone(),
+                None,
+                error_format,
+                &sources.lint_levels,
+                jobs,
+                &sources.artifacts(),
+                &sources.codegen,
+            )?;
+            let executable_path = sources.executable(main_file.as_ref())?;
+            let objects = object_files(&files, &sources, &executable_path, None, error_format)?;
+            build_static_files(&objects, &executable_path)?;
         }
-        Command::Run { files } => {
-            let (files, main_file) = compile_command(files)?;
-            if let Some(ref main_file) = main_file {
-                build_static_files(&files, main_file)?;
-                run(path_to_str(&replace_last(
-                    main_file,
-                    get_stem(main_file)?,
-                )?)?)?;
-            } else {
-                return Err(error("No main method could be found."));
-            }
+        Command::Run {
+            files,
+            profile,
+            error_format,
+            lint_arguments,
+            jobs,
+            opt_level,
+            debug_info,
+            lto,
+        } => {
+            let sources = Sources::find(
+                files,
+                profile.as_deref(),
+                &lint_arguments,
+                opt_level,
+                debug_info,
+                lto,
+            )?;
+            let (files, main_file) = compile_command(
+                sources.files.clone(),
+                None,
+                error_format,
+                &sources.lint_levels,
+                jobs,
+                &sources.artifacts(),
+                &sources.codegen,
+            )?;
+            let executable_path = sources.executable(main_file.as_ref())?;
+            let objects = object_files(&files, &sources, &executable_path, None, error_format)?;
+            build_static_files(&objects, &executable_path)?;
+            run(path_to_str(&executable_path)?)?;
         }
+        Command::Check {
+            files,
+            profile,
+            error_format,
+            lint_arguments,
+            watch,
+        } => check_command(
+            files,
+            profile.as_deref(),
+            error_format,
+            &lint_arguments,
+            watch,
+        )?,
+        Command::New { name } => new_command(&name)?,
+        Command::Explain { code } => explain_command(&code)?,
+        Command::Fix { files } => fix_command(files)?,
+        Command::Fmt {
+            files,
+            check,
+            config,
+        } => fmt_command(files, check, config)?,
         Command::Unknown => return Err(error("Unrecognized command")),
     }
 
     Ok(())
 }
 
-fn compile_code(
+fn compile_code<'a>(
     error_reporter: &ErrorReporter,
-    interner: &mut Interner<String>,
-    module_name: &str,
-    file_path: &str,
-    output_file_path: &str,
-    bytes: &[u8],
+    database: &mut Database<'a>,
+    job: &Job<'a>,
+    artifacts: &Artifacts,
+    codegen: &Codegen,
+    library: Option<LibraryKind>,
+    lint_levels: &LintLevels,
 ) -> Option<bool> {
-    let tokens = time("Lexing", &error_reporter, || {
-        lex(file_path, bytes, interner)
+    let (module_name, file_path) = (job.file_stem, job.file_path);
+    // The key covers everything that changes the outputs of the compilation.
+    let levels = Lint::ALL
+        .iter()
+        .map(|lint| format!("{:?}", lint_levels.get(*lint)))
+        .collect::<Vec<_>>();
+    let emit = artifacts.emit.iter().map(Emit::name).collect::<Vec<_>>();
+    let key = Fingerprint::of(&(
+        job.source.as_bytes(),
+        module_name,
+        format!("{:?}", library),
+        levels,
+        emit,
+        &artifacts.out_dir,
+        codegen,
+    ));
+    if let Some(build) = job.cache.get(file_path, key) {
+        if error_reporter.format() == ErrorFormat::Human {
+            let message = format!(
+                "File `{}` did not change since it was last compiled.\n",
+                file_path
+            );
+            error_reporter.write(message.as_bytes()).ok()?;
+        }
+
+        return Some(build.contains_main);
+    }
+
+    let mut outputs = vec![];
+    let tokens = time("Lexing", error_reporter, || database.lex(file_path))?;
+    if artifacts.emit.contains(&Emit::Tokens) {
+        let path = artifacts.path(Path::new(file_path), Emit::Tokens).ok()?;
+        let printed = print_tokens(&tokens, database.interner());
+        write_output(error_reporter, &path, printed)?;
+        outputs.push(path);
+    }
+
+    let (program, syntax_errors) = time("Parsing", error_reporter, || {
+        Ok::<_, Vec<CompilerError>>(database.parse(file_path))
     })?;
 
-    let filtered_tokens = filter_tokens(tokens);
-    let program = time("Parsing", &error_reporter, || parse(filtered_tokens))?;
+    // The program is checked even if it contains syntax errors,
+    // so that the type errors in the rest of the file are reported as well.
+    let parsed = syntax_errors.report(error_reporter, true);
+    if parsed.is_some() && artifacts.emit.contains(&Emit::Ast) {
+        let path = artifacts.path(Path::new(file_path), Emit::Ast).ok()?;
+        let ast = Printer::new(database.interner(), 4).print_program(&program);
+        write_output(error_reporter, &path, ast)?;
+        outputs.push(path);
+    }
 
-    let mut type_env = Environment::default();
-    let mut function_table = FunctionTable::default();
-    let typed_program = time("Checking", &error_reporter, || {
-        type_check(program, &mut type_env, &mut function_table)
+    let typed_program = time("Checking", error_reporter, || {
+        database.type_check(file_path)
     })?;
+    parsed?;
 
-    time("Compiling", &error_reporter, || {
-        let config = Config {
-            writing_to_file: true,
-            output_file_path,
-        };
+    if artifacts.emit.contains(&Emit::TypedAst) {
+        let path = artifacts.path(Path::new(file_path), Emit::TypedAst).ok()?;
+        let typed_ast = Printer::new(database.interner(), 4).print_typed_program(&typed_program);
+        write_output(error_reporter, &path, typed_ast)?;
+        outputs.push(path);
+    }
 
-        compile(&typed_program, module_name, interner, Some(config))
+    // Warnings whose lint is denied stop the compilation like any other error.
+    let warnings = time("Linting", error_reporter, || {
+        lint(&typed_program, database.interner(), lint_levels)
     })?;
+    let warned = !warnings.is_empty();
+    warnings.report(error_reporter, true)?;
+
+    // The program is only compiled by LLVM if one of its artifacts was requested.
+    let mut llvm_outputs = vec![];
+    for emit in &artifacts.emit {
+        if emit.is_llvm() {
+            llvm_outputs.push((*emit, artifacts.path(Path::new(file_path), *emit).ok()?));
+        }
+    }
 
+    if !llvm_outputs.is_empty() {
+        time("Compiling", error_reporter, || {
+            let config = Config {
+                outputs: llvm_outputs
+                    .iter()
+                    .map(|(emit, path)| (*emit, path.as_path()))
+                    .collect(),
+                position_independent: matches!(library, Some(LibraryKind::Shared)),
+                opt_level: codegen.opt_level,
+                target: codegen.target.clone(),
+                debug_info: codegen.debug_info,
+            };
+
+            compile(
+                &typed_program,
+                module_name,
+                database.interner_mut(),
+                Some(config),
+            )
+        })?;
+        outputs.extend(llvm_outputs.into_iter().map(|(_, path)| path));
+    }
+
+    if library.is_some()
+        && typed_program
+            .functions
+            .iter()
+            .any(|function| function.prototype.export)
+    {
+        let header = generate_header(&typed_program, module_name, database.interner());
+        let header_path = replace_last(Path::new(file_path), format!("{}.h", module_name)).ok()?;
+        write_output(error_reporter, &header_path, header)?;
+        outputs.push(header_path);
+    }
+
+    let main = database.interner_mut().insert("main".to_string());
     let contains_main = typed_program.functions.iter().any(|function| {
-        function.prototype.name == interner.insert("main".to_string())
+        function.prototype.name == main
             && function.prototype.parameters.is_empty()
             && function.prototype.return_type == Type::Void
     });
 
+    // A file with warnings is compiled again next time, so that its warnings are
+    // reported again. Failing to write the cache only makes the next build slower.
+    if !warned {
+        let _ = job.cache.insert(file_path, key, &outputs, contains_main);
+    }
+
     Some(contains_main)
 }
 
+/// Writes a file that is produced while compiling, such as a header or an
+/// artifact, and reports the failure if the file could not be written.
+///
+/// # Arguments
+/// * `error_reporter` - The reporter the failure is written to.
+/// * `path` - The path of the file.
+/// * `contents` - The contents of the file.
+fn write_output(error_reporter: &ErrorReporter, path: &Path, contents: String) -> Option<()> {
+    if let Err(error) = fs::write(path, contents) {
+        let message = format!("Failed to write `{}`: {}\n", path.display(), error);
+        error_reporter.write(message.as_bytes()).ok()?;
+        return None;
+    }
+
+    Some(())
+}
+
 fn time<O: Reporter>(
     name: &str,
     error_reporter: &ErrorReporter,
@@ -160,27 +562,188 @@ fn time<O: Reporter>(
 ) -> Option<O::Output> {
     let start = Instant::now();
     let value = function();
-    println!(
-        "Process `{}` took {} seconds.",
-        name,
-        start.elapsed().as_secs_f64()
-    );
+    if error_reporter.format() == ErrorFormat::Human {
+        let message = format!(
+            "Process `{}` took {} seconds.\n",
+            name,
+            start.elapsed().as_secs_f64()
+        );
+        error_reporter.write(message.as_bytes()).ok()?;
+    }
+
     value.report(error_reporter, true)
 }
 
-fn build_static_files(files: &[PathBuf], main_file_path: &Path) -> Result<(), Box<dyn Error>> {
-    let mut command = process::Command::new("g++");
+/// Creates the argument that selects the profile of the package.
+fn profile_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("profile")
+        .long("profile")
+        .takes_value(true)
+        .value_name("NAME")
+        .conflicts_with("files")
+        .help("The profile of the package to build with, which defaults to `debug`")
+}
+
+/// Creates the argument that selects the format of the reported errors.
+fn error_format_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("error-format")
+        .long("error-format")
+        .takes_value(true)
+        .possible_values(&["human", "json", "sarif"])
+        .default_value("human")
+        .help("The format of the reported errors")
+}
+
+/// Creates the argument that sets the number of files compiled at the same time.
+fn jobs_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("jobs")
+        .short("j")
+        .long("jobs")
+        .takes_value(true)
+        .value_name("N")
+        .validator(|jobs: String| match jobs.parse::<usize>() {
+            Ok(jobs) if jobs > 0 => Ok(()),
+            _ => Err(format!("`{}` is not a positive number", jobs)),
+        })
+        .help("The number of files compiled at the same time, which defaults to the number of CPUs")
+}
+
+/// Creates the argument that sets the optimization level.
+fn opt_level_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("opt-level")
+        .short("O")
+        .long("opt-level")
+        .takes_value(true)
+        .value_name("LEVEL")
+        .possible_values(&["0", "1", "2", "3", "s", "z"])
+        .help("The optimization level, where `s` and `z` optimize for size, which defaults to the level of the profile or to 2")
+}
+
+/// Creates the argument that compiles the files with debug information.
+fn debug_info_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("debug-info")
+        .short("g")
+        .long("debug-info")
+        .help("Describes the source of the files in DWARF, so that the program can be debugged with gdb or lldb")
+}
+
+/// Creates the argument that optimizes the files together when they are linked.
+fn lto_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("lto")
+        .long("lto")
+        .help("Optimizes the files together when they are linked, which allows functions to be inlined into other files")
+}
+
+/// Creates the arguments that change the levels of the lints.
+fn lint_level_args<'a, 'b>() -> Vec<Arg<'a, 'b>> {
+    let lint_arg = |name, short, help| {
+        Arg::with_name(name)
+            .short(short)
+            .long(name)
+            .takes_value(true)
+            .multiple(true)
+            .number_of_values(1)
+            .value_name("LINT")
+            .validator(|lint: String| {
+                if lint == "warnings" || Lint::from_name(&lint).is_some() {
+                    Ok(())
+                } else {
+                    Err(format!("`{}` is not a lint", lint))
+                }
+            })
+            .help(help)
+    };
+
+    vec![
+        lint_arg(
+            "allow",
+            "A",
+            "Silences a lint, or every lint with `warnings`",
+        ),
+        lint_arg(
+            "warn",
+            "W",
+            "Reports a lint as a warning, or every lint with `warnings`",
+        ),
+        lint_arg(
+            "deny",
+            "D",
+            "Reports a lint as an error, or every lint with `warnings`",
+        ),
+    ]
+}
+
+/// Returns the object files that are linked into an executable or a library.
+/// With link time optimization, the bitcode of the files is first linked into a
+/// single module, which is optimized as a whole and written next to the output.
+///
+/// # Arguments
+/// * `files` - The files that were compiled.
+/// * `sources` - The sources the files were found in.
+/// * `output_path` - The path of the executable or library, without an extension.
+/// * `library` - The kind of library that is built, if any.
+/// * `error_format` - The format of the reported errors.
+fn object_files(
+    files: &[PathBuf],
+    sources: &Sources,
+    output_path: &Path,
+    library: Option<LibraryKind>,
+    error_format: ErrorFormat,
+) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let artifacts = sources.artifacts();
+    if !sources.lto {
+        return files
+            .iter()
+            .map(|file| artifacts.path(file, Emit::Obj))
+            .collect();
+    }
+
+    let mut bitcode_files = vec![];
     for file in files {
-        let file_stem = get_stem(file)?;
-        command.arg(replace_last(file, format!("{}.o", file_stem))?);
+        bitcode_files.push(artifacts.path(file, Emit::LlvmBc)?);
+    }
+    let bitcode_paths = bitcode_files
+        .iter()
+        .map(PathBuf::as_path)
+        .collect::<Vec<_>>();
+
+    let mut object_path = output_path.as_os_str().to_owned();
+    object_path.push(".o");
+    let object_path = PathBuf::from(object_path);
+    let config = Config {
+        outputs: vec![(Emit::Obj, &object_path)],
+        position_independent: matches!(library, Some(LibraryKind::Shared)),
+        opt_level: sources.codegen.opt_level,
+        target: sources.codegen.target.clone(),
+        debug_info: sources.codegen.debug_info,
+    };
+
+    // The exported functions of a library are used by other programs,
+    // so only the functions of an executable are made private.
+    let error_reporter = ErrorReporter::new(vec![]).with_format(error_format);
+    let linked = link_time_optimize(
+        &bitcode_paths,
+        get_stem(output_path)?,
+        config,
+        library.is_none(),
+    )
+    .report(&error_reporter, true);
+    if error_format == ErrorFormat::Sarif {
+        println!("{}", error_reporter.sarif_log());
     }
 
-    let executable_path = replace_last(main_file_path, get_stem(main_file_path)?.to_string())?;
-    let std_path = home_dir().ok_or("Could not find home directory.")?.join(".envious/std/std.o");
+    linked.ok_or_else(|| error("Failed to optimize the files together."))?;
+    Ok(vec![object_path])
+}
+
+fn build_static_files(objects: &[PathBuf], executable_path: &Path) -> Result<(), Box<dyn Error>> {
+    let mut command = process::Command::new("g++");
+    command.args(objects);
+
     let output = command
-        .arg(std_path)
+        .arg(std_object(false)?)
         .arg("-o")
-        .arg(&executable_path)
+        .arg(executable_path)
         .output()?;
 
     if !output.status.success() {
@@ -190,6 +753,74 @@ fn build_static_files(files: &[PathBuf], main_file_path: &Path) -> Result<(), Bo
     Ok(())
 }
 
+/// Returns the path of the object file of the standard library.
+/// Shared libraries can be loaded at any address, so they are linked with
+/// `std.pic.o`, which is compiled as position independent code.
+///
+/// # Arguments
+/// * `position_independent` - Whether the object is linked into a shared library.
+fn std_object(position_independent: bool) -> Result<PathBuf, Box<dyn Error>> {
+    let directory = home_dir()
+        .ok_or("Could not find home directory.")?
+        .join(".envious/std");
+    if !position_independent {
+        return Ok(directory.join("std.o"));
+    }
+
+    let path = directory.join("std.pic.o");
+    if !path.exists() {
+        return Err(error(format!(
+            "Shared libraries are linked with the position independent standard library, but `{}` is not installed.",
+            path.display()
+        )));
+    }
+
+    Ok(path)
+}
+
+/// Archives or links the compiled files into a library.
+/// The standard library is included so that the library can be used on its own.
+///
+/// # Arguments
+/// * `objects` - The object files of the library.
+/// * `library` - The kind of library to build.
+/// * `directory` - The directory the library is written to.
+/// * `library_name` - The name of the library, without the `lib` prefix or an extension.
+fn build_library(
+    objects: &[PathBuf],
+    library: LibraryKind,
+    directory: &Path,
+    library_name: &str,
+) -> Result<(), Box<dyn Error>> {
+    let mut command = match library {
+        LibraryKind::Static => {
+            let mut command = process::Command::new("ar");
+            command
+                .arg("rcs")
+                .arg(directory.join(format!("lib{}.a", library_name)));
+            command
+        }
+        LibraryKind::Shared => {
+            let mut command = process::Command::new("g++");
+            command.arg("-shared").arg("-o").arg(directory.join(format!(
+                "lib{}.{}",
+                library_name,
+                env::consts::DLL_EXTENSION
+            )));
+            command
+        }
+    };
+
+    command.args(objects);
+    let std_path = std_object(matches!(library, LibraryKind::Shared))?;
+    let output = command.arg(std_path).output()?;
+    if !output.status.success() {
+        return Err(error("Failed to build the library"));
+    }
+
+    Ok(())
+}
+
 fn run(executable_path: &str) -> Result<(), Box<dyn Error>> {
     let output = process::Command::new(executable_path).output()?;
 
@@ -0,0 +1,393 @@
+use std::{
+    collections::HashMap,
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use envyc::{
+    error::warning::{Lint, LintLevel, LintLevels},
+    OptLevel,
+};
+use serde::Deserialize;
+
+use crate::utils::{error, find_sources};
+
+/// The name of the manifest at the root of every package.
+pub const MANIFEST_FILE: &str = "Envious.toml";
+
+/// The profile that is used when no profile is given.
+pub const DEFAULT_PROFILE: &str = "debug";
+
+// The version of the standard library that is shipped with the compiler.
+const STD_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Struct that describes the contents of an `Envious.toml` manifest.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Manifest {
+    pub package: Package,
+    #[serde(default)]
+    pub profile: HashMap<String, Profile>,
+}
+
+/// Struct that describes the `[package]` table of a manifest. The paths are
+/// relative to the directory that contains the manifest.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Package {
+    pub name: String,
+    pub version: String,
+    // The file that defines the `main` function.
+    #[serde(default = "default_entry")]
+    pub entry: PathBuf,
+    // The directories whose `.envy` files are compiled.
+    #[serde(default = "default_sources")]
+    pub sources: Vec<PathBuf>,
+    // The version of the standard library the package is written against.
+    #[serde(default = "default_std")]
+    pub std: String,
+}
+
+/// Struct that describes a `[profile.<name>]` table of a manifest, which
+/// changes the levels of the lints and how the files are optimized. The lints
+/// are allowed first, then warned about and then denied, and `warnings` names every lint.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    #[serde(default)]
+    pub allow: Vec<String>,
+    #[serde(default)]
+    pub warn: Vec<String>,
+    #[serde(default)]
+    pub deny: Vec<String>,
+    // The optimization level, written as a number such as `3` or as a name such as `"s"`.
+    #[serde(default, rename = "opt-level")]
+    pub opt_level: Option<toml::Value>,
+    // Whether the files are optimized together when they are built.
+    #[serde(default)]
+    pub lto: bool,
+    // Whether the files are compiled with debug information.
+    #[serde(default)]
+    pub debug: bool,
+}
+
+fn default_entry() -> PathBuf {
+    PathBuf::from("src/main.envy")
+}
+
+fn default_sources() -> Vec<PathBuf> {
+    vec![PathBuf::from("src")]
+}
+
+fn default_std() -> String {
+    let mut parts = STD_VERSION.split('.');
+    format!(
+        "{}.{}",
+        parts.next().unwrap_or("0"),
+        parts.next().unwrap_or("0")
+    )
+}
+
+/// Struct that describes a package, which is a directory with a manifest.
+#[derive(Debug)]
+pub struct Project {
+    pub root: PathBuf,
+    pub manifest: Manifest,
+}
+
+impl Project {
+    /// Finds the package that contains a directory by looking for a manifest
+    /// in the directory and in each of its parents.
+    ///
+    /// # Arguments
+    /// * `directory` - The directory to start looking in.
+    pub fn find(directory: &Path) -> Result<Self, Box<dyn Error>> {
+        let root = directory
+            .ancestors()
+            .find(|ancestor| ancestor.join(MANIFEST_FILE).is_file())
+            .ok_or_else(|| {
+                error(format!(
+                    "Could not find `{}` in `{}` or any of its parents. Pass the files to compile with `--files` instead.",
+                    MANIFEST_FILE,
+                    directory.display()
+                ))
+            })?;
+
+        Self::load(root)
+    }
+
+    /// Reads and validates the manifest of a package.
+    ///
+    /// # Arguments
+    /// * `root` - The directory that contains the manifest.
+    pub fn load(root: &Path) -> Result<Self, Box<dyn Error>> {
+        let manifest_path = root.join(MANIFEST_FILE);
+        let contents = fs::read_to_string(&manifest_path)?;
+        let manifest = toml::from_str::<Manifest>(&contents).map_err(|toml_error| {
+            error(format!(
+                "Could not read `{}`: {}",
+                manifest_path.display(),
+                toml_error
+            ))
+        })?;
+
+        validate_name(&manifest.package.name)?;
+        if !std_matches(&manifest.package.std) {
+            return Err(error(format!(
+                "The package `{}` requires version {} of the standard library, but version {} is installed.",
+                manifest.package.name, manifest.package.std, STD_VERSION
+            )));
+        }
+
+        for (name, profile) in &manifest.profile {
+            if let Some(opt_level) = &profile.opt_level {
+                if parse_opt_level(opt_level).is_none() {
+                    return Err(error(format!(
+                        "The profile `{}` in `{}` has the optimization level `{}`, which is not one of 0, 1, 2, 3, \"s\" or \"z\".",
+                        name,
+                        manifest_path.display(),
+                        opt_level
+                    )));
+                }
+            }
+
+            for lint in profile
+                .allow
+                .iter()
+                .chain(&profile.warn)
+                .chain(&profile.deny)
+            {
+                if lint != "warnings" && Lint::from_name(lint).is_none() {
+                    return Err(error(format!(
+                        "The profile `{}` in `{}` names `{}`, which is not a lint.",
+                        name,
+                        manifest_path.display(),
+                        lint
+                    )));
+                }
+            }
+        }
+
+        Ok(Self {
+            root: root.canonicalize()?,
+            manifest,
+        })
+    }
+
+    /// Returns the `.envy` files in the source directories of the package,
+    /// sorted by their paths so that they are always compiled in the same order.
+    pub fn sources(&self) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+        let mut sources = vec![];
+        for directory in &self.manifest.package.sources {
+            let directory = self.root.join(directory);
+            if !directory.is_dir() {
+                return Err(error(format!(
+                    "The source directory `{}` does not exist.",
+                    directory.display()
+                )));
+            }
+
+            sources.extend(find_sources(&directory)?);
+        }
+
+        let entry = self.entry()?;
+        if !sources.contains(&entry) {
+            sources.push(entry);
+        }
+
+        sources.sort();
+        sources.dedup();
+        Ok(sources)
+    }
+
+    /// Returns the path of the file that defines the `main` function.
+    pub fn entry(&self) -> Result<PathBuf, Box<dyn Error>> {
+        let entry = self.root.join(&self.manifest.package.entry);
+        entry.canonicalize().map_err(|_| {
+            error(format!(
+                "The entry file `{}` does not exist.",
+                entry.display()
+            ))
+        })
+    }
+
+    /// Returns the levels of the lints in a profile. The `debug` and `release`
+    /// profiles exist even if the manifest does not define them.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the profile.
+    pub fn lint_levels(&self, name: &str) -> Result<LintLevels, Box<dyn Error>> {
+        let mut lint_levels = LintLevels::default();
+        let profile = match self.manifest.profile.get(name) {
+            Some(profile) => profile,
+            None if name == "debug" || name == "release" => return Ok(lint_levels),
+            None => {
+                return Err(error(format!(
+                    "The package `{}` does not have a profile named `{}`.",
+                    self.manifest.package.name, name
+                )))
+            }
+        };
+
+        for (lints, level) in &[
+            (&profile.allow, LintLevel::Allow),
+            (&profile.warn, LintLevel::Warn),
+            (&profile.deny, LintLevel::Deny),
+        ] {
+            for lint in lints.iter() {
+                match Lint::from_name(lint) {
+                    Some(lint) => lint_levels.set(lint, *level),
+                    None => lint_levels.set_all(*level),
+                }
+            }
+        }
+
+        Ok(lint_levels)
+    }
+
+    /// Returns the optimization level of a profile, which defaults to `3` in
+    /// the `release` profile and to `0` in every other profile.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the profile.
+    pub fn opt_level(&self, name: &str) -> OptLevel {
+        // The levels were already validated when the manifest was loaded.
+        match self
+            .manifest
+            .profile
+            .get(name)
+            .and_then(|profile| profile.opt_level.as_ref())
+            .and_then(parse_opt_level)
+        {
+            Some(opt_level) => opt_level,
+            None if name == "release" => OptLevel::O3,
+            None => OptLevel::O0,
+        }
+    }
+
+    /// Returns whether a profile optimizes the files together when they are built.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the profile.
+    pub fn lto(&self, name: &str) -> bool {
+        self.manifest
+            .profile
+            .get(name)
+            .is_some_and(|profile| profile.lto)
+    }
+
+    /// Returns whether a profile compiles the files with debug information.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the profile.
+    pub fn debug(&self, name: &str) -> bool {
+        self.manifest
+            .profile
+            .get(name)
+            .is_some_and(|profile| profile.debug)
+    }
+}
+
+/// Returns the optimization level written in a profile, if it is one.
+///
+/// # Arguments
+/// * `value` - The value of `opt-level`.
+fn parse_opt_level(value: &toml::Value) -> Option<OptLevel> {
+    match value {
+        toml::Value::Integer(level) => OptLevel::from_name(&level.to_string()),
+        toml::Value::String(name) => OptLevel::from_name(name),
+        _ => None,
+    }
+}
+
+/// Returns whether the installed standard library satisfies the version required
+/// by a package. Every part of the required version, such as `0.1` or `0.1.0`,
+/// must be equal to the same part of the installed version.
+///
+/// # Arguments
+/// * `required` - The version required by the package.
+fn std_matches(required: &str) -> bool {
+    let installed = STD_VERSION.split('.').collect::<Vec<_>>();
+    let required = required.split('.').collect::<Vec<_>>();
+    required.len() <= installed.len()
+        && required
+            .iter()
+            .zip(&installed)
+            .all(|(required, installed)| required == installed)
+}
+
+/// Checks that a package name can be used as the name of a directory and of an executable.
+///
+/// # Arguments
+/// * `name` - The name of the package.
+pub fn validate_name(name: &str) -> Result<(), Box<dyn Error>> {
+    let valid = name
+        .chars()
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if valid {
+        Ok(())
+    } else {
+        Err(error(format!(
+            "`{}` is not a valid package name. Names start with a letter and only contain letters, digits, `_` and `-`.",
+            name
+        )))
+    }
+}
+
+/// Creates a new package with a manifest, a `main` function and a `.gitignore`.
+///
+/// # Arguments
+/// * `directory` - The directory of the package, which must not exist yet.
+/// * `name` - The name of the package.
+pub fn create_project(directory: &Path, name: &str) -> Result<(), Box<dyn Error>> {
+    validate_name(name)?;
+    if directory.exists() {
+        return Err(error(format!(
+            "The destination `{}` already exists.",
+            directory.display()
+        )));
+    }
+
+    fs::create_dir_all(directory.join("src"))?;
+    fs::write(
+        directory.join(MANIFEST_FILE),
+        format!(
+            "[package]
+name = \"{}\"
+version = \"0.1.0\"
+entry = \"src/main.envy\"
+sources = [\"src\"]
+std = \"{}\"
+
+[profile.debug]
+debug = true
+
+[profile.release]
+opt-level = 3
+deny = [\"warnings\"]
+",
+            name,
+            default_std()
+        ),
+    )?;
+    fs::write(
+        directory.join("src/main.envy"),
+        "extern printi(Int) :: Void
+
+define square(number: Int) :: Int = number * number
+
+define main() :: Void = printi(square(4))
+",
+    )?;
+    fs::write(
+        directory.join(".gitignore"),
+        format!("/{}\n*.o\n.envious/\n", name),
+    )?;
+
+    Ok(())
+}
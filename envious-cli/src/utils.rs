@@ -4,12 +4,64 @@ use std::{
     path::{Path, PathBuf},
 };
 
-pub fn clean_file(file: PathBuf) -> Result<PathBuf, Box<dyn Error>> {
-    if file.is_file() {
-        Ok(file.canonicalize()?)
-    } else {
-        Err(error("Compilation of directories is not yet supported."))
+/// Returns the canonical paths of the files to compile. Every directory is
+/// replaced by the `.envy` files within it, and a file is only returned once.
+///
+/// # Arguments
+/// * `files` - The files and directories given on the command line.
+pub fn clean_files(files: Vec<PathBuf>) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let mut clean_files = vec![];
+    for file in files {
+        let sources = if file.is_dir() {
+            find_sources(&file.canonicalize()?)?
+        } else if file.is_file() {
+            vec![file.canonicalize()?]
+        } else {
+            return Err(error(format!(
+                "The file `{}` does not exist.",
+                file.display()
+            )));
+        };
+
+        for source in sources {
+            if !clean_files.contains(&source) {
+                clean_files.push(source);
+            }
+        }
+    }
+
+    Ok(clean_files)
+}
+
+/// Returns the `.envy` files within a directory and its subdirectories, sorted
+/// by their paths. Hidden directories, such as the build cache, are skipped.
+///
+/// # Arguments
+/// * `directory` - The directory to search.
+pub fn find_sources(directory: &Path) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let mut sources = vec![];
+    let mut directories = vec![directory.to_path_buf()];
+    while let Some(directory) = directories.pop() {
+        for entry in fs::read_dir(&directory)? {
+            let path = entry?.path();
+            let hidden = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with('.'));
+            if path.is_dir() && !hidden {
+                directories.push(path);
+            } else if path.is_file()
+                && path
+                    .extension()
+                    .is_some_and(|extension| extension == "envy")
+            {
+                sources.push(path);
+            }
+        }
     }
+
+    sources.sort();
+    Ok(sources)
 }
 
 pub fn get_source(file: &Path) -> Result<String, Box<dyn Error>> {
@@ -0,0 +1,178 @@
+use std::{
+    fs,
+    io::{BufRead, BufReader},
+    path::{Path, PathBuf},
+    process::{Command, Output, Stdio},
+    sync::mpsc,
+    thread,
+    time::Duration,
+};
+
+/// Creates a directory with the given files.
+///
+/// # Arguments
+/// * `name` - The name of the directory, which is unique to the test.
+/// * `files` - The names and sources of the files.
+fn setup(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!("envious-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&directory);
+    fs::create_dir_all(&directory).unwrap();
+    for (file, source) in files {
+        fs::write(directory.join(file), source).unwrap();
+    }
+
+    directory
+}
+
+/// Runs `envious check` on files in a directory.
+///
+/// # Arguments
+/// * `directory` - The directory to run in.
+/// * `arguments` - The arguments passed after `check`.
+fn check(directory: &Path, arguments: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_envious"))
+        .arg("check")
+        .args(arguments)
+        .current_dir(directory)
+        .output()
+        .unwrap()
+}
+
+/// Removes the time from a summary line, since it changes with every run.
+///
+/// # Arguments
+/// * `summary` - The summary line.
+fn without_time(summary: &str) -> String {
+    summary[..summary.find(" after ").unwrap()].to_string()
+        + &summary[summary.find(" with ").unwrap()..]
+}
+
+const WARNING: &str = "define scale(x: Int) :: Int = {\n    let unused = 1\n    x * 2\n}\n";
+const TYPE_ERROR: &str = "define broken() :: Int = 1 + true\n";
+const SYNTAX_ERROR: &str = "define half(x: Int) :: Int = x / / 2\n";
+
+#[test]
+fn every_file_is_counted_in_the_summary() {
+    let directory = setup(
+        "check-summary",
+        &[
+            ("warning.envy", WARNING),
+            ("type.envy", TYPE_ERROR),
+            ("syntax.envy", SYNTAX_ERROR),
+            ("clean.envy", "define one() :: Int = 1\n"),
+        ],
+    );
+    let files = [
+        "-f",
+        "warning.envy",
+        "type.envy",
+        "syntax.envy",
+        "clean.envy",
+    ];
+
+    let output = check(&directory, &files);
+    assert!(!output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let summary = stdout.lines().last().unwrap();
+    assert_eq!(
+        without_time(summary),
+        "Checked 4 files with 2 errors and 1 warning."
+    );
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("Could not check the files because of 2 errors."),
+        "{}",
+        stderr
+    );
+
+    // Denied warnings are counted as errors.
+    let output = check(&directory, &[&files[..], &["-D", "warnings"]].concat());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        without_time(stdout.lines().last().unwrap()),
+        "Checked 4 files with 3 errors and 0 warnings."
+    );
+
+    let output = check(&directory, &["-f", "warning.envy", "clean.envy"]);
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        without_time(stdout.lines().last().unwrap()),
+        "Checked 2 files with 0 errors and 1 warning."
+    );
+    fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn checking_never_generates_code() {
+    let directory = setup(
+        "check-no-code",
+        &[
+            ("main.envy", "define main() :: Void = {}\n"),
+            ("warning.envy", WARNING),
+        ],
+    );
+
+    let output = check(&directory, &["-f", "main.envy", "warning.envy"]);
+    assert!(output.status.success(), "{:?}", output);
+    // Nothing is compiled, so there is no progress, no object file and no cache.
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(!stdout.contains("Compiling"), "{}", stdout);
+    let mut entries = fs::read_dir(&directory)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect::<Vec<_>>();
+    entries.sort();
+    assert_eq!(entries, vec!["main.envy", "warning.envy"]);
+    fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn watching_checks_the_files_again_after_every_change() {
+    let directory = setup(
+        "check-watch",
+        &[("type.envy", TYPE_ERROR), ("warning.envy", WARNING)],
+    );
+    let mut child = Command::new(env!("CARGO_BIN_EXE_envious"))
+        .args(["check", "--watch", "-f", "type.envy", "warning.envy"])
+        .current_dir(&directory)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+
+    let (sender, receiver) = mpsc::channel();
+    let stdout = child.stdout.take().unwrap();
+    thread::spawn(move || {
+        for line in BufReader::new(stdout).lines() {
+            let line = line.unwrap();
+            if line.starts_with("Checked ") && sender.send(without_time(&line)).is_err() {
+                break;
+            }
+        }
+    });
+    let next_summary = || receiver.recv_timeout(Duration::from_secs(30)).unwrap();
+
+    assert_eq!(
+        next_summary(),
+        "Checked 2 files with 1 error and 1 warning."
+    );
+    fs::write(
+        directory.join("type.envy"),
+        "define fixed() :: Int = 1 + 2\n",
+    )
+    .unwrap();
+    assert_eq!(
+        next_summary(),
+        "Checked 2 files with 0 errors and 1 warning."
+    );
+    fs::write(directory.join("warning.envy"), SYNTAX_ERROR).unwrap();
+    assert_eq!(
+        next_summary(),
+        "Checked 2 files with 1 error and 0 warnings."
+    );
+
+    child.kill().unwrap();
+    child.wait().unwrap();
+    fs::remove_dir_all(directory).unwrap();
+}
@@ -0,0 +1,90 @@
+use std::{
+    fs,
+    path::PathBuf,
+    process::{Command, Output},
+};
+
+/// Writes a file into a new directory and runs `envious fix` on it. Returns the
+/// output of the command and the source of the file afterwards.
+///
+/// # Arguments
+/// * `name` - The name of the directory, which is unique to the test.
+/// * `source` - The source of the file.
+fn fix(name: &str, source: &str) -> (Output, String) {
+    let directory: PathBuf =
+        std::env::temp_dir().join(format!("envious-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&directory);
+    fs::create_dir_all(&directory).unwrap();
+    fs::write(directory.join("main.envy"), source).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_envious"))
+        .args(["fix", "-f", "main.envy"])
+        .current_dir(&directory)
+        .output()
+        .unwrap();
+    let fixed = fs::read_to_string(directory.join("main.envy")).unwrap();
+    fs::remove_dir_all(directory).unwrap();
+    (output, fixed)
+}
+
+#[test]
+fn errors_revealed_by_fixes_are_fixed() {
+    // The type checker stops at the first error of a function, so the second
+    // error is only found once the first one was fixed.
+    let source = "define add(a: Int, b: Int) :: Int = a + b
+define f() :: Float = {
+    let x = add(1, 2, 3)
+    let y: Float = 2
+    y
+}
+";
+    let (output, fixed) = fix("fix-passes", source);
+    assert!(output.status.success(), "{:?}", output);
+    assert!(
+        String::from_utf8_lossy(&output.stdout).starts_with("Applied 2 fixes to file"),
+        "{:?}",
+        output
+    );
+    assert_eq!(
+        fixed,
+        "define add(a: Int, b: Int) :: Int = a + b
+define f() :: Float = {
+    let x = add(1, 2)
+    let y: Float = 2.0
+    y
+}
+"
+    );
+}
+
+#[test]
+fn errors_without_fixes_are_left_alone() {
+    let source = "define f() :: Int = {
+    let x = 1
+    x + true
+}
+";
+    let (output, fixed) = fix("fix-none", source);
+    assert!(output.status.success(), "{:?}", output);
+    assert!(
+        String::from_utf8_lossy(&output.stdout).starts_with("No fixes could be applied"),
+        "{:?}",
+        output
+    );
+    assert_eq!(fixed, source);
+}
+
+#[test]
+fn fixes_are_kept_next_to_errors_without_fixes() {
+    let source = "define f() :: Float = 1
+define g() :: Int = 1 + true
+";
+    let (output, fixed) = fix("fix-partial", source);
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(
+        fixed,
+        "define f() :: Float = 1.0
+define g() :: Int = 1 + true
+"
+    );
+}
@@ -0,0 +1,192 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Output},
+};
+
+/// Creates a directory with the given files.
+///
+/// # Arguments
+/// * `name` - The name of the directory, which is unique to the test.
+/// * `files` - The names and sources of the files.
+fn setup(name: &str, files: &[(String, String)]) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!("envious-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&directory);
+    fs::create_dir_all(&directory).unwrap();
+    for (file, source) in files {
+        fs::write(directory.join(file), source).unwrap();
+    }
+
+    directory
+}
+
+/// Compiles the files of a directory on `jobs` threads, writing the object files
+/// to `out_dir`. The cache of the previous compilation is removed first, so that
+/// every file is compiled.
+///
+/// # Arguments
+/// * `directory` - The directory created by `setup`.
+/// * `files` - The names of the files, in the order they are given.
+/// * `jobs` - The number of threads.
+/// * `out_dir` - The directory the object files are written to.
+/// * `error_format` - The format of the reported errors.
+fn compile(
+    directory: &Path,
+    files: &[String],
+    jobs: usize,
+    out_dir: &str,
+    error_format: &str,
+) -> Output {
+    let _ = fs::remove_dir_all(directory.join(".envious"));
+    Command::new(env!("CARGO_BIN_EXE_envious"))
+        .arg("compile")
+        .arg("-f")
+        .args(files)
+        .args(["-j", &jobs.to_string(), "--out-dir", out_dir])
+        .arg(format!("--error-format={}", error_format))
+        .current_dir(directory)
+        .output()
+        .unwrap()
+}
+
+/// Creates files that each define a function and a warning, so that every
+/// file reports something.
+///
+/// # Arguments
+/// * `count` - The number of files.
+fn files_with_warnings(count: usize) -> Vec<(String, String)> {
+    (0..count)
+        .map(|index| {
+            (
+                format!("file{}.envy", index),
+                format!(
+                    "define scale{}(x: Int) :: Int = {{\n    let unused = {}\n    x * {}\n}}\n",
+                    index, index, index
+                ),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn files_compile_to_the_same_output_on_any_number_of_threads() {
+    let files = files_with_warnings(8);
+    let names = files
+        .iter()
+        .map(|(name, _)| name.clone())
+        .collect::<Vec<_>>();
+    let directory = setup("jobs-output", &files);
+
+    let serial = compile(&directory, &names, 1, "serial", "json");
+    assert!(serial.status.success(), "{:?}", serial);
+    let lines = String::from_utf8(serial.stdout.clone()).unwrap();
+    assert_eq!(lines.lines().count(), files.len(), "{}", lines);
+    for (line, name) in lines.lines().zip(&names) {
+        assert!(line.contains(name.as_str()), "{} {}", name, line);
+    }
+
+    for jobs in [2, 4, 8] {
+        let parallel = compile(&directory, &names, jobs, "parallel", "json");
+        assert!(parallel.status.success(), "{:?}", parallel);
+        assert_eq!(
+            String::from_utf8(parallel.stdout).unwrap(),
+            lines,
+            "-j {}",
+            jobs
+        );
+
+        for name in &names {
+            let object = name.replace(".envy", ".o");
+            assert_eq!(
+                fs::read(directory.join("serial").join(&object)).unwrap(),
+                fs::read(directory.join("parallel").join(&object)).unwrap(),
+                "{} with -j {}",
+                object,
+                jobs
+            );
+        }
+    }
+
+    fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn nothing_is_reported_after_the_first_failure_on_any_number_of_threads() {
+    let mut files = files_with_warnings(6);
+    files.insert(
+        3,
+        (
+            "broken.envy".to_string(),
+            "define broken() :: Int = 1 + true\n".to_string(),
+        ),
+    );
+    let names = files
+        .iter()
+        .map(|(name, _)| name.clone())
+        .collect::<Vec<_>>();
+    let directory = setup("jobs-failure", &files);
+
+    let serial = compile(&directory, &names, 1, "serial", "json");
+    let lines = String::from_utf8(serial.stdout).unwrap();
+    let codes = lines
+        .lines()
+        .map(|line| line.split('"').nth(3).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        codes,
+        vec![
+            "unused_variable",
+            "unused_variable",
+            "unused_variable",
+            "E0008"
+        ]
+    );
+
+    for jobs in [2, 7] {
+        let parallel = compile(&directory, &names, jobs, "parallel", "json");
+        assert_eq!(
+            String::from_utf8(parallel.stdout).unwrap(),
+            lines,
+            "-j {}",
+            jobs
+        );
+    }
+
+    fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn multiple_main_functions_are_reported_after_the_sarif_log() {
+    let files = vec![
+        (
+            "first.envy".to_string(),
+            "define main() :: Void = {\n    let unused = 1\n    {}\n}\n".to_string(),
+        ),
+        (
+            "second.envy".to_string(),
+            "define main() :: Void = {}\n".to_string(),
+        ),
+    ];
+    let names = files
+        .iter()
+        .map(|(name, _)| name.clone())
+        .collect::<Vec<_>>();
+    let directory = setup("jobs-mains", &files);
+
+    let output = compile(&directory, &names, 2, "out", "sarif");
+    assert!(!output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with('{'), "{}", stdout);
+    assert!(
+        stdout.contains("\"ruleId\": \"unused_variable\""),
+        "{}",
+        stdout
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Found multiple main methods."),
+        "{}",
+        stderr
+    );
+    fs::remove_dir_all(directory).unwrap();
+}
@@ -0,0 +1,163 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Output},
+};
+
+const LIBRARY: &str = "export define add(int: Int, default: Int) :: Int = int + default
+define twice(x: Int) :: Int = x * 2
+export define quadruple(x: Int) :: Int = twice(twice(x))
+";
+
+const PROGRAM: &str = "#include <stdio.h>
+#include \"math.h\"
+
+int main(void) {
+    printf(\"%ld %ld\\n\", (long) add(1, 2), (long) quadruple(3));
+    return 0;
+}
+";
+
+const STD: &str = "#include <stdio.h>
+void printi(long value) { printf(\"%ld\\n\", value); }
+";
+
+/// Creates a directory with the library and a home directory with the standard
+/// library. `std.pic.o` is only installed when `position_independent` is set.
+///
+/// # Arguments
+/// * `name` - The name of the directory, which is unique to the test.
+/// * `position_independent` - Whether the position independent standard library is installed.
+fn setup(name: &str, position_independent: bool) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!("envious-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&directory);
+    let std_directory = directory.join("home/.envious/std");
+    fs::create_dir_all(&std_directory).unwrap();
+    fs::write(directory.join("math.envy"), LIBRARY).unwrap();
+    fs::write(directory.join("main.c"), PROGRAM).unwrap();
+    fs::write(directory.join("std.c"), STD).unwrap();
+
+    let mut objects = vec![("std.o", vec![])];
+    if position_independent {
+        objects.push(("std.pic.o", vec!["-fPIC"]));
+    }
+
+    for (object, flags) in objects {
+        run(Command::new("cc")
+            .args(flags)
+            .arg("-c")
+            .arg(directory.join("std.c"))
+            .arg("-o")
+            .arg(std_directory.join(object)));
+    }
+
+    directory
+}
+
+/// Runs `envious` in a directory, with the home directory created by `setup`.
+///
+/// # Arguments
+/// * `directory` - The directory created by `setup`.
+/// * `arguments` - The arguments passed to `envious`.
+fn envious(directory: &Path, arguments: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_envious"))
+        .args(arguments)
+        .current_dir(directory)
+        .env("HOME", directory.join("home"))
+        .output()
+        .unwrap()
+}
+
+/// Runs a command and returns its standard output, failing the test if it fails.
+fn run(command: &mut Command) -> String {
+    let output = command.output().unwrap();
+    assert!(
+        output.status.success(),
+        "{:?} failed: {}",
+        command,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn headers_declare_the_exported_functions() {
+    let directory = setup("header", false);
+    let output = envious(&directory, &["build", "--lib", "static", "-f", "math.envy"]);
+    assert!(output.status.success(), "{:?}", output);
+
+    let header = fs::read_to_string(directory.join("math.h")).unwrap();
+    assert!(header.starts_with("#ifndef ENVIOUS_MATH_H\n#define ENVIOUS_MATH_H\n"));
+    assert!(header.contains("int64_t add(int64_t int_, int64_t default_);"));
+    assert!(header.contains("int64_t quadruple(int64_t x);"));
+    assert!(!header.contains("twice"));
+    fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn static_libraries_are_linked_into_c_programs() {
+    let directory = setup("static", false);
+    let output = envious(&directory, &["build", "--lib", "static", "-f", "math.envy"]);
+    assert!(output.status.success(), "{:?}", output);
+    assert!(directory.join("libmath.a").exists());
+
+    run(Command::new("cc")
+        .current_dir(&directory)
+        .args(["main.c", "libmath.a", "-o", "main"]));
+    let printed = run(&mut Command::new(directory.join("main")));
+    assert_eq!(printed, "3 12\n");
+    fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn libraries_keep_their_exported_functions_with_lto() {
+    let directory = setup("static-lto", false);
+    let output = envious(
+        &directory,
+        &["build", "--lib", "static", "--lto", "-f", "math.envy"],
+    );
+    assert!(output.status.success(), "{:?}", output);
+
+    run(Command::new("cc")
+        .current_dir(&directory)
+        .args(["main.c", "libmath.a", "-o", "main"]));
+    let printed = run(&mut Command::new(directory.join("main")));
+    assert_eq!(printed, "3 12\n");
+    fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn shared_libraries_are_linked_into_c_programs() {
+    let directory = setup("shared", true);
+    let output = envious(&directory, &["build", "--lib", "shared", "-f", "math.envy"]);
+    assert!(output.status.success(), "{:?}", output);
+    let library = format!("libmath.{}", std::env::consts::DLL_EXTENSION);
+    assert!(directory.join(&library).exists());
+
+    // Only the exported functions are visible to the users of the library.
+    let symbols =
+        run(Command::new("nm")
+            .current_dir(&directory)
+            .args(["-D", "--defined-only", &library]));
+    assert!(symbols.contains(" add\n"), "{}", symbols);
+    assert!(symbols.contains(" quadruple\n"), "{}", symbols);
+    assert!(!symbols.contains("twice"), "{}", symbols);
+
+    let rpath = format!("-Wl,-rpath,{}", directory.display());
+    run(Command::new("cc")
+        .current_dir(&directory)
+        .args(["main.c", "-L.", "-lmath", &rpath, "-o", "main"]));
+    let printed = run(&mut Command::new(directory.join("main")));
+    assert_eq!(printed, "3 12\n");
+    fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn shared_libraries_require_the_position_independent_standard_library() {
+    let directory = setup("shared-without-pic", false);
+    let output = envious(&directory, &["build", "--lib", "shared", "-f", "math.envy"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("std.pic.o` is not installed"), "{}", stderr);
+    fs::remove_dir_all(directory).unwrap();
+}
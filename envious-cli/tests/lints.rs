@@ -0,0 +1,125 @@
+use std::{
+    fs,
+    path::PathBuf,
+    process::{Command, Output},
+};
+
+const SOURCE: &str = "define main() :: Int = {
+    let x = 1
+    0
+}
+";
+
+/// Creates a directory with a file whose only problem is an unused variable.
+///
+/// # Arguments
+/// * `name` - The name of the directory, which is unique to the test.
+fn setup(name: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!("envious-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&directory);
+    fs::create_dir_all(&directory).unwrap();
+    fs::write(directory.join("main.envy"), SOURCE).unwrap();
+    directory
+}
+
+/// Checks the file created by `setup` with the given lint arguments, and returns
+/// the severity of every diagnostic reported in the JSON format.
+///
+/// # Arguments
+/// * `name` - The name of the directory, which is unique to the test.
+/// * `arguments` - The `-A`, `-W` and `-D` arguments.
+fn check(name: &str, arguments: &[&str]) -> (Output, Vec<String>) {
+    let directory = setup(name);
+    let output = Command::new(env!("CARGO_BIN_EXE_envious"))
+        .args(["check", "-f", "main.envy", "--error-format=json"])
+        .args(arguments)
+        .current_dir(&directory)
+        .output()
+        .unwrap();
+    fs::remove_dir_all(directory).unwrap();
+
+    let severities = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter(|line| line.starts_with('{'))
+        .map(|line| {
+            assert!(
+                line.starts_with("{\"code\":\"unused_variable\""),
+                "{}",
+                line
+            );
+            let severity = line.split("\"severity\":\"").nth(1).unwrap();
+            severity[..severity.find('"').unwrap()].to_string()
+        })
+        .collect();
+    (output, severities)
+}
+
+#[test]
+fn lints_are_warnings_by_default() {
+    let (output, severities) = check("lints-default", &[]);
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(severities, vec!["warning"]);
+}
+
+#[test]
+fn denied_lints_are_errors() {
+    let (output, severities) = check("lints-deny", &["-D", "unused_variable"]);
+    assert!(!output.status.success());
+    assert_eq!(severities, vec!["error"]);
+
+    let (output, severities) = check("lints-deny-warnings", &["-D", "warnings"]);
+    assert!(!output.status.success());
+    assert_eq!(severities, vec!["error"]);
+}
+
+#[test]
+fn allowed_lints_are_silenced() {
+    let (output, severities) = check("lints-allow", &["-A", "unused_variable"]);
+    assert!(output.status.success(), "{:?}", output);
+    assert!(severities.is_empty());
+}
+
+#[test]
+fn later_lint_arguments_override_earlier_ones() {
+    let cases = [
+        (&["-D", "warnings", "-A", "unused_variable"][..], vec![]),
+        (
+            &["-A", "unused_variable", "-D", "warnings"][..],
+            vec!["error"],
+        ),
+        (
+            &["-D", "unused_variable", "-W", "warnings"][..],
+            vec!["warning"],
+        ),
+        (
+            &["-A", "warnings", "-W", "unused_variable"][..],
+            vec!["warning"],
+        ),
+        (
+            &["--deny", "unused_variable", "--allow", "unused_variable"][..],
+            vec![],
+        ),
+    ];
+    for (index, (arguments, expected)) in cases.iter().enumerate() {
+        let (output, severities) = check(&format!("lints-order-{}", index), arguments);
+        assert_eq!(&severities, expected, "{:?}", arguments);
+        assert_eq!(
+            output.status.success(),
+            !expected.contains(&"error"),
+            "{:?}",
+            arguments
+        );
+    }
+}
+
+#[test]
+fn unknown_lints_are_rejected() {
+    let (output, _) = check("lints-unknown", &["-D", "unused_variables"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("`unused_variables` is not a lint"),
+        "{}",
+        stderr
+    );
+}
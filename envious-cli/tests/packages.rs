@@ -0,0 +1,350 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Output},
+};
+
+const MANIFEST: &str = "[package]
+name = \"shapes\"
+version = \"0.1.0\"
+";
+
+const MAIN: &str = "define main() :: Void = {
+    let unused = 1
+    {}
+}
+";
+
+/// Creates an empty directory for a test.
+///
+/// # Arguments
+/// * `name` - The name of the directory, which is unique to the test.
+fn setup(name: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!("envious-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&directory);
+    fs::create_dir_all(&directory).unwrap();
+    directory
+}
+
+/// Creates a package with the given manifest, a `src/main.envy` file whose only
+/// problem is an unused variable and a `src/shapes/area.envy` file.
+///
+/// # Arguments
+/// * `name` - The name of the directory, which is unique to the test.
+/// * `manifest` - The contents of `Envious.toml`.
+fn setup_package(name: &str, manifest: &str) -> PathBuf {
+    let directory = setup(name);
+    fs::create_dir_all(directory.join("src/shapes")).unwrap();
+    fs::write(directory.join("Envious.toml"), manifest).unwrap();
+    fs::write(directory.join("src/main.envy"), MAIN).unwrap();
+    fs::write(
+        directory.join("src/shapes/area.envy"),
+        "define area(width: Int, height: Int) :: Int = width * height\n",
+    )
+    .unwrap();
+    directory
+}
+
+/// Runs `envious` in a directory.
+///
+/// # Arguments
+/// * `directory` - The directory to run in.
+/// * `arguments` - The arguments passed to `envious`.
+fn envious(directory: &Path, arguments: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_envious"))
+        .args(arguments)
+        .current_dir(directory)
+        .output()
+        .unwrap()
+}
+
+/// Returns the standard output and the standard error of a command.
+fn output_text(output: &Output) -> (String, String) {
+    (
+        String::from_utf8_lossy(&output.stdout).to_string(),
+        String::from_utf8_lossy(&output.stderr).to_string(),
+    )
+}
+
+/// Checks the package in a directory and returns the summary line that is
+/// printed at the end, failing the test if there is none.
+///
+/// # Arguments
+/// * `directory` - The directory to check in.
+/// * `arguments` - The arguments passed to `envious check`.
+fn check_summary(directory: &Path, arguments: &[&str]) -> (bool, String) {
+    let output = envious(directory, &[&["check"], arguments].concat());
+    let (stdout, stderr) = output_text(&output);
+    let summary = stdout
+        .lines()
+        .find(|line| line.starts_with("Checked "))
+        .unwrap_or_else(|| panic!("no summary: {} {}", stdout, stderr));
+    // The time it took changes with every run.
+    let summary = summary[..summary.find(" after ").unwrap()].to_string()
+        + &summary[summary.find(" with ").unwrap()..];
+    (output.status.success(), summary)
+}
+
+#[test]
+fn new_creates_a_package() {
+    let directory = setup("packages-new");
+    let output = envious(&directory, &["new", "hello"]);
+    assert!(output.status.success(), "{:?}", output);
+
+    let package = directory.join("hello");
+    let manifest = fs::read_to_string(package.join("Envious.toml")).unwrap();
+    assert!(
+        manifest.starts_with("[package]\nname = \"hello\"\nversion = \"0.1.0\"\n"),
+        "{}",
+        manifest
+    );
+    assert!(manifest.contains("[profile.release]\nopt-level = 3\ndeny = [\"warnings\"]\n"));
+    assert_eq!(
+        fs::read_to_string(package.join(".gitignore")).unwrap(),
+        "/hello\n*.o\n.envious/\n"
+    );
+    assert!(package.join("src/main.envy").is_file());
+
+    // The new package checks without any errors or warnings.
+    assert_eq!(
+        check_summary(&package, &[]),
+        (
+            true,
+            "Checked 1 file with 0 errors and 0 warnings.".to_string()
+        )
+    );
+    fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn new_rejects_existing_directories_and_invalid_names() {
+    let directory = setup("packages-new-errors");
+    assert!(envious(&directory, &["new", "hello"]).status.success());
+    let output = envious(&directory, &["new", "hello"]);
+    assert!(!output.status.success());
+    assert!(output_text(&output).1.contains("already exists"));
+
+    for name in ["1hello", "hello world", "héllo", "hello!"] {
+        let output = envious(&directory, &["new", name]);
+        assert!(!output.status.success(), "{}", name);
+        assert!(
+            output_text(&output)
+                .1
+                .contains("is not a valid package name"),
+            "{}",
+            name
+        );
+        assert!(!directory.join(name).exists(), "{}", name);
+    }
+
+    fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn sources_are_found_from_any_directory_of_the_package() {
+    let directory = setup_package("packages-sources", MANIFEST);
+    // Hidden directories and other files are not sources.
+    fs::create_dir_all(directory.join("src/.hidden")).unwrap();
+    fs::write(directory.join("src/.hidden/broken.envy"), "define").unwrap();
+    fs::write(directory.join("src/notes.txt"), "define").unwrap();
+
+    let summary = (
+        true,
+        "Checked 2 files with 0 errors and 1 warning.".to_string(),
+    );
+    assert_eq!(check_summary(&directory, &[]), summary);
+    assert_eq!(check_summary(&directory.join("src/shapes"), &[]), summary);
+    fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn the_entry_is_compiled_even_outside_of_the_sources() {
+    let manifest = format!(
+        "{}entry = \"main.envy\"\nsources = [\"src/shapes\"]\n",
+        MANIFEST
+    );
+    let directory = setup_package("packages-entry", &manifest);
+    fs::rename(directory.join("src/main.envy"), directory.join("main.envy")).unwrap();
+    assert_eq!(
+        check_summary(&directory, &[]),
+        (
+            true,
+            "Checked 2 files with 0 errors and 1 warning.".to_string()
+        )
+    );
+    fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn profiles_set_the_levels_of_lints() {
+    let manifest = format!(
+        "{}
+[profile.strict]
+deny = [\"warnings\"]
+allow = [\"unused_variable\", \"unused_parameter\"]
+
+[profile.release]
+deny = [\"unused_variable\"]
+",
+        MANIFEST
+    );
+    let directory = setup_package("packages-profiles", &manifest);
+
+    // The lints are allowed first and denied last, whatever their order in the manifest.
+    assert_eq!(
+        check_summary(&directory, &["--profile", "strict"]),
+        (
+            false,
+            "Checked 2 files with 1 error and 0 warnings.".to_string()
+        )
+    );
+    assert_eq!(
+        check_summary(&directory, &["--profile", "release"]),
+        (
+            false,
+            "Checked 2 files with 1 error and 0 warnings.".to_string()
+        )
+    );
+    // The `debug` profile is used by default and exists even if it is not defined.
+    assert_eq!(
+        check_summary(&directory, &[]),
+        (
+            true,
+            "Checked 2 files with 0 errors and 1 warning.".to_string()
+        )
+    );
+    // The arguments override the profile.
+    assert_eq!(
+        check_summary(&directory, &["--profile", "strict", "-A", "warnings"]),
+        (
+            true,
+            "Checked 2 files with 0 errors and 0 warnings.".to_string()
+        )
+    );
+
+    let output = envious(&directory, &["check", "--profile", "fast"]);
+    assert!(!output.status.success());
+    assert!(output_text(&output)
+        .1
+        .contains("The package `shapes` does not have a profile named `fast`."));
+    fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn profiles_set_the_optimization_level() {
+    let manifest = format!(
+        "{}
+[profile.small]
+opt-level = \"z\"
+",
+        MANIFEST
+    );
+    let directory = setup_package("packages-opt-level", &manifest);
+    fs::write(
+        directory.join("src/main.envy"),
+        "define square(x: Int) :: Int = x * x\ndefine sixteen() :: Int = square(4)\n",
+    )
+    .unwrap();
+
+    let ir = |profile: &str| {
+        let _ = fs::remove_dir_all(directory.join("src/.envious"));
+        let output = envious(
+            &directory,
+            &["compile", "--profile", profile, "--emit", "llvm-ir"],
+        );
+        assert!(output.status.success(), "{:?}", output);
+        fs::read_to_string(directory.join("src/main.ll")).unwrap()
+    };
+
+    // Only the optimized profiles fold the application into a constant.
+    assert!(ir("debug").contains("call i64 @square(i64 4)"));
+    for profile in ["release", "small"] {
+        let ir = ir(profile);
+        assert!(ir.contains("ret i64 16"), "{}: {}", profile, ir);
+    }
+    fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn invalid_manifests_are_reported() {
+    let cases = [
+        ("", "missing field `package`"),
+        (
+            "[package]\nname = \"shapes\"\nversion = \"0.1.0\"\nauthor = \"me\"\n",
+            "unknown field `author`",
+        ),
+        (
+            "[package]\nname = \"2d\"\nversion = \"0.1.0\"\n",
+            "`2d` is not a valid package name",
+        ),
+        (
+            "[package]\nname = \"shapes\"\nversion = \"0.1.0\"\nstd = \"9.9\"\n",
+            "requires version 9.9 of the standard library",
+        ),
+        (
+            "[package]\nname = \"shapes\"\nversion = \"0.1.0\"\n\n[profile.fast]\nopt-level = 4\n",
+            "has the optimization level `4`",
+        ),
+        (
+            "[package]\nname = \"shapes\"\nversion = \"0.1.0\"\n\n[profile.debug]\ndeny = [\"unused\"]\n",
+            "names `unused`, which is not a lint",
+        ),
+        (
+            "[package]\nname = \"shapes\"\nversion = \"0.1.0\"\nentry = \"app.envy\"\n",
+            "app.envy` does not exist",
+        ),
+        (
+            "[package]\nname = \"shapes\"\nversion = \"0.1.0\"\nsources = [\"lib\"]\n",
+            "lib` does not exist",
+        ),
+    ];
+    for (index, (manifest, message)) in cases.iter().enumerate() {
+        let directory = setup_package(&format!("packages-invalid-{}", index), manifest);
+        let output = envious(&directory, &["check"]);
+        assert!(!output.status.success(), "{}", manifest);
+        let stderr = output_text(&output).1;
+        assert!(stderr.contains(message), "{}: {}", manifest, stderr);
+        fs::remove_dir_all(directory).unwrap();
+    }
+
+    let directory = setup("packages-missing");
+    let output = envious(&directory, &["check"]);
+    assert!(!output.status.success());
+    assert!(output_text(&output)
+        .1
+        .contains("Could not find `Envious.toml`"));
+    fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn directories_given_on_the_command_line_are_searched() {
+    let directory = setup_package("packages-directories", MANIFEST);
+    fs::create_dir_all(directory.join("src/.envious")).unwrap();
+    fs::write(directory.join("src/.envious/cached.envy"), "define").unwrap();
+
+    // Every file is only checked once, even if it is given more than once.
+    assert_eq!(
+        check_summary(
+            &directory,
+            &["-f", "src", "src/main.envy", "src/shapes/../main.envy"]
+        ),
+        (
+            true,
+            "Checked 2 files with 0 errors and 1 warning.".to_string()
+        )
+    );
+    assert_eq!(
+        check_summary(&directory, &["-f", "src/shapes"]),
+        (
+            true,
+            "Checked 1 file with 0 errors and 0 warnings.".to_string()
+        )
+    );
+
+    let output = envious(&directory, &["check", "-f", "missing.envy"]);
+    assert!(!output.status.success());
+    assert!(output_text(&output)
+        .1
+        .contains("The file `missing.envy` does not exist."));
+    fs::remove_dir_all(directory).unwrap();
+}
@@ -0,0 +1,806 @@
+use envyc::{
+    environment::Environment,
+    error::{
+        diagnostic::{Diagnostic, LabelStyle, Severity},
+        reporter::ErrorReporter,
+        warning::LintLevels,
+        Error, Span,
+    },
+    filter_tokens,
+    function_table::FunctionTable,
+    interner::Interner,
+    lex,
+    lexer::token::{Token, TokenKind},
+    lint, parse_with_recovery,
+    parser::{
+        ast::{Function, Program},
+        expression::{Expression, ExpressionKind, Identifier},
+        typed_ast::TypedProgram,
+        typed_expression::{TypedExpression, TypedExpressionKind},
+    },
+    semantic_analyzer::types::Type,
+    type_check,
+};
+use lsp_types::{
+    CompletionItem, CompletionItemKind, DiagnosticRelatedInformation, DiagnosticSeverity,
+    DocumentSymbol, Location, NumberOrString, SymbolKind, Url,
+};
+
+use crate::line_index::LineIndex;
+
+/// Struct that stores the result of lexing and parsing a document.
+/// Every request analyzes the document again, since the results
+/// borrow the source and documents are small.
+struct Parsed<'a> {
+    tokens: Vec<Token<'a>>,
+    program: Program<'a>,
+    errors: Vec<Error<'a>>,
+    interner: Interner<String>,
+}
+
+/// Lexes and parses a document, recovering from syntax errors.
+///
+/// # Arguments
+/// * `file_name` - The name of the document used in its spans.
+/// * `source` - The source of the document.
+fn parse<'a>(file_name: &'a str, source: &'a str) -> Result<Parsed<'a>, Vec<Error<'a>>> {
+    let mut interner = Interner::default();
+    let tokens = lex(file_name, source.as_bytes(), &mut interner)?;
+    let (program, errors) = parse_with_recovery(filter_tokens(tokens.clone()), &interner);
+
+    Ok(Parsed {
+        tokens,
+        program,
+        errors,
+        interner,
+    })
+}
+
+/// Finds every error and warning of a document, in the order the compiler
+/// reports them. The lints are only run if the document has no syntax errors,
+/// since the placeholders of broken expressions would cause false warnings.
+///
+/// # Arguments
+/// * `uri` - The URI of the document.
+/// * `source` - The source of the document.
+pub fn diagnostics(uri: &Url, source: &str) -> Vec<lsp_types::Diagnostic> {
+    let file_name = uri.as_str();
+    let reporter = ErrorReporter::new(vec![(file_name, source)]);
+    let mut diagnostics = vec![];
+    match parse(file_name, source) {
+        Ok(parsed) => {
+            let syntax_errors = parsed.errors;
+            diagnostics.extend(syntax_errors.iter().map(|error| reporter.diagnostic(error)));
+
+            let mut type_env = Environment::default();
+            let mut function_table = FunctionTable::default();
+            let checked = type_check(
+                parsed.program,
+                &mut type_env,
+                &mut function_table,
+                &parsed.interner,
+            );
+            match checked {
+                Ok(typed_program) if syntax_errors.is_empty() => {
+                    match lint(&typed_program, &parsed.interner, &LintLevels::default()) {
+                        Ok(warnings) => {
+                            diagnostics.extend(warnings.iter().map(|(warning, level)| {
+                                reporter.warning_diagnostic(warning, *level)
+                            }))
+                        }
+                        Err(errors) => diagnostics
+                            .extend(errors.iter().map(|error| reporter.diagnostic(error))),
+                    }
+                }
+                Ok(_) => {}
+                Err(errors) => {
+                    diagnostics.extend(errors.iter().map(|error| reporter.diagnostic(error)))
+                }
+            }
+        }
+        Err(errors) => diagnostics.extend(errors.iter().map(|error| reporter.diagnostic(error))),
+    }
+
+    let index = LineIndex::new(source);
+    diagnostics
+        .iter()
+        .filter_map(|diagnostic| convert_diagnostic(uri, &index, diagnostic))
+        .collect()
+}
+
+/// Converts a `Diagnostic` of the compiler into a diagnostic of the protocol.
+/// The primary label marks the range of the diagnostic, while the secondary
+/// labels become related information. Diagnostics without labels, which
+/// only come from LLVM, are skipped.
+///
+/// # Arguments
+/// * `uri` - The URI of the document.
+/// * `index` - The `LineIndex` of the document.
+/// * `diagnostic` - The `Diagnostic` to convert.
+fn convert_diagnostic(
+    uri: &Url,
+    index: &LineIndex,
+    diagnostic: &Diagnostic,
+) -> Option<lsp_types::Diagnostic> {
+    let primary = diagnostic
+        .labels
+        .iter()
+        .find(|label| label.style == LabelStyle::Primary)
+        .or_else(|| diagnostic.labels.first())?;
+
+    let mut message = diagnostic.message.clone();
+    if !primary.message.is_empty() {
+        message.push_str(&format!("\n{}", primary.message));
+    }
+    for note in &diagnostic.notes {
+        message.push_str(&format!("\nnote: {}", note));
+    }
+    for fix in &diagnostic.fixes {
+        message.push_str(&format!("\nhelp: {}", fix.message));
+    }
+
+    let related_information = diagnostic
+        .labels
+        .iter()
+        .filter(|label| label.style == LabelStyle::Secondary)
+        .map(|label| DiagnosticRelatedInformation {
+            location: Location::new(uri.clone(), index.range(label.span)),
+            message: label.message.clone(),
+        })
+        .collect::<Vec<_>>();
+
+    let severity = match diagnostic.severity {
+        Severity::Error => DiagnosticSeverity::ERROR,
+        Severity::Warning => DiagnosticSeverity::WARNING,
+    };
+
+    Some(lsp_types::Diagnostic {
+        range: index.range(primary.span),
+        severity: Some(severity),
+        code: Some(NumberOrString::String(diagnostic.code.to_string())),
+        source: Some("envious".to_string()),
+        message,
+        related_information: Some(related_information).filter(|related| !related.is_empty()),
+        ..lsp_types::Diagnostic::default()
+    })
+}
+
+/// Finds the code under the offset and describes its type. Hovering over a
+/// variable shows its name and type, hovering over the name of a function shows
+/// its signature, and hovering over any other expression shows the type it
+/// results in. Types are only known if the document type checks.
+///
+/// # Arguments
+/// * `file_name` - The name of the document used in its spans.
+/// * `source` - The source of the document.
+/// * `offset` - The byte offset under the cursor.
+pub fn hover<'a>(file_name: &'a str, source: &'a str, offset: usize) -> Option<(Span<'a>, String)> {
+    let parsed = parse(file_name, source).ok()?;
+    let interner = parsed.interner;
+    let program = type_check(
+        parsed.program,
+        &mut Environment::default(),
+        &mut FunctionTable::default(),
+        &interner,
+    )
+    .ok()?;
+
+    let finder = HoverFinder {
+        program: &program,
+        interner: &interner,
+        offset,
+    };
+    finder.find()
+}
+
+/// Struct that searches a `TypedProgram` for the innermost code under an offset.
+struct HoverFinder<'p, 'a> {
+    program: &'p TypedProgram<'a>,
+    interner: &'p Interner<String>,
+    offset: usize,
+}
+
+impl<'p, 'a> HoverFinder<'p, 'a> {
+    /// Finds the code under the offset in any item of the program.
+    fn find(&self) -> Option<(Span<'a>, String)> {
+        for constant in &self.program.constants {
+            if self.under(constant.name.0) {
+                let name = self.interner.get(constant.name.1);
+                return Some((constant.name.0, format!("const {}: {}", name, constant.ty)));
+            } else if self.under(constant.expression.0) {
+                return self.find_expression(&constant.expression);
+            }
+        }
+
+        for extern_declaration in &self.program.extern_declarations {
+            if self.under(extern_declaration.span) {
+                let signature = self.signature(extern_declaration.name)?;
+                return Some((extern_declaration.span, signature));
+            }
+        }
+
+        for function in &self.program.functions {
+            let prototype = &function.prototype;
+            if let Some(parameter) = prototype
+                .parameters
+                .iter()
+                .find(|parameter| self.under(parameter.span))
+            {
+                let name = self.interner.get(parameter.name);
+                return Some((parameter.span, format!("{}: {}", name, parameter.ty)));
+            } else if self.under(prototype.span) {
+                return Some((prototype.span, self.signature(prototype.name)?));
+            } else if self.under(function.body.0) {
+                return self.find_expression(&function.body);
+            }
+        }
+
+        None
+    }
+
+    /// Finds the innermost expression under the offset. If none of the
+    /// subexpressions are under the offset, the expression itself is described.
+    ///
+    /// # Arguments
+    /// * `expression` - The expression that may contain the offset.
+    fn find_expression(&self, expression: &TypedExpression<'a>) -> Option<(Span<'a>, String)> {
+        if !self.under(expression.0) {
+            return None;
+        }
+
+        let inner = match &expression.1 {
+            TypedExpressionKind::Unary(unary) => self.find_expression(&unary.expression),
+            TypedExpressionKind::Binary(binary) => self
+                .find_expression(&binary.left)
+                .or_else(|| self.find_expression(&binary.right)),
+            TypedExpressionKind::If(if_expression) => self
+                .find_expression(&if_expression.condition)
+                .or_else(|| self.find_expression(&if_expression.then_branch))
+                .or_else(|| {
+                    if_expression
+                        .else_branch
+                        .as_ref()
+                        .and_then(|else_branch| self.find_expression(else_branch))
+                }),
+            TypedExpressionKind::Let(let_expression) => {
+                let (name_span, identifier) = &let_expression.name;
+                if self.under(*name_span) {
+                    let name = self.interner.get(identifier.id);
+                    return Some((*name_span, format!("{}: {}", name, identifier.ty)));
+                }
+
+                self.find_expression(&let_expression.expression)
+            }
+            TypedExpressionKind::Store(store) => self
+                .find_expression(&store.pointer)
+                .or_else(|| self.find_expression(&store.expression)),
+            TypedExpressionKind::Block(expressions) => expressions
+                .iter()
+                .find_map(|expression| self.find_expression(expression)),
+            TypedExpressionKind::Application(application) => {
+                let (name_span, name) = application.function_name;
+                if self.under(name_span) {
+                    return Some((name_span, self.signature(name)?));
+                }
+
+                application
+                    .parameters
+                    .iter()
+                    .find_map(|parameter| self.find_expression(parameter))
+            }
+            TypedExpressionKind::While(while_expression) => self
+                .find_expression(&while_expression.condition)
+                .or_else(|| self.find_expression(&while_expression.expression)),
+            TypedExpressionKind::Return(Some(expression))
+            | TypedExpressionKind::Unsafe(expression) => self.find_expression(expression),
+            _ => None,
+        };
+
+        inner.or_else(|| {
+            let description = match &expression.1 {
+                TypedExpressionKind::Identifier(identifier) => {
+                    format!("{}: {}", self.interner.get(identifier.id), identifier.ty)
+                }
+                kind => kind.get_type().to_string(),
+            };
+
+            Some((expression.0, description))
+        })
+    }
+
+    /// Returns the signature of a function or an external declaration.
+    ///
+    /// # Arguments
+    /// * `name` - The id of the name of the function.
+    fn signature(&self, name: usize) -> Option<String> {
+        let function_name = self.interner.get(name);
+        if let Some(function) = self
+            .program
+            .functions
+            .iter()
+            .find(|function| function.prototype.name == name)
+        {
+            let parameters = function.prototype.parameters.iter().map(|parameter| {
+                format!("{}: {}", self.interner.get(parameter.name), parameter.ty)
+            });
+            let return_type = function.prototype.return_type;
+            return Some(signature(
+                "define",
+                function_name,
+                parameters,
+                false,
+                return_type,
+            ));
+        }
+
+        self.program
+            .extern_declarations
+            .iter()
+            .find(|extern_declaration| extern_declaration.name == name)
+            .map(|extern_declaration| {
+                let parameters = extern_declaration
+                    .parameters
+                    .iter()
+                    .map(|(ty, _)| ty.to_string());
+                signature(
+                    "extern",
+                    function_name,
+                    parameters,
+                    extern_declaration.variadic,
+                    extern_declaration.return_type.0,
+                )
+            })
+    }
+
+    /// Determines whether the offset lies within the span.
+    ///
+    /// # Arguments
+    /// * `span` - The `Span` to inspect.
+    fn under(&self, span: Span) -> bool {
+        span.start <= self.offset && self.offset < span.end
+    }
+}
+
+/// Finds where the name under the offset is defined. Variables resolve to the
+/// parameter or the first `let` expression that defined them in the scope of their
+/// use, named arguments resolve to their parameter, and any other name resolves to
+/// the function, external declaration or constant with that name.
+///
+/// # Arguments
+/// * `file_name` - The name of the document used in its spans.
+/// * `source` - The source of the document.
+/// * `offset` - The byte offset of the cursor.
+pub fn definition<'a>(file_name: &'a str, source: &'a str, offset: usize) -> Option<Span<'a>> {
+    let parsed = parse(file_name, source).ok()?;
+    let (_, name) = identifier_at(&parsed.tokens, offset)?;
+    let program = &parsed.program;
+
+    let mut walker = ScopeWalker::new(offset);
+    if let Some(function) = program
+        .functions
+        .iter()
+        .find(|function| touches(function_span(function), offset))
+    {
+        walker.walk_function(function);
+    }
+
+    if let Some(span) = walker.definition {
+        return Some(span);
+    } else if let Some((function_name, argument_name)) = walker.argument {
+        return program
+            .functions
+            .iter()
+            .find(|function| function.prototype.name == function_name)?
+            .prototype
+            .parameters
+            .iter()
+            .find(|parameter| parameter.name == argument_name)
+            .map(|parameter| parameter.span);
+    }
+
+    let functions = program.functions.iter().filter_map(|function| {
+        Some(function.prototype.span).filter(|_| function.prototype.name == name)
+    });
+    let extern_declarations = program
+        .extern_declarations
+        .iter()
+        .filter(|extern_declaration| extern_declaration.name == name)
+        .map(|extern_declaration| extern_declaration.span);
+    let item = functions.chain(extern_declarations).next();
+    if let Some(item_span) = item {
+        return Some(name_span(&parsed.tokens, item_span, name));
+    }
+
+    program
+        .constants
+        .iter()
+        .find(|constant| constant.name.1 == name)
+        .map(|constant| constant.name.0)
+}
+
+/// Lists the names that can be written at the offset: the functions and
+/// constants known to the `FunctionTable`, whose types are stored in the
+/// `Environment`, and the parameters and variables in scope at the offset.
+///
+/// # Arguments
+/// * `file_name` - The name of the document used in its spans.
+/// * `source` - The source of the document.
+/// * `offset` - The byte offset of the cursor.
+pub fn completions(file_name: &str, source: &str, offset: usize) -> Vec<CompletionItem> {
+    let parsed = match parse(file_name, source) {
+        Ok(parsed) => parsed,
+        Err(_) => return vec![],
+    };
+
+    let mut walker = ScopeWalker::new(offset);
+    if let Some(function) = parsed
+        .program
+        .functions
+        .iter()
+        .find(|function| touches(function_span(function), offset))
+    {
+        walker.walk_function(function);
+    }
+    let variables = walker.visible.unwrap_or_default();
+
+    // The functions and constants are added to the tables before any
+    // body is checked, so they are known even if the document has errors.
+    let interner = parsed.interner;
+    let mut type_env = Environment::default();
+    let mut function_table = FunctionTable::default();
+    let _ = type_check(
+        parsed.program,
+        &mut type_env,
+        &mut function_table,
+        &interner,
+    );
+
+    let mut items = vec![];
+    for (name, signature) in function_table.functions() {
+        let parameters = signature
+            .parameters
+            .iter()
+            .map(|parameter| match parameter.name {
+                Some(name) => format!("{}: {}", interner.get(name), parameter.ty),
+                None => parameter.ty.to_string(),
+            })
+            .chain(Some("...".to_string()).filter(|_| signature.variadic))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let return_type = type_env
+            .get(name)
+            .map_or(String::new(), |ty| format!(" :: {}", ty));
+
+        items.push(completion_item(
+            interner.get(name),
+            CompletionItemKind::FUNCTION,
+            format!("({}){}", parameters, return_type),
+        ));
+    }
+    for name in function_table.constants() {
+        let ty = type_env
+            .get(name)
+            .map_or(String::new(), |ty| ty.to_string());
+        items.push(completion_item(
+            interner.get(name),
+            CompletionItemKind::CONSTANT,
+            ty,
+        ));
+    }
+    for name in variables {
+        items.push(completion_item(
+            interner.get(name),
+            CompletionItemKind::VARIABLE,
+            String::new(),
+        ));
+    }
+
+    items.sort_by(|first, second| first.label.cmp(&second.label));
+    items.dedup_by(|first, second| first.label == second.label);
+    items
+}
+
+/// Creates a completion item for a name.
+///
+/// # Arguments
+/// * `label` - The name to complete.
+/// * `kind` - The kind of the named item.
+/// * `detail` - The type or the signature of the item, if it is known.
+fn completion_item(label: &str, kind: CompletionItemKind, detail: String) -> CompletionItem {
+    CompletionItem {
+        label: label.to_string(),
+        kind: Some(kind),
+        detail: Some(detail).filter(|detail| !detail.is_empty()),
+        ..CompletionItem::default()
+    }
+}
+
+/// Lists the external declarations, constants and functions of a document
+/// in the order they are written.
+///
+/// # Arguments
+/// * `file_name` - The name of the document used in its spans.
+/// * `source` - The source of the document.
+pub fn symbols(file_name: &str, source: &str) -> Vec<DocumentSymbol> {
+    let parsed = match parse(file_name, source) {
+        Ok(parsed) => parsed,
+        Err(_) => return vec![],
+    };
+
+    let index = LineIndex::new(source);
+    let interner = &parsed.interner;
+    let symbol = |name: usize, kind: SymbolKind, detail: String, span: Span, name_span: Span| {
+        #[allow(deprecated)]
+        DocumentSymbol {
+            name: interner.get(name).clone(),
+            detail: Some(detail),
+            kind,
+            tags: None,
+            deprecated: None,
+            range: index.range(span),
+            selection_range: index.range(name_span),
+            children: None,
+        }
+    };
+
+    let mut symbols = vec![];
+    for extern_declaration in &parsed.program.extern_declarations {
+        let parameters = extern_declaration
+            .parameters
+            .iter()
+            .map(|(ty, _)| ty.to_string());
+        let name = extern_declaration.name;
+        let detail = signature(
+            "extern",
+            interner.get(name),
+            parameters,
+            extern_declaration.variadic,
+            extern_declaration.return_type.0,
+        );
+        let span = extern_declaration.span;
+        let symbol = symbol(
+            name,
+            SymbolKind::FUNCTION,
+            detail,
+            span,
+            name_span(&parsed.tokens, span, name),
+        );
+        symbols.push((span.start, symbol));
+    }
+    for constant in &parsed.program.constants {
+        let (name_span, name) = constant.name;
+        let detail = constant.ty.0.to_string();
+        let symbol = symbol(name, SymbolKind::CONSTANT, detail, constant.span, name_span);
+        symbols.push((constant.span.start, symbol));
+    }
+    for function in &parsed.program.functions {
+        let prototype = &function.prototype;
+        let parameters = prototype
+            .parameters
+            .iter()
+            .map(|parameter| format!("{}: {}", interner.get(parameter.name), parameter.ty));
+        let name = prototype.name;
+        let detail = signature(
+            "define",
+            interner.get(name),
+            parameters,
+            false,
+            prototype.return_type.0,
+        );
+        let span = function_span(function);
+        let symbol = symbol(
+            name,
+            SymbolKind::FUNCTION,
+            detail,
+            span,
+            name_span(&parsed.tokens, prototype.span, name),
+        );
+        symbols.push((span.start, symbol));
+    }
+
+    symbols.sort_by_key(|(start, _)| *start);
+    symbols.into_iter().map(|(_, symbol)| symbol).collect()
+}
+
+/// Struct that walks through the body of a function and keeps track of the
+/// variables in scope, in the same way as the type checker. Only the code
+/// before the offset is walked.
+struct ScopeWalker<'a> {
+    offset: usize,
+    // The spans of the parameters and variables in scope.
+    env: Environment<Span<'a>>,
+    // The definition of the variable under the offset.
+    definition: Option<Span<'a>>,
+    // The ids of the function and the name of the named argument under the offset.
+    argument: Option<(usize, usize)>,
+    // The names in scope at the offset.
+    visible: Option<Vec<usize>>,
+}
+
+impl<'a> ScopeWalker<'a> {
+    /// Creates a new `ScopeWalker`.
+    ///
+    /// # Arguments
+    /// * `offset` - The byte offset of the cursor.
+    fn new(offset: usize) -> Self {
+        Self {
+            offset,
+            env: Environment::default(),
+            definition: None,
+            argument: None,
+            visible: None,
+        }
+    }
+
+    /// Walks through the parameters and the body of a function.
+    ///
+    /// # Arguments
+    /// * `function` - The function to walk through.
+    fn walk_function(&mut self, function: &Function<'a>) {
+        self.env.new_scope();
+        for parameter in &function.prototype.parameters {
+            if let Some(default) = &parameter.default {
+                self.walk(default);
+            }
+            if touches(parameter.span, self.offset) {
+                self.definition = Some(parameter.span);
+            }
+
+            self.env.define(parameter.name, parameter.span);
+        }
+
+        self.walk(&function.body);
+        if self.visible.is_none() && touches(function.body.0, self.offset) {
+            self.visible = Some(self.env.names());
+        }
+
+        self.env.remove_top_scope();
+    }
+
+    /// Walks through an expression and the expressions within it.
+    ///
+    /// # Arguments
+    /// * `expression` - The expression to walk through.
+    fn walk(&mut self, expression: &Expression<'a>) {
+        let (span, kind) = expression;
+        if span.start > self.offset {
+            return;
+        }
+
+        match kind {
+            ExpressionKind::Identifier(Identifier(id)) if touches(*span, self.offset) => {
+                self.definition = self.env.get(*id);
+            }
+            ExpressionKind::Unary(unary) => self.walk(&unary.expression),
+            ExpressionKind::Binary(binary) => {
+                self.walk(&binary.left);
+                self.walk(&binary.right);
+            }
+            ExpressionKind::If(if_expression) => {
+                self.walk(&if_expression.condition);
+                self.walk(&if_expression.then_branch);
+                if let Some(else_branch) = &if_expression.else_branch {
+                    self.walk(else_branch);
+                }
+            }
+            ExpressionKind::Let(let_expression) => {
+                self.walk(&let_expression.expression);
+                let (name_span, Identifier(id)) = &let_expression.name;
+                if touches(*name_span, self.offset) {
+                    self.definition = self.env.get(*id).or(Some(*name_span));
+                }
+
+                // A `let` expression of a variable in scope assigns to it instead of defining it.
+                if self.env.get(*id).is_none() && let_expression.expression.0.end <= self.offset {
+                    self.env.define(*id, *name_span);
+                }
+            }
+            ExpressionKind::Block(expressions) => {
+                self.env.new_scope();
+                for expression in expressions {
+                    self.walk(expression);
+                }
+                if self.visible.is_none() && touches(*span, self.offset) {
+                    self.visible = Some(self.env.names());
+                }
+
+                self.env.remove_top_scope();
+            }
+            ExpressionKind::Application(application) => {
+                for argument in &application.arguments {
+                    if let Some((name_span, Identifier(name))) = &argument.name {
+                        if touches(*name_span, self.offset) {
+                            let Identifier(function_name) = application.function_name.1;
+                            self.argument = Some((function_name, *name));
+                        }
+                    }
+
+                    self.walk(&argument.value);
+                }
+            }
+            ExpressionKind::Store(store) => {
+                self.walk(&store.pointer);
+                self.walk(&store.expression);
+            }
+            ExpressionKind::While(while_expression) => {
+                self.walk(&while_expression.condition);
+                self.walk(&while_expression.expression);
+            }
+            ExpressionKind::Return(Some(expression)) | ExpressionKind::Unsafe(expression) => {
+                self.walk(expression)
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Formats the signature of a function or an external declaration.
+///
+/// # Arguments
+/// * `keyword` - The keyword that declares the function.
+/// * `name` - The name of the function.
+/// * `parameters` - The formatted parameters of the function.
+/// * `variadic` - Whether the function accepts any number of extra arguments.
+/// * `return_type` - The return type of the function.
+fn signature(
+    keyword: &str,
+    name: &str,
+    parameters: impl Iterator<Item = String>,
+    variadic: bool,
+    return_type: Type,
+) -> String {
+    let parameters = parameters
+        .chain(Some("...".to_string()).filter(|_| variadic))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("{} {}({}) :: {}", keyword, name, parameters, return_type)
+}
+
+/// Returns the span of a whole function, from its prototype to the end of its body.
+///
+/// # Arguments
+/// * `function` - The function.
+fn function_span<'a>(function: &Function<'a>) -> Span<'a> {
+    function.prototype.span.combine(function.body.0)
+}
+
+/// Finds the identifier under the offset, or the identifier that ends at the
+/// offset, since the cursor is often placed right after a name.
+///
+/// # Arguments
+/// * `tokens` - The tokens of the document.
+/// * `offset` - The byte offset of the cursor.
+fn identifier_at<'a>(tokens: &[Token<'a>], offset: usize) -> Option<(Span<'a>, usize)> {
+    let identifiers = tokens.iter().filter_map(|(span, kind)| match kind {
+        TokenKind::Identifier(id) => Some((*span, *id)),
+        _ => None,
+    });
+
+    identifiers
+        .clone()
+        .find(|(span, _)| span.start <= offset && offset < span.end)
+        .or_else(|| identifiers.clone().find(|(span, _)| span.end == offset))
+}
+
+/// Returns the span of the first identifier with the name within an item, which
+/// is the name of the item. The span of the item is returned if there is none.
+///
+/// # Arguments
+/// * `tokens` - The tokens of the document.
+/// * `item_span` - The `Span` of the item.
+/// * `name` - The id of the name of the item.
+fn name_span<'a>(tokens: &[Token<'a>], item_span: Span<'a>, name: usize) -> Span<'a> {
+    tokens
+        .iter()
+        .find(|(span, kind)| item_span.contains(span) && *kind == TokenKind::Identifier(name))
+        .map_or(item_span, |(span, _)| *span)
+}
+
+/// Determines whether the offset lies within the span or right after it.
+///
+/// # Arguments
+/// * `span` - The `Span` to inspect.
+/// * `offset` - The byte offset of the cursor.
+fn touches(span: Span, offset: usize) -> bool {
+    span.start <= offset && offset <= span.end
+}
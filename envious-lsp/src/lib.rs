@@ -0,0 +1,253 @@
+//! A language server for Envious. The server speaks the Language Server Protocol
+//! and reports the errors and warnings of open documents as diagnostics, and
+//! answers hover, go-to-definition, completion and document symbol requests.
+//! Every document is analyzed on its own, so names are only known within a file.
+
+pub mod analysis;
+pub mod line_index;
+
+use std::{collections::HashMap, error::Error};
+
+use lsp_server::{
+    Connection, ErrorCode, ExtractError, Message, Notification, Request, RequestId, Response,
+};
+use lsp_types::{
+    notification::{
+        DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument,
+        Notification as NotificationTrait, PublishDiagnostics,
+    },
+    request::{
+        Completion, DocumentSymbolRequest, GotoDefinition, HoverRequest, Request as RequestTrait,
+    },
+    CompletionOptions, CompletionResponse, DocumentSymbolResponse, GotoDefinitionResponse, Hover,
+    HoverContents, HoverProviderCapability, Location, MarkupContent, MarkupKind, OneOf,
+    PublishDiagnosticsParams, ServerCapabilities, TextDocumentPositionParams,
+    TextDocumentSyncCapability, TextDocumentSyncKind, Url,
+};
+
+use crate::line_index::LineIndex;
+
+/// Runs the language server on a connection until the client asks it to exit.
+///
+/// # Arguments
+/// * `connection` - The `Connection` to the client.
+pub fn run(connection: Connection) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        definition_provider: Some(OneOf::Left(true)),
+        completion_provider: Some(CompletionOptions::default()),
+        document_symbol_provider: Some(OneOf::Left(true)),
+        ..ServerCapabilities::default()
+    };
+    connection.initialize(serde_json::to_value(capabilities)?)?;
+
+    let mut server = Server::default();
+    for message in &connection.receiver {
+        match message {
+            Message::Request(request) => {
+                if connection.handle_shutdown(&request)? {
+                    break;
+                }
+
+                let response = server.handle_request(request);
+                connection.sender.send(Message::Response(response))?;
+            }
+            Message::Notification(notification) => {
+                for diagnostics in server.handle_notification(notification) {
+                    let notification =
+                        Notification::new(PublishDiagnostics::METHOD.to_string(), diagnostics);
+                    connection
+                        .sender
+                        .send(Message::Notification(notification))?;
+                }
+            }
+            Message::Response(_) => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Struct that stores the source of every open document.
+#[derive(Default)]
+struct Server {
+    documents: HashMap<Url, String>,
+}
+
+impl Server {
+    /// Answers a request of the client. Unknown requests and requests
+    /// with invalid parameters are responded to with an error.
+    ///
+    /// # Arguments
+    /// * `request` - The `Request` to answer.
+    fn handle_request(&self, request: Request) -> Response {
+        let id = request.id.clone();
+        match request.method.as_str() {
+            HoverRequest::METHOD => match params::<HoverRequest>(request) {
+                Ok(params) => self.respond(id, &params.text_document_position_params, Self::hover),
+                Err(response) => response,
+            },
+            GotoDefinition::METHOD => match params::<GotoDefinition>(request) {
+                Ok(params) => {
+                    self.respond(id, &params.text_document_position_params, Self::definition)
+                }
+                Err(response) => response,
+            },
+            Completion::METHOD => match params::<Completion>(request) {
+                Ok(params) => self.respond(id, &params.text_document_position, Self::completion),
+                Err(response) => response,
+            },
+            DocumentSymbolRequest::METHOD => match params::<DocumentSymbolRequest>(request) {
+                Ok(params) => {
+                    let uri = params.text_document.uri;
+                    let result = self.documents.get(&uri).map(|source| {
+                        DocumentSymbolResponse::Nested(analysis::symbols(uri.as_str(), source))
+                    });
+                    Response::new_ok(id, result)
+                }
+                Err(response) => response,
+            },
+            method => Response::new_err(
+                id,
+                ErrorCode::MethodNotFound as i32,
+                format!("unknown request `{}`", method),
+            ),
+        }
+    }
+
+    /// Answers a request about a position in a document. The result is
+    /// `null` if the document is not open or nothing is found.
+    ///
+    /// # Arguments
+    /// * `id` - The id of the request.
+    /// * `params` - The document and the position of the request.
+    /// * `handler` - The function that finds the result.
+    fn respond<T: serde::Serialize>(
+        &self,
+        id: RequestId,
+        params: &TextDocumentPositionParams,
+        handler: fn(&Url, &str, &LineIndex, usize) -> Option<T>,
+    ) -> Response {
+        let uri = &params.text_document.uri;
+        let result = self.documents.get(uri).and_then(|source| {
+            let index = LineIndex::new(source);
+            let offset = index.offset(params.position);
+            handler(uri, source, &index, offset)
+        });
+
+        Response::new_ok(id, result)
+    }
+
+    fn hover(uri: &Url, source: &str, index: &LineIndex, offset: usize) -> Option<Hover> {
+        let (span, description) = analysis::hover(uri.as_str(), source, offset)?;
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: format!("```envious\n{}\n```", description),
+            }),
+            range: Some(index.range(span)),
+        })
+    }
+
+    fn definition(
+        uri: &Url,
+        source: &str,
+        index: &LineIndex,
+        offset: usize,
+    ) -> Option<GotoDefinitionResponse> {
+        let span = analysis::definition(uri.as_str(), source, offset)?;
+        let location = Location::new(uri.clone(), index.range(span));
+        Some(GotoDefinitionResponse::Scalar(location))
+    }
+
+    fn completion(
+        uri: &Url,
+        source: &str,
+        _: &LineIndex,
+        offset: usize,
+    ) -> Option<CompletionResponse> {
+        let items = analysis::completions(uri.as_str(), source, offset);
+        Some(CompletionResponse::Array(items))
+    }
+
+    /// Updates the open documents. Returns the diagnostics to
+    /// publish for the documents that were opened, changed or closed.
+    ///
+    /// # Arguments
+    /// * `notification` - The `Notification` sent by the client.
+    fn handle_notification(&mut self, notification: Notification) -> Vec<PublishDiagnosticsParams> {
+        let uri = match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                match serde_json::from_value::<<DidOpenTextDocument as NotificationTrait>::Params>(
+                    notification.params,
+                ) {
+                    Ok(params) => {
+                        let document = params.text_document;
+                        self.documents.insert(document.uri.clone(), document.text);
+                        document.uri
+                    }
+                    Err(_) => return vec![],
+                }
+            }
+            DidChangeTextDocument::METHOD => {
+                match serde_json::from_value::<<DidChangeTextDocument as NotificationTrait>::Params>(
+                    notification.params,
+                ) {
+                    // The server asks for full synchronization, so the
+                    // last change contains the whole document.
+                    Ok(mut params) => match params.content_changes.pop() {
+                        Some(change) => {
+                            let uri = params.text_document.uri;
+                            self.documents.insert(uri.clone(), change.text);
+                            uri
+                        }
+                        None => return vec![],
+                    },
+                    Err(_) => return vec![],
+                }
+            }
+            DidCloseTextDocument::METHOD => {
+                match serde_json::from_value::<<DidCloseTextDocument as NotificationTrait>::Params>(
+                    notification.params,
+                ) {
+                    Ok(params) => {
+                        let uri = params.text_document.uri;
+                        self.documents.remove(&uri);
+                        return vec![PublishDiagnosticsParams::new(uri, vec![], None)];
+                    }
+                    Err(_) => return vec![],
+                }
+            }
+            _ => return vec![],
+        };
+
+        let diagnostics = analysis::diagnostics(&uri, &self.documents[&uri]);
+        vec![PublishDiagnosticsParams::new(uri, diagnostics, None)]
+    }
+}
+
+/// Reads the parameters of a request. If they are invalid,
+/// the error response to send to the client is returned instead.
+///
+/// # Arguments
+/// * `request` - The `Request` to read.
+fn params<R>(request: Request) -> Result<R::Params, Response>
+where
+    R: lsp_types::request::Request,
+{
+    let id = request.id.clone();
+    match request.extract(R::METHOD) {
+        Ok((_, params)) => Ok(params),
+        Err(ExtractError::JsonError { method, error }) => Err(Response::new_err(
+            id,
+            ErrorCode::InvalidParams as i32,
+            format!("invalid parameters for `{}`: {}", method, error),
+        )),
+        Err(ExtractError::MethodMismatch(request)) => Err(Response::new_err(
+            id,
+            ErrorCode::MethodNotFound as i32,
+            format!("unknown request `{}`", request.method),
+        )),
+    }
+}
@@ -0,0 +1,75 @@
+use envyc::error::Span;
+use lsp_types::{Position, Range};
+
+/// Struct that converts between the byte offsets stored in a `Span` and the
+/// positions used by the Language Server Protocol. A position counts the
+/// characters of a line in UTF-16 code units, so a character such as `α` may take
+/// two bytes in the source but only a single unit in a position.
+pub struct LineIndex<'a> {
+    // The source of the document.
+    source: &'a str,
+    // The byte offset of the first byte of every line.
+    line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    /// Creates a new `LineIndex`.
+    ///
+    /// # Arguments
+    /// * `source` - The source of the document.
+    pub fn new(source: &'a str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(index, _)| index + 1))
+            .collect();
+
+        Self {
+            source,
+            line_starts,
+        }
+    }
+
+    /// Converts a byte offset into a position.
+    ///
+    /// # Arguments
+    /// * `offset` - The byte offset, which must lie on the boundary of a character.
+    pub fn position(&self, offset: usize) -> Position {
+        let offset = offset.min(self.source.len());
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        let character = self.source[self.line_starts[line]..offset]
+            .encode_utf16()
+            .count();
+
+        Position::new(line as u32, character as u32)
+    }
+
+    /// Converts a position into a byte offset. Positions after the end of
+    /// a line refer to the end of the line.
+    ///
+    /// # Arguments
+    /// * `position` - The position to convert.
+    pub fn offset(&self, position: Position) -> usize {
+        let line = match self.line_starts.get(position.line as usize) {
+            Some(&start) => start,
+            None => return self.source.len(),
+        };
+
+        let mut units = 0;
+        for (index, character) in self.source[line..].char_indices() {
+            if character == '\n' || units >= position.character as usize {
+                return line + index;
+            }
+
+            units += character.len_utf16();
+        }
+
+        self.source.len()
+    }
+
+    /// Converts the byte offsets of a span into a range.
+    ///
+    /// # Arguments
+    /// * `span` - The `Span` to convert.
+    pub fn range(&self, span: Span) -> Range {
+        Range::new(self.position(span.start), self.position(span.end))
+    }
+}
@@ -0,0 +1,10 @@
+use std::error::Error;
+
+use lsp_server::Connection;
+
+fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
+    let (connection, io_threads) = Connection::stdio();
+    envious_lsp::run(connection)?;
+    io_threads.join()?;
+    Ok(())
+}
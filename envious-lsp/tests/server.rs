@@ -0,0 +1,212 @@
+use std::thread;
+
+use lsp_server::{Connection, Message, Notification, Request, RequestId, Response};
+use lsp_types::{
+    notification::{
+        DidChangeTextDocument, DidOpenTextDocument, Exit, Initialized,
+        Notification as NotificationTrait, PublishDiagnostics,
+    },
+    request::{
+        Completion, DocumentSymbolRequest, GotoDefinition, HoverRequest, Initialize,
+        Request as RequestTrait, Shutdown,
+    },
+    CompletionResponse, DocumentSymbolResponse, GotoDefinitionResponse, Hover, HoverContents,
+    Position, PublishDiagnosticsParams, Range,
+};
+use serde_json::{json, Value};
+
+const URI: &str = "file:///test.envy";
+
+const SOURCE: &str = "extern printi(Int) :: Void
+
+const LIMIT: Int = 10
+
+define square(number: Int) :: Int = number * number
+
+define main() :: Int = {
+    let total = square(number: LIMIT)
+    printi(total)
+    total
+}
+";
+
+/// A client that talks to a server running on another thread.
+struct Client {
+    connection: Connection,
+    server: Option<thread::JoinHandle<()>>,
+    next_id: i32,
+}
+
+impl Client {
+    fn start() -> Self {
+        let (server, connection) = Connection::memory();
+        let server = thread::spawn(move || envious_lsp::run(server).unwrap());
+        let mut client = Self {
+            connection,
+            server: Some(server),
+            next_id: 0,
+        };
+
+        client.request::<Initialize>(json!({ "capabilities": {} }));
+        client.notify::<Initialized>(json!({}));
+        client
+    }
+
+    fn request<R: RequestTrait>(&mut self, params: Value) -> Value {
+        self.next_id += 1;
+        let id = RequestId::from(self.next_id);
+        let request = Request::new(id.clone(), R::METHOD.to_string(), params);
+        self.connection
+            .sender
+            .send(Message::Request(request))
+            .unwrap();
+
+        loop {
+            match self.connection.receiver.recv().unwrap() {
+                Message::Response(Response {
+                    id: response_id,
+                    result,
+                    error,
+                }) if response_id == id => {
+                    assert!(error.is_none(), "request failed: {:?}", error);
+                    return result.unwrap_or(Value::Null);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn notify<N: NotificationTrait>(&self, params: Value) {
+        let notification = Notification::new(N::METHOD.to_string(), params);
+        self.connection
+            .sender
+            .send(Message::Notification(notification))
+            .unwrap();
+    }
+
+    fn diagnostics(&self) -> PublishDiagnosticsParams {
+        loop {
+            if let Message::Notification(notification) = self.connection.receiver.recv().unwrap() {
+                if notification.method == PublishDiagnostics::METHOD {
+                    return serde_json::from_value(notification.params).unwrap();
+                }
+            }
+        }
+    }
+
+    fn open(&self, text: &str) -> PublishDiagnosticsParams {
+        self.notify::<DidOpenTextDocument>(json!({
+            "textDocument": { "uri": URI, "languageId": "envious", "version": 1, "text": text }
+        }));
+        self.diagnostics()
+    }
+}
+
+impl Drop for Client {
+    fn drop(&mut self) {
+        self.request::<Shutdown>(Value::Null);
+        self.notify::<Exit>(Value::Null);
+        self.server.take().unwrap().join().unwrap();
+    }
+}
+
+fn position(line: u32, character: u32) -> Value {
+    json!({ "textDocument": { "uri": URI }, "position": { "line": line, "character": character } })
+}
+
+#[test]
+fn diagnostics_are_published_for_open_documents() {
+    let client = Client::start();
+    assert!(client.open(SOURCE).diagnostics.is_empty());
+
+    client.notify::<DidChangeTextDocument>(json!({
+        "textDocument": { "uri": URI, "version": 2 },
+        "contentChanges": [{ "text": "define main() :: Int = 1 + true\n" }]
+    }));
+    let published = client.diagnostics();
+    assert_eq!(published.diagnostics.len(), 1);
+    let diagnostic = &published.diagnostics[0];
+    assert_eq!(diagnostic.source.as_deref(), Some("envious"));
+    assert_eq!(diagnostic.range.start.line, 0);
+}
+
+#[test]
+fn hover_shows_types_and_signatures() {
+    let mut client = Client::start();
+    client.open(SOURCE);
+
+    let hover = |client: &mut Client, line, character| {
+        let hover: Hover =
+            serde_json::from_value(client.request::<HoverRequest>(position(line, character)))
+                .unwrap();
+        match hover.contents {
+            HoverContents::Markup(content) => content.value,
+            contents => panic!("expected markup, found {:?}", contents),
+        }
+    };
+
+    assert!(hover(&mut client, 7, 9).contains("total: Int"));
+    assert!(hover(&mut client, 7, 18).contains("define square(number: Int) :: Int"));
+    assert!(hover(&mut client, 8, 4).contains("extern printi(Int) :: Void"));
+}
+
+#[test]
+fn definitions_resolve_variables_arguments_and_items() {
+    let mut client = Client::start();
+    client.open(SOURCE);
+
+    let mut definition = |line, character| {
+        let response: GotoDefinitionResponse =
+            serde_json::from_value(client.request::<GotoDefinition>(position(line, character)))
+                .unwrap();
+        match response {
+            GotoDefinitionResponse::Scalar(location) => location.range,
+            response => panic!("expected a single location, found {:?}", response),
+        }
+    };
+
+    let range = |line, start, end| Range::new(Position::new(line, start), Position::new(line, end));
+    // The variable `total` in `printi(total)`.
+    assert_eq!(definition(8, 12), range(7, 8, 13));
+    // The function `square`.
+    assert_eq!(definition(7, 18), range(4, 7, 13));
+    // The named argument `number`.
+    assert_eq!(definition(7, 25), range(4, 14, 20));
+    // The constant `LIMIT`.
+    assert_eq!(definition(7, 33), range(2, 6, 11));
+}
+
+#[test]
+fn completion_lists_names_in_scope() {
+    let mut client = Client::start();
+    client.open(SOURCE);
+
+    let response: CompletionResponse =
+        serde_json::from_value(client.request::<Completion>(position(9, 4))).unwrap();
+    let labels = match response {
+        CompletionResponse::Array(items) => {
+            items.into_iter().map(|item| item.label).collect::<Vec<_>>()
+        }
+        response => panic!("expected a list of items, found {:?}", response),
+    };
+    assert_eq!(labels, ["LIMIT", "main", "printi", "square", "total"]);
+}
+
+#[test]
+fn document_symbols_are_listed_in_order() {
+    let mut client = Client::start();
+    client.open(SOURCE);
+
+    let response: DocumentSymbolResponse = serde_json::from_value(
+        client.request::<DocumentSymbolRequest>(json!({ "textDocument": { "uri": URI } })),
+    )
+    .unwrap();
+    let names = match response {
+        DocumentSymbolResponse::Nested(symbols) => symbols
+            .into_iter()
+            .map(|symbol| symbol.name)
+            .collect::<Vec<_>>(),
+        response => panic!("expected nested symbols, found {:?}", response),
+    };
+    assert_eq!(names, ["printi", "LIMIT", "square", "main"]);
+}
@@ -130,24 +130,20 @@ pub fn run_tui() -> Result<(), Box<dyn Error>> {
                             app.line_width += 1;
                         }
                     }
-                    KeyCode::Up => {
-                        if app.current_line > 1 {
-                            app.index -= app.line_width;
-                            app.current_line -= 1;
-                            let current_line_width = get_current_line_width(&app);
-                            app.line_width = min(app.line_width, current_line_width);
-                            app.index -= current_line_width - app.line_width + 1;
-                        }
+                    KeyCode::Up if app.current_line > 1 => {
+                        app.index -= app.line_width;
+                        app.current_line -= 1;
+                        let current_line_width = get_current_line_width(&app);
+                        app.line_width = min(app.line_width, current_line_width);
+                        app.index -= current_line_width - app.line_width + 1;
                     }
-                    KeyCode::Down => {
-                        if app.current_line < app.line_count {
-                            let remaining_chars = get_current_line_width(&app) - app.line_width + 1;
-                            app.index += remaining_chars;
-                            app.current_line += 1;
-                            let current_line_width = get_current_line_width(&app);
-                            app.line_width = min(app.line_width, current_line_width);
-                            app.index += app.line_width;
-                        }
+                    KeyCode::Down if app.current_line < app.line_count => {
+                        let remaining_chars = get_current_line_width(&app) - app.line_width + 1;
+                        app.index += remaining_chars;
+                        app.current_line += 1;
+                        let current_line_width = get_current_line_width(&app);
+                        app.line_width = min(app.line_width, current_line_width);
+                        app.index += app.line_width;
                     }
                     KeyCode::Esc => {
                         app.focused_block = FocusedBlock::Output;
@@ -180,10 +176,10 @@ fn compile_code(code: &str) -> Result<String, Vec<String>> {
     let tokens =
         lex("editor", code.as_bytes(), &mut interner).report_result(&error_reporter, true)?;
     let filtered_tokens = filter_tokens(tokens);
-    let program = parse(filtered_tokens).report_result(&error_reporter, true)?;
+    let program = parse(filtered_tokens, &interner).report_result(&error_reporter, true)?;
     let mut type_env = Environment::default();
     let mut function_table = FunctionTable::default();
-    let typed_program = type_check(program, &mut type_env, &mut function_table)
+    let typed_program = type_check(program, &mut type_env, &mut function_table, &interner)
         .report_result(&error_reporter, true)?;
     compile(&typed_program, "editor", &mut interner, None).report_result(&error_reporter, true)
 }
@@ -1,28 +1,33 @@
 use inkwell::{
     builder::Builder,
     context::Context,
-    module::Module,
-    types::{BasicType, BasicTypeEnum},
-    values::{BasicValue, BasicValueEnum, FunctionValue, PointerValue},
-    FloatPredicate, IntPredicate,
+    module::{Linkage, Module},
+    types::{BasicMetadataTypeEnum, BasicType, BasicTypeEnum, PointerType},
+    values::{BasicMetadataValueEnum, BasicValue, BasicValueEnum, FunctionValue, PointerValue},
+    AddressSpace, FloatPredicate, GlobalVisibility, IntPredicate,
 };
 
 use crate::{
     environment::Environment,
-    error::Error,
+    error::{suggestion::Suggestion, Error},
     interner::Interner,
     parser::{
         expression::{BinaryOperation, UnaryOperation},
-        typed_ast::{TypedExternDeclaration, TypedFunction, TypedProgram, TypedPrototype},
+        typed_ast::{
+            TypedConstant, TypedExternDeclaration, TypedFunction, TypedProgram, TypedPrototype,
+        },
         typed_expression::{
             TypedApplication, TypedBinary, TypedExpression, TypedExpressionKind, TypedIdentifier,
-            TypedIf, TypedLet, TypedUnary, TypedWhile,
+            TypedIf, TypedLet, TypedStore, TypedUnary, TypedWhile,
         },
     },
-    semantic_analyzer::types::Type,
+    semantic_analyzer::{
+        constant_evaluator::ConstantValue,
+        types::{PointeeType, Type},
+    },
 };
 
-use super::context::FunctionContext;
+use super::{context::FunctionContext, debug_info::DebugInfo};
 
 pub struct CodeGenerator<'a, 'b, 'ctx> {
     context: &'ctx Context,
@@ -30,6 +35,8 @@ pub struct CodeGenerator<'a, 'b, 'ctx> {
     builder: &'a Builder<'ctx>,
     interner: &'b mut Interner<String>,
     env: &'a mut Environment<PointerValue<'ctx>>,
+    // The description of the source, if the module is compiled with debug information.
+    debug_info: Option<DebugInfo<'ctx>>,
 }
 
 impl<'a, 'b, 'c, 'ctx> CodeGenerator<'a, 'b, 'ctx> {
@@ -46,29 +53,61 @@ impl<'a, 'b, 'c, 'ctx> CodeGenerator<'a, 'b, 'ctx> {
             builder,
             interner,
             env,
+            debug_info: None,
         }
     }
 
+    /// Describes the source of the generated code, so that it can be debugged.
+    ///
+    /// # Arguments
+    /// * `debug_info` - The compile unit of the file the program was parsed from.
+    pub fn with_debug_info(mut self, debug_info: DebugInfo<'ctx>) -> Self {
+        self.debug_info = Some(debug_info);
+        self
+    }
+
     pub fn generate_program(&mut self, program: &TypedProgram<'c>) -> Result<(), Vec<Error<'c>>> {
         let mut errors = vec![];
         for extern_declaration in &program.extern_declarations {
             if let Err(error) = self.generate_extern(extern_declaration) {
-                errors.push(error);
+                errors.push(*error);
             }
         }
 
+        for constant in &program.constants {
+            self.generate_constant(constant);
+        }
+
         for function in &program.functions {
             if let Err(error) = self.generate_prototype(&function.prototype) {
-                errors.push(error);
+                errors.push(*error);
             }
         }
 
         if !errors.is_empty() {
             Err(errors)
         } else {
+            let mut generated_functions = vec![];
             for function in &program.functions {
-                if let Err(error) = self.generate_function(function) {
-                    errors.push(error);
+                match self.generate_function(function) {
+                    Ok(generated_function) => generated_functions.push(generated_function),
+                    Err(error) => errors.push(*error),
+                }
+            }
+
+            if let Some(debug_info) = &self.debug_info {
+                debug_info.finalize();
+            }
+
+            // The descriptions of the functions are only complete once they are
+            // finalized, so the functions are verified afterwards.
+            for function in generated_functions {
+                if !function.verify(true) {
+                    unsafe {
+                        function.delete();
+                    }
+
+                    errors.push(Error::LLVMFunctionFailure);
                 }
             }
 
@@ -83,36 +122,55 @@ impl<'a, 'b, 'c, 'ctx> CodeGenerator<'a, 'b, 'ctx> {
     fn generate_extern(
         &mut self,
         extern_declaration: &TypedExternDeclaration<'c>,
-    ) -> Result<(), Error<'c>> {
+    ) -> Result<(), Box<Error<'c>>> {
         let parameter_types = extern_declaration
             .parameters
             .iter()
             .map(|parameter| parameter.0)
-            .map(|ty| convert_basic_type(ty, self.context))
-            .collect::<Vec<_>>();
+            .map(|ty| convert_basic_type(ty, self.context).into())
+            .collect::<Vec<BasicMetadataTypeEnum>>();
 
+        let variadic = extern_declaration.variadic;
         let function_type = if let Type::Void = extern_declaration.return_type.0 {
-            self.context.void_type().fn_type(&parameter_types, false)
+            self.context.void_type().fn_type(&parameter_types, variadic)
         } else {
             convert_type(extern_declaration.return_type.0, self.context)
-                .fn_type(&parameter_types, false)
+                .fn_type(&parameter_types, variadic)
         };
 
         self.module.add_function(
-            &self.interner.get(extern_declaration.name),
+            self.interner.get(extern_declaration.name),
             function_type,
             None,
         );
         Ok(())
     }
 
-    fn generate_prototype(&mut self, prototype: &TypedPrototype<'c>) -> Result<(), Error<'c>> {
+    /// Generates a global constant that holds the value computed by the
+    /// constant evaluator. The global is only visible within its module.
+    ///
+    /// # Arguments
+    /// * `constant` - The constant to generate.
+    fn generate_constant(&mut self, constant: &TypedConstant<'c>) {
+        let (_, name) = constant.name;
+        let global = self.module.add_global(
+            convert_basic_type(constant.ty, self.context),
+            None,
+            self.interner.get(name),
+        );
+        global.set_initializer(&self.compile_constant_value(constant.value));
+        global.set_constant(true);
+        global.set_linkage(Linkage::Internal);
+        self.env.define(name, global.as_pointer_value());
+    }
+
+    fn generate_prototype(&mut self, prototype: &TypedPrototype<'c>) -> Result<(), Box<Error<'c>>> {
         let parameter_types = prototype
             .parameters
             .iter()
             .map(|parameter| parameter.ty)
-            .map(|ty| convert_basic_type(ty, self.context))
-            .collect::<Vec<_>>();
+            .map(|ty| convert_basic_type(ty, self.context).into())
+            .collect::<Vec<BasicMetadataTypeEnum>>();
 
         let function_type = if let Type::Void = prototype.return_type {
             self.context.void_type().fn_type(&parameter_types, false)
@@ -120,40 +178,79 @@ impl<'a, 'b, 'c, 'ctx> CodeGenerator<'a, 'b, 'ctx> {
             convert_type(prototype.return_type, self.context).fn_type(&parameter_types, false)
         };
 
-        self.module
-            .add_function(&self.interner.get(prototype.name), function_type, None);
+        let function =
+            self.module
+                .add_function(self.interner.get(prototype.name), function_type, None);
+
+        // Exported functions are part of the public interface of a library, so
+        // they remain visible outside of it. They use the C calling convention,
+        // which is the default of LLVM. Every other function is hidden, although
+        // it can still be linked with the other files of the same program.
+        if prototype.export {
+            function
+                .as_global_value()
+                .set_visibility(GlobalVisibility::Default);
+        } else {
+            function
+                .as_global_value()
+                .set_visibility(GlobalVisibility::Hidden);
+        }
+
         Ok(())
     }
 
-    fn generate_function(&mut self, defined_function: &TypedFunction<'c>) -> Result<(), Error<'c>> {
+    fn generate_function(
+        &mut self,
+        defined_function: &TypedFunction<'c>,
+    ) -> Result<FunctionValue<'ctx>, Box<Error<'c>>> {
         let function = self
             .module
             .get_function(self.interner.get(defined_function.prototype.name))
-            .ok_or(Error::UnknownFunction(defined_function.prototype.span))?;
+            .ok_or(Error::UnknownFunction {
+                span: defined_function.prototype.span,
+                suggestion: Suggestion::Resolved(None),
+            })?;
         let entry_block = self.context.append_basic_block(function, "entry");
         let return_block = self.context.append_basic_block(function, "return");
         self.builder.position_at_end(entry_block);
 
+        let prototype = &defined_function.prototype;
+        if let Some(debug_info) = &mut self.debug_info {
+            debug_info.start_function(function, prototype, defined_function.body.0, self.interner);
+            debug_info.set_location(self.builder, prototype.span);
+        }
+
         self.env.new_scope();
-        function
+        for (index, (llvm_param, param)) in function
             .get_param_iter()
-            .zip(defined_function.prototype.parameters.iter())
-            .map(|(llvm_param, param)| (llvm_param, param.name))
-            .for_each(|(llvm_param, param_name)| {
-                let name = self.interner.get(param_name);
-                llvm_param.set_name(name);
-                let pointer = self.builder.build_alloca(llvm_param.get_type(), name);
-                self.builder.build_store(pointer, llvm_param);
-                self.env.define(param_name, pointer);
-            });
-
-        let mut function_context =
-            FunctionContext::new(defined_function.prototype.name, return_block);
+            .zip(prototype.parameters.iter())
+            .enumerate()
+        {
+            let name = self.interner.get(param.name);
+            llvm_param.set_name(name);
+            let pointer = self.builder.build_alloca(llvm_param.get_type(), name);
+            self.builder.build_store(pointer, llvm_param);
+            if let Some(debug_info) = &self.debug_info {
+                debug_info.declare_parameter(
+                    pointer,
+                    name,
+                    index as u32 + 1,
+                    param.span,
+                    param.ty,
+                    entry_block,
+                );
+            }
+
+            self.env.define(param.name, pointer);
+        }
+
+        let mut function_context = FunctionContext::new(return_block);
         let expression =
             self.compile_expression(&defined_function.body, function, &mut function_context)?;
 
         if defined_function.body.1.get_type() != Type::Never {
             if defined_function.body.1.get_type() != Type::Void {
+                let expression = self.coerce_to_return_type(expression, function);
                 function_context
                     .add_return_block(self.builder.get_insert_block().unwrap(), Some(expression));
             } else {
@@ -164,6 +261,10 @@ impl<'a, 'b, 'c, 'ctx> CodeGenerator<'a, 'b, 'ctx> {
         }
 
         self.builder.position_at_end(return_block);
+        if let Some(debug_info) = &self.debug_info {
+            debug_info.set_location(self.builder, defined_function.body.0);
+        }
+
         if defined_function.prototype.return_type != Type::Void {
             let return_value = self.builder.build_phi(
                 convert_basic_type(defined_function.prototype.return_type, self.context),
@@ -183,41 +284,52 @@ impl<'a, 'b, 'c, 'ctx> CodeGenerator<'a, 'b, 'ctx> {
         }
 
         self.env.remove_top_scope();
+        if let Some(debug_info) = &mut self.debug_info {
+            debug_info.finish_function(self.builder);
+        }
 
-        if function.verify(true) {
-            Ok(())
-        } else {
-            unsafe {
-                function.delete();
-            }
+        Ok(function)
+    }
+
+    fn compile_expression(
+        &mut self,
+        expression: &TypedExpression<'c>,
+        current_function: FunctionValue<'ctx>,
+        function_context: &mut FunctionContext<'ctx>,
+    ) -> Result<BasicValueEnum<'ctx>, Box<Error<'c>>> {
+        if let Some(debug_info) = &mut self.debug_info {
+            debug_info.enter_expression(self.builder, expression.0);
+        }
 
-            Err(Error::LLVMFunctionFailure)
+        let value = self.compile_expression_kind(expression, current_function, function_context);
+        if let Some(debug_info) = &mut self.debug_info {
+            debug_info.exit_expression(self.builder);
         }
+
+        value
     }
 
-    fn compile_expression(
+    fn compile_expression_kind(
         &mut self,
         expression: &TypedExpression<'c>,
         current_function: FunctionValue<'ctx>,
         function_context: &mut FunctionContext<'ctx>,
-    ) -> Result<BasicValueEnum<'ctx>, Error<'c>> {
+    ) -> Result<BasicValueEnum<'ctx>, Box<Error<'c>>> {
         match expression.1 {
             TypedExpressionKind::Int(value) => {
-                let int = self.context.i64_type().const_int(value.abs() as u64, false);
-                if value < 0 {
-                    Ok(BasicValueEnum::IntValue(int.const_neg()))
-                } else {
-                    Ok(BasicValueEnum::IntValue(int))
-                }
+                Ok(self.compile_constant_value(ConstantValue::Int(value)))
             }
-            TypedExpressionKind::Float(value) => Ok(BasicValueEnum::FloatValue(
-                self.context.f64_type().const_float(value),
-            )),
-            TypedExpressionKind::Boolean(value) => Ok(BasicValueEnum::IntValue(
-                self.context.bool_type().const_int(value as u64, false),
-            )),
-            TypedExpressionKind::Char(value) => Ok(BasicValueEnum::IntValue(
-                self.context.i8_type().const_int(value as u64, false),
+            TypedExpressionKind::Float(value) => {
+                Ok(self.compile_constant_value(ConstantValue::Float(value)))
+            }
+            TypedExpressionKind::Boolean(value) => {
+                Ok(self.compile_constant_value(ConstantValue::Boolean(value)))
+            }
+            TypedExpressionKind::Char(value) => {
+                Ok(self.compile_constant_value(ConstantValue::Char(value)))
+            }
+            TypedExpressionKind::Null => Ok(BasicValueEnum::PointerValue(
+                convert_pointer_type(PointeeType::Void, 1, self.context).const_null(),
             )),
             TypedExpressionKind::Identifier(ref inner) => self.compile_identifier(inner),
             TypedExpressionKind::Unary(ref inner) => {
@@ -235,10 +347,14 @@ impl<'a, 'b, 'c, 'ctx> CodeGenerator<'a, 'b, 'ctx> {
                     self.context.i64_type().const_zero(),
                 ))
             }
-            TypedExpressionKind::Block(ref expressions) => expressions.iter().fold(
+            TypedExpressionKind::Store(ref inner) => {
+                self.compile_store(inner, current_function, function_context)?;
                 Ok(BasicValueEnum::IntValue(
                     self.context.i64_type().const_zero(),
-                )),
+                ))
+            }
+            TypedExpressionKind::Block(ref expressions) => expressions.iter().try_fold(
+                BasicValueEnum::IntValue(self.context.i64_type().const_zero()),
                 |_, expression| {
                     self.compile_expression(expression, current_function, function_context)
                 },
@@ -255,11 +371,11 @@ impl<'a, 'b, 'c, 'ctx> CodeGenerator<'a, 'b, 'ctx> {
             TypedExpressionKind::Return(ref value) => {
                 let return_value = value
                     .as_ref()
-                    .map(|expression| {
+                    .and_then(|expression| {
                         self.compile_expression(expression, current_function, function_context)
                             .ok()
                     })
-                    .flatten();
+                    .map(|value| self.coerce_to_return_type(value, current_function));
                 function_context
                     .add_return_block(self.builder.get_insert_block().unwrap(), return_value);
                 self.builder
@@ -272,13 +388,46 @@ impl<'a, 'b, 'c, 'ctx> CodeGenerator<'a, 'b, 'ctx> {
                     ))
                 }
             }
+            TypedExpressionKind::Unsafe(ref inner) => {
+                self.compile_expression(inner, current_function, function_context)
+            }
+            // Programs with syntax errors are never compiled.
+            TypedExpressionKind::Error => unreachable!(),
+        }
+    }
+
+    fn compile_constant_value(&self, value: ConstantValue) -> BasicValueEnum<'ctx> {
+        match value {
+            ConstantValue::Int(value) => {
+                // `i64::MIN` has no positive counterpart, so its absolute
+                // value is computed without overflowing.
+                let int = self
+                    .context
+                    .i64_type()
+                    .const_int(value.unsigned_abs(), false);
+                if value < 0 {
+                    BasicValueEnum::IntValue(int.const_neg())
+                } else {
+                    BasicValueEnum::IntValue(int)
+                }
+            }
+            ConstantValue::Float(value) => {
+                BasicValueEnum::FloatValue(self.context.f64_type().const_float(value))
+            }
+            ConstantValue::Boolean(value) => {
+                BasicValueEnum::IntValue(self.context.bool_type().const_int(value as u64, false))
+            }
+            ConstantValue::Char(value) => {
+                BasicValueEnum::IntValue(self.context.i8_type().const_int(value as u64, false))
+            }
+            ConstantValue::Void => BasicValueEnum::IntValue(self.context.i64_type().const_zero()),
         }
     }
 
     fn compile_identifier(
         &mut self,
         identifier: &TypedIdentifier,
-    ) -> Result<BasicValueEnum<'ctx>, Error<'c>> {
+    ) -> Result<BasicValueEnum<'ctx>, Box<Error<'c>>> {
         let value = self.env.get(identifier.id).unwrap();
         Ok(self
             .builder
@@ -290,7 +439,17 @@ impl<'a, 'b, 'c, 'ctx> CodeGenerator<'a, 'b, 'ctx> {
         unary: &TypedUnary<'c>,
         current_function: FunctionValue<'ctx>,
         function_context: &mut FunctionContext<'ctx>,
-    ) -> Result<BasicValueEnum<'ctx>, Error<'c>> {
+    ) -> Result<BasicValueEnum<'ctx>, Box<Error<'c>>> {
+        // The address of a variable is the pointer to its stack slot,
+        // so the operand must not be loaded.
+        if let (UnaryOperation::AddressOf, TypedExpressionKind::Identifier(ref identifier)) =
+            (unary.operation, &unary.expression.1)
+        {
+            return Ok(BasicValueEnum::PointerValue(
+                self.env.get(identifier.id).unwrap(),
+            ));
+        }
+
         let expression =
             self.compile_expression(&unary.expression, current_function, function_context)?;
         let value = match (unary.operation, expression) {
@@ -304,6 +463,9 @@ impl<'a, 'b, 'c, 'ctx> CodeGenerator<'a, 'b, 'ctx> {
             (UnaryOperation::Not, BasicValueEnum::IntValue(value)) => {
                 BasicValueEnum::IntValue(self.builder.build_not(value, "boolnot"))
             }
+            (UnaryOperation::Dereference, BasicValueEnum::PointerValue(value)) => {
+                self.builder.build_load(value, "deref")
+            }
             _ => unreachable!(),
         };
 
@@ -315,7 +477,7 @@ impl<'a, 'b, 'c, 'ctx> CodeGenerator<'a, 'b, 'ctx> {
         binary: &TypedBinary<'c>,
         current_function: FunctionValue<'ctx>,
         function_context: &mut FunctionContext<'ctx>,
-    ) -> Result<BasicValueEnum<'ctx>, Error<'c>> {
+    ) -> Result<BasicValueEnum<'ctx>, Box<Error<'c>>> {
         let left = self.compile_expression(&binary.left, current_function, function_context)?;
         let right = self.compile_expression(&binary.right, current_function, function_context)?;
         let value = match (binary.operation, left, right) {
@@ -362,6 +524,7 @@ impl<'a, 'b, 'c, 'ctx> CodeGenerator<'a, 'b, 'ctx> {
             (operation, BasicValueEnum::IntValue(left), BasicValueEnum::IntValue(right)) => {
                 let op = match operation {
                     BinaryOperation::Equals => IntPredicate::EQ,
+                    BinaryOperation::NotEquals => IntPredicate::NE,
                     BinaryOperation::LessThan => IntPredicate::SLT,
                     BinaryOperation::GreaterThan => IntPredicate::SGT,
                     BinaryOperation::LessThanEquals => IntPredicate::SLE,
@@ -384,6 +547,7 @@ impl<'a, 'b, 'c, 'ctx> CodeGenerator<'a, 'b, 'ctx> {
             (operation, BasicValueEnum::FloatValue(left), BasicValueEnum::FloatValue(right)) => {
                 let op = match operation {
                     BinaryOperation::Equals => FloatPredicate::OEQ,
+                    BinaryOperation::NotEquals => FloatPredicate::UNE,
                     BinaryOperation::LessThan => FloatPredicate::OLT,
                     BinaryOperation::GreaterThan => FloatPredicate::OGT,
                     BinaryOperation::LessThanEquals => FloatPredicate::OLE,
@@ -396,6 +560,27 @@ impl<'a, 'b, 'c, 'ctx> CodeGenerator<'a, 'b, 'ctx> {
                         .build_float_compare(op, left, right, "floatcmp"),
                 )
             }
+            (
+                operation,
+                BasicValueEnum::PointerValue(left),
+                BasicValueEnum::PointerValue(right),
+            ) => {
+                let op = match operation {
+                    BinaryOperation::Equals => IntPredicate::EQ,
+                    BinaryOperation::NotEquals => IntPredicate::NE,
+                    _ => unreachable!(),
+                };
+
+                // Pointers of different types (e.g. a pointer and `null`) are compared
+                // by their addresses.
+                let left = self
+                    .builder
+                    .build_ptr_to_int(left, self.context.i64_type(), "ptrtoint");
+                let right =
+                    self.builder
+                        .build_ptr_to_int(right, self.context.i64_type(), "ptrtoint");
+                BasicValueEnum::IntValue(self.builder.build_int_compare(op, left, right, "ptrcmp"))
+            }
             _ => unreachable!(),
         };
 
@@ -407,7 +592,7 @@ impl<'a, 'b, 'c, 'ctx> CodeGenerator<'a, 'b, 'ctx> {
         typed_if: &TypedIf<'c>,
         current_function: FunctionValue<'ctx>,
         function_context: &mut FunctionContext<'ctx>,
-    ) -> Result<BasicValueEnum<'ctx>, Error<'c>> {
+    ) -> Result<BasicValueEnum<'ctx>, Box<Error<'c>>> {
         let then_block = self.context.append_basic_block(current_function, "ifthen");
         let else_block = self.context.append_basic_block(current_function, "ifelse");
         let end_block = self.context.append_basic_block(current_function, "ifend");
@@ -459,14 +644,19 @@ impl<'a, 'b, 'c, 'ctx> CodeGenerator<'a, 'b, 'ctx> {
         typed_let: &TypedLet<'c>,
         current_function: FunctionValue<'ctx>,
         function_context: &mut FunctionContext<'ctx>,
-    ) -> Result<(), Error<'c>> {
+    ) -> Result<(), Box<Error<'c>>> {
         let value =
             self.compile_expression(&typed_let.expression, current_function, function_context)?;
+        let value = self.coerce_pointer(value, convert_basic_type(typed_let.ty, self.context));
         let id = typed_let.name.1.id;
         if self.env.get(id).is_none() {
-            let pointer = self
-                .builder
-                .build_alloca(value.get_type(), self.interner.get(id));
+            let name = self.interner.get(id);
+            let pointer = self.builder.build_alloca(value.get_type(), name);
+            if let Some(debug_info) = &self.debug_info {
+                let block = self.builder.get_insert_block().unwrap();
+                debug_info.declare_variable(pointer, name, typed_let.name.0, typed_let.ty, block);
+            }
+
             self.env.define(id, pointer);
         }
 
@@ -476,22 +666,52 @@ impl<'a, 'b, 'c, 'ctx> CodeGenerator<'a, 'b, 'ctx> {
         Ok(())
     }
 
+    fn compile_store(
+        &mut self,
+        store: &TypedStore<'c>,
+        current_function: FunctionValue<'ctx>,
+        function_context: &mut FunctionContext<'ctx>,
+    ) -> Result<(), Box<Error<'c>>> {
+        let pointer =
+            self.compile_expression(&store.pointer, current_function, function_context)?;
+        let value =
+            self.compile_expression(&store.expression, current_function, function_context)?;
+        let value = self.coerce_pointer(value, convert_basic_type(store.ty, self.context));
+        self.builder
+            .build_store(pointer.into_pointer_value(), value);
+
+        Ok(())
+    }
+
     fn compile_application(
         &mut self,
         application: &TypedApplication<'c>,
         current_function: FunctionValue<'ctx>,
         function_context: &mut FunctionContext<'ctx>,
-    ) -> Result<BasicValueEnum<'ctx>, Error<'c>> {
+    ) -> Result<BasicValueEnum<'ctx>, Box<Error<'c>>> {
         let function_name = self.interner.get(application.function_name.1);
         let function_call = format!("call_{}", function_name);
-        let function = self.module.get_function(&function_name).unwrap();
-        let mut arguments = Vec::new();
-        for parameter in &application.parameters {
-            arguments.push(self.compile_expression(
-                parameter,
-                current_function,
-                function_context,
-            )?);
+        let function = self.module.get_function(function_name).unwrap();
+        let parameter_types = function.get_type().get_param_types();
+        let mut values = vec![None; application.parameters.len()];
+        for &index in &application.evaluation_order {
+            let parameter = &application.parameters[index];
+            values[index] =
+                Some(self.compile_expression(parameter, current_function, function_context)?);
+        }
+
+        let mut arguments: Vec<BasicMetadataValueEnum> = Vec::new();
+        for (index, (parameter, argument)) in application.parameters.iter().zip(values).enumerate()
+        {
+            let argument = argument.unwrap();
+            if let Some(parameter_type) = parameter_types.get(index) {
+                arguments.push(self.coerce_pointer(argument, *parameter_type).into());
+            } else {
+                arguments.push(
+                    self.promote_variadic_argument(parameter.1.get_type(), argument)
+                        .into(),
+                );
+            }
         }
 
         Ok(self
@@ -502,12 +722,81 @@ impl<'a, 'b, 'c, 'ctx> CodeGenerator<'a, 'b, 'ctx> {
             .unwrap_or_else(|| BasicValueEnum::IntValue(self.context.i64_type().const_zero())))
     }
 
+    /// Applies the C default argument promotions to an argument passed
+    /// in the variadic part of a call. Integers smaller than a C `int` are
+    /// widened to 32 bits. `Float` is already a double, so it is unchanged.
+    ///
+    /// # Arguments
+    /// * `ty` - The `Type` of the argument.
+    /// * `value` - The compiled argument.
+    fn promote_variadic_argument(
+        &mut self,
+        ty: Type,
+        value: BasicValueEnum<'ctx>,
+    ) -> BasicValueEnum<'ctx> {
+        match ty {
+            Type::Boolean => BasicValueEnum::IntValue(self.builder.build_int_z_extend(
+                value.into_int_value(),
+                self.context.i32_type(),
+                "boolpromote",
+            )),
+            Type::Char => BasicValueEnum::IntValue(self.builder.build_int_s_extend(
+                value.into_int_value(),
+                self.context.i32_type(),
+                "charpromote",
+            )),
+            _ => value,
+        }
+    }
+
+    /// Casts a pointer to the expected pointer type. The type checker allows
+    /// `Ptr<Void>` (e.g. `null`) wherever a pointer is expected, so the LLVM
+    /// types of the value and its destination may differ. Other values are unchanged.
+    ///
+    /// # Arguments
+    /// * `value` - The compiled value.
+    /// * `expected_type` - The LLVM type of the destination.
+    fn coerce_pointer(
+        &mut self,
+        value: BasicValueEnum<'ctx>,
+        expected_type: BasicTypeEnum<'ctx>,
+    ) -> BasicValueEnum<'ctx> {
+        match (value, expected_type) {
+            (BasicValueEnum::PointerValue(pointer), BasicTypeEnum::PointerType(pointer_type))
+                if pointer.get_type() != pointer_type =>
+            {
+                BasicValueEnum::PointerValue(self.builder.build_pointer_cast(
+                    pointer,
+                    pointer_type,
+                    "ptrcast",
+                ))
+            }
+            _ => value,
+        }
+    }
+
+    /// Casts a returned value to the return type of the function.
+    ///
+    /// # Arguments
+    /// * `value` - The compiled return value.
+    /// * `function` - The function being returned from.
+    fn coerce_to_return_type(
+        &mut self,
+        value: BasicValueEnum<'ctx>,
+        function: FunctionValue<'ctx>,
+    ) -> BasicValueEnum<'ctx> {
+        match function.get_type().get_return_type() {
+            Some(return_type) => self.coerce_pointer(value, return_type),
+            None => value,
+        }
+    }
+
     fn compile_while(
         &mut self,
         typed_while: &TypedWhile<'c>,
         current_function: FunctionValue<'ctx>,
         function_context: &mut FunctionContext<'ctx>,
-    ) -> Result<(), Error<'c>> {
+    ) -> Result<(), Box<Error<'c>>> {
         let condition_check_block = self
             .context
             .append_basic_block(current_function, "condition_check");
@@ -540,22 +829,50 @@ impl<'a, 'b, 'c, 'ctx> CodeGenerator<'a, 'b, 'ctx> {
     }
 }
 
-fn convert_type(ty: Type, context: &Context) -> Box<dyn BasicType + '_> {
+fn convert_type(ty: Type, context: &Context) -> Box<dyn BasicType<'_> + '_> {
     match ty {
         Type::Int => Box::new(context.i64_type()),
         Type::Float => Box::new(context.f64_type()),
         Type::Boolean => Box::new(context.bool_type()),
         Type::Char => Box::new(context.i8_type()),
+        Type::Pointer(pointee, depth) => Box::new(convert_pointer_type(pointee, depth, context)),
         _ => unreachable!(),
     }
 }
 
-fn convert_basic_type(ty: Type, context: &Context) -> BasicTypeEnum {
+fn convert_basic_type(ty: Type, context: &Context) -> BasicTypeEnum<'_> {
     match ty {
         Type::Int => BasicTypeEnum::IntType(context.i64_type()),
         Type::Float => BasicTypeEnum::FloatType(context.f64_type()),
         Type::Boolean => BasicTypeEnum::IntType(context.bool_type()),
         Type::Char => BasicTypeEnum::IntType(context.i8_type()),
+        Type::Pointer(pointee, depth) => {
+            BasicTypeEnum::PointerType(convert_pointer_type(pointee, depth, context))
+        }
         _ => unreachable!(),
     }
 }
+
+/// Converts a pointer type into its LLVM representation. LLVM has no
+/// `void *`, so opaque pointers are represented as `i8 *`, as C does.
+///
+/// # Arguments
+/// * `pointee` - The innermost type of the pointer.
+/// * `depth` - The number of pointers wrapping the pointee.
+/// * `context` - The LLVM `Context`.
+fn convert_pointer_type(pointee: PointeeType, depth: usize, context: &Context) -> PointerType<'_> {
+    let mut pointer_type = match pointee {
+        PointeeType::Void | PointeeType::Char => {
+            context.i8_type().ptr_type(AddressSpace::default())
+        }
+        PointeeType::Int => context.i64_type().ptr_type(AddressSpace::default()),
+        PointeeType::Float => context.f64_type().ptr_type(AddressSpace::default()),
+        PointeeType::Boolean => context.bool_type().ptr_type(AddressSpace::default()),
+    };
+
+    for _ in 1..depth {
+        pointer_type = pointer_type.ptr_type(AddressSpace::default());
+    }
+
+    pointer_type
+}
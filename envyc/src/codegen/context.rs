@@ -2,15 +2,13 @@ use inkwell::{basic_block::BasicBlock, values::BasicValueEnum};
 
 #[derive(Debug)]
 pub struct FunctionContext<'ctx> {
-    function_name: usize,
     pub return_blocks: Vec<(BasicBlock<'ctx>, Option<BasicValueEnum<'ctx>>)>,
     pub return_block: BasicBlock<'ctx>,
 }
 
 impl<'ctx> FunctionContext<'ctx> {
-    pub fn new(function_name: usize, return_block: BasicBlock<'ctx>) -> Self {
+    pub fn new(return_block: BasicBlock<'ctx>) -> Self {
         Self {
-            function_name,
             return_blocks: Vec::new(),
             return_block,
         }
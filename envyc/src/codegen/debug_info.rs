@@ -0,0 +1,358 @@
+use std::path::Path;
+
+use inkwell::{
+    basic_block::BasicBlock,
+    builder::Builder,
+    context::Context,
+    debug_info::{
+        AsDIScope, DICompileUnit, DIFlags, DIFlagsConstants, DIScope, DISubprogram, DIType,
+        DWARFEmissionKind, DWARFSourceLanguage, DebugInfoBuilder,
+    },
+    module::{FlagBehavior, Module},
+    values::{FunctionValue, PointerValue},
+    AddressSpace,
+};
+
+use crate::{
+    error::span::Span,
+    interner::Interner,
+    parser::typed_ast::TypedPrototype,
+    semantic_analyzer::types::{PointeeType, Type},
+};
+
+// The encodings of the basic types in DWARF.
+const DW_ATE_BOOLEAN: u32 = 0x02;
+const DW_ATE_FLOAT: u32 = 0x04;
+const DW_ATE_SIGNED: u32 = 0x05;
+const DW_ATE_SIGNED_CHAR: u32 = 0x06;
+const DW_ATE_UNSIGNED_CHAR: u32 = 0x08;
+
+// The version of the debug information metadata that LLVM expects.
+const DEBUG_INFO_VERSION: u64 = 3;
+const DWARF_VERSION: u64 = 4;
+
+/// Struct that describes the source of a module in DWARF, so that debuggers
+/// can show the source lines of the instructions, break on lines and print
+/// the parameters and variables of the functions.
+pub struct DebugInfo<'ctx> {
+    context: &'ctx Context,
+    builder: DebugInfoBuilder<'ctx>,
+    compile_unit: DICompileUnit<'ctx>,
+    pointer_size_in_bits: u64,
+    // Whether the module is optimized, so its variables may not always be available.
+    is_optimized: bool,
+    // The function whose body is being generated, which is the scope of its locations.
+    subprogram: Option<DISubprogram<'ctx>>,
+    // The lines and columns of the expressions that are being generated, innermost last.
+    expressions: Vec<(u32, u32)>,
+}
+
+impl<'ctx> DebugInfo<'ctx> {
+    /// Creates the compile unit of a file and marks the module as having debug information.
+    ///
+    /// # Arguments
+    /// * `context` - The LLVM `Context` of the module.
+    /// * `module` - The module that is generated from the file.
+    /// * `file_path` - The path of the file.
+    /// * `is_optimized` - Whether the module is optimized.
+    /// * `pointer_size_in_bits` - The size of a pointer on the target.
+    pub fn new(
+        context: &'ctx Context,
+        module: &Module<'ctx>,
+        file_path: &str,
+        is_optimized: bool,
+        pointer_size_in_bits: u64,
+    ) -> Self {
+        let path = Path::new(file_path);
+        let file_name = path
+            .file_name()
+            .map_or(file_path.into(), |name| name.to_string_lossy());
+        let directory = path
+            .parent()
+            .map_or(".".into(), |directory| directory.to_string_lossy());
+
+        module.add_basic_value_flag(
+            "Debug Info Version",
+            FlagBehavior::Warning,
+            context.i32_type().const_int(DEBUG_INFO_VERSION, false),
+        );
+        module.add_basic_value_flag(
+            "Dwarf Version",
+            FlagBehavior::Warning,
+            context.i32_type().const_int(DWARF_VERSION, false),
+        );
+
+        // DWARF has no code for Envious, and the types of Envious are described
+        // like the types of C, so debuggers print the values as they would in C.
+        let (builder, compile_unit) = module.create_debug_info_builder(
+            true,
+            DWARFSourceLanguage::C,
+            &file_name,
+            &directory,
+            concat!("envyc ", env!("CARGO_PKG_VERSION")),
+            is_optimized,
+            "",
+            0,
+            "",
+            DWARFEmissionKind::Full,
+            0,
+            false,
+            false,
+            "",
+            "",
+        );
+
+        Self {
+            context,
+            builder,
+            compile_unit,
+            pointer_size_in_bits,
+            is_optimized,
+            subprogram: None,
+            expressions: vec![],
+        }
+    }
+
+    /// Describes a function and makes it the scope of the following locations.
+    ///
+    /// # Arguments
+    /// * `function` - The LLVM function.
+    /// * `prototype` - The prototype of the function.
+    /// * `body_span` - The span of the body of the function.
+    /// * `interner` - The `Interner` that stores the name of the function.
+    pub fn start_function(
+        &mut self,
+        function: FunctionValue<'ctx>,
+        prototype: &TypedPrototype,
+        body_span: Span,
+        interner: &Interner<String>,
+    ) {
+        let file = self.compile_unit.get_file();
+        let parameter_types = prototype
+            .parameters
+            .iter()
+            .map(|parameter| self.create_type(parameter.ty))
+            .collect::<Vec<_>>();
+        let return_type = match prototype.return_type {
+            Type::Void | Type::Never => None,
+            ty => Some(self.create_type(ty)),
+        };
+        let subroutine_type =
+            self.builder
+                .create_subroutine_type(file, return_type, &parameter_types, DIFlags::ZERO);
+
+        let flags = if prototype.export {
+            DIFlags::PUBLIC
+        } else {
+            DIFlags::ZERO
+        };
+        let subprogram = self.builder.create_function(
+            self.compile_unit.as_debug_info_scope(),
+            interner.get(prototype.name),
+            None,
+            file,
+            prototype.span.line_start as u32,
+            subroutine_type,
+            false,
+            true,
+            body_span.line_start as u32,
+            flags,
+            self.is_optimized,
+        );
+        function.set_subprogram(subprogram);
+        self.subprogram = Some(subprogram);
+        self.expressions.clear();
+    }
+
+    /// Ends the function whose body was generated, so that no other
+    /// instructions are given a location in its scope.
+    ///
+    /// # Arguments
+    /// * `builder` - The `Builder` that generated the function.
+    pub fn finish_function(&mut self, builder: &Builder<'ctx>) {
+        builder.unset_current_debug_location();
+        self.expressions.clear();
+        self.subprogram = None;
+    }
+
+    /// Gives the location of a span to the instructions that are built next.
+    ///
+    /// # Arguments
+    /// * `builder` - The `Builder` that builds the instructions.
+    /// * `span` - The span the instructions are built for.
+    pub fn set_location(&self, builder: &Builder<'ctx>, span: Span) {
+        self.set_line_and_column(builder, (span.line_start as u32, span.column_start as u32));
+    }
+
+    /// Gives the location of an expression to the instructions that are built
+    /// for it, until the expression is exited.
+    ///
+    /// # Arguments
+    /// * `builder` - The `Builder` that builds the instructions.
+    /// * `span` - The span of the expression.
+    pub fn enter_expression(&mut self, builder: &Builder<'ctx>, span: Span) {
+        let location = (span.line_start as u32, span.column_start as u32);
+        self.expressions.push(location);
+        self.set_line_and_column(builder, location);
+    }
+
+    /// Gives the instructions that are built after an expression the location of
+    /// the expression that contains it, such as the addition after its operands.
+    ///
+    /// # Arguments
+    /// * `builder` - The `Builder` that builds the instructions.
+    pub fn exit_expression(&mut self, builder: &Builder<'ctx>) {
+        self.expressions.pop();
+        if let Some(location) = self.expressions.last() {
+            self.set_line_and_column(builder, *location);
+        }
+    }
+
+    /// Describes a parameter of the current function, whose value is stored on the stack.
+    ///
+    /// # Arguments
+    /// * `pointer` - The stack slot of the parameter.
+    /// * `name` - The name of the parameter.
+    /// * `number` - The position of the parameter, starting at 1.
+    /// * `span` - The span of the parameter.
+    /// * `ty` - The type of the parameter.
+    /// * `block` - The block the stack slot is declared in.
+    pub fn declare_parameter(
+        &self,
+        pointer: PointerValue<'ctx>,
+        name: &str,
+        number: u32,
+        span: Span,
+        ty: Type,
+        block: BasicBlock<'ctx>,
+    ) {
+        if let Some(scope) = self.scope() {
+            let variable = self.builder.create_parameter_variable(
+                scope,
+                name,
+                number,
+                self.compile_unit.get_file(),
+                span.line_start as u32,
+                self.create_type(ty),
+                true,
+                DIFlags::ZERO,
+            );
+            let location = self.builder.create_debug_location(
+                self.context,
+                span.line_start as u32,
+                span.column_start as u32,
+                scope,
+                None,
+            );
+            self.builder
+                .insert_declare_at_end(pointer, Some(variable), None, location, block);
+        }
+    }
+
+    /// Describes a variable of the current function that is defined by a `let`.
+    ///
+    /// # Arguments
+    /// * `pointer` - The stack slot of the variable.
+    /// * `name` - The name of the variable.
+    /// * `span` - The span of the name of the variable.
+    /// * `ty` - The type of the variable.
+    /// * `block` - The block the stack slot is declared in.
+    pub fn declare_variable(
+        &self,
+        pointer: PointerValue<'ctx>,
+        name: &str,
+        span: Span,
+        ty: Type,
+        block: BasicBlock<'ctx>,
+    ) {
+        if let Some(scope) = self.scope() {
+            let variable = self.builder.create_auto_variable(
+                scope,
+                name,
+                self.compile_unit.get_file(),
+                span.line_start as u32,
+                self.create_type(ty),
+                true,
+                DIFlags::ZERO,
+                0,
+            );
+            let location = self.builder.create_debug_location(
+                self.context,
+                span.line_start as u32,
+                span.column_start as u32,
+                scope,
+                None,
+            );
+            self.builder
+                .insert_declare_at_end(pointer, Some(variable), None, location, block);
+        }
+    }
+
+    /// Resolves the descriptions, which must be done before the module is verified or written.
+    pub fn finalize(&self) {
+        self.builder.finalize();
+    }
+
+    fn set_line_and_column(&self, builder: &Builder<'ctx>, (line, column): (u32, u32)) {
+        if let Some(scope) = self.scope() {
+            let location =
+                self.builder
+                    .create_debug_location(self.context, line, column, scope, None);
+            builder.set_current_debug_location(location);
+        }
+    }
+
+    fn scope(&self) -> Option<DIScope<'ctx>> {
+        self.subprogram
+            .map(|subprogram| subprogram.as_debug_info_scope())
+    }
+
+    /// Describes a type of Envious as a basic type or a pointer type of DWARF.
+    ///
+    /// # Arguments
+    /// * `ty` - The type to describe.
+    fn create_type(&self, ty: Type) -> DIType<'ctx> {
+        match ty {
+            Type::Pointer(pointee, depth) => {
+                let pointee = match pointee {
+                    PointeeType::Int => Type::Int,
+                    PointeeType::Float => Type::Float,
+                    PointeeType::Boolean => Type::Boolean,
+                    PointeeType::Char => Type::Char,
+                    // `Ptr<Void>` is described as a pointer to bytes, as it is generated.
+                    PointeeType::Void => Type::Void,
+                };
+
+                let mut pointer_type = self.create_type(pointee);
+                for _ in 0..depth {
+                    pointer_type = self
+                        .builder
+                        .create_pointer_type(
+                            "",
+                            pointer_type,
+                            self.pointer_size_in_bits,
+                            self.pointer_size_in_bits as u32,
+                            AddressSpace::default(),
+                        )
+                        .as_type();
+                }
+
+                pointer_type
+            }
+            _ => {
+                let (name, size_in_bits, encoding) = match ty {
+                    Type::Int => ("Int", 64, DW_ATE_SIGNED),
+                    Type::Float => ("Float", 64, DW_ATE_FLOAT),
+                    Type::Boolean => ("Boolean", 8, DW_ATE_BOOLEAN),
+                    Type::Char => ("Char", 8, DW_ATE_SIGNED_CHAR),
+                    _ => ("Void", 8, DW_ATE_UNSIGNED_CHAR),
+                };
+
+                self.builder
+                    .create_basic_type(name, size_in_bits, encoding, DIFlags::ZERO)
+                    .unwrap()
+                    .as_type()
+            }
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use crate::{
+    interner::Interner,
+    parser::typed_ast::{TypedProgram, TypedPrototype},
+    semantic_analyzer::types::{PointeeType, Type},
+};
+
+/// Generates a C header that declares every exported function of the program.
+/// The header can be included from C and C++ code that links against
+/// a library built from the program.
+///
+/// # Arguments
+/// * `program` - The `TypedProgram` to describe.
+/// * `module_name` - The name of the module, which is used for the include guard.
+/// * `interner` - The `Interner` that stores the names of the functions.
+pub fn generate_header(
+    program: &TypedProgram,
+    module_name: &str,
+    interner: &Interner<String>,
+) -> String {
+    let guard = format!(
+        "ENVIOUS_{}_H",
+        module_name
+            .chars()
+            .map(|ch| if ch.is_ascii_alphanumeric() {
+                ch.to_ascii_uppercase()
+            } else {
+                '_'
+            })
+            .collect::<String>()
+    );
+
+    let mut header = String::new();
+    header.push_str(&format!("#ifndef {}\n#define {}\n\n", guard, guard));
+    header.push_str("#include <stdbool.h>\n#include <stdint.h>\n\n");
+    header.push_str("#ifdef __cplusplus\nextern \"C\" {\n#endif\n\n");
+    for function in &program.functions {
+        if function.prototype.export {
+            header.push_str(&generate_declaration(&function.prototype, interner));
+            header.push('\n');
+        }
+    }
+
+    header.push_str("\n#ifdef __cplusplus\n}\n#endif\n\n");
+    header.push_str(&format!("#endif /* {} */\n", guard));
+    header
+}
+
+/// Generates the C declaration of a single prototype.
+///
+/// # Arguments
+/// * `prototype` - The `TypedPrototype` to declare.
+/// * `interner` - The `Interner` that stores the names of the function and its parameters.
+fn generate_declaration(prototype: &TypedPrototype, interner: &Interner<String>) -> String {
+    let parameters = if prototype.parameters.is_empty() {
+        "void".to_string()
+    } else {
+        prototype
+            .parameters
+            .iter()
+            .map(|parameter| {
+                format!(
+                    "{} {}",
+                    convert_c_type(parameter.ty),
+                    c_parameter_name(interner.get(parameter.name))
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    format!(
+        "{} {}({});",
+        convert_c_type(prototype.return_type),
+        interner.get(prototype.name),
+        parameters
+    )
+}
+
+// The keywords of C and C++, and the macros of `stdbool.h`, which can not be
+// used as the names of parameters.
+const C_KEYWORDS: &str = "alignas alignof and asm auto bool break case catch char class const \
+    constexpr continue default delete do double else enum explicit extern false float for \
+    friend goto if inline int long mutable namespace new noexcept not nullptr operator or \
+    private protected public register restrict return short signed sizeof static struct switch \
+    template this throw true try typedef typename union unsigned using virtual void volatile \
+    while";
+
+/// Returns the name of a parameter in C. Names of parameters do not affect how a
+/// function is called, so a name that is a keyword in C or C++ is followed by an
+/// underscore instead.
+///
+/// # Arguments
+/// * `name` - The name of the parameter in Envious.
+fn c_parameter_name(name: &str) -> String {
+    if C_KEYWORDS.split_whitespace().any(|keyword| keyword == name) {
+        format!("{}_", name)
+    } else {
+        name.to_string()
+    }
+}
+
+/// Converts a `Type` into the C type with the same representation.
+///
+/// # Arguments
+/// * `ty` - The `Type` to convert.
+fn convert_c_type(ty: Type) -> String {
+    match ty {
+        Type::Void | Type::Never => "void".to_string(),
+        Type::Int => "int64_t".to_string(),
+        Type::Float => "double".to_string(),
+        Type::Boolean => "bool".to_string(),
+        Type::Char => "char".to_string(),
+        Type::Pointer(pointee, depth) => {
+            let pointee = match pointee {
+                PointeeType::Void => "void",
+                PointeeType::Int => "int64_t",
+                PointeeType::Float => "double",
+                PointeeType::Boolean => "bool",
+                PointeeType::Char => "char",
+            };
+
+            format!("{}{}", pointee, "*".repeat(depth))
+        }
+    }
+}
@@ -1,2 +1,4 @@
 pub mod code_generator;
 pub mod context;
+pub mod debug_info;
+pub mod header;
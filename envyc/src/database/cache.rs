@@ -0,0 +1,106 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::fingerprint::Fingerprint;
+
+/// The directory within a project that stores the build cache.
+pub const CACHE_DIRECTORY: &str = ".envious/cache";
+
+// The version of the compiler, since a different compiler may generate different code.
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Struct that describes a file that was compiled without any errors or warnings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedBuild {
+    version: String,
+    // The fingerprint of the source and of the options the file was compiled with.
+    key: Fingerprint,
+    // The files written by the compilation and the fingerprints of their contents.
+    outputs: Vec<(PathBuf, Fingerprint)>,
+    // Whether the file defines the `main` function.
+    pub contains_main: bool,
+}
+
+/// Struct that stores the builds of files on disk, so that a file which did not
+/// change since it was last compiled is not compiled again. Every file has its own
+/// entry in the cache directory, named after the fingerprint of its path.
+pub struct BuildCache {
+    directory: PathBuf,
+}
+
+impl BuildCache {
+    /// Creates a new `BuildCache`. Nothing is written until a build is inserted.
+    ///
+    /// # Arguments
+    /// * `project_directory` - The directory that contains the cache directory.
+    pub fn new(project_directory: &Path) -> Self {
+        Self {
+            directory: project_directory.join(CACHE_DIRECTORY),
+        }
+    }
+
+    /// Returns the build of a file if it was compiled with the same key by the
+    /// same version of the compiler, and none of its outputs changed since.
+    ///
+    /// # Arguments
+    /// * `file_path` - The path of the compiled file.
+    /// * `key` - The fingerprint of the source and of the options of the compilation.
+    pub fn get(&self, file_path: &str, key: Fingerprint) -> Option<CachedBuild> {
+        let entry = fs::read_to_string(self.entry(file_path)).ok()?;
+        let build = serde_json::from_str::<CachedBuild>(&entry).ok()?;
+        let unchanged = build.version == VERSION
+            && build.key == key
+            && build.outputs.iter().all(|(path, fingerprint)| {
+                matches!(fs::read(path), Ok(bytes) if Fingerprint::of(&bytes) == *fingerprint)
+            });
+
+        if unchanged {
+            Some(build)
+        } else {
+            None
+        }
+    }
+
+    /// Stores the build of a file, replacing any earlier build of the file.
+    ///
+    /// # Arguments
+    /// * `file_path` - The path of the compiled file.
+    /// * `key` - The fingerprint of the source and of the options of the compilation.
+    /// * `outputs` - The files written by the compilation.
+    /// * `contains_main` - Whether the file defines the `main` function.
+    pub fn insert(
+        &self,
+        file_path: &str,
+        key: Fingerprint,
+        outputs: &[PathBuf],
+        contains_main: bool,
+    ) -> io::Result<()> {
+        let mut fingerprints = vec![];
+        for path in outputs {
+            let bytes = fs::read(path)?;
+            fingerprints.push((path.clone(), Fingerprint::of(&bytes)));
+        }
+
+        let build = CachedBuild {
+            version: VERSION.to_string(),
+            key,
+            outputs: fingerprints,
+            contains_main,
+        };
+        fs::create_dir_all(&self.directory)?;
+        fs::write(self.entry(file_path), serde_json::to_string(&build)?)
+    }
+
+    /// Returns the path of the entry of a file.
+    ///
+    /// # Arguments
+    /// * `file_path` - The path of the compiled file.
+    fn entry(&self, file_path: &str) -> PathBuf {
+        self.directory
+            .join(format!("{}.json", Fingerprint::of(file_path)))
+    }
+}
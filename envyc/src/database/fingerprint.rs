@@ -0,0 +1,69 @@
+use std::{
+    fmt::Display,
+    hash::{Hash, Hasher},
+};
+
+use serde::{Deserialize, Serialize};
+
+/// A 64-bit hash that identifies a value, such as the source of a file or the
+/// signature of a function. Unlike the hashes of the standard library, the
+/// fingerprint of a value is the same in every run of the compiler, so it can
+/// be stored on disk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Fingerprint(u64);
+
+impl Fingerprint {
+    /// Computes the fingerprint of a value.
+    ///
+    /// # Arguments
+    /// * `value` - The value to hash.
+    pub fn of<T: Hash + ?Sized>(value: &T) -> Self {
+        let mut hasher = FingerprintHasher::default();
+        value.hash(&mut hasher);
+        hasher.fingerprint()
+    }
+}
+
+impl Display for Fingerprint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
+
+// The offset basis and the prime of the 64-bit FNV-1a hash.
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Struct that computes a `Fingerprint` from several values with the
+/// FNV-1a hash, which is simple, fast and does not depend on a random seed.
+pub struct FingerprintHasher {
+    state: u64,
+}
+
+impl FingerprintHasher {
+    /// Returns the fingerprint of the values written so far.
+    pub fn fingerprint(&self) -> Fingerprint {
+        Fingerprint(self.state)
+    }
+}
+
+impl Default for FingerprintHasher {
+    fn default() -> Self {
+        Self {
+            state: FNV_OFFSET_BASIS,
+        }
+    }
+}
+
+impl Hasher for FingerprintHasher {
+    fn finish(&self) -> u64 {
+        self.state
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.state ^= u64::from(*byte);
+            self.state = self.state.wrapping_mul(FNV_PRIME);
+        }
+    }
+}
@@ -0,0 +1,516 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    hash::Hash,
+};
+
+use crate::{
+    environment::Environment,
+    error::{suggestion::resolve_suggestions, Error, Span},
+    filter_tokens,
+    function_table::FunctionTable,
+    interner::Interner,
+    lex,
+    lexer::token::Token,
+    parse_with_recovery,
+    parser::{
+        ast::{Attribute, Program},
+        typed_ast::{TypedFunction, TypedProgram},
+        typed_expression::{TypedExpression, TypedExpressionKind},
+    },
+    semantic_analyzer::{
+        type_check::{check_program, TypeCheck},
+        types::Type,
+    },
+};
+
+use self::fingerprint::{Fingerprint, FingerprintHasher};
+
+pub mod cache;
+pub mod fingerprint;
+
+/// Enum that details the queries executed by the `Database`. A query is
+/// only recorded when it is executed, not when its result is reused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Query<'a> {
+    Lex(&'a str),
+    Parse(&'a str),
+    // Collects the signatures of a file and checks its constants.
+    TypeCheck(&'a str),
+    // Checks the function with the given name in a file.
+    CheckFunction(&'a str, usize),
+}
+
+/// Struct that answers queries about the files of a program, in the style of salsa.
+/// The sources of the files are the inputs, and the tokens, the program and the
+/// types of a file are derived from its source. The result of every query is
+/// memoized and reused until the source of its file changes.
+///
+/// The functions of a file are checked one at a time, and a checked function is
+/// reused as long as its text and the signatures of the functions and constants
+/// it references do not change. Editing the body of a function therefore only
+/// checks that function again, while changing the signature of a function also
+/// checks the functions that apply it. A function that moved because the code
+/// above it changed is reused as well, with its spans moved to its new position.
+#[derive(Default)]
+pub struct Database<'a> {
+    interner: Interner<String>,
+    // The revision increases whenever the source of any file changes.
+    revision: usize,
+    files: HashMap<&'a str, File<'a>>,
+    executed: Vec<Query<'a>>,
+}
+
+/// Struct that stores the source of a file and the memoized results of its queries.
+struct File<'a> {
+    source: &'a str,
+    tokens: Option<Result<Vec<Token<'a>>, Vec<Error<'a>>>>,
+    program: Option<(Program<'a>, Vec<Error<'a>>)>,
+    typed_program: Option<Result<TypedProgram<'a>, Vec<Error<'a>>>>,
+    // The last function that was checked successfully with each name. Unlike the
+    // other results, these are kept when the source changes.
+    functions: HashMap<usize, CheckedFunction<'a>>,
+}
+
+/// Struct that stores a checked function together with everything its types depend on.
+struct CheckedFunction<'a> {
+    // The fingerprint of the text of the function and of whether it is exported.
+    text: Fingerprint,
+    extent: Extent,
+    // The names the function references and the fingerprints of their signatures.
+    dependencies: Vec<(usize, Fingerprint)>,
+    function: TypedFunction<'a>,
+}
+
+/// Struct that describes the text of a function, which starts at its first
+/// attribute and ends where the next item starts.
+#[derive(Debug, Clone, Copy)]
+struct Extent {
+    start: usize,
+    end: usize,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> Database<'a> {
+    /// Returns the `Interner` that stores the names of every file.
+    pub fn interner(&self) -> &Interner<String> {
+        &self.interner
+    }
+
+    /// Returns the `Interner` that stores the names of every file, which
+    /// the code generator uses to add the names it needs.
+    pub fn interner_mut(&mut self) -> &mut Interner<String> {
+        &mut self.interner
+    }
+
+    /// Returns the current revision, which changes whenever a source changes.
+    pub fn revision(&self) -> usize {
+        self.revision
+    }
+
+    /// Sets the source of a file. The memoized results of the file are
+    /// discarded if the source differs from the previous one.
+    ///
+    /// # Arguments
+    /// * `file_path` - The path of the file.
+    /// * `source` - The new source of the file.
+    pub fn set_source(&mut self, file_path: &'a str, source: &'a str) {
+        match self.files.get_mut(file_path) {
+            Some(file) if file.source == source => {}
+            Some(file) => {
+                file.source = source;
+                file.tokens = None;
+                file.program = None;
+                file.typed_program = None;
+                self.revision += 1;
+            }
+            None => {
+                self.files.insert(
+                    file_path,
+                    File {
+                        source,
+                        tokens: None,
+                        program: None,
+                        typed_program: None,
+                        functions: HashMap::new(),
+                    },
+                );
+                self.revision += 1;
+            }
+        }
+    }
+
+    /// Returns the queries that were executed since this function was last called.
+    pub fn take_executed(&mut self) -> Vec<Query<'a>> {
+        std::mem::take(&mut self.executed)
+    }
+
+    /// Returns every token of a file, including the whitespace and the comments.
+    ///
+    /// # Arguments
+    /// * `file_path` - The path of the file, whose source must have been set.
+    pub fn lex(&mut self, file_path: &'a str) -> Result<Vec<Token<'a>>, Vec<Error<'a>>> {
+        let file = self.files.get_mut(file_path).unwrap();
+        if file.tokens.is_none() {
+            self.executed.push(Query::Lex(file_path));
+            file.tokens = Some(lex(file_path, file.source.as_bytes(), &mut self.interner));
+        }
+
+        file.tokens.clone().unwrap()
+    }
+
+    /// Returns the program of a file together with its syntax errors. If the
+    /// file could not be lexed, the program is empty and the lexing errors are returned.
+    ///
+    /// # Arguments
+    /// * `file_path` - The path of the file, whose source must have been set.
+    pub fn parse(&mut self, file_path: &'a str) -> (Program<'a>, Vec<Error<'a>>) {
+        if let Some(program) = &self.files[file_path].program {
+            return program.clone();
+        }
+
+        let tokens = self.lex(file_path);
+        self.executed.push(Query::Parse(file_path));
+        let program = match tokens {
+            Ok(tokens) => parse_with_recovery(filter_tokens(tokens), &self.interner),
+            Err(errors) => (Program::new(vec![], vec![], vec![]), errors),
+        };
+
+        let file = self.files.get_mut(file_path).unwrap();
+        file.program = Some(program.clone());
+        program
+    }
+
+    /// Checks the types of the program of a file. The program is checked even if
+    /// it contains syntax errors, which are returned by `parse` instead.
+    ///
+    /// # Arguments
+    /// * `file_path` - The path of the file, whose source must have been set.
+    pub fn type_check(&mut self, file_path: &'a str) -> Result<TypedProgram<'a>, Vec<Error<'a>>> {
+        if let Some(typed_program) = &self.files[file_path].typed_program {
+            return typed_program.clone();
+        }
+
+        let (program, _) = self.parse(file_path);
+        self.executed.push(Query::TypeCheck(file_path));
+        let extents = extents(&program, self.files[file_path].source);
+
+        let file = self.files.get_mut(file_path).unwrap();
+        let source = file.source;
+        let functions = &mut file.functions;
+        let executed = &mut self.executed;
+        let interner = &self.interner;
+        let mut env = Environment::default();
+        let mut function_table = FunctionTable::default();
+        let result = check_program(
+            program,
+            &mut env,
+            &mut function_table,
+            |function, env, function_table| {
+                let name = function.prototype.name;
+                let extent = extents[&function.prototype.span.start];
+                let text = Fingerprint::of(&(
+                    &source[extent.start..extent.end],
+                    function.prototype.export,
+                ));
+
+                if let Some(checked) = functions.get(&name) {
+                    let unchanged = checked.text == text
+                        && checked.extent.column == extent.column
+                        && checked.dependencies.iter().all(|(name, fingerprint)| {
+                            signature(*name, env, function_table) == *fingerprint
+                        });
+                    if unchanged {
+                        let mut typed_function = checked.function.clone();
+                        Rebase::new(checked.extent, extent).function(&mut typed_function);
+                        return Ok(typed_function);
+                    }
+                }
+
+                executed.push(Query::CheckFunction(file_path, name));
+                match function.check(env, function_table) {
+                    Ok(typed_function) => {
+                        let mut references = BTreeSet::new();
+                        collect_references(&typed_function.body, &mut references);
+                        // Local names are recorded too, since adding a global with the
+                        // same name, such as a constant, changes how they are checked.
+                        let dependencies = references
+                            .into_iter()
+                            .map(|name| (name, signature(name, env, function_table)))
+                            .collect();
+                        functions.insert(
+                            name,
+                            CheckedFunction {
+                                text,
+                                extent,
+                                dependencies,
+                                function: typed_function.clone(),
+                            },
+                        );
+                        Ok(typed_function)
+                    }
+                    Err(error) => {
+                        functions.remove(&name);
+                        Err(error)
+                    }
+                }
+            },
+        )
+        .map_err(|mut errors| {
+            resolve_suggestions(&mut errors, interner);
+            errors
+        });
+
+        file.typed_program = Some(result.clone());
+        result
+    }
+}
+
+/// Finds the extent of every function of a program, keyed by the start of its prototype.
+///
+/// # Arguments
+/// * `program` - The `Program` of the file.
+/// * `source` - The source of the file.
+fn extents(program: &Program, source: &str) -> HashMap<usize, Extent> {
+    let mut starts = program
+        .extern_declarations
+        .iter()
+        .map(|declaration| first_span(declaration.span, &declaration.attributes).start)
+        .chain(
+            program
+                .constants
+                .iter()
+                .map(|constant| first_span(constant.span, &constant.attributes).start),
+        )
+        .chain(
+            program
+                .functions
+                .iter()
+                .map(|function| first_span(function.prototype.span, &function.attributes).start),
+        )
+        .collect::<Vec<_>>();
+    starts.sort_unstable();
+
+    program
+        .functions
+        .iter()
+        .map(|function| {
+            let prototype = function.prototype.span;
+            let first = first_span(prototype, &function.attributes);
+            let end = starts
+                .iter()
+                .copied()
+                .find(|start| *start > prototype.start)
+                .unwrap_or(source.len());
+            let extent = Extent {
+                start: first.start,
+                end,
+                line: first.line_start,
+                column: first.column_start,
+            };
+            (prototype.start, extent)
+        })
+        .collect()
+}
+
+/// Returns the span of an item or of its first attribute, whichever comes first.
+///
+/// # Arguments
+/// * `span` - The `Span` of the item.
+/// * `attributes` - The attributes written before the item.
+fn first_span<'a>(span: Span<'a>, attributes: &[Attribute<'a>]) -> Span<'a> {
+    attributes
+        .iter()
+        .map(|attribute| attribute.span)
+        .chain(Some(span))
+        .min_by_key(|span| span.start)
+        .unwrap()
+}
+
+/// Computes the fingerprint of everything a function can learn about a name
+/// from outside of its body: the type of the name, the parameters of the function
+/// with that name and whether it is a constant. The spans of the parameters are
+/// left out, so moving a function does not change its signature, but the default
+/// values are included since they are copied into the applications of the function.
+///
+/// # Arguments
+/// * `name` - The id of the name.
+/// * `env` - The `Environment` that stores the types of the names.
+/// * `function_table` - The `FunctionTable` that stores the known functions.
+fn signature(name: usize, env: &Environment<Type>, function_table: &FunctionTable) -> Fingerprint {
+    let mut hasher = FingerprintHasher::default();
+    format!("{:?}", env.get(name)).hash(&mut hasher);
+    if let Some((_, signature)) = function_table.functions().find(|(id, _)| *id == name) {
+        for parameter in &signature.parameters {
+            parameter.name.hash(&mut hasher);
+            format!("{:?} {:?}", parameter.ty, parameter.default).hash(&mut hasher);
+        }
+
+        signature.variadic.hash(&mut hasher);
+    }
+
+    function_table
+        .get_constant(name)
+        .is_some()
+        .hash(&mut hasher);
+    hasher.fingerprint()
+}
+
+/// Collects the names of the variables and the functions an expression references,
+/// including the names that are assigned with `let`.
+///
+/// # Arguments
+/// * `expression` - The expression to search.
+/// * `references` - The set the names are added to.
+fn collect_references(expression: &TypedExpression, references: &mut BTreeSet<usize>) {
+    match &expression.1 {
+        TypedExpressionKind::Identifier(identifier) => {
+            references.insert(identifier.id);
+        }
+        TypedExpressionKind::Let(let_expression) => {
+            references.insert(let_expression.name.1.id);
+        }
+        TypedExpressionKind::Application(application) => {
+            references.insert(application.function_name.1);
+        }
+        _ => {}
+    }
+
+    for child in children(expression) {
+        collect_references(child, references);
+    }
+}
+
+/// Returns the expressions directly within an expression.
+///
+/// # Arguments
+/// * `expression` - The expression whose children are returned.
+fn children<'e, 'a>(expression: &'e TypedExpression<'a>) -> Vec<&'e TypedExpression<'a>> {
+    match &expression.1 {
+        TypedExpressionKind::Unary(unary) => vec![&unary.expression],
+        TypedExpressionKind::Binary(binary) => vec![&binary.left, &binary.right],
+        TypedExpressionKind::If(if_expression) => {
+            let mut children = vec![&*if_expression.condition, &if_expression.then_branch];
+            children.extend(if_expression.else_branch.as_deref());
+            children
+        }
+        TypedExpressionKind::Let(let_expression) => vec![&let_expression.expression],
+        TypedExpressionKind::Store(store) => vec![&store.pointer, &store.expression],
+        TypedExpressionKind::Block(expressions) => expressions.iter().collect(),
+        TypedExpressionKind::Application(application) => application.parameters.iter().collect(),
+        TypedExpressionKind::While(while_expression) => {
+            vec![&while_expression.condition, &while_expression.expression]
+        }
+        TypedExpressionKind::Return(returned) => returned.as_deref().into_iter().collect(),
+        TypedExpressionKind::Unsafe(block) => vec![block],
+        TypedExpressionKind::Int(_)
+        | TypedExpressionKind::Float(_)
+        | TypedExpressionKind::Boolean(_)
+        | TypedExpressionKind::Char(_)
+        | TypedExpressionKind::Null
+        | TypedExpressionKind::Identifier(_)
+        | TypedExpressionKind::Error => vec![],
+    }
+}
+
+/// Struct that moves the spans of a function that was checked at another position.
+/// Only the spans within the old extent of the function are moved, since the
+/// default values copied from other functions keep their own positions.
+struct Rebase {
+    old: Extent,
+    offset: isize,
+    lines: isize,
+}
+
+impl Rebase {
+    /// Creates a new `Rebase`. The columns do not change, since the function
+    /// must start in the same column at both positions.
+    ///
+    /// # Arguments
+    /// * `old` - The extent the function was checked at.
+    /// * `new` - The extent the function is found at now.
+    fn new(old: Extent, new: Extent) -> Self {
+        Self {
+            old,
+            offset: new.start as isize - old.start as isize,
+            lines: new.line as isize - old.line as isize,
+        }
+    }
+
+    fn function(&self, function: &mut TypedFunction) {
+        self.span(&mut function.prototype.span);
+        for parameter in &mut function.prototype.parameters {
+            self.span(&mut parameter.span);
+        }
+
+        for attribute in &mut function.attributes {
+            self.span(&mut attribute.span);
+            self.span(&mut attribute.name.0);
+            for (span, _) in &mut attribute.arguments {
+                self.span(span);
+            }
+        }
+
+        self.expression(&mut function.body);
+    }
+
+    fn expression(&self, expression: &mut TypedExpression) {
+        self.span(&mut expression.0);
+        match &mut expression.1 {
+            TypedExpressionKind::Unary(unary) => self.expression(&mut unary.expression),
+            TypedExpressionKind::Binary(binary) => {
+                self.expression(&mut binary.left);
+                self.expression(&mut binary.right);
+            }
+            TypedExpressionKind::If(if_expression) => {
+                self.expression(&mut if_expression.condition);
+                self.expression(&mut if_expression.then_branch);
+                if let Some(else_branch) = &mut if_expression.else_branch {
+                    self.expression(else_branch);
+                }
+            }
+            TypedExpressionKind::Let(let_expression) => {
+                self.span(&mut let_expression.name.0);
+                self.expression(&mut let_expression.expression);
+            }
+            TypedExpressionKind::Store(store) => {
+                self.expression(&mut store.pointer);
+                self.expression(&mut store.expression);
+            }
+            TypedExpressionKind::Block(expressions) => {
+                for expression in expressions {
+                    self.expression(expression);
+                }
+            }
+            TypedExpressionKind::Application(application) => {
+                self.span(&mut application.function_name.0);
+                for parameter in &mut application.parameters {
+                    self.expression(parameter);
+                }
+            }
+            TypedExpressionKind::While(while_expression) => {
+                self.expression(&mut while_expression.condition);
+                self.expression(&mut while_expression.expression);
+            }
+            TypedExpressionKind::Return(Some(returned)) => self.expression(returned),
+            TypedExpressionKind::Unsafe(block) => self.expression(block),
+            TypedExpressionKind::Int(_)
+            | TypedExpressionKind::Float(_)
+            | TypedExpressionKind::Boolean(_)
+            | TypedExpressionKind::Char(_)
+            | TypedExpressionKind::Null
+            | TypedExpressionKind::Identifier(_)
+            | TypedExpressionKind::Return(None)
+            | TypedExpressionKind::Error => {}
+        }
+    }
+
+    fn span(&self, span: &mut Span) {
+        if span.start >= self.old.start && span.start < self.old.end {
+            span.start = (span.start as isize + self.offset) as usize;
+            span.end = (span.end as isize + self.offset) as usize;
+            span.line_start = (span.line_start as isize + self.lines) as usize;
+            span.line_end = (span.line_end as isize + self.lines) as usize;
+        }
+    }
+}
@@ -10,10 +10,37 @@ impl<T: Copy> Environment<T> {
         self.scopes.push(Scope::default());
     }
 
+    /// Creates a new scope in which unsafe operations, such as
+    /// dereferencing pointers, are allowed.
+    pub fn new_unsafe_scope(&mut self) {
+        self.scopes.push(Scope {
+            inner: HashMap::new(),
+            is_unsafe: true,
+        });
+    }
+
+    /// Determines whether any of the enclosing scopes is unsafe.
+    pub fn is_unsafe(&self) -> bool {
+        self.scopes.iter().any(|scope| scope.is_unsafe)
+    }
+
     pub fn remove_top_scope(&mut self) -> Option<Scope<T>> {
         self.scopes.pop()
     }
 
+    /// Returns the number of scopes.
+    pub fn depth(&self) -> usize {
+        self.scopes.len()
+    }
+
+    /// Removes every scope above the given depth.
+    ///
+    /// # Arguments
+    /// * `depth` - The number of scopes to keep.
+    pub fn truncate(&mut self, depth: usize) {
+        self.scopes.truncate(depth);
+    }
+
     pub fn define(&mut self, id: usize, value: T) {
         if self.scopes.is_empty() {
             self.new_scope();
@@ -25,6 +52,14 @@ impl<T: Copy> Environment<T> {
     pub fn get(&self, id: usize) -> Option<T> {
         self.scopes.iter().rev().find_map(|scope| scope.get(id))
     }
+
+    /// Returns the ids of every name defined in any of the scopes.
+    pub fn names(&self) -> Vec<usize> {
+        self.scopes
+            .iter()
+            .flat_map(|scope| scope.inner.keys().copied())
+            .collect()
+    }
 }
 
 impl<T> Default for Environment<T> {
@@ -36,6 +71,7 @@ impl<T> Default for Environment<T> {
 #[derive(Debug)]
 pub struct Scope<T> {
     inner: HashMap<usize, T>,
+    is_unsafe: bool,
 }
 
 impl<T: Copy> Scope<T> {
@@ -52,6 +88,7 @@ impl<T> Default for Scope<T> {
     fn default() -> Self {
         Self {
             inner: HashMap::new(),
+            is_unsafe: false,
         }
     }
 }
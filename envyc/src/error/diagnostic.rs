@@ -0,0 +1,147 @@
+use serde::Serialize;
+
+use super::{fix::Fix, Span};
+
+/// Enum that represents how severe a `Diagnostic` is.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// Enum that represents the role of a `Label` in a `Diagnostic`.
+/// A primary label points at the cause of the diagnostic, while a
+/// secondary label points at code that gives more context.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LabelStyle {
+    Primary,
+    Secondary,
+}
+
+/// Struct that attaches an optional message to a `Span` of the source code.
+#[derive(Debug, Clone, Serialize)]
+pub struct Label<'a> {
+    pub style: LabelStyle,
+    pub span: Span<'a>,
+    pub message: String,
+}
+
+impl<'a> Label<'a> {
+    /// Creates a primary label without a message.
+    ///
+    /// # Arguments
+    /// * `span` - The `Span` the label points at.
+    pub fn primary(span: Span<'a>) -> Self {
+        Self {
+            style: LabelStyle::Primary,
+            span,
+            message: String::new(),
+        }
+    }
+
+    /// Creates a secondary label without a message.
+    ///
+    /// # Arguments
+    /// * `span` - The `Span` the label points at.
+    pub fn secondary(span: Span<'a>) -> Self {
+        Self {
+            style: LabelStyle::Secondary,
+            span,
+            message: String::new(),
+        }
+    }
+
+    /// Sets the message of the label.
+    ///
+    /// # Arguments
+    /// * `message` - The message to show next to the span.
+    pub fn with_message(mut self, message: impl Into<String>) -> Self {
+        self.message = message.into();
+        self
+    }
+}
+
+/// Struct that describes a single problem found in the source code.
+///
+/// Every `Error` is converted into a `Diagnostic` by the `ErrorReporter`,
+/// which is then rendered as text for the terminal or serialized for other tools.
+/// The serialized fields are part of the stable output of `--error-format=json`,
+/// so they should only ever be added to.
+#[derive(Debug, Clone, Serialize)]
+pub struct Diagnostic<'a> {
+    pub code: &'static str,
+    pub severity: Severity,
+    pub message: String,
+    pub labels: Vec<Label<'a>>,
+    pub notes: Vec<String>,
+    pub fixes: Vec<Fix<'a>>,
+}
+
+impl<'a> Diagnostic<'a> {
+    /// Creates an error without a code, message, labels, notes or fixes.
+    pub fn error() -> Self {
+        Self {
+            code: "",
+            severity: Severity::Error,
+            message: String::new(),
+            labels: vec![],
+            notes: vec![],
+            fixes: vec![],
+        }
+    }
+
+    /// Creates a warning without a code, message, labels, notes or fixes.
+    pub fn warning() -> Self {
+        Self {
+            severity: Severity::Warning,
+            ..Self::error()
+        }
+    }
+
+    /// Sets the code of the diagnostic.
+    ///
+    /// # Arguments
+    /// * `code` - The stable code of the diagnostic, such as `E0001`.
+    pub fn with_code(mut self, code: &'static str) -> Self {
+        self.code = code;
+        self
+    }
+
+    /// Sets the message of the diagnostic.
+    ///
+    /// # Arguments
+    /// * `message` - The summary of the problem.
+    pub fn with_message(mut self, message: impl Into<String>) -> Self {
+        self.message = message.into();
+        self
+    }
+
+    /// Sets the labels of the diagnostic.
+    ///
+    /// # Arguments
+    /// * `labels` - The labels that point at the source code.
+    pub fn with_labels(mut self, labels: Vec<Label<'a>>) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    /// Sets the notes of the diagnostic.
+    ///
+    /// # Arguments
+    /// * `notes` - The additional explanations shown after the labels.
+    pub fn with_notes(mut self, notes: Vec<String>) -> Self {
+        self.notes = notes;
+        self
+    }
+
+    /// Sets the fixes of the diagnostic.
+    ///
+    /// # Arguments
+    /// * `fixes` - The changes to the source code that resolve the problem.
+    pub fn with_fixes(mut self, fixes: Vec<Fix<'a>>) -> Self {
+        self.fixes = fixes;
+        self
+    }
+}
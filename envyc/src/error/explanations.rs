@@ -0,0 +1,24 @@
+/// Generates `explain` from the list of codes that have an explanation.
+/// The explanation of every code is stored in `explanations/<code>.md`.
+macro_rules! explanations {
+    ($($code: literal),* $(,)?) => {
+        /// Returns the long-form explanation of an error code, which
+        /// describes the error and shows how it can be fixed.
+        ///
+        /// # Arguments
+        /// * `code` - The stable code of the error, such as `E0001`.
+        pub fn explain(code: &str) -> Option<&'static str> {
+            match code {
+                $($code => Some(include_str!(concat!("explanations/", $code, ".md"))),)*
+                _ => None,
+            }
+        }
+    };
+}
+
+explanations!(
+    "E0001", "E0002", "E0003", "E0004", "E0005", "E0006", "E0007", "E0008", "E0009", "E0010",
+    "E0011", "E0012", "E0013", "E0014", "E0015", "E0016", "E0017", "E0018", "E0019", "E0020",
+    "E0021", "E0022", "E0023", "E0024", "E0025", "E0026", "E0027", "E0028", "E0029", "E0030",
+    "E0031", "E0032", "E0033", "E0034", "E0035", "E0036", "E0037", "E0038",
+);
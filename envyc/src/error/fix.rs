@@ -0,0 +1,112 @@
+use serde::Serialize;
+
+use super::Span;
+
+/// Enum that represents how confident the compiler is that a fix is what was meant.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Applicability {
+    /// The fix is certainly correct, so `envious fix` applies it without asking.
+    MachineApplicable,
+    /// The fix may not be what was meant, so it is only shown to the user.
+    MaybeIncorrect,
+}
+
+/// Struct that replaces the source code of a span with new text.
+/// An empty replacement removes the code of the span.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Edit<'a> {
+    pub span: Span<'a>,
+    pub replacement: String,
+}
+
+impl<'a> Edit<'a> {
+    /// Creates a new `Edit`.
+    ///
+    /// # Arguments
+    /// * `span` - The `Span` of the code to replace.
+    /// * `replacement` - The text that replaces the code.
+    pub fn new(span: Span<'a>, replacement: impl Into<String>) -> Self {
+        Self {
+            span,
+            replacement: replacement.into(),
+        }
+    }
+}
+
+/// Struct that describes a change to the source code which resolves an error.
+/// The edits of a fix are applied together or not at all.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Fix<'a> {
+    pub message: String,
+    pub applicability: Applicability,
+    pub edits: Vec<Edit<'a>>,
+}
+
+impl<'a> Fix<'a> {
+    /// Creates a fix that is certainly correct.
+    ///
+    /// # Arguments
+    /// * `message` - The description of the fix.
+    /// * `edits` - The edits that make up the fix.
+    pub fn machine_applicable(message: impl Into<String>, edits: Vec<Edit<'a>>) -> Self {
+        Self {
+            message: message.into(),
+            applicability: Applicability::MachineApplicable,
+            edits,
+        }
+    }
+
+    /// Creates a fix that may not be what was meant.
+    ///
+    /// # Arguments
+    /// * `message` - The description of the fix.
+    /// * `edits` - The edits that make up the fix.
+    pub fn maybe_incorrect(message: impl Into<String>, edits: Vec<Edit<'a>>) -> Self {
+        Self {
+            message: message.into(),
+            applicability: Applicability::MaybeIncorrect,
+            edits,
+        }
+    }
+}
+
+/// Applies the fixes to the source of a single file. A fix is skipped if one of its
+/// edits overlaps with an edit of a fix that was applied before it, since the code
+/// it would replace has already changed. Returns the new source and the number of
+/// fixes that were applied.
+///
+/// # Arguments
+/// * `source` - The source of the file.
+/// * `fixes` - The fixes to apply, whose spans must belong to the file.
+pub fn apply_fixes(source: &str, fixes: &[Fix]) -> (String, usize) {
+    let mut ranges: Vec<(usize, usize, &str)> = vec![];
+    let mut applied = 0;
+    for fix in fixes {
+        let fix_ranges = fix
+            .edits
+            .iter()
+            .map(|edit| (edit.span.start, edit.span.end, edit.replacement.as_str()))
+            .collect::<Vec<_>>();
+        let overlaps = fix_ranges.iter().any(|&(start, end, _)| {
+            ranges
+                .iter()
+                .any(|&(other_start, other_end, _)| start < other_end && other_start < end)
+        });
+
+        if !overlaps {
+            ranges.extend(fix_ranges);
+            applied += 1;
+        }
+    }
+
+    // The edits are applied from the end of the file, so that the
+    // byte offsets of the remaining edits stay the same.
+    ranges.sort_by_key(|&(start, _, _)| start);
+    let mut fixed = source.to_string();
+    for &(start, end, replacement) in ranges.iter().rev() {
+        fixed.replace_range(start..end, replacement);
+    }
+
+    (fixed, applied)
+}
@@ -1,19 +1,31 @@
 use crate::{lexer::token::TokenKind, semantic_analyzer::types::Type};
 
+use self::{
+    fix::{Edit, Fix},
+    suggestion::Suggestion,
+};
+
 /// Enum used by compiler to construct the various errors.
 /// Every error needs to keep a track of the span of the error
 /// to provide a better representation when reported to the user,
 /// unless the error stems from the LLVM compiler, which is not
 /// derived from the user's code.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Error<'a> {
     // Occurs when an integer that exceeeds the maximum possible value of an integer.
     IntegerOverflow(Span<'a>),
     // Occurs when a float that exceeeds the maximum possible value of a float.
     FloatOverflow(Span<'a>),
     UnterminatedChar(Span<'a>),
+    // Occurs when a backslash in a char is followed by an escape sequence that does not exist.
+    InvalidEscape(Span<'a>),
+    // Occurs when a char literal holds a character that does not fit in the single byte of a `Char`.
+    CharOutOfRange(Span<'a>),
     // Occurs when a character that is not recognized by the `Lexer`.
     UnrecognizedCharacter(Span<'a>),
+    // Occurs when a number contains digits that are not allowed by its base,
+    // such as `0b102`, or is directly followed by letters.
+    MalformedNumber(Span<'a>),
 
     // Occurs when an expression was expected by the `Parser` but
     // there were no more tokens to inspect.
@@ -24,12 +36,18 @@ pub enum Error<'a> {
         found_kind: TokenKind,
     },
     // Occurs when a certain token was expected by an expression but
-    // a different token was found.
+    // a different token was found. If an identifier was found where a keyword
+    // was expected, the suggestion is the expected keyword closest to it.
     ExpectedKind {
         span: Span<'a>,
         expected_kinds: Vec<TokenKind>,
         actual_kind: TokenKind,
+        suggestion: Option<String>,
+        fix: Option<Fix<'a>>,
     },
+    // Occurs when a `*` starts a line after an expression. It could either multiply
+    // the expression or dereference a pointer in a new expression, so it is rejected.
+    AmbiguousStar(Span<'a>),
 
     // Occurs when the specified operation could not be applied to operands.
     UnsupportedOperation {
@@ -41,6 +59,7 @@ pub enum Error<'a> {
         span: Span<'a>,
         expected_type: Type,
         actual_type: Type,
+        fix: Option<Fix<'a>>,
     },
     // Occurs when the type of two branches do not match. For example, if the type
     // of the then branch and the type of the else branch do not match, this error
@@ -50,6 +69,7 @@ pub enum Error<'a> {
         first_type: Type,
         second_span: Span<'a>,
         second_type: Type,
+        fix: Option<Fix<'a>>,
     },
     // Occurs when the type of a variable does not match its previous definitions.
     ConflictingPreviousType {
@@ -60,19 +80,173 @@ pub enum Error<'a> {
     },
     // Occurs when a type was found that could not be used.
     IllegalType(Span<'a>),
-    UndefinedVariable(Span<'a>),
+    UndefinedVariable {
+        span: Span<'a>,
+        suggestion: Suggestion,
+    },
     ParameterMismatch {
         span: Span<'a>,
         expected_parameter_count: usize,
         actual_parameter_count: usize,
+        fix: Option<Fix<'a>>,
+    },
+
+    // Occurs when a named argument does not match any parameter of the function.
+    UnknownArgumentName {
+        span: Span<'a>,
+        function_span: Span<'a>,
+    },
+    // Occurs when the same parameter is given more than one argument.
+    DuplicateArgument {
+        span: Span<'a>,
+        previous_span: Span<'a>,
+    },
+    // Occurs when a positional argument follows a named argument.
+    PositionalAfterNamedArgument {
+        span: Span<'a>,
+        named_span: Span<'a>,
     },
+    // Occurs when a parameter without a default value was not given an argument.
+    MissingArgument {
+        span: Span<'a>,
+        parameter_span: Span<'a>,
+    },
+
+    // Occurs when a pointer operation is used outside of an `unsafe` block.
+    UnsafeOperation(Span<'a>),
+    // Occurs when the address of an expression that is not a variable is taken.
+    IllegalAddressOf(Span<'a>),
+
+    // Occurs when a constant divides by zero during its evaluation.
+    DivisionByZero(Span<'a>),
+    // Occurs when an operation overflows during the evaluation of a constant.
+    ConstantOverflow(Span<'a>),
+    // Occurs when a constant uses an expression that can only be evaluated at run time.
+    NonConstantExpression(Span<'a>),
+    // Occurs when a constant depends on its own value.
+    CyclicConstant(Span<'a>),
+    // Occurs when the evaluation of a constant recurses or loops for too long.
+    ConstantEvaluationLimit(Span<'a>),
+    // Occurs when a `let` expression assigns a new value to a constant.
+    AssignmentToConstant {
+        span: Span<'a>,
+        constant_span: Span<'a>,
+    },
+
+    UnknownFunction {
+        span: Span<'a>,
+        suggestion: Suggestion,
+    },
+
+    // Occurs when an attribute other than `#allow` is written before an item.
+    UnknownAttribute(Span<'a>),
+    // Occurs when an `#allow` attribute names a lint that does not exist.
+    UnknownLint(Span<'a>),
 
-    UnknownFunction(Span<'a>),
     /// Occurs when a function was expected during the LLVM compilation.
     ExpectedFunction,
     LLVMFunctionFailure,
+    // Occurs when LLVM could not write an output file, such as the object file.
+    WriteFailure {
+        path: String,
+        message: String,
+    },
+    // Occurs when the modules of several files could not be linked together
+    // for link time optimization.
+    LinkFailure {
+        path: String,
+        message: String,
+    },
+    // Occurs when LLVM can not generate code for the target triple, CPU or features.
+    InvalidTarget {
+        triple: String,
+        message: String,
+    },
+}
+
+impl<'a> Error<'a> {
+    /// Returns the stable code of the error, which identifies the
+    /// kind of the error in the output of the compiler. The codes of
+    /// existing errors must never change.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Error::IntegerOverflow(_) => "E0001",
+            Error::FloatOverflow(_) => "E0002",
+            Error::UnterminatedChar(_) => "E0003",
+            Error::UnrecognizedCharacter(_) => "E0004",
+            Error::UnexpectedEndOfInput(_) => "E0005",
+            Error::ExpectedPrefixExpression { .. } => "E0006",
+            Error::ExpectedKind { .. } => "E0007",
+            Error::UnsupportedOperation { .. } => "E0008",
+            Error::TypeMismatch { .. } => "E0009",
+            Error::ConflictingType { .. } => "E0010",
+            Error::ConflictingPreviousType { .. } => "E0011",
+            Error::IllegalType(_) => "E0012",
+            Error::UndefinedVariable { .. } => "E0013",
+            Error::ParameterMismatch { .. } => "E0014",
+            Error::UnknownArgumentName { .. } => "E0015",
+            Error::DuplicateArgument { .. } => "E0016",
+            Error::PositionalAfterNamedArgument { .. } => "E0017",
+            Error::MissingArgument { .. } => "E0018",
+            Error::UnsafeOperation(_) => "E0019",
+            Error::IllegalAddressOf(_) => "E0020",
+            Error::DivisionByZero(_) => "E0021",
+            Error::ConstantOverflow(_) => "E0022",
+            Error::NonConstantExpression(_) => "E0023",
+            Error::CyclicConstant(_) => "E0024",
+            Error::ConstantEvaluationLimit(_) => "E0025",
+            Error::AssignmentToConstant { .. } => "E0026",
+            Error::UnknownFunction { .. } => "E0027",
+            Error::ExpectedFunction => "E0028",
+            Error::LLVMFunctionFailure => "E0029",
+            Error::UnknownAttribute(_) => "E0030",
+            Error::UnknownLint(_) => "E0031",
+            Error::MalformedNumber(_) => "E0032",
+            Error::InvalidEscape(_) => "E0033",
+            Error::CharOutOfRange(_) => "E0034",
+            Error::WriteFailure { .. } => "E0035",
+            Error::LinkFailure { .. } => "E0036",
+            Error::InvalidTarget { .. } => "E0037",
+            Error::AmbiguousStar(_) => "E0038",
+        }
+    }
+
+    /// Returns the change to the source code that resolves the error, if there is one.
+    /// Names that were suggested for a misspelled name are only a guess, so their
+    /// fixes are never applied automatically.
+    pub fn fix(&self) -> Option<Fix<'a>> {
+        let suggested_fix = |span: Span<'a>, name: &str| {
+            Fix::maybe_incorrect(
+                format!("did you mean `{}`?", name),
+                vec![Edit::new(span, name)],
+            )
+        };
+
+        match self {
+            Error::ExpectedKind { fix: Some(fix), .. }
+            | Error::TypeMismatch { fix: Some(fix), .. }
+            | Error::ConflictingType { fix: Some(fix), .. }
+            | Error::ParameterMismatch { fix: Some(fix), .. } => Some(fix.clone()),
+            Error::ExpectedKind {
+                span,
+                suggestion: Some(keyword),
+                ..
+            } => Some(suggested_fix(*span, keyword)),
+            Error::UndefinedVariable { span, suggestion }
+            | Error::UnknownFunction { span, suggestion } => {
+                suggestion.name().map(|name| suggested_fix(*span, name))
+            }
+            _ => None,
+        }
+    }
 }
 
+pub mod diagnostic;
+pub mod explanations;
+pub mod fix;
 pub mod reporter;
+mod sarif;
 pub mod span;
+pub mod suggestion;
+pub mod warning;
 pub use span::Span;
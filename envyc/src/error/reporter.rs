@@ -1,17 +1,36 @@
 use std::{
+    cell::RefCell,
     collections::HashMap,
     io::{self, Write},
 };
 
 use codespan_reporting::{
-    diagnostic::{Diagnostic, Label},
+    diagnostic as codespan,
     files::SimpleFiles,
     term::termcolor::{BufferWriter, ColorChoice},
 };
+use serde_json::Value;
 
 use crate::{lexer::token::TokenKind, semantic_analyzer::types::Type};
 
-use super::{Error, Span};
+use super::{
+    diagnostic::{Diagnostic, Label, LabelStyle, Severity},
+    fix::Applicability,
+    sarif,
+    warning::{Lint, LintLevel, Warning},
+    Error, Span,
+};
+
+/// Enum that represents the formats errors can be reported in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ErrorFormat {
+    /// Text meant to be read by people in a terminal.
+    Human,
+    /// One JSON object per error, each on its own line.
+    Json,
+    /// A single SARIF 2.1.0 log that contains every error.
+    Sarif,
+}
 
 /// Struct that handles reporting the different errors that occur.
 pub struct ErrorReporter<'a> {
@@ -21,6 +40,13 @@ pub struct ErrorReporter<'a> {
     /// This is used by the codespan_reporting crate.
     /// This map goes from the name of the file to its id.
     file_ids: HashMap<&'a str, usize>,
+    /// The format that errors are reported in.
+    format: ErrorFormat,
+    /// The SARIF results of the errors reported so far.
+    sarif_results: RefCell<Vec<Value>>,
+    /// The output that is kept instead of being written to stdout, so that
+    /// files compiled at the same time can be reported in order.
+    buffer: Option<RefCell<Vec<u8>>>,
 }
 
 impl<'a> ErrorReporter<'a> {
@@ -32,7 +58,64 @@ impl<'a> ErrorReporter<'a> {
             file_ids.insert(name.as_ref(), id);
         }
 
-        Self { files, file_ids }
+        Self {
+            files,
+            file_ids,
+            format: ErrorFormat::Human,
+            sarif_results: RefCell::new(vec![]),
+            buffer: None,
+        }
+    }
+
+    /// Keeps the output of the reporter in a buffer instead of writing it
+    /// to stdout. The buffer is written once the reporter is appended to
+    /// another reporter with `append`.
+    pub fn buffered(mut self) -> Self {
+        self.buffer = Some(RefCell::new(vec![]));
+        self
+    }
+
+    /// Writes output of the reporter, such as a reported error or a progress message.
+    ///
+    /// # Arguments
+    /// * `bytes` - The bytes to write.
+    pub fn write(&self, bytes: &[u8]) -> io::Result<()> {
+        match &self.buffer {
+            Some(buffer) => {
+                buffer.borrow_mut().extend_from_slice(bytes);
+                Ok(())
+            }
+            None => io::stdout().write_all(bytes),
+        }
+    }
+
+    /// Writes the buffered output of another reporter and takes its SARIF
+    /// results, as if everything had been reported by this reporter.
+    ///
+    /// # Arguments
+    /// * `other` - The `ErrorReporter` to append.
+    pub fn append(&self, other: ErrorReporter) -> io::Result<()> {
+        self.sarif_results
+            .borrow_mut()
+            .extend(other.sarif_results.into_inner());
+        match other.buffer {
+            Some(buffer) => self.write(&buffer.into_inner()),
+            None => Ok(()),
+        }
+    }
+
+    /// Sets the format that errors are reported in.
+    ///
+    /// # Arguments
+    /// * `format` - The `ErrorFormat` to use.
+    pub fn with_format(mut self, format: ErrorFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Returns the format that errors are reported in.
+    pub fn format(&self) -> ErrorFormat {
+        self.format
     }
 
     /// Adds a file to the input files.
@@ -45,17 +128,65 @@ impl<'a> ErrorReporter<'a> {
         self.file_ids.insert(file_name, id);
     }
 
-    /// Reports the error to the user. Note that this method does not consume the error.
-    /// This allows errors to be reported in many different places.
+    /// Reports the error to the user in the format of the reporter. Note that this method
+    /// does not consume the error. This allows errors to be reported in many different places.
+    ///
+    /// When the format is `ErrorFormat::Sarif`, the error is stored until the
+    /// log is created with `sarif_log`, so no bytes are returned.
     ///
     /// # Arguments
     /// * `error` - The error to report.
-    pub fn report(&self, error: &Error, color: bool) -> Vec<u8> {
+    /// * `color` - Whether the text of the `ErrorFormat::Human` format is colored.
+    pub fn report(&self, error: &Error<'a>, color: bool) -> Vec<u8> {
+        self.emit(&self.diagnostic(error), color)
+    }
+
+    /// Reports the warning to the user in the format of the reporter. A warning
+    /// whose lint is denied is reported as an error.
+    ///
+    /// # Arguments
+    /// * `warning` - The warning to report.
+    /// * `level` - The `LintLevel` of the lint that produced the warning.
+    /// * `color` - Whether the text of the `ErrorFormat::Human` format is colored.
+    pub fn report_warning(&self, warning: &Warning<'a>, level: LintLevel, color: bool) -> Vec<u8> {
+        self.emit(&self.warning_diagnostic(warning, level), color)
+    }
+
+    /// Converts the diagnostic into the format of the reporter.
+    ///
+    /// # Arguments
+    /// * `diagnostic` - The `Diagnostic` to convert.
+    /// * `color` - Whether the text of the `ErrorFormat::Human` format is colored.
+    fn emit(&self, diagnostic: &Diagnostic<'a>, color: bool) -> Vec<u8> {
+        match self.format {
+            ErrorFormat::Human => self.render(diagnostic, color),
+            ErrorFormat::Json => {
+                let mut bytes = serde_json::to_vec(diagnostic).unwrap();
+                bytes.push(b'\n');
+                bytes
+            }
+            ErrorFormat::Sarif => {
+                self.sarif_results
+                    .borrow_mut()
+                    .push(sarif::create_result(diagnostic));
+                vec![]
+            }
+        }
+    }
+
+    /// Converts the error into a `Diagnostic`, which is shared by every format.
+    ///
+    /// # Arguments
+    /// * `error` - The error to convert.
+    pub fn diagnostic(&self, error: &Error<'a>) -> Diagnostic<'a> {
         let diagnostic = match error {
             Error::IntegerOverflow(span) => self.handle_integer_overflow(*span),
             Error::FloatOverflow(span) => self.handle_float_overflow(*span),
             Error::UnterminatedChar(span) => self.handle_unterminated_char(*span),
+            Error::InvalidEscape(span) => self.handle_invalid_escape(*span),
+            Error::CharOutOfRange(span) => self.handle_char_out_of_range(*span),
             Error::UnrecognizedCharacter(span) => self.handle_unrecognized_character(*span),
+            Error::MalformedNumber(span) => self.handle_malformed_number(*span),
             Error::UnexpectedEndOfInput(span) => self.handle_end_of_input(*span),
             Error::ExpectedPrefixExpression {
                 span,
@@ -65,7 +196,9 @@ impl<'a> ErrorReporter<'a> {
                 span,
                 expected_kinds,
                 actual_kind,
+                ..
             } => self.handle_expected_kind(*span, expected_kinds, *actual_kind),
+            Error::AmbiguousStar(span) => self.handle_ambiguous_star(*span),
             Error::UnsupportedOperation {
                 operation_span,
                 operands,
@@ -74,12 +207,14 @@ impl<'a> ErrorReporter<'a> {
                 span,
                 expected_type,
                 actual_type,
+                ..
             } => self.handle_type_mismatch(*span, *expected_type, *actual_type),
             Error::ConflictingType {
                 first_span,
                 first_type,
                 second_span,
                 second_type,
+                ..
             } => self.handle_conflicting_type(*first_span, *first_type, *second_span, *second_type),
             Error::ConflictingPreviousType {
                 name_span,
@@ -93,29 +228,178 @@ impl<'a> ErrorReporter<'a> {
                 *second_type,
             ),
             Error::IllegalType(span) => self.handle_illegal_type(*span),
-            Error::UndefinedVariable(span) => self.handle_undefined_variable(*span),
+            Error::UndefinedVariable { span, .. } => self.handle_undefined_variable(*span),
             Error::ParameterMismatch {
                 span,
                 expected_parameter_count,
                 actual_parameter_count,
+                ..
             } => self.handle_parameter_mismatch(
                 *span,
                 *expected_parameter_count,
                 *actual_parameter_count,
             ),
-            Error::UnknownFunction(span) => self.handle_unknown_function(*span),
-            Error::ExpectedFunction => {
-                return "Expected a function to be selected when compiling to LLVM."
-                    .as_bytes()
-                    .to_vec()
+            Error::UnknownArgumentName {
+                span,
+                function_span,
+            } => self.handle_unknown_argument_name(*span, *function_span),
+            Error::DuplicateArgument {
+                span,
+                previous_span,
+            } => self.handle_duplicate_argument(*span, *previous_span),
+            Error::PositionalAfterNamedArgument { span, named_span } => {
+                self.handle_positional_after_named_argument(*span, *named_span)
             }
-            Error::LLVMFunctionFailure => {
-                return "An unexpected error occurred when compiling a function to LLVM."
-                    .as_bytes()
-                    .to_vec()
+            Error::MissingArgument {
+                span,
+                parameter_span,
+            } => self.handle_missing_argument(*span, *parameter_span),
+            Error::UnsafeOperation(span) => self.handle_unsafe_operation(*span),
+            Error::IllegalAddressOf(span) => self.handle_illegal_address_of(*span),
+            Error::DivisionByZero(span) => self.handle_division_by_zero(*span),
+            Error::ConstantOverflow(span) => self.handle_constant_overflow(*span),
+            Error::NonConstantExpression(span) => self.handle_non_constant_expression(*span),
+            Error::CyclicConstant(span) => self.handle_cyclic_constant(*span),
+            Error::ConstantEvaluationLimit(span) => self.handle_constant_evaluation_limit(*span),
+            Error::AssignmentToConstant {
+                span,
+                constant_span,
+            } => self.handle_assignment_to_constant(*span, *constant_span),
+            Error::UnknownFunction { span, .. } => self.handle_unknown_function(*span),
+            Error::UnknownAttribute(span) => self.handle_unknown_attribute(*span),
+            Error::UnknownLint(span) => self.handle_unknown_lint(*span),
+            Error::ExpectedFunction => Diagnostic::error()
+                .with_message("Expected a function to be selected when compiling to LLVM."),
+            Error::LLVMFunctionFailure => Diagnostic::error()
+                .with_message("An unexpected error occurred when compiling a function to LLVM."),
+            Error::WriteFailure { path, message } => Diagnostic::error()
+                .with_message(format!("Could not write the file `{}`: {}", path, message)),
+            Error::LinkFailure { path, message } => Diagnostic::error()
+                .with_message(format!("Could not link the file `{}`: {}", path, message)),
+            Error::InvalidTarget { triple, message } => Diagnostic::error()
+                .with_message(format!("Could not compile for the target `{}`: {}", triple, message))
+                .with_notes(vec![
+                    "targets are written as triples, such as `x86_64-unknown-linux-gnu` or `aarch64-unknown-linux-gnu`.".to_string(),
+                ]),
+        };
+
+        diagnostic
+            .with_code(error.code())
+            .with_fixes(error.fix().into_iter().collect())
+    }
+
+    /// Converts the warning into a `Diagnostic`, which is shared by every format.
+    /// The name of the lint is used as the code of the diagnostic.
+    ///
+    /// # Arguments
+    /// * `warning` - The warning to convert.
+    /// * `level` - The `LintLevel` of the lint that produced the warning.
+    pub fn warning_diagnostic(&self, warning: &Warning<'a>, level: LintLevel) -> Diagnostic<'a> {
+        let diagnostic = match warning {
+            Warning::UnusedVariable(span) => self.handle_unused_variable(*span),
+            Warning::UnusedParameter(span) => self.handle_unused_parameter(*span),
+            Warning::UnreachableCode {
+                span,
+                diverging_span,
+            } => self.handle_unreachable_code(*span, *diverging_span),
+            Warning::ShadowedParameter {
+                span,
+                parameter_span,
+            } => self.handle_shadowed_parameter(*span, *parameter_span),
+            Warning::ConstantCondition { span, value } => {
+                self.handle_constant_condition(*span, *value)
             }
         };
 
+        let lint = warning.lint().name();
+        let (severity, note) = if level == LintLevel::Deny {
+            (
+                Severity::Error,
+                format!(
+                    "the `{}` lint is denied, so it is reported as an error",
+                    lint
+                ),
+            )
+        } else {
+            (
+                Severity::Warning,
+                format!("`#allow({})` before the item silences this warning", lint),
+            )
+        };
+
+        let mut notes = diagnostic.notes.clone();
+        notes.push(note);
+        Diagnostic {
+            severity,
+            ..diagnostic
+        }
+        .with_code(lint)
+        .with_notes(notes)
+    }
+
+    /// Renders the diagnostic as text with the codespan_reporting crate.
+    ///
+    /// # Arguments
+    /// * `diagnostic` - The `Diagnostic` to render.
+    /// * `color` - Whether the text is colored.
+    fn render(&self, diagnostic: &Diagnostic, color: bool) -> Vec<u8> {
+        let severity = match diagnostic.severity {
+            Severity::Error => codespan::Severity::Error,
+            Severity::Warning => codespan::Severity::Warning,
+        };
+
+        let mut labels = diagnostic
+            .labels
+            .iter()
+            .map(|label| {
+                let style = match label.style {
+                    LabelStyle::Primary => codespan::LabelStyle::Primary,
+                    LabelStyle::Secondary => codespan::LabelStyle::Secondary,
+                };
+
+                codespan::Label::new(
+                    style,
+                    self.get_file_id(label.span.file_name),
+                    label.span.start..label.span.end,
+                )
+                .with_message(&label.message)
+            })
+            .collect::<Vec<_>>();
+
+        // Every fix is shown as a help note, and its edits as labels that
+        // point at the code they replace.
+        let mut notes = diagnostic.notes.clone();
+        for fix in &diagnostic.fixes {
+            let mut note = format!("help: {}", fix.message);
+            if fix.applicability == Applicability::MachineApplicable {
+                note.push_str(" (`envious fix` applies this automatically)");
+            }
+
+            notes.push(note);
+            labels.extend(fix.edits.iter().map(|edit| {
+                let message = if edit.replacement.is_empty() {
+                    "remove this".to_string()
+                } else {
+                    format!("replace with `{}`", edit.replacement)
+                };
+
+                codespan::Label::secondary(
+                    self.get_file_id(edit.span.file_name),
+                    edit.span.start..edit.span.end,
+                )
+                .with_message(message)
+            }));
+        }
+
+        let mut rendered = codespan::Diagnostic::new(severity)
+            .with_message(&diagnostic.message)
+            .with_labels(labels)
+            .with_notes(notes);
+
+        if !diagnostic.code.is_empty() {
+            rendered = rendered.with_code(diagnostic.code);
+        }
+
         let color_choice = if color {
             ColorChoice::Always
         } else {
@@ -125,23 +409,25 @@ impl<'a> ErrorReporter<'a> {
         let buffer_writer = BufferWriter::stderr(color_choice);
         let mut buffer = buffer_writer.buffer();
         let config = codespan_reporting::term::Config::default();
-        codespan_reporting::term::emit(&mut buffer, &config, &self.files, &diagnostic).unwrap();
+        codespan_reporting::term::emit(&mut buffer, &config, &self.files, &rendered).unwrap();
 
         buffer.as_slice().to_vec()
     }
 
+    /// Creates a SARIF log that contains every error reported since the
+    /// reporter was created. This is only used by the `ErrorFormat::Sarif` format.
+    pub fn sarif_log(&self) -> String {
+        sarif::create_log(&self.sarif_results.borrow())
+    }
+
     /// Handles an integer overflow error.
     ///
     /// # Arguments
     /// * `span` - The `Span` of this error.
-    fn handle_integer_overflow(&self, span: Span) -> Diagnostic<usize> {
-        let (start_column, end_column) = self.construct_source(span);
+    fn handle_integer_overflow(&self, span: Span<'a>) -> Diagnostic<'a> {
         Diagnostic::error()
             .with_message("integer overflowed")
-            .with_labels(vec![Label::primary(
-                self.get_file_id(&span.file_name),
-                start_column..end_column,
-            )])
+            .with_labels(vec![Label::primary(span)])
             .with_notes(vec![format!(
                 "integers must be >= {} and <= {}",
                 i64::MIN,
@@ -153,14 +439,10 @@ impl<'a> ErrorReporter<'a> {
     ///
     /// # Arguments
     /// * `span` - The `Span` of this error.
-    fn handle_float_overflow(&self, span: Span) -> Diagnostic<usize> {
-        let (start_column, end_column) = self.construct_source(span);
+    fn handle_float_overflow(&self, span: Span<'a>) -> Diagnostic<'a> {
         Diagnostic::error()
             .with_message("float overflow")
-            .with_labels(vec![Label::primary(
-                self.get_file_id(&span.file_name),
-                start_column..end_column,
-            )])
+            .with_labels(vec![Label::primary(span)])
             .with_notes(vec![format!(
                 "floats must be >= {} and <= {}",
                 f64::MIN,
@@ -172,28 +454,60 @@ impl<'a> ErrorReporter<'a> {
     ///
     /// # Arguments
     /// * `span` - The `Span` of this error.
-    fn handle_unterminated_char(&self, span: Span) -> Diagnostic<usize> {
-        let (start_column, end_column) = self.construct_source(span);
+    fn handle_unterminated_char(&self, span: Span<'a>) -> Diagnostic<'a> {
         Diagnostic::error()
             .with_message("unterminated char")
-            .with_labels(vec![Label::primary(
-                self.get_file_id(&span.file_name),
-                start_column..end_column,
-            )])
+            .with_labels(vec![Label::primary(span)])
             .with_notes(vec!["try ending the char with a \'".to_string()])
     }
 
+    /// Handles an invalid escape error.
+    ///
+    /// # Arguments
+    /// * `span` - The `Span` of this error.
+    fn handle_invalid_escape(&self, span: Span<'a>) -> Diagnostic<'a> {
+        Diagnostic::error()
+            .with_message("invalid escape sequence")
+            .with_labels(vec![Label::primary(span)])
+            .with_notes(vec![
+                "the escape sequences are `\\n`, `\\t`, `\\r`, `\\0`, `\\\\`, `\\'` and `\\u{...}`"
+                    .to_string(),
+            ])
+    }
+
+    /// Handles a char out of range error.
+    ///
+    /// # Arguments
+    /// * `span` - The `Span` of this error.
+    fn handle_char_out_of_range(&self, span: Span<'a>) -> Diagnostic<'a> {
+        Diagnostic::error()
+            .with_message("character does not fit in a `Char`")
+            .with_labels(vec![Label::primary(span)])
+            .with_notes(vec![
+                "a `Char` holds a single ASCII character, so it can only hold the characters up to `\\u{7f}`"
+                    .to_string(),
+            ])
+    }
+
     /// Handles an unrecognized character error.
     ///
     /// # Arguments
     /// * `span` - The `Span` of this error.
-    fn handle_unrecognized_character(&self, span: Span) -> Diagnostic<usize> {
-        let (start_column, end_column) = self.construct_source(span);
+    fn handle_unrecognized_character(&self, span: Span<'a>) -> Diagnostic<'a> {
         Diagnostic::error()
             .with_message("unrecognized character")
-            .with_labels(vec![Label::primary(
-                self.get_file_id(&span.file_name),
-                start_column..end_column,
+            .with_labels(vec![Label::primary(span)])
+    }
+
+    /// Handles a malformed number error.
+    ///
+    /// # Arguments
+    /// * `span` - The `Span` of this error.
+    fn handle_malformed_number(&self, span: Span<'a>) -> Diagnostic<'a> {
+        Diagnostic::error()
+            .with_message("malformed number")
+            .with_labels(vec![Label::primary(span).with_message(
+                "this number has no digits, or a `_` that is not between two digits",
             )])
     }
 
@@ -201,14 +515,10 @@ impl<'a> ErrorReporter<'a> {
     ///
     /// # Arguments
     /// * `span` - The `Span` of this error.
-    fn handle_end_of_input(&self, span: Span) -> Diagnostic<usize> {
-        let (start_column, end_column) = self.construct_source(span);
+    fn handle_end_of_input(&self, span: Span<'a>) -> Diagnostic<'a> {
         Diagnostic::error()
             .with_message("expected an expression")
-            .with_labels(vec![Label::primary(
-                self.get_file_id(&span.file_name),
-                start_column..end_column,
-            )])
+            .with_labels(vec![Label::primary(span)])
     }
 
     /// Handles an expected prefix expression error.
@@ -216,20 +526,31 @@ impl<'a> ErrorReporter<'a> {
     /// # Arguments
     /// * `span` - The `Span` of this error.
     /// * `kind` - The `TokenKind` found.
-    fn handle_expected_prefix_expression(&self, span: Span, kind: TokenKind) -> Diagnostic<usize> {
-        let (start_column, end_column) = self.construct_source(span);
+    fn handle_expected_prefix_expression(&self, span: Span<'a>, kind: TokenKind) -> Diagnostic<'a> {
         Diagnostic::error()
             .with_message("expected prefix expression")
-            .with_labels(vec![Label::primary(
-                self.get_file_id(&span.file_name),
-                start_column..end_column,
-            )
-            .with_message(format!(
+            .with_labels(vec![Label::primary(span).with_message(format!(
                 "the token `{}` does not correspond to any prefix expression",
                 kind
             ))])
     }
 
+    /// Handles an ambiguous star error.
+    ///
+    /// # Arguments
+    /// * `span` - The `Span` of this error.
+    fn handle_ambiguous_star(&self, span: Span<'a>) -> Diagnostic<'a> {
+        Diagnostic::error()
+            .with_message("`*` at the start of a line is ambiguous")
+            .with_labels(vec![Label::primary(span).with_message(
+                "this could multiply the previous expression or dereference a pointer",
+            )])
+            .with_notes(vec![
+                "to multiply, move the `*` to the end of the previous line".to_string(),
+                "to dereference, wrap the dereference in a block: `{ *p }`".to_string(),
+            ])
+    }
+
     /// Handles an expected kind error.
     ///
     /// # Arguments
@@ -238,11 +559,10 @@ impl<'a> ErrorReporter<'a> {
     /// * `actual_kind` - The `TokenKind` found.
     fn handle_expected_kind(
         &self,
-        span: Span,
+        span: Span<'a>,
         expected_kinds: &[TokenKind],
         actual_kind: TokenKind,
-    ) -> Diagnostic<usize> {
-        let (start_column, end_column) = self.construct_source(span);
+    ) -> Diagnostic<'a> {
         let expected_kinds = expected_kinds
             .iter()
             .map(|kind| format!("{}", kind))
@@ -250,11 +570,9 @@ impl<'a> ErrorReporter<'a> {
             .join(", or ");
         Diagnostic::error()
             .with_message(format!("expected {}", expected_kinds))
-            .with_labels(vec![Label::primary(
-                self.get_file_id(&span.file_name),
-                start_column..end_column,
-            )
-            .with_message(format!("but found {}", actual_kind))])
+            .with_labels(vec![
+                Label::primary(span).with_message(format!("but found {}", actual_kind))
+            ])
     }
 
     /// Handles an unsupported operation error.
@@ -264,22 +582,13 @@ impl<'a> ErrorReporter<'a> {
     /// * `operands` - The `Span` and the `Type` of the operands.
     fn handle_unsupported_operation(
         &self,
-        operation_span: Span,
-        operands: &[(Span, Type)],
-    ) -> Diagnostic<usize> {
-        let (operation_start, operation_end) = self.construct_source(operation_span);
-        let mut labels = vec![Label::primary(
-            self.get_file_id(&operation_span.file_name),
-            operation_start..operation_end,
-        )];
+        operation_span: Span<'a>,
+        operands: &[(Span<'a>, Type)],
+    ) -> Diagnostic<'a> {
+        let mut labels = vec![Label::primary(operation_span)];
         for operand in operands {
-            let (operand_start, operand_end) = self.construct_source(operand.0);
             labels.push(
-                Label::secondary(
-                    self.get_file_id(&operand.0.file_name),
-                    operand_start..operand_end,
-                )
-                .with_message(format!("has a type of {}", operand.1)),
+                Label::secondary(operand.0).with_message(format!("has a type of {}", operand.1)),
             )
         }
 
@@ -296,18 +605,13 @@ impl<'a> ErrorReporter<'a> {
     /// * `actual_type` - The `Type` found.
     fn handle_type_mismatch(
         &self,
-        span: Span,
+        span: Span<'a>,
         expected_type: Type,
         actual_type: Type,
-    ) -> Diagnostic<usize> {
-        let (start_column, end_column) = self.construct_source(span);
+    ) -> Diagnostic<'a> {
         Diagnostic::error()
             .with_message("type mismatch")
-            .with_labels(vec![Label::primary(
-                self.get_file_id(&span.file_name),
-                start_column..end_column,
-            )
-            .with_message(format!(
+            .with_labels(vec![Label::primary(span).with_message(format!(
                 "expected `{}` but found `{}`",
                 expected_type, actual_type
             ))])
@@ -322,26 +626,16 @@ impl<'a> ErrorReporter<'a> {
     /// * `second_type` - The `Type` of the second branch.
     fn handle_conflicting_type(
         &self,
-        first_span: Span,
+        first_span: Span<'a>,
         first_type: Type,
-        second_span: Span,
+        second_span: Span<'a>,
         second_type: Type,
-    ) -> Diagnostic<usize> {
-        let (first_start_column, first_end_column) = self.construct_source(first_span);
-        let (second_start_column, second_end_column) = self.construct_source(second_span);
+    ) -> Diagnostic<'a> {
         Diagnostic::error()
             .with_message("type conflict occurred")
             .with_labels(vec![
-                Label::primary(
-                    self.get_file_id(&first_span.file_name),
-                    first_start_column..first_end_column,
-                )
-                .with_message(format!("results in `{}`", first_type)),
-                Label::primary(
-                    self.get_file_id(&second_span.file_name),
-                    second_start_column..second_end_column,
-                )
-                .with_message(format!("results in `{}`", second_type)),
+                Label::primary(first_span).with_message(format!("results in `{}`", first_type)),
+                Label::primary(second_span).with_message(format!("results in `{}`", second_type)),
             ])
     }
 
@@ -354,26 +648,17 @@ impl<'a> ErrorReporter<'a> {
     /// * `second_type` - The `Type` of the second branch.
     fn handle_conflicting_previous_type(
         &self,
-        name_span: Span,
+        name_span: Span<'a>,
         previous_type: Type,
-        second_span: Span,
+        second_span: Span<'a>,
         second_type: Type,
-    ) -> Diagnostic<usize> {
-        let (first_start_column, first_end_column) = self.construct_source(name_span);
-        let (second_start_column, second_end_column) = self.construct_source(second_span);
+    ) -> Diagnostic<'a> {
         Diagnostic::error()
             .with_message("type conflict occurred")
             .with_labels(vec![
-                Label::primary(
-                    self.get_file_id(&name_span.file_name),
-                    first_start_column..first_end_column,
-                )
-                .with_message(format!("was defined as `{}`", previous_type)),
-                Label::primary(
-                    self.get_file_id(&second_span.file_name),
-                    second_start_column..second_end_column,
-                )
-                .with_message(format!("results in `{}`", second_type)),
+                Label::primary(name_span)
+                    .with_message(format!("was defined as `{}`", previous_type)),
+                Label::primary(second_span).with_message(format!("results in `{}`", second_type)),
             ])
     }
 
@@ -381,28 +666,20 @@ impl<'a> ErrorReporter<'a> {
     ///
     /// # Arguments
     /// * `span` - The `Span` of this error.
-    fn handle_illegal_type(&self, span: Span) -> Diagnostic<usize> {
-        let (start_column, end_column) = self.construct_source(span);
+    fn handle_illegal_type(&self, span: Span<'a>) -> Diagnostic<'a> {
         Diagnostic::error()
             .with_message("placed a type where it was not allowed")
-            .with_labels(vec![Label::primary(
-                self.get_file_id(&span.file_name),
-                start_column..end_column,
-            )])
+            .with_labels(vec![Label::primary(span)])
     }
 
     /// Handles an undefined variable error.
     ///
     /// # Arguments
     /// * `span` - The `Span` of this error.
-    fn handle_undefined_variable(&self, span: Span) -> Diagnostic<usize> {
-        let (start_column, end_column) = self.construct_source(span);
+    fn handle_undefined_variable(&self, span: Span<'a>) -> Diagnostic<'a> {
         Diagnostic::error()
             .with_message("found undefined variable")
-            .with_labels(vec![Label::primary(
-                self.get_file_id(&span.file_name),
-                start_column..end_column,
-            )])
+            .with_labels(vec![Label::primary(span)])
     }
 
     /// Handles a parameter mismatch error.
@@ -413,89 +690,296 @@ impl<'a> ErrorReporter<'a> {
     /// * `actual_parameter_count` - The number of parameters that were provided.
     fn handle_parameter_mismatch(
         &self,
-        span: Span,
+        span: Span<'a>,
         expected_parameter_count: usize,
         actual_parameter_count: usize,
-    ) -> Diagnostic<usize> {
-        let (start_column, end_column) = self.construct_source(span);
+    ) -> Diagnostic<'a> {
         Diagnostic::error()
-            .with_message(&format!(
+            .with_message(format!(
                 "this function expected {} parameters but received {} parameters",
                 expected_parameter_count, actual_parameter_count
             ))
-            .with_labels(vec![Label::primary(
-                self.get_file_id(&span.file_name),
-                start_column..end_column,
-            )])
+            .with_labels(vec![Label::primary(span)])
+    }
+
+    /// Handles an unknown argument name error.
+    ///
+    /// # Arguments
+    /// * `span` - The `Span` of the name of the argument.
+    /// * `function_span` - The `Span` of the name of the function.
+    fn handle_unknown_argument_name(
+        &self,
+        span: Span<'a>,
+        function_span: Span<'a>,
+    ) -> Diagnostic<'a> {
+        Diagnostic::error()
+            .with_message("found unknown argument name")
+            .with_labels(vec![
+                Label::primary(span).with_message("this name does not match any parameter"),
+                Label::secondary(function_span).with_message("of this function"),
+            ])
+    }
+
+    /// Handles a duplicate argument error.
+    ///
+    /// # Arguments
+    /// * `span` - The `Span` of the duplicated argument.
+    /// * `previous_span` - The `Span` of the argument that was given first.
+    fn handle_duplicate_argument(&self, span: Span<'a>, previous_span: Span<'a>) -> Diagnostic<'a> {
+        Diagnostic::error()
+            .with_message("parameter was given more than one argument")
+            .with_labels(vec![
+                Label::primary(span).with_message("duplicate argument"),
+                Label::secondary(previous_span).with_message("previously given here"),
+            ])
+    }
+
+    /// Handles a positional argument after a named argument error.
+    ///
+    /// # Arguments
+    /// * `span` - The `Span` of the positional argument.
+    /// * `named_span` - The `Span` of the named argument that came before it.
+    fn handle_positional_after_named_argument(
+        &self,
+        span: Span<'a>,
+        named_span: Span<'a>,
+    ) -> Diagnostic<'a> {
+        Diagnostic::error()
+            .with_message("positional argument found after a named argument")
+            .with_labels(vec![
+                Label::primary(span),
+                Label::secondary(named_span).with_message("named argument given here"),
+            ])
+            .with_notes(vec![
+                "positional arguments must come before any named arguments".to_string(),
+            ])
+    }
+
+    /// Handles a missing argument error.
+    ///
+    /// # Arguments
+    /// * `span` - The `Span` of the application.
+    /// * `parameter_span` - The `Span` of the parameter that was not given.
+    fn handle_missing_argument(&self, span: Span<'a>, parameter_span: Span<'a>) -> Diagnostic<'a> {
+        Diagnostic::error()
+            .with_message("missing argument for parameter")
+            .with_labels(vec![
+                Label::primary(span),
+                Label::secondary(parameter_span)
+                    .with_message("this parameter has no default value"),
+            ])
+    }
+
+    /// Handles an unsafe operation error.
+    ///
+    /// # Arguments
+    /// * `span` - The `Span` of this error.
+    fn handle_unsafe_operation(&self, span: Span<'a>) -> Diagnostic<'a> {
+        Diagnostic::error()
+            .with_message("pointer operation used outside of an unsafe block")
+            .with_labels(vec![Label::primary(span)])
+            .with_notes(vec![
+                "wrap the operation in `unsafe { ... }` to allow it".to_string()
+            ])
+    }
+
+    /// Handles an illegal address of error.
+    ///
+    /// # Arguments
+    /// * `span` - The `Span` of this error.
+    fn handle_illegal_address_of(&self, span: Span<'a>) -> Diagnostic<'a> {
+        Diagnostic::error()
+            .with_message("can only take the address of a variable")
+            .with_labels(vec![Label::primary(span)])
+    }
+
+    /// Handles a division by zero error.
+    ///
+    /// # Arguments
+    /// * `span` - The `Span` of this error.
+    fn handle_division_by_zero(&self, span: Span<'a>) -> Diagnostic<'a> {
+        Diagnostic::error()
+            .with_message("attempted to divide by zero while evaluating a constant")
+            .with_labels(vec![Label::primary(span)])
+    }
+
+    /// Handles a constant overflow error.
+    ///
+    /// # Arguments
+    /// * `span` - The `Span` of this error.
+    fn handle_constant_overflow(&self, span: Span<'a>) -> Diagnostic<'a> {
+        Diagnostic::error()
+            .with_message("operation overflowed while evaluating a constant")
+            .with_labels(vec![Label::primary(span)])
+    }
+
+    /// Handles a non constant expression error.
+    ///
+    /// # Arguments
+    /// * `span` - The `Span` of this error.
+    fn handle_non_constant_expression(&self, span: Span<'a>) -> Diagnostic<'a> {
+        Diagnostic::error()
+            .with_message("expression can not be evaluated at compile time")
+            .with_labels(vec![Label::primary(span)])
+            .with_notes(vec![
+                "constants may only use arithmetic, `if`, `while`, `let` and applications of functions defined in Envious".to_string(),
+            ])
+    }
+
+    /// Handles a cyclic constant error.
+    ///
+    /// # Arguments
+    /// * `span` - The `Span` of this error.
+    fn handle_cyclic_constant(&self, span: Span<'a>) -> Diagnostic<'a> {
+        Diagnostic::error()
+            .with_message("constant depends on its own value")
+            .with_labels(vec![Label::primary(span)])
+    }
+
+    /// Handles a constant evaluation limit error.
+    ///
+    /// # Arguments
+    /// * `span` - The `Span` of this error.
+    fn handle_constant_evaluation_limit(&self, span: Span<'a>) -> Diagnostic<'a> {
+        Diagnostic::error()
+            .with_message("evaluation of the constant took too long")
+            .with_labels(vec![Label::primary(span)])
+            .with_notes(vec!["the constant may recurse or loop forever".to_string()])
+    }
+
+    /// Handles an assignment to constant error.
+    ///
+    /// # Arguments
+    /// * `span` - The `Span` of the name being assigned.
+    /// * `constant_span` - The `Span` of the name of the constant.
+    fn handle_assignment_to_constant(
+        &self,
+        span: Span<'a>,
+        constant_span: Span<'a>,
+    ) -> Diagnostic<'a> {
+        Diagnostic::error()
+            .with_message("can not assign to a constant")
+            .with_labels(vec![
+                Label::primary(span),
+                Label::secondary(constant_span).with_message("constant defined here"),
+            ])
     }
 
     /// Handles an unknown function error.
     ///
     /// # Arguments
     /// * `span` - The `Span` of this error.
-    fn handle_unknown_function(&self, span: Span) -> Diagnostic<usize> {
-        let (start_column, end_column) = self.construct_source(span);
+    fn handle_unknown_function(&self, span: Span<'a>) -> Diagnostic<'a> {
         Diagnostic::error()
             .with_message("found unknown function")
-            .with_labels(vec![Label::primary(
-                self.get_file_id(&span.file_name),
-                start_column..end_column,
-            )])
+            .with_labels(vec![Label::primary(span)])
     }
 
-    /// Takes the span of the error and
-    /// calculates the beginning column and the ending column
-    /// with respect to the entire file.
-    ///
-    /// # Arguments
-    /// * `span` - The `Span` referenced by this error.
-    fn construct_source(&self, span: Span) -> (usize, usize) {
-        let mut start_column = 0;
-        let mut end_column = 0;
-        let mut current_line = 1;
-        let mut found_start = false;
-        let bytes = self.get_file_source(&span.file_name).as_bytes();
-        let mut index = 0;
-        while index < bytes.len() {
-            let byte = bytes[index];
-            if byte == b'\n' {
-                current_line += 1;
-            } else if current_line == span.line_start && !found_start {
-                start_column += span.column_start;
-                end_column += span.column_start;
-                index += span.column_start;
-                found_start = true;
-                continue;
-            } else if current_line == span.line_end {
-                if current_line != span.line_start {
-                    end_column += span.column_end;
-                } else if span.line_start == span.line_end {
-                    end_column += span.column_end - span.column_start;
-                }
-
-                break;
-            }
+    /// Handles an unknown attribute error.
+    ///
+    /// # Arguments
+    /// * `span` - The `Span` of the name of the attribute.
+    fn handle_unknown_attribute(&self, span: Span<'a>) -> Diagnostic<'a> {
+        Diagnostic::error()
+            .with_message("found unknown attribute")
+            .with_labels(vec![Label::primary(span)])
+            .with_notes(vec!["the only supported attribute is `#allow`".to_string()])
+    }
 
-            if !found_start {
-                start_column += 1;
-            }
+    /// Handles an unknown lint error.
+    ///
+    /// # Arguments
+    /// * `span` - The `Span` of the name of the lint.
+    fn handle_unknown_lint(&self, span: Span<'a>) -> Diagnostic<'a> {
+        let lints = Lint::ALL
+            .iter()
+            .map(|lint| format!("`{}`", lint.name()))
+            .collect::<Vec<_>>()
+            .join(", ");
 
-            end_column += 1;
-            index += 1;
-        }
+        Diagnostic::error()
+            .with_message("found unknown lint")
+            .with_labels(vec![Label::primary(span)])
+            .with_notes(vec![format!("the lints are `warnings`, {}", lints)])
+    }
 
-        (start_column - 1, end_column)
+    /// Handles an unused variable warning.
+    ///
+    /// # Arguments
+    /// * `span` - The `Span` of the name of the variable.
+    fn handle_unused_variable(&self, span: Span<'a>) -> Diagnostic<'a> {
+        Diagnostic::warning()
+            .with_message("unused variable")
+            .with_labels(vec![
+                Label::primary(span).with_message("this variable is never read")
+            ])
+            .with_notes(vec![
+                "variables whose names start with `_` are never reported".to_string(),
+            ])
     }
 
-    /// Gets the source of the file associated with the given file.
-    /// This function unwraps the result, thus it expects
-    /// that the file actually exists.
+    /// Handles an unused parameter warning.
     ///
     /// # Arguments
-    /// * `file_name` - The name of the file.
-    fn get_file_source(&self, file_name: &str) -> &str {
-        let file_id = self.get_file_id(file_name);
-        self.files.get(file_id).unwrap().source()
+    /// * `span` - The `Span` of the parameter.
+    fn handle_unused_parameter(&self, span: Span<'a>) -> Diagnostic<'a> {
+        Diagnostic::warning()
+            .with_message("unused parameter")
+            .with_labels(vec![
+                Label::primary(span).with_message("this parameter is never read")
+            ])
+            .with_notes(vec![
+                "parameters whose names start with `_` are never reported".to_string(),
+            ])
+    }
+
+    /// Handles an unreachable code warning.
+    ///
+    /// # Arguments
+    /// * `span` - The `Span` of the code that is never run.
+    /// * `diverging_span` - The `Span` of the expression that never finishes.
+    fn handle_unreachable_code(&self, span: Span<'a>, diverging_span: Span<'a>) -> Diagnostic<'a> {
+        Diagnostic::warning()
+            .with_message("unreachable code")
+            .with_labels(vec![
+                Label::primary(span).with_message("this code is never run"),
+                Label::secondary(diverging_span)
+                    .with_message("any code after this expression is unreachable"),
+            ])
+    }
+
+    /// Handles a shadowed parameter warning.
+    ///
+    /// # Arguments
+    /// * `span` - The `Span` of the name in the `let` expression.
+    /// * `parameter_span` - The `Span` of the parameter.
+    fn handle_shadowed_parameter(
+        &self,
+        span: Span<'a>,
+        parameter_span: Span<'a>,
+    ) -> Diagnostic<'a> {
+        Diagnostic::warning()
+            .with_message("`let` replaces the value of a parameter")
+            .with_labels(vec![
+                Label::primary(span).with_message("this assigns a new value to the parameter"),
+                Label::secondary(parameter_span).with_message("parameter defined here"),
+            ])
+            .with_notes(vec![
+                "use a different name to define a new variable".to_string()
+            ])
+    }
+
+    /// Handles a constant condition warning.
+    ///
+    /// # Arguments
+    /// * `span` - The `Span` of the condition.
+    /// * `value` - The value the condition always has.
+    fn handle_constant_condition(&self, span: Span<'a>, value: bool) -> Diagnostic<'a> {
+        Diagnostic::warning()
+            .with_message("constant condition")
+            .with_labels(vec![
+                Label::primary(span).with_message(format!("this condition is always `{}`", value))
+            ])
     }
 
     /// Gets the file id associated with the given file.
@@ -561,7 +1045,7 @@ impl<'a> Reporter for Vec<Error<'a>> {
     fn report(self, error_reporter: &ErrorReporter, color: bool) -> Option<Self::Output> {
         for error in &self {
             let bytes = error_reporter.report(error, color);
-            io::stdout().write(&bytes).ok()?;
+            error_reporter.write(&bytes).ok()?;
         }
 
         if !self.is_empty() {
@@ -572,6 +1056,29 @@ impl<'a> Reporter for Vec<Error<'a>> {
     }
 }
 
+/// The warnings are always reported, but only the warnings
+/// whose lint is denied cause the report to fail.
+impl<'a> Reporter for Vec<(Warning<'a>, LintLevel)> {
+    type Output = ();
+
+    fn is_err(&self) -> bool {
+        self.iter().any(|(_, level)| *level == LintLevel::Deny)
+    }
+
+    fn report(self, error_reporter: &ErrorReporter, color: bool) -> Option<Self::Output> {
+        for (warning, level) in &self {
+            let bytes = error_reporter.report_warning(warning, *level, color);
+            error_reporter.write(&bytes).ok()?;
+        }
+
+        if self.is_err() {
+            None
+        } else {
+            Some(())
+        }
+    }
+}
+
 impl<'a> ReporterResult for Vec<Error<'a>> {
     type Output = ();
     type Error = Vec<String>;
@@ -608,7 +1115,7 @@ impl<'a> Reporter for Option<Error<'a>> {
     fn report(self, error_reporter: &ErrorReporter, color: bool) -> Option<Self::Output> {
         if let Some(ref error) = self {
             let bytes = error_reporter.report(error, color);
-            io::stdout().write(&bytes).ok()?;
+            error_reporter.write(&bytes).ok()?;
             Some(())
         } else {
             None
@@ -638,7 +1145,7 @@ impl<'a> ReporterResult for Option<Error<'a>> {
     }
 }
 
-impl<'a, T> Reporter for Result<T, Error<'a>> {
+impl<'a, T> Reporter for Result<T, Box<Error<'a>>> {
     type Output = T;
 
     fn is_err(&self) -> bool {
@@ -650,14 +1157,14 @@ impl<'a, T> Reporter for Result<T, Error<'a>> {
             Ok(val) => Some(val),
             Err(error) => {
                 let bytes = error_reporter.report(&error, color);
-                io::stdout().write(&bytes).ok()?;
+                error_reporter.write(&bytes).ok()?;
                 None
             }
         }
     }
 }
 
-impl<'a, T> ReporterResult for Result<T, Error<'a>> {
+impl<'a, T> ReporterResult for Result<T, Box<Error<'a>>> {
     type Output = T;
     type Error = String;
 
@@ -0,0 +1,193 @@
+use std::{collections::BTreeSet, path::Path};
+
+use serde_json::{json, Value};
+
+use super::{
+    diagnostic::{Diagnostic, Label, LabelStyle, Severity},
+    fix::Fix,
+    Span,
+};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+// The base of the relative URIs of files, which is the directory the compiler runs in.
+const SOURCE_ROOT: &str = "%SRCROOT%";
+
+/// Creates the SARIF result of a single diagnostic. The primary labels
+/// become the locations of the result and the secondary labels become
+/// its related locations.
+///
+/// # Arguments
+/// * `diagnostic` - The `Diagnostic` to convert.
+pub fn create_result(diagnostic: &Diagnostic) -> Value {
+    let level = match diagnostic.severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+    };
+
+    let labels_with_style = |style| {
+        diagnostic
+            .labels
+            .iter()
+            .filter(|label| label.style == style)
+            .map(create_location)
+            .collect::<Vec<_>>()
+    };
+
+    json!({
+        "ruleId": diagnostic.code,
+        "level": level,
+        "message": { "text": diagnostic.message },
+        "locations": labels_with_style(LabelStyle::Primary),
+        "relatedLocations": labels_with_style(LabelStyle::Secondary),
+        "fixes": diagnostic.fixes.iter().map(create_fix).collect::<Vec<_>>(),
+        "properties": { "notes": diagnostic.notes },
+    })
+}
+
+/// Creates a SARIF log with a single run that contains the given results.
+///
+/// # Arguments
+/// * `results` - The results created by `create_result`.
+pub fn create_log(results: &[Value]) -> String {
+    let rules = results
+        .iter()
+        .filter_map(|result| result["ruleId"].as_str())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .map(|code| json!({ "id": code }))
+        .collect::<Vec<_>>();
+
+    let mut run = json!({
+        "tool": {
+            "driver": {
+                "name": "envious",
+                "version": env!("CARGO_PKG_VERSION"),
+                "rules": rules,
+            },
+        },
+        "results": results,
+    });
+
+    if let Ok(directory) = std::env::current_dir() {
+        run["originalUriBaseIds"] = json!({
+            SOURCE_ROOT: { "uri": format!("{}/", file_uri(&directory.to_string_lossy())) },
+        });
+    }
+
+    let log = json!({
+        "$schema": SARIF_SCHEMA,
+        "version": SARIF_VERSION,
+        "runs": [run],
+    });
+
+    serde_json::to_string_pretty(&log).unwrap()
+}
+
+/// Creates the SARIF fix of a diagnostic. Every edit becomes a replacement
+/// in the artifact change of its file.
+///
+/// # Arguments
+/// * `fix` - The `Fix` to convert.
+fn create_fix(fix: &Fix) -> Value {
+    let artifact_changes = fix
+        .edits
+        .iter()
+        .map(|edit| {
+            json!({
+                "artifactLocation": create_artifact_location(edit.span.file_name),
+                "replacements": [{
+                    "deletedRegion": create_region(edit.span),
+                    "insertedContent": { "text": edit.replacement },
+                }],
+            })
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "description": { "text": fix.message },
+        "artifactChanges": artifact_changes,
+    })
+}
+
+/// Creates the SARIF location of a label.
+///
+/// # Arguments
+/// * `label` - The `Label` to convert.
+fn create_location(label: &Label) -> Value {
+    let mut location = json!({
+        "physicalLocation": {
+            "artifactLocation": create_artifact_location(label.span.file_name),
+            "region": create_region(label.span),
+        },
+    });
+
+    if !label.message.is_empty() {
+        location["message"] = json!({ "text": label.message });
+    }
+
+    location
+}
+
+/// Creates the SARIF region of a span. SARIF regions end at the column
+/// after the last character, while a `Span` ends at the last character.
+///
+/// # Arguments
+/// * `span` - The `Span` to convert.
+fn create_region(span: Span) -> Value {
+    json!({
+        "startLine": span.line_start,
+        "startColumn": span.column_start,
+        "endLine": span.line_end,
+        "endColumn": span.column_end + 1,
+        "byteOffset": span.start,
+        "byteLength": span.end - span.start,
+    })
+}
+
+/// Creates the SARIF artifact location of a file. Absolute paths become
+/// `file` URIs, while relative paths become relative references that are
+/// resolved against the directory the compiler runs in.
+///
+/// # Arguments
+/// * `file_name` - The path of the file, as it was given to the compiler.
+fn create_artifact_location(file_name: &str) -> Value {
+    if Path::new(file_name).is_absolute() {
+        json!({ "uri": file_uri(file_name) })
+    } else {
+        json!({ "uri": encode_path(file_name), "uriBaseId": SOURCE_ROOT })
+    }
+}
+
+/// Converts an absolute path into a `file` URI, such as `file:///home/main.envy`.
+///
+/// # Arguments
+/// * `path` - The absolute path to convert.
+fn file_uri(path: &str) -> String {
+    let path = encode_path(path);
+    if path.starts_with('/') {
+        format!("file://{}", path)
+    } else {
+        // Windows paths start with a drive letter instead of a slash.
+        format!("file:///{}", path)
+    }
+}
+
+/// Percent encodes the bytes of a path that may not appear in a URI, and
+/// separates its components with slashes.
+///
+/// # Arguments
+/// * `path` - The path to encode.
+fn encode_path(path: &str) -> String {
+    let mut encoded = String::new();
+    for byte in path.bytes() {
+        match byte {
+            b'\\' => encoded.push('/'),
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b':' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
@@ -1,15 +1,19 @@
-use std::cmp::{max, min};
+use serde::Serialize;
 
 /// Struct used by all parts of the program to note the location information
 /// of the different tokens and expressions generated by the `Lexer` and the
 /// `Parser` respectively.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
 pub struct Span<'a> {
     // The name of the file.
     pub file_name: &'a str,
+    // The byte offset of the first byte of this span in the file.
+    pub start: usize,
+    // The byte offset after the last byte of this span in the file.
+    pub end: usize,
     // The line where this span starts.
     pub line_start: usize,
-    // The column where this span starts.
+    // The column where this span starts. Columns count characters, not bytes.
     pub column_start: usize,
     // The line where this span ends.
     pub line_end: usize,
@@ -21,6 +25,8 @@ impl<'a> Span<'a> {
     /// Creates a new `Span`.
     pub fn new(
         file_name: &'a str,
+        start: usize,
+        end: usize,
         line_start: usize,
         column_start: usize,
         line_end: usize,
@@ -28,6 +34,8 @@ impl<'a> Span<'a> {
     ) -> Self {
         Self {
             file_name,
+            start,
+            end,
             line_start,
             column_start,
             line_end,
@@ -35,10 +43,9 @@ impl<'a> Span<'a> {
         }
     }
 
-    /// Combines two spans together.
-    /// This function expects that the spans passed
-    /// are from the same file. If this is not true,
-    /// the generated span may not be correct.
+    /// Combines two spans together into the smallest span that contains both.
+    /// The start and the end are compared as positions, so that the columns
+    /// of spans on different lines are never mixed.
     ///
     /// # Arguments
     /// * `other` - The other span to use when combining.
@@ -47,12 +54,54 @@ impl<'a> Span<'a> {
             panic!("The file names of the Span's do not match!");
         }
 
+        let (first, last) = if self.start <= other.start {
+            (self, &other)
+        } else {
+            (&other, self)
+        };
+        let last = if first.end > last.end { first } else { last };
+
         Span::new(
             self.file_name,
-            min(self.line_start, other.line_start),
-            min(self.column_start, other.column_start),
-            max(self.line_end, other.line_end),
-            max(self.column_end, other.column_end),
+            first.start,
+            last.end,
+            first.line_start,
+            first.column_start,
+            last.line_end,
+            last.column_end,
+        )
+    }
+
+    /// Splits a span on a single line after its first character, which must be
+    /// a single byte, such as the `>` and the `=` of a `>=` token.
+    pub fn split_first(&self) -> (Span<'a>, Span<'a>) {
+        (
+            Span::new(
+                self.file_name,
+                self.start,
+                self.start + 1,
+                self.line_start,
+                self.column_start,
+                self.line_start,
+                self.column_start + 1,
+            ),
+            Span::new(
+                self.file_name,
+                self.start + 1,
+                self.end,
+                self.line_start,
+                self.column_start + 1,
+                self.line_end,
+                self.column_end,
+            ),
         )
     }
+
+    /// Determines whether the other span lies within this span.
+    ///
+    /// # Arguments
+    /// * `other` - The span that may be contained.
+    pub fn contains(&self, other: &Span<'a>) -> bool {
+        self.file_name == other.file_name && self.start <= other.start && other.end <= self.end
+    }
 }
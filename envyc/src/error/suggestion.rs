@@ -0,0 +1,164 @@
+use std::cmp::min;
+
+use crate::{interner::Interner, lexer::token::TokenKind};
+
+use super::Error;
+
+/// Enum that stores the names that may have been meant instead of an unknown name.
+/// The type checker only knows the ids of names, so it collects the candidates,
+/// which are ranked once the `Interner` is available.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Suggestion {
+    // The id of the unknown name and the ids of the names visible where it was used.
+    Unresolved { name: usize, candidates: Vec<usize> },
+    // The name closest to the unknown name, if any name is close enough.
+    Resolved(Option<String>),
+}
+
+impl Suggestion {
+    /// Returns the suggested name, if the suggestion has been resolved to one.
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            Suggestion::Resolved(Some(name)) => Some(name),
+            _ => None,
+        }
+    }
+}
+
+/// Resolves the suggestions of the errors with the names stored in the `Interner`.
+/// Identifiers that were found where a keyword was expected are compared against
+/// the expected keywords, so that typos such as `defin` suggest `define`.
+///
+/// # Arguments
+/// * `errors` - The errors to resolve.
+/// * `interner` - The `Interner` that stores the names.
+pub fn resolve_suggestions(errors: &mut [Error], interner: &Interner<String>) {
+    for error in errors {
+        match error {
+            Error::UndefinedVariable { suggestion, .. } => {
+                // The literals that are written as keywords are common typos as well.
+                let literals = ["true", "false", "null", "inf", "nan"];
+                resolve(suggestion, interner, &literals);
+            }
+            Error::UnknownFunction { suggestion, .. } => resolve(suggestion, interner, &[]),
+            Error::ExpectedKind {
+                expected_kinds,
+                actual_kind: TokenKind::Identifier(id),
+                suggestion,
+                ..
+            } => {
+                let keywords = expected_kinds
+                    .iter()
+                    .filter(|kind| kind.is_keyword())
+                    .map(|kind| kind.to_string())
+                    .collect::<Vec<_>>();
+                *suggestion = closest(
+                    interner.get(*id),
+                    keywords.iter().map(|keyword| keyword.as_str()),
+                )
+                .map(|keyword| keyword.to_string());
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Replaces the candidates of an unresolved suggestion with the closest name.
+///
+/// # Arguments
+/// * `suggestion` - The suggestion to resolve.
+/// * `interner` - The `Interner` that stores the names.
+/// * `keywords` - Keywords that are candidates in addition to the names.
+fn resolve(suggestion: &mut Suggestion, interner: &Interner<String>, keywords: &[&str]) {
+    if let Suggestion::Unresolved { name, candidates } = suggestion {
+        let candidates = candidates
+            .iter()
+            .map(|candidate| interner.get(*candidate).as_str())
+            .chain(keywords.iter().copied());
+        let closest = closest(interner.get(*name), candidates).map(|name| name.to_string());
+        *suggestion = Suggestion::Resolved(closest);
+    }
+}
+
+/// Finds the candidate most similar to the name. A candidate is similar if it is
+/// only a few edits away from the name, or if one of the names starts with the
+/// other one, such as `Integer` and `Int`. Ties are broken alphabetically, so that
+/// the suggestion does not depend on the order of the candidates.
+///
+/// # Arguments
+/// * `name` - The unknown name.
+/// * `candidates` - The names that may have been meant.
+pub fn closest<'c>(name: &str, candidates: impl Iterator<Item = &'c str>) -> Option<&'c str> {
+    let max_distance = (name.chars().count() / 3).max(1);
+    candidates
+        .filter(|candidate| *candidate != name)
+        .filter_map(|candidate| {
+            // Replacing every character of a short name is not a typo.
+            let distance = edit_distance(name, candidate);
+            let is_typo = distance <= max_distance && distance < candidate.chars().count();
+            if is_typo || is_prefix(name, candidate) {
+                Some((distance, candidate))
+            } else {
+                None
+            }
+        })
+        .min()
+        .map(|(_, candidate)| candidate)
+}
+
+/// Determines whether one of the names starts with the other one, ignoring case.
+/// Names shorter than three characters are never a prefix, since almost any name
+/// would start with them.
+///
+/// # Arguments
+/// * `first` - The first name.
+/// * `second` - The second name.
+fn is_prefix(first: &str, second: &str) -> bool {
+    let (first, second) = (first.to_lowercase(), second.to_lowercase());
+    let (shorter, longer) = if first.len() < second.len() {
+        (first, second)
+    } else {
+        (second, first)
+    };
+
+    shorter.chars().count() >= 3 && longer.starts_with(&shorter)
+}
+
+/// Calculates the edit distance between two names, which is the number of characters
+/// that have to be inserted, removed, replaced or swapped with their neighbour to turn
+/// one name into the other.
+///
+/// # Arguments
+/// * `first` - The first name.
+/// * `second` - The second name.
+fn edit_distance(first: &str, second: &str) -> usize {
+    let (first, second) = (
+        first.chars().collect::<Vec<_>>(),
+        second.chars().collect::<Vec<_>>(),
+    );
+    // The distances between every prefix of the first name and every prefix of the second.
+    let mut distances = vec![vec![0; second.len() + 1]; first.len() + 1];
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+        *distance = j;
+    }
+
+    for i in 1..=first.len() {
+        for j in 1..=second.len() {
+            let cost = (first[i - 1] != second[j - 1]) as usize;
+            let mut distance = min(
+                distances[i - 1][j - 1] + cost,
+                min(distances[i - 1][j], distances[i][j - 1]) + 1,
+            );
+            if i > 1 && j > 1 && first[i - 1] == second[j - 2] && first[i - 2] == second[j - 1] {
+                distance = min(distance, distances[i - 2][j - 2] + 1);
+            }
+
+            distances[i][j] = distance;
+        }
+    }
+
+    distances[first.len()][second.len()]
+}
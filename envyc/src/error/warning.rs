@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+
+use super::Span;
+
+/// Enum that details the different lints, which find code that is
+/// valid but most likely a mistake.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Lint {
+    // A variable defined with `let` that is never read.
+    UnusedVariable,
+    // A parameter that is never read in the body of its function.
+    UnusedParameter,
+    // An expression that follows an expression which never finishes, such as `return`.
+    UnreachableCode,
+    // A `let` expression that replaces the value of a parameter.
+    ShadowedParameter,
+    // An `if` or `while` condition whose value is known while compiling.
+    ConstantCondition,
+}
+
+impl Lint {
+    /// Every lint, in the order they are listed to the user.
+    pub const ALL: [Lint; 5] = [
+        Lint::UnusedVariable,
+        Lint::UnusedParameter,
+        Lint::UnreachableCode,
+        Lint::ShadowedParameter,
+        Lint::ConstantCondition,
+    ];
+
+    /// Returns the name of the lint, which is used by `#allow` attributes
+    /// and by the command line. The names of existing lints must never change.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Lint::UnusedVariable => "unused_variable",
+            Lint::UnusedParameter => "unused_parameter",
+            Lint::UnreachableCode => "unreachable_code",
+            Lint::ShadowedParameter => "shadowed_parameter",
+            Lint::ConstantCondition => "constant_condition",
+        }
+    }
+
+    /// Finds the lint with the given name.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the lint, such as `unused_variable`.
+    pub fn from_name(name: &str) -> Option<Lint> {
+        Lint::ALL.iter().copied().find(|lint| lint.name() == name)
+    }
+}
+
+/// Enum that represents how a lint is reported.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LintLevel {
+    /// The lint is not checked.
+    Allow,
+    /// The lint is reported as a warning, which does not stop the compilation.
+    Warn,
+    /// The lint is reported as an error, which stops the compilation.
+    Deny,
+}
+
+/// Struct that stores the level of every lint. Every lint is a warning by default.
+#[derive(Debug, Clone, Default)]
+pub struct LintLevels {
+    levels: HashMap<Lint, LintLevel>,
+}
+
+impl LintLevels {
+    /// Returns the level of a lint.
+    ///
+    /// # Arguments
+    /// * `lint` - The lint to look up.
+    pub fn get(&self, lint: Lint) -> LintLevel {
+        self.levels.get(&lint).copied().unwrap_or(LintLevel::Warn)
+    }
+
+    /// Sets the level of a lint.
+    ///
+    /// # Arguments
+    /// * `lint` - The lint to change.
+    /// * `level` - The new `LintLevel` of the lint.
+    pub fn set(&mut self, lint: Lint, level: LintLevel) {
+        self.levels.insert(lint, level);
+    }
+
+    /// Sets the level of every lint.
+    ///
+    /// # Arguments
+    /// * `level` - The new `LintLevel` of the lints.
+    pub fn set_all(&mut self, level: LintLevel) {
+        for lint in Lint::ALL.iter() {
+            self.set(*lint, level);
+        }
+    }
+}
+
+/// Enum used by the linter to construct the various warnings.
+/// Unlike an `Error`, a warning does not stop the compilation
+/// unless its lint is denied.
+#[derive(Debug)]
+pub enum Warning<'a> {
+    UnusedVariable(Span<'a>),
+    UnusedParameter(Span<'a>),
+    UnreachableCode {
+        span: Span<'a>,
+        diverging_span: Span<'a>,
+    },
+    ShadowedParameter {
+        span: Span<'a>,
+        parameter_span: Span<'a>,
+    },
+    ConstantCondition {
+        span: Span<'a>,
+        value: bool,
+    },
+}
+
+impl<'a> Warning<'a> {
+    /// Returns the lint that produced the warning.
+    pub fn lint(&self) -> Lint {
+        match self {
+            Warning::UnusedVariable(_) => Lint::UnusedVariable,
+            Warning::UnusedParameter(_) => Lint::UnusedParameter,
+            Warning::UnreachableCode { .. } => Lint::UnreachableCode,
+            Warning::ShadowedParameter { .. } => Lint::ShadowedParameter,
+            Warning::ConstantCondition { .. } => Lint::ConstantCondition,
+        }
+    }
+}
@@ -0,0 +1,257 @@
+use crate::{
+    error::Span,
+    lexer::token::{Token, TokenKind},
+    parser::syntax_tree::{SyntaxElement, SyntaxKind, SyntaxNode, SyntaxTree},
+};
+
+/// Struct that stores the options of the `Formatter`.
+#[derive(Debug, Clone, Copy)]
+pub struct FormatConfig {
+    // The number of spaces used for every level of indentation.
+    pub indent_width: usize,
+}
+
+impl Default for FormatConfig {
+    fn default() -> Self {
+        Self { indent_width: 4 }
+    }
+}
+
+/// Struct that writes a `SyntaxTree` in the canonical layout of Envious.
+///
+/// Every item starts on its own line, every expression of a block is written on
+/// its own line and indented, and the `}` of a block is written on its own line
+/// after them. Any other expression is written on a single line, with a space
+/// between its tokens except after prefix operators and around parentheses, commas,
+/// colons and the angle brackets of types. Comments are kept where they were
+/// written and a single blank line is kept between items and between the
+/// expressions of a block. Formatting the output again does not change it.
+pub struct Formatter<'t, 'a> {
+    tree: &'t SyntaxTree<'a>,
+    config: FormatConfig,
+    output: String,
+    // The current level of indentation.
+    indent: usize,
+    // Whether a comment broke an expression over several lines,
+    // in which case the following lines are indented once more.
+    continuation: bool,
+    // The last token that was written and the kind of the node that contains it.
+    previous: Option<(Token<'a>, SyntaxKind)>,
+    // The number of line breaks in the source since the last token or comment.
+    newlines: usize,
+    // Whether the next token starts a new line, and whether a
+    // blank line of the source may be kept before it.
+    line_break: Option<bool>,
+}
+
+impl<'t, 'a> Formatter<'t, 'a> {
+    /// Creates a new `Formatter`.
+    ///
+    /// # Arguments
+    /// * `tree` - The `SyntaxTree` to format.
+    /// * `config` - The `FormatConfig` to follow.
+    pub fn new(tree: &'t SyntaxTree<'a>, config: FormatConfig) -> Self {
+        Self {
+            tree,
+            config,
+            output: String::new(),
+            indent: 0,
+            continuation: false,
+            previous: None,
+            newlines: 0,
+            line_break: None,
+        }
+    }
+
+    /// Formats the tree. The result ends with a single line break unless it is empty.
+    pub fn format(mut self) -> String {
+        self.format_node(self.tree.root());
+        if !self.output.is_empty() {
+            self.output.push('\n');
+        }
+
+        self.output
+    }
+
+    /// Formats a node and its children.
+    ///
+    /// # Arguments
+    /// * `node` - The `SyntaxNode` to format.
+    fn format_node(&mut self, node: &SyntaxNode<'a>) {
+        // The items of the root and the expressions of a block are statements,
+        // which are written on their own lines.
+        let statements = matches!(node.kind, SyntaxKind::Root | SyntaxKind::Block);
+        let empty_block = node.kind == SyntaxKind::Block
+            && !node.children.iter().any(|child| match child {
+                SyntaxElement::Node(_) => true,
+                SyntaxElement::Token((_, kind)) => *kind == TokenKind::Comment,
+            });
+
+        for child in &node.children {
+            match child {
+                SyntaxElement::Node(child) => {
+                    if statements {
+                        self.break_line(true);
+                    }
+
+                    self.format_node(child);
+                }
+                SyntaxElement::Token((_, TokenKind::Whitespace('\n'))) => self.newlines += 1,
+                SyntaxElement::Token((_, TokenKind::Whitespace(_))) => {}
+                SyntaxElement::Token((span, TokenKind::Comment)) => {
+                    self.format_comment(*span, statements)
+                }
+                SyntaxElement::Token(token) if node.kind == SyntaxKind::Block => match token.1 {
+                    TokenKind::LeftCurlyBrace if !empty_block => {
+                        self.format_token(*token, node.kind);
+                        self.indent += 1;
+                        self.break_line(false);
+                    }
+                    TokenKind::RightCurlyBrace if !empty_block => {
+                        self.indent -= 1;
+                        self.break_line(false);
+                        self.format_token(*token, node.kind);
+                    }
+                    _ => self.format_token(*token, node.kind),
+                },
+                SyntaxElement::Token(token) => self.format_token(*token, node.kind),
+            }
+        }
+    }
+
+    /// Writes a token, preceded by a line break or a space if needed.
+    ///
+    /// # Arguments
+    /// * `token` - The token to write.
+    /// * `owner` - The kind of the node that contains the token.
+    fn format_token(&mut self, token: Token<'a>, owner: SyntaxKind) {
+        // Outside of a comparison, a `>=` is the end of a pointer type followed by a default value.
+        if token.1 == TokenKind::GreaterThanEqualSign
+            && owner != SyntaxKind::Binary
+            && self.tree.text(&token) == ">="
+        {
+            let (right_angle_span, equal_span) = token.0.split_first();
+            self.format_token((right_angle_span, TokenKind::RightAngleBracket), owner);
+            self.format_token((equal_span, TokenKind::EqualSign), owner);
+            return;
+        }
+
+        if let Some(keep_blank) = self.line_break.take() {
+            self.new_line(keep_blank);
+        } else if let Some(previous) = self.previous {
+            if self.space_between(previous, (token, owner)) {
+                self.output.push(' ');
+            }
+        }
+
+        self.output.push_str(self.tree.text(&token));
+        self.previous = Some((token, owner));
+        self.newlines = 0;
+    }
+
+    /// Writes a comment. A comment that was written after code stays on the line of
+    /// the code, while a comment on its own line stays on its own line. The code after
+    /// a comment always starts on a new line.
+    ///
+    /// # Arguments
+    /// * `span` - The `Span` of the comment.
+    /// * `statement` - Whether the comment is written between statements.
+    fn format_comment(&mut self, span: Span<'a>, statement: bool) {
+        let text = self.tree.text(&(span, TokenKind::Comment)).trim_end();
+        if self.newlines == 0 && !self.output.is_empty() {
+            self.output.push(' ');
+        } else {
+            self.break_line(statement);
+            self.continuation = !statement;
+            let keep_blank = self.line_break.take().unwrap_or_default();
+            self.new_line(keep_blank);
+        }
+
+        self.output.push_str(text);
+        self.newlines = 0;
+        self.break_line(statement);
+        self.continuation = !statement;
+    }
+
+    /// Requests a line break before the next token. A blank line is only kept if every
+    /// request allows it, so blank lines after `{` and before `}` are removed.
+    ///
+    /// # Arguments
+    /// * `keep_blank` - Whether a blank line of the source may be kept.
+    fn break_line(&mut self, keep_blank: bool) {
+        // A line break from the structure of the code ends any
+        // expression that was broken over several lines by a comment.
+        self.continuation = false;
+        self.line_break = Some(
+            self.line_break
+                .map_or(keep_blank, |keep| keep && keep_blank),
+        );
+    }
+
+    /// Starts a new line and indents it.
+    ///
+    /// # Arguments
+    /// * `keep_blank` - Whether a blank line of the source may be kept.
+    fn new_line(&mut self, keep_blank: bool) {
+        if self.output.is_empty() {
+            return;
+        }
+
+        self.output.push('\n');
+        if keep_blank && self.newlines > 1 {
+            self.output.push('\n');
+        }
+
+        let indent = self.indent + self.continuation as usize;
+        self.output
+            .push_str(&" ".repeat(indent * self.config.indent_width));
+    }
+
+    /// Determines whether a space is written between two tokens on the same line.
+    ///
+    /// # Arguments
+    /// * `previous` - The token before the space and the kind of the node that contains it.
+    /// * `next` - The token after the space and the kind of the node that contains it.
+    fn space_between(
+        &self,
+        (previous, previous_owner): (Token<'a>, SyntaxKind),
+        (next, next_owner): (Token<'a>, SyntaxKind),
+    ) -> bool {
+        // The operator of a unary expression, a store or a negative literal is written
+        // right before its operand, unless it is a word such as `not`.
+        let prefix_operator = matches!(
+            previous_owner,
+            SyntaxKind::Unary | SyntaxKind::Store | SyntaxKind::Literal
+        ) && matches!(
+            previous.1,
+            TokenKind::Plus
+                | TokenKind::Minus
+                | TokenKind::Star
+                | TokenKind::Ampersand
+                | TokenKind::Not
+        ) && !self.tree.text(&previous).chars().all(char::is_alphabetic);
+        // Outside of a comparison, angle brackets surround the pointee of a pointer type.
+        let type_bracket = |kind: TokenKind, owner: SyntaxKind| {
+            matches!(
+                kind,
+                TokenKind::LeftAngleBracket | TokenKind::RightAngleBracket
+            ) && owner != SyntaxKind::Binary
+        };
+
+        match (previous.1, next.1) {
+            _ if prefix_operator => false,
+            (TokenKind::LeftParenthesis, _)
+            | (TokenKind::Hash, _)
+            | (TokenKind::LeftCurlyBrace, TokenKind::RightCurlyBrace) => false,
+            (_, TokenKind::RightParenthesis)
+            | (_, TokenKind::Comma)
+            | (_, TokenKind::Colon)
+            | (_, TokenKind::SemiColon) => false,
+            // The name of a function is always followed by its parameters or arguments.
+            (TokenKind::Identifier(_), TokenKind::LeftParenthesis) => false,
+            (TokenKind::LeftAngleBracket, _) if type_bracket(previous.1, previous_owner) => false,
+            (_, kind) if type_bracket(kind, next_owner) => false,
+            _ => true,
+        }
+    }
+}
@@ -1,41 +1,129 @@
 use std::collections::HashMap;
 
 use crate::{
-    error::{Error, Span},
+    error::{suggestion::Suggestion, Error, Span},
+    parser::typed_expression::TypedExpression,
     semantic_analyzer::types::Type,
 };
 
-pub struct FunctionTable {
-    function_parameter_types: HashMap<usize, Vec<Type>>,
+/// Struct that describes a single parameter of a function known
+/// to the `FunctionTable`. The name is only present for functions
+/// defined in Envious; external declarations only describe types.
+#[derive(Debug)]
+pub struct FunctionParameter<'a> {
+    pub span: Span<'a>,
+    pub name: Option<usize>,
+    pub ty: Type,
+    pub default: Option<TypedExpression<'a>>,
 }
 
-impl FunctionTable {
+impl<'a> FunctionParameter<'a> {
+    pub fn new(span: Span<'a>, name: Option<usize>, ty: Type) -> Self {
+        Self {
+            span,
+            name,
+            ty,
+            default: None,
+        }
+    }
+}
+
+/// Struct that describes the parameters a function accepts.
+/// A variadic function accepts any number of arguments after
+/// its fixed parameters.
+#[derive(Debug)]
+pub struct FunctionSignature<'a> {
+    pub parameters: Vec<FunctionParameter<'a>>,
+    pub variadic: bool,
+}
+
+#[derive(Default)]
+pub struct FunctionTable<'a> {
+    function_signatures: HashMap<usize, FunctionSignature<'a>>,
+    // The top-level constants and the spans of their names. Constants share
+    // the global namespace with functions and can not be assigned to.
+    constants: HashMap<usize, Span<'a>>,
+}
+
+impl<'a> FunctionTable<'a> {
     pub fn add_function_definition(
         &mut self,
         function_name: usize,
-        function_parameter_types: Vec<Type>,
+        function_parameters: Vec<FunctionParameter<'a>>,
+        variadic: bool,
     ) {
-        self.function_parameter_types
-            .insert(function_name, function_parameter_types);
+        self.function_signatures.insert(
+            function_name,
+            FunctionSignature {
+                parameters: function_parameters,
+                variadic,
+            },
+        );
     }
 
-    pub fn get_function_definition<'a>(
-        &self,
+    /// Sets the default value of a parameter of a previously added function.
+    /// The defaults are added after all of the functions are known, which
+    /// allows the default values to apply other functions.
+    ///
+    /// # Arguments
+    /// * `function_name` - The id of the function.
+    /// * `index` - The position of the parameter.
+    /// * `default` - The typed default value.
+    pub fn set_parameter_default(
+        &mut self,
         function_name: usize,
-        function_span: Span<'a>,
-    ) -> Result<&Vec<Type>, Error<'a>> {
-        if let Some(function_parameter_types) = self.function_parameter_types.get(&function_name) {
-            Ok(function_parameter_types)
-        } else {
-            Err(Error::UnknownFunction(function_span))
+        index: usize,
+        default: TypedExpression<'a>,
+    ) {
+        if let Some(parameter) = self
+            .function_signatures
+            .get_mut(&function_name)
+            .and_then(|signature| signature.parameters.get_mut(index))
+        {
+            parameter.default = Some(default);
         }
     }
-}
 
-impl Default for FunctionTable {
-    fn default() -> Self {
-        Self {
-            function_parameter_types: HashMap::new(),
+    pub fn add_constant(&mut self, constant_name: usize, name_span: Span<'a>) {
+        self.constants.insert(constant_name, name_span);
+    }
+
+    /// Returns the `Span` of the name of the constant, if there is a
+    /// constant with the given name.
+    ///
+    /// # Arguments
+    /// * `constant_name` - The id of the constant.
+    pub fn get_constant(&self, constant_name: usize) -> Option<Span<'a>> {
+        self.constants.get(&constant_name).copied()
+    }
+
+    /// Returns the ids of the known functions together with their signatures.
+    pub fn functions(&self) -> impl Iterator<Item = (usize, &FunctionSignature<'a>)> {
+        self.function_signatures
+            .iter()
+            .map(|(name, signature)| (*name, signature))
+    }
+
+    /// Returns the ids of the top-level constants.
+    pub fn constants(&self) -> impl Iterator<Item = usize> + '_ {
+        self.constants.keys().copied()
+    }
+
+    pub fn get_function_definition(
+        &self,
+        function_name: usize,
+        function_span: Span<'a>,
+    ) -> Result<&FunctionSignature<'a>, Box<Error<'a>>> {
+        if let Some(function_signature) = self.function_signatures.get(&function_name) {
+            Ok(function_signature)
+        } else {
+            Err(Box::new(Error::UnknownFunction {
+                span: function_span,
+                suggestion: Suggestion::Unresolved {
+                    name: function_name,
+                    candidates: self.function_signatures.keys().copied().collect(),
+                },
+            }))
         }
     }
 }
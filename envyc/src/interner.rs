@@ -28,7 +28,7 @@ impl<T: Hash + Eq> Interner<T> {
 
     /// Gets the value in the `Interner` with the given id.
     /// This function assumes that the id exists within the
-    /// * `Interner`. Using this function without that check
+    /// `Interner`. Using this function without that check
     /// will cause it to panic.
     ///
     /// # Arguments
@@ -1,11 +1,13 @@
 pub mod token;
 
+use unicode_xid::UnicodeXID;
+
 use crate::{error::Error, error::Span, interner::Interner};
 
 use self::token::{Token, TokenKind};
 
 /// Represents an internal type to simplify the code.
-type LexResult<'a> = Result<Token<'a>, Error<'a>>;
+type LexResult<'a> = Result<Token<'a>, Box<Error<'a>>>;
 
 /// Struct that transforms the input into a vector of tokens.
 /// The `Lexer` operates on the slice of bytes to
@@ -14,7 +16,9 @@ type LexResult<'a> = Result<Token<'a>, Error<'a>>;
 /// The `Lexer` follows a simple procedure: first, it gets
 /// and decodes the next byte; then, consecutive bytes of
 /// the same token type are grouped together; finally, the
-/// `Token` is constructed.
+/// `Token` is constructed. Bytes that are not ASCII are decoded
+/// as UTF-8, so that identifiers and operators may use any
+/// Unicode character.
 pub struct Lexer<'a> {
     // The name of the file currently being analyzed.
     file_name: &'a str,
@@ -57,7 +61,7 @@ impl<'a> Lexer<'a> {
             match byte {
                 whitespace if whitespace.is_ascii_whitespace() => {
                     tokens.push((
-                        self.make_span(self.current_column),
+                        self.make_span(self.start()),
                         TokenKind::Whitespace(whitespace as char),
                     ));
                     if whitespace == b'\n' {
@@ -65,98 +69,95 @@ impl<'a> Lexer<'a> {
                         self.current_column = 0;
                     }
                 }
-                b'-' if self.peek().map_or(false, |digit| digit.is_ascii_digit()) => {
-                    let start_column = self.current_column;
-                    let digit: i64 = (self.next().unwrap() - b'0').into();
-                    match self.form_number(-digit, start_column) {
-                        Ok(token) => tokens.push(token),
-                        Err(error) => errors.push(error),
-                    }
-                }
-                digit if digit.is_ascii_digit() => {
-                    match self.form_number((digit - b'0').into(), self.current_column) {
-                        Ok(token) => tokens.push(token),
-                        Err(error) => errors.push(error),
-                    }
-                }
+                digit if digit.is_ascii_digit() => match self.form_number(self.start()) {
+                    Ok(token) => tokens.push(token),
+                    Err(error) => errors.push(*error),
+                },
                 b'\'' => match self.form_char() {
                     Ok(token) => tokens.push(token),
-                    Err(error) => errors.push(error),
+                    Err(error) => errors.push(*error),
                 },
                 letter if letter.is_ascii_alphabetic() || letter == b'_' => {
-                    match self.form_word(letter as char, interner) {
+                    match self.form_word(letter as char, self.start(), interner) {
                         Ok(token) => tokens.push(token),
-                        Err(error) => errors.push(error),
+                        Err(error) => errors.push(*error),
+                    }
+                }
+                b'+' => tokens.push((self.make_span(self.start()), TokenKind::Plus)),
+                b'-' => tokens.push((self.make_span(self.start()), TokenKind::Minus)),
+                b'*' => tokens.push((self.make_span(self.start()), TokenKind::Star)),
+                b'/' if self.peek() == Some(b'/') => {
+                    let start = self.start();
+                    while !matches!(self.peek(), Some(b'\n') | None) {
+                        self.next();
                     }
+
+                    tokens.push((self.make_span(start), TokenKind::Comment))
                 }
-                b'+' => tokens.push((self.make_span(self.current_column), TokenKind::Plus)),
-                b'-' => tokens.push((self.make_span(self.current_column), TokenKind::Minus)),
-                b'*' => tokens.push((self.make_span(self.current_column), TokenKind::Star)),
-                b'/' => tokens.push((self.make_span(self.current_column), TokenKind::Slash)),
-                b'%' => tokens.push((self.make_span(self.current_column), TokenKind::PercentSign)),
+                b'/' => tokens.push((self.make_span(self.start()), TokenKind::Slash)),
+                b'%' => tokens.push((self.make_span(self.start()), TokenKind::PercentSign)),
+                b'&' => tokens.push((self.make_span(self.start()), TokenKind::Ampersand)),
+                b'#' => tokens.push((self.make_span(self.start()), TokenKind::Hash)),
                 b'!' if self.peek() == Some(b'=') => {
-                    let start_column = self.current_column;
+                    let start = self.start();
                     self.next();
-                    tokens.push((
-                        self.make_span(start_column),
-                        TokenKind::ExclamationEqualSign,
-                    ))
+                    tokens.push((self.make_span(start), TokenKind::ExclamationEqualSign))
                 }
-                b'=' => tokens.push((self.make_span(self.current_column), TokenKind::EqualSign)),
-                b'(' => tokens.push((
-                    self.make_span(self.current_column),
-                    TokenKind::LeftParenthesis,
-                )),
-                b')' => tokens.push((
-                    self.make_span(self.current_column),
-                    TokenKind::RightParenthesis,
-                )),
-                b'{' => tokens.push((
-                    self.make_span(self.current_column),
-                    TokenKind::LeftCurlyBrace,
-                )),
-                b'}' => tokens.push((
-                    self.make_span(self.current_column),
-                    TokenKind::RightCurlyBrace,
-                )),
+                b'=' => tokens.push((self.make_span(self.start()), TokenKind::EqualSign)),
+                b'(' => tokens.push((self.make_span(self.start()), TokenKind::LeftParenthesis)),
+                b')' => tokens.push((self.make_span(self.start()), TokenKind::RightParenthesis)),
+                b'{' => tokens.push((self.make_span(self.start()), TokenKind::LeftCurlyBrace)),
+                b'}' => tokens.push((self.make_span(self.start()), TokenKind::RightCurlyBrace)),
                 b'<' if self.peek() == Some(b'=') => {
-                    let start_column = self.current_column;
+                    let start = self.start();
                     self.next();
-                    tokens.push((self.make_span(start_column), TokenKind::LessThanEqualSign))
+                    tokens.push((self.make_span(start), TokenKind::LessThanEqualSign))
                 }
-                b'<' => tokens.push((
-                    self.make_span(self.current_column),
-                    TokenKind::LeftAngleBracket,
-                )),
+                b'<' => tokens.push((self.make_span(self.start()), TokenKind::LeftAngleBracket)),
                 b'>' if self.peek() == Some(b'=') => {
-                    let start_column = self.current_column;
+                    let start = self.start();
                     self.next();
-                    tokens.push((
-                        self.make_span(start_column),
-                        TokenKind::GreaterThanEqualSign,
-                    ))
+                    tokens.push((self.make_span(start), TokenKind::GreaterThanEqualSign))
+                }
+                b'>' => tokens.push((self.make_span(self.start()), TokenKind::RightAngleBracket)),
+                b',' => tokens.push((self.make_span(self.start()), TokenKind::Comma)),
+                b'.' if self.peek() == Some(b'.') && self.peek_second() == Some(b'.') => {
+                    let start = self.start();
+                    self.next();
+                    self.next();
+                    tokens.push((self.make_span(start), TokenKind::Ellipsis))
                 }
-                b'>' => tokens.push((
-                    self.make_span(self.current_column),
-                    TokenKind::RightAngleBracket,
-                )),
-                b',' => tokens.push((self.make_span(self.current_column), TokenKind::Comma)),
                 b':' if self.peek() == Some(b'=') => {
-                    let start_column = self.current_column;
+                    let start = self.start();
                     self.next();
-                    tokens.push((self.make_span(start_column), TokenKind::ColonEqualSign))
+                    tokens.push((self.make_span(start), TokenKind::ColonEqualSign))
                 }
                 b':' if self.peek() == Some(b':') => {
-                    let start_column = self.current_column;
+                    let start = self.start();
                     self.next();
-                    tokens.push((self.make_span(start_column), TokenKind::ColonColon))
+                    tokens.push((self.make_span(start), TokenKind::ColonColon))
                 }
-                b':' => tokens.push((self.make_span(self.current_column), TokenKind::Colon)),
-                b';' => tokens.push((self.make_span(self.current_column), TokenKind::SemiColon)),
+                b':' => tokens.push((self.make_span(self.start()), TokenKind::Colon)),
+                b';' => tokens.push((self.make_span(self.start()), TokenKind::SemiColon)),
                 b'\0' => break,
-                _ => errors.push(Error::UnrecognizedCharacter(
-                    self.make_span(self.current_column),
-                )),
+                _ => {
+                    let start = self.start();
+                    let character = self.finish_char(start.0);
+                    match character {
+                        Some(letter) if letter.is_xid_start() => {
+                            match self.form_word(letter, start, interner) {
+                                Ok(token) => tokens.push(token),
+                                Err(error) => errors.push(*error),
+                            }
+                        }
+                        _ => match character.and_then(operator_alias) {
+                            Some(kind) => tokens.push((self.make_span(start), kind)),
+                            None => {
+                                errors.push(Error::UnrecognizedCharacter(self.make_span(start)))
+                            }
+                        },
+                    }
+                }
             }
         }
 
@@ -168,23 +169,63 @@ impl<'a> Lexer<'a> {
     }
 
     /// Greedily walks through consecutive bytes and forms the largest possible number,
-    /// either an int or a float. There are two possible errors that can occur: both involve
-    /// forming a number that is greater than the highest possible value for the given type.
+    /// either an int or a float. Integers may be written in hexadecimal, octal or binary
+    /// with the `0x`, `0o` and `0b` prefixes, floats may have an exponent such as `1.5e-3`,
+    /// and two digits of both may be separated with `_`. The sign of a number is not part
+    /// of the literal, since `-` is parsed as an operator.
     ///
     /// # Arguments
-    /// * `digit` - The first digit of the number.
-    /// * `start_column` - The starting column of the number. This changes when dealing with negative numbers.
-    fn form_number(&mut self, digit: i64, start_column: usize) -> LexResult<'a> {
-        let mut number = digit.to_string();
-        let mut seen_decimal_point = false;
+    /// * `start` - The start of the number.
+    fn form_number(&mut self, start: (usize, usize)) -> LexResult<'a> {
+        let radix = match (self.bytes[start.0], self.peek()) {
+            (b'0', Some(b'x')) => 16,
+            (b'0', Some(b'o')) => 8,
+            (b'0', Some(b'b')) => 2,
+            _ => 10,
+        };
+        let mut number = if radix == 10 {
+            (self.bytes[start.0] as char).to_string()
+        } else {
+            self.next();
+            String::new()
+        };
+
+        // The number ends at the first byte that cannot continue it, so `1else`
+        // is the number `1` followed by `else`.
+        let mut is_float = false;
+        let mut has_exponent = false;
+        let mut is_malformed = false;
         while let Some(next) = self.peek() {
             match next {
-                digit if digit.is_ascii_digit() => {
-                    number.push(digit.into());
+                digit if (digit as char).is_digit(radix) => number.push(digit.into()),
+                // A separator is only allowed between two digits.
+                b'_' => {
+                    let follows_digit = number.ends_with(|c: char| c.is_digit(radix));
+                    let precedes_digit = self
+                        .peek_second()
+                        .is_some_and(|after| (after as char).is_digit(radix));
+                    is_malformed |= !follows_digit || !precedes_digit;
                 }
-                b'.' if !seen_decimal_point => {
+                b'.' if radix == 10 && !is_float && self.peek_second() != Some(b'.') => {
                     number.push('.');
-                    seen_decimal_point = true;
+                    is_float = true;
+                }
+                // An exponent is only read if digits follow it, optionally after a sign.
+                b'e' | b'E' if radix == 10 && !has_exponent => {
+                    let has_sign = matches!(self.peek_second(), Some(b'+' | b'-'));
+                    let digit_index = self.index + 1 + has_sign as usize;
+                    if !self.bytes.get(digit_index).is_some_and(u8::is_ascii_digit) {
+                        break;
+                    }
+
+                    number.push(next.into());
+                    if has_sign {
+                        self.next();
+                        number.push(self.bytes[self.index].into());
+                    }
+
+                    is_float = true;
+                    has_exponent = true;
                 }
                 _ => break,
             }
@@ -192,102 +233,164 @@ impl<'a> Lexer<'a> {
             self.next();
         }
 
-        let span = self.make_span(start_column);
-        if seen_decimal_point {
+        let span = self.make_span(start);
+        if is_malformed || number.is_empty() {
+            Err(Box::new(Error::MalformedNumber(span)))
+        } else if is_float {
             match number.parse::<f64>() {
-                Ok(float) => Ok((span, TokenKind::FloatLiteral(float))),
-                Err(_) => Err(Error::FloatOverflow(span)),
+                Ok(float) if float.is_finite() => Ok((span, TokenKind::FloatLiteral(float))),
+                Ok(_) => Err(Box::new(Error::FloatOverflow(span))),
+                Err(_) => Err(Box::new(Error::MalformedNumber(span))),
             }
         } else {
-            match number.parse::<i64>() {
+            match u64::from_str_radix(&number, radix) {
                 Ok(int) => Ok((span, TokenKind::IntegerLiteral(int))),
-                Err(_) => Err(Error::IntegerOverflow(span)),
+                Err(_) => Err(Box::new(Error::IntegerOverflow(span))),
             }
         }
     }
 
     /// Walks through the character and ensures that exactly one character is represented.
+    /// The character may be written as an escape sequence, such as `\n` or `\u{7e}`.
+    /// A `Char` holds a single ASCII character, so characters after `\u{7f}` are reported as errors.
     fn form_char(&mut self) -> LexResult<'a> {
-        let (start_line, start_column) = (self.current_line, self.current_column);
-        let ch = if let Some(ch) = self.next() {
-            ch as char
-        } else {
-            return Err(Error::UnexpectedEndOfInput(Span::new(
-                self.file_name,
-                start_line,
-                start_column,
-                self.current_line,
-                self.current_column,
-            )));
+        let start = self.start();
+        let ch = match self.next() {
+            Some(b'\\') => match self.form_escape() {
+                Ok(ch) => ch,
+                Err(error) => {
+                    // The rest of the literal is skipped, so that its closing
+                    // quote is not read as the start of another char.
+                    while self
+                        .peek()
+                        .is_some_and(|next| next != b'\'' && next != b'\n')
+                    {
+                        self.next();
+                    }
+                    if self.peek() == Some(b'\'') {
+                        self.next();
+                    }
+
+                    return Err(error);
+                }
+            },
+            Some(_) => match self.finish_char(self.index - 1) {
+                Some(ch) => ch,
+                None => {
+                    return Err(Box::new(Error::UnrecognizedCharacter(
+                        self.make_span(start),
+                    )))
+                }
+            },
+            None => return Err(Box::new(Error::UnexpectedEndOfInput(self.make_span(start)))),
         };
 
-        if let Some(b'\'') = self.next() {
-            let span = Span::new(
-                self.file_name,
-                start_line,
-                start_column,
-                self.current_line,
-                self.current_column,
-            );
-            Ok((span, TokenKind::CharLiteral(ch)))
+        if self.next() != Some(b'\'') {
+            Err(Box::new(Error::UnterminatedChar(self.make_span(start))))
+        } else if !ch.is_ascii() {
+            Err(Box::new(Error::CharOutOfRange(self.make_span(start))))
         } else {
-            return Err(Error::UnterminatedChar(Span::new(
-                self.file_name,
-                start_line,
-                start_column,
-                self.current_line,
-                self.current_column,
-            )));
+            Ok((self.make_span(start), TokenKind::CharLiteral(ch)))
         }
     }
 
-    /// Greedily walks through consecutive bytes and forms the largest possible word.
-    /// This word may represent a type, a literal, or an identifier.
+    /// Forms the character of an escape sequence whose backslash was just consumed.
+    /// An error is returned if the escape sequence does not exist, or if a `\u{...}`
+    /// escape does not contain the hexadecimal code of a Unicode character.
+    fn form_escape(&mut self) -> Result<char, Box<Error<'a>>> {
+        let start = self.start();
+        let ch = match self.next() {
+            Some(b'n') => Some('\n'),
+            Some(b't') => Some('\t'),
+            Some(b'r') => Some('\r'),
+            Some(b'0') => Some('\0'),
+            Some(b'\\') => Some('\\'),
+            Some(b'\'') => Some('\''),
+            Some(b'u') if self.peek() == Some(b'{') => {
+                self.next();
+                let mut code = String::new();
+                while let Some(digit) = self.peek().filter(u8::is_ascii_hexdigit) {
+                    code.push(digit.into());
+                    self.next();
+                }
+
+                if code.len() <= 6 && self.peek() == Some(b'}') {
+                    self.next();
+                    u32::from_str_radix(&code, 16)
+                        .ok()
+                        .and_then(std::char::from_u32)
+                } else {
+                    None
+                }
+            }
+            Some(_) => {
+                self.finish_char(self.index - 1);
+                None
+            }
+            None => return Err(Box::new(Error::UnexpectedEndOfInput(self.make_span(start)))),
+        };
+
+        ch.ok_or_else(|| Box::new(Error::InvalidEscape(self.make_span(start))))
+    }
+
+    /// Greedily walks through consecutive characters and forms the largest possible word.
+    /// This word may represent a type, a literal, or an identifier. Identifiers follow
+    /// the Unicode `XID_Start` and `XID_Continue` properties, so names such as `Δx` are
+    /// allowed.
     ///
     /// # Arguments
-    /// * `leter` - The character with which the word started with.
+    /// * `letter` - The character with which the word started with.
+    /// * `start` - The start of the word.
     /// * `interner` - The `Interner` which stores the different string literals.
-    fn form_word(&mut self, letter: char, interner: &mut Interner<String>) -> LexResult<'a> {
-        let start_column = self.current_column;
+    fn form_word(
+        &mut self,
+        letter: char,
+        start: (usize, usize),
+        interner: &mut Interner<String>,
+    ) -> LexResult<'a> {
         let mut word = letter.to_string();
-        while let Some(next) = self.peek() {
-            if next.is_ascii_whitespace() {
-                break;
-            } else if !next.is_ascii_punctuation() || next == b'_' {
-                word.push(self.next().unwrap() as char);
-            } else {
+        while let Some(next) = self.char_at(self.index) {
+            if !next.is_xid_continue() {
                 break;
             }
+
+            word.push(next);
+            self.next();
+            self.finish_char(self.index - 1);
         }
 
         match word.as_str() {
-            "Void" => Ok((self.make_span(start_column), TokenKind::Void)),
-            "Int" => Ok((self.make_span(start_column), TokenKind::Int)),
-            "Float" => Ok((self.make_span(start_column), TokenKind::Float)),
-            "Boolean" => Ok((self.make_span(start_column), TokenKind::Boolean)),
-            "Char" => Ok((self.make_span(start_column), TokenKind::Char)),
-            "true" => Ok((
-                self.make_span(start_column),
-                TokenKind::BooleanLiteral(true),
-            )),
-            "false" => Ok((
-                self.make_span(start_column),
-                TokenKind::BooleanLiteral(false),
+            "Void" => Ok((self.make_span(start), TokenKind::Void)),
+            "Int" => Ok((self.make_span(start), TokenKind::Int)),
+            "Float" => Ok((self.make_span(start), TokenKind::Float)),
+            "Boolean" => Ok((self.make_span(start), TokenKind::Boolean)),
+            "Char" => Ok((self.make_span(start), TokenKind::Char)),
+            "Ptr" => Ok((self.make_span(start), TokenKind::Ptr)),
+            "null" => Ok((self.make_span(start), TokenKind::Null)),
+            "true" => Ok((self.make_span(start), TokenKind::BooleanLiteral(true))),
+            "false" => Ok((self.make_span(start), TokenKind::BooleanLiteral(false))),
+            "inf" => Ok((
+                self.make_span(start),
+                TokenKind::FloatLiteral(f64::INFINITY),
             )),
-            "not" => Ok((self.make_span(start_column), TokenKind::Not)),
-            "or" => Ok((self.make_span(start_column), TokenKind::Or)),
-            "and" => Ok((self.make_span(start_column), TokenKind::And)),
-            "let" => Ok((self.make_span(start_column), TokenKind::Let)),
-            "if" => Ok((self.make_span(start_column), TokenKind::If)),
-            "then" => Ok((self.make_span(start_column), TokenKind::Then)),
-            "else" => Ok((self.make_span(start_column), TokenKind::Else)),
-            "while" => Ok((self.make_span(start_column), TokenKind::While)),
-            "define" => Ok((self.make_span(start_column), TokenKind::Define)),
-            "extern" => Ok((self.make_span(start_column), TokenKind::Extern)),
-            "return" => Ok((self.make_span(start_column), TokenKind::Return)),
+            "nan" => Ok((self.make_span(start), TokenKind::FloatLiteral(f64::NAN))),
+            "not" => Ok((self.make_span(start), TokenKind::Not)),
+            "or" => Ok((self.make_span(start), TokenKind::Or)),
+            "and" => Ok((self.make_span(start), TokenKind::And)),
+            "let" => Ok((self.make_span(start), TokenKind::Let)),
+            "if" => Ok((self.make_span(start), TokenKind::If)),
+            "then" => Ok((self.make_span(start), TokenKind::Then)),
+            "else" => Ok((self.make_span(start), TokenKind::Else)),
+            "while" => Ok((self.make_span(start), TokenKind::While)),
+            "define" => Ok((self.make_span(start), TokenKind::Define)),
+            "const" => Ok((self.make_span(start), TokenKind::Const)),
+            "export" => Ok((self.make_span(start), TokenKind::Export)),
+            "extern" => Ok((self.make_span(start), TokenKind::Extern)),
+            "return" => Ok((self.make_span(start), TokenKind::Return)),
+            "unsafe" => Ok((self.make_span(start), TokenKind::Unsafe)),
             _ => {
                 let id = interner.insert(word);
-                Ok((self.make_span(start_column), TokenKind::Identifier(id)))
+                Ok((self.make_span(start), TokenKind::Identifier(id)))
             }
         }
     }
@@ -297,22 +400,69 @@ impl<'a> Lexer<'a> {
         self.bytes.get(self.index).copied()
     }
 
+    /// Peeks at the byte after the next byte without consuming either.
+    fn peek_second(&self) -> Option<u8> {
+        self.bytes.get(self.index + 1).copied()
+    }
+
     /// Consumes the next byte and increment both the index and
-    /// the current column.
+    /// the current column. The column counts characters instead of bytes,
+    /// so the bytes that continue a UTF-8 character do not start a new column.
     fn next(&mut self) -> Option<u8> {
+        let byte = self.bytes.get(self.index).copied();
         self.index += 1;
-        self.current_column += 1;
-        self.bytes.get(self.index - 1).copied()
+        if !byte.is_some_and(is_continuation_byte) {
+            self.current_column += 1;
+        }
+
+        byte
     }
 
-    /// Helper method that creates a `Span` based on
-    /// the start_column.
+    /// Decodes the UTF-8 character that starts at the byte offset, if the bytes
+    /// form a valid character.
     ///
     /// # Arguments
-    /// * `start_column` - The starting column of the `Token`.
-    fn make_span(&self, start_column: usize) -> Span<'a> {
+    /// * `index` - The byte offset of the first byte of the character.
+    fn char_at(&self, index: usize) -> Option<char> {
+        let bytes = self.bytes.get(index..(index + 4).min(self.bytes.len()))?;
+        let valid = match std::str::from_utf8(bytes) {
+            Ok(valid) => valid,
+            Err(error) => std::str::from_utf8(&bytes[..error.valid_up_to()]).ok()?,
+        };
+
+        valid.chars().next()
+    }
+
+    /// Consumes the bytes that continue the character whose first byte was just
+    /// consumed and returns the decoded character. The continuation bytes are consumed
+    /// even if the character is not valid UTF-8, so that it is only reported once.
+    ///
+    /// # Arguments
+    /// * `index` - The byte offset of the first byte of the character.
+    fn finish_char(&mut self, index: usize) -> Option<char> {
+        while self.peek().is_some_and(is_continuation_byte) {
+            self.next();
+        }
+
+        self.char_at(index)
+    }
+
+    /// Returns the byte offset and the column of the last consumed byte,
+    /// which is where the token that is currently being formed starts.
+    fn start(&self) -> (usize, usize) {
+        (self.index - 1, self.current_column)
+    }
+
+    /// Helper method that creates a `Span` from the start
+    /// of the token to the last consumed byte.
+    ///
+    /// # Arguments
+    /// * `start` - The byte offset and the column where the `Token` starts.
+    fn make_span(&self, (start_index, start_column): (usize, usize)) -> Span<'a> {
         Span::new(
             self.file_name,
+            start_index,
+            self.index.min(self.bytes.len()),
             self.current_line,
             start_column,
             self.current_line,
@@ -320,3 +470,30 @@ impl<'a> Lexer<'a> {
         )
     }
 }
+
+/// Determines whether the byte continues a UTF-8 character instead of starting one.
+///
+/// # Arguments
+/// * `byte` - The byte to inspect.
+fn is_continuation_byte(byte: u8) -> bool {
+    byte & 0b1100_0000 == 0b1000_0000
+}
+
+/// Returns the token of a mathematical symbol that can be written in place of an operator,
+/// such as `≤` for `<=`.
+///
+/// # Arguments
+/// * `symbol` - The symbol to look up.
+fn operator_alias(symbol: char) -> Option<TokenKind> {
+    match symbol {
+        '≤' => Some(TokenKind::LessThanEqualSign),
+        '≥' => Some(TokenKind::GreaterThanEqualSign),
+        '≠' => Some(TokenKind::ExclamationEqualSign),
+        '¬' => Some(TokenKind::Not),
+        '∧' => Some(TokenKind::And),
+        '∨' => Some(TokenKind::Or),
+        '×' => Some(TokenKind::Star),
+        '÷' => Some(TokenKind::Slash),
+        _ => None,
+    }
+}
@@ -14,16 +14,23 @@ pub type Token<'a> = (Span<'a>, TokenKind);
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum TokenKind {
     Whitespace(char),
+    // A line comment, which starts with `//`. Its text is read from the
+    // source through the `Span`, since it is only needed by the formatter.
+    Comment,
 
     Void,
     Int,
     Float,
     Boolean,
     Char,
-    IntegerLiteral(i64),
+    Ptr,
+    // The sign of an integer is not part of its literal, so the
+    // value is stored without one and is checked by the `Parser`.
+    IntegerLiteral(u64),
     FloatLiteral(f64),
     BooleanLiteral(bool),
     CharLiteral(char),
+    Null,
     // The actual value for the `Identifier` are
     // stored in the `Interner` to reduce redundency in values. Instead,
     // the id's are stored in the variant.
@@ -49,6 +56,9 @@ pub enum TokenKind {
     Colon,
     SemiColon,
     ColonColon,
+    Ellipsis,
+    Ampersand,
+    Hash,
 
     Not,
     Or,
@@ -59,23 +69,66 @@ pub enum TokenKind {
     Else,
     While,
     Define,
+    Const,
+    Export,
     Extern,
     Return,
+    Unsafe,
+}
+
+impl TokenKind {
+    /// Determines whether the token is trivia, which does not affect the meaning
+    /// of the program and is only kept for tools such as the formatter.
+    pub fn is_trivia(&self) -> bool {
+        matches!(self, TokenKind::Whitespace(_) | TokenKind::Comment)
+    }
+
+    /// Determines whether the token is a keyword, which is written as a word
+    /// but can not be used as an identifier.
+    pub fn is_keyword(&self) -> bool {
+        matches!(
+            self,
+            TokenKind::Void
+                | TokenKind::Int
+                | TokenKind::Float
+                | TokenKind::Boolean
+                | TokenKind::Char
+                | TokenKind::Ptr
+                | TokenKind::Null
+                | TokenKind::Not
+                | TokenKind::Or
+                | TokenKind::And
+                | TokenKind::Let
+                | TokenKind::If
+                | TokenKind::Then
+                | TokenKind::Else
+                | TokenKind::While
+                | TokenKind::Define
+                | TokenKind::Const
+                | TokenKind::Export
+                | TokenKind::Extern
+                | TokenKind::Return
+                | TokenKind::Unsafe
+        )
+    }
 }
 
 impl Display for TokenKind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match *self {
             TokenKind::Whitespace(ch) => write!(f, "{}", ch),
+            TokenKind::Comment => write!(f, "comment"),
             TokenKind::Void => write!(f, "Void"),
             TokenKind::Int => write!(f, "Int"),
             TokenKind::Float => write!(f, "Float"),
             TokenKind::Boolean => write!(f, "Boolean"),
             TokenKind::Char => write!(f, "Char"),
+            TokenKind::Ptr => write!(f, "Ptr"),
             TokenKind::IntegerLiteral(_) => write!(f, "integer literal"),
             TokenKind::FloatLiteral(_) => write!(f, "float literal"),
             TokenKind::BooleanLiteral(_) => write!(f, "boolean literal"),
             TokenKind::CharLiteral(_) => write!(f, "char literal"),
+            TokenKind::Null => write!(f, "null"),
             TokenKind::Identifier(_) => write!(f, "identifier"),
             TokenKind::LeftParenthesis => write!(f, "("),
             TokenKind::RightParenthesis => write!(f, ")"),
@@ -97,6 +150,9 @@ impl Display for TokenKind {
             TokenKind::Colon => write!(f, ":"),
             TokenKind::SemiColon => write!(f, ";"),
             TokenKind::ColonColon => write!(f, "::"),
+            TokenKind::Ellipsis => write!(f, "..."),
+            TokenKind::Ampersand => write!(f, "&"),
+            TokenKind::Hash => write!(f, "#"),
             TokenKind::Not => write!(f, "not"),
             TokenKind::Or => write!(f, "or"),
             TokenKind::And => write!(f, "and"),
@@ -106,8 +162,11 @@ impl Display for TokenKind {
             TokenKind::Else => write!(f, "else"),
             TokenKind::While => write!(f, "while"),
             TokenKind::Define => write!(f, "define"),
+            TokenKind::Const => write!(f, "const"),
+            TokenKind::Export => write!(f, "export"),
             TokenKind::Extern => write!(f, "extern"),
             TokenKind::Return => write!(f, "return"),
+            TokenKind::Unsafe => write!(f, "unsafe"),
         }
     }
 }
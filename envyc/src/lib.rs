@@ -1,29 +1,38 @@
-use std::{iter::Peekable, path::Path};
+use std::{iter::Peekable, path::Path, sync::Once};
 
-use codegen::code_generator::CodeGenerator;
+use codegen::{code_generator::CodeGenerator, debug_info::DebugInfo};
 use environment::Environment;
-use error::Error;
+use error::{
+    suggestion::resolve_suggestions,
+    warning::{LintLevel, LintLevels, Warning},
+    Error,
+};
+use formatter::{FormatConfig, Formatter};
 use function_table::FunctionTable;
 use inkwell::{
     context::Context,
+    module::{Linkage, Module},
     passes::{PassManager, PassManagerBuilder},
-    targets::{CodeModel, FileType, InitializationConfig, RelocMode, Target, TargetMachine},
+    targets::{
+        CodeModel, FileType, InitializationConfig, RelocMode, Target, TargetMachine, TargetTriple,
+    },
     OptimizationLevel,
 };
 use interner::Interner;
 use lexer::{token::Token, Lexer};
-use parser::{ast::Program, typed_ast::TypedProgram, Parser};
-use semantic_analyzer::{type_check::TypeCheck, types::Type};
-
-use crate::lexer::token::TokenKind;
+use parser::{ast::Program, syntax_tree::SyntaxTree, typed_ast::TypedProgram, Parser};
+use semantic_analyzer::{lint::Linter, type_check::TypeCheck, types::Type};
 
 pub mod codegen;
+pub mod database;
 pub mod environment;
 pub mod error;
+pub mod formatter;
 pub mod function_table;
 pub mod interner;
 pub mod lexer;
 pub mod parser;
+pub mod printer;
 pub mod semantic_analyzer;
 
 pub fn lex<'a>(
@@ -37,29 +46,305 @@ pub fn lex<'a>(
 pub fn filter_tokens(tokens: Vec<Token>) -> Peekable<impl Iterator<Item = Token>> {
     tokens
         .into_iter()
-        .filter(|token| !matches!(token.1, TokenKind::Whitespace(_)))
+        .filter(|token| !token.1.is_trivia())
         .peekable()
 }
 
 pub fn parse<'a>(
     filtered_tokens: Peekable<impl Iterator<Item = Token<'a>>>,
+    interner: &Interner<String>,
 ) -> Result<Program<'a>, Vec<Error<'a>>> {
-    Parser::new(filtered_tokens).parse()
+    Parser::new(filtered_tokens).parse().map_err(|mut errors| {
+        resolve_suggestions(&mut errors, interner);
+        errors
+    })
 }
 
+/// Parses the tokens while recovering from syntax errors. Every item
+/// that could be parsed is kept in the program and broken expressions are
+/// replaced with placeholders, so the program can still be type checked.
+///
+/// # Arguments
+/// * `filtered_tokens` - The tokens to parse, without any whitespace.
+/// * `interner` - The `Interner` used to suggest keywords for misspelled identifiers.
+pub fn parse_with_recovery<'a>(
+    filtered_tokens: Peekable<impl Iterator<Item = Token<'a>>>,
+    interner: &Interner<String>,
+) -> (Program<'a>, Vec<Error<'a>>) {
+    let (program, mut errors) = Parser::new(filtered_tokens).parse_with_recovery();
+    resolve_suggestions(&mut errors, interner);
+    (program, errors)
+}
+
+/// Formats the source of a file in the canonical layout of Envious. The source
+/// must not contain any syntax errors, since the layout follows the parsed program.
+///
+/// # Arguments
+/// * `file_path` - The path of the file, which is used in the errors.
+/// * `source` - The source to format.
+/// * `config` - The `FormatConfig` to follow.
+pub fn format_source<'a>(
+    file_path: &'a str,
+    source: &'a str,
+    config: FormatConfig,
+) -> Result<String, Vec<Error<'a>>> {
+    let mut interner = Interner::default();
+    let tokens = lex(file_path, source.as_bytes(), &mut interner)?;
+    let program = parse(filter_tokens(tokens.clone()), &interner)?;
+    let tree = SyntaxTree::new(source, tokens, &program);
+    Ok(Formatter::new(&tree, config).format())
+}
+
+/// Checks the types of the program. Unknown variables and functions are
+/// reported together with a similar name, if one exists.
+///
+/// # Arguments
+/// * `program` - The `Program` to check.
+/// * `env` - The `Environment` that stores the types of the variables.
+/// * `function_table` - The `FunctionTable` that stores the known functions.
+/// * `interner` - The `Interner` that stores the names used for suggestions.
 pub fn type_check<'a>(
     program: Program<'a>,
     env: &mut Environment<Type>,
-    function_table: &mut FunctionTable,
+    function_table: &mut FunctionTable<'a>,
+    interner: &Interner<String>,
 ) -> Result<TypedProgram<'a>, Vec<Error<'a>>> {
-    program.check(env, function_table)
+    program.check(env, function_table).map_err(|mut errors| {
+        resolve_suggestions(&mut errors, interner);
+        errors
+    })
+}
+
+/// Finds code in the program that is valid but most likely a mistake.
+/// The warnings are returned together with the level of their lint,
+/// which is never `LintLevel::Allow`.
+///
+/// # Arguments
+/// * `program` - The `TypedProgram` to lint.
+/// * `interner` - The `Interner` that stores the names of the attributes and variables.
+/// * `levels` - The `LintLevels` given on the command line.
+pub fn lint<'a>(
+    program: &TypedProgram<'a>,
+    interner: &Interner<String>,
+    levels: &LintLevels,
+) -> Result<Vec<(Warning<'a>, LintLevel)>, Vec<Error<'a>>> {
+    Linter::new(program, interner, levels).lint_program(program)
+}
+
+/// Generates a C header for the exported functions of the program.
+///
+/// # Arguments
+/// * `program` - The `TypedProgram` to describe.
+/// * `module_name` - The name of the module.
+/// * `interner` - The `Interner` that stores the names of the functions.
+pub fn generate_header(
+    program: &TypedProgram,
+    module_name: &str,
+    interner: &Interner<String>,
+) -> String {
+    codegen::header::generate_header(program, module_name, interner)
+}
+
+static INITIALIZE_TARGETS: Once = Once::new();
+
+/// Enum that represents the artifacts that the compiler can write for a file.
+/// The tokens and the programs are written by the caller, and the other
+/// artifacts are written by `compile` when they are part of its `Config`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Emit {
+    Tokens,
+    Ast,
+    TypedAst,
+    LlvmIr,
+    LlvmBc,
+    Asm,
+    Obj,
+}
+
+impl Emit {
+    /// Every artifact, in the order they are produced while compiling.
+    pub const ALL: [Emit; 7] = [
+        Emit::Tokens,
+        Emit::Ast,
+        Emit::TypedAst,
+        Emit::LlvmIr,
+        Emit::LlvmBc,
+        Emit::Asm,
+        Emit::Obj,
+    ];
+
+    /// Returns the name of the artifact, as written in `--emit`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Emit::Tokens => "tokens",
+            Emit::Ast => "ast",
+            Emit::TypedAst => "typed-ast",
+            Emit::LlvmIr => "llvm-ir",
+            Emit::LlvmBc => "llvm-bc",
+            Emit::Asm => "asm",
+            Emit::Obj => "obj",
+        }
+    }
+
+    /// Returns the artifact with a name, if there is one.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the artifact, such as `llvm-ir`.
+    pub fn from_name(name: &str) -> Option<Emit> {
+        Emit::ALL.iter().copied().find(|emit| emit.name() == name)
+    }
+
+    /// Returns whether the artifact is produced by LLVM, and so written by `compile`.
+    pub fn is_llvm(&self) -> bool {
+        matches!(self, Emit::LlvmIr | Emit::LlvmBc | Emit::Asm | Emit::Obj)
+    }
+
+    /// Returns the extension of the file that the artifact is written to.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Emit::Tokens => "tokens",
+            Emit::Ast => "ast",
+            Emit::TypedAst => "typed-ast",
+            Emit::LlvmIr => "ll",
+            Emit::LlvmBc => "bc",
+            Emit::Asm => "s",
+            Emit::Obj => "o",
+        }
+    }
+}
+
+/// Enum that represents the optimization levels, as written in `-O`. The files
+/// are optimized with `O2` by default, which is what `compile` did before the
+/// levels could be chosen.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum OptLevel {
+    O0,
+    O1,
+    #[default]
+    O2,
+    O3,
+    // Optimizes like `O2`, but prefers smaller code.
+    Os,
+    // Optimizes like `O2`, but makes the code as small as possible.
+    Oz,
+}
+
+impl OptLevel {
+    /// Every optimization level.
+    pub const ALL: [OptLevel; 6] = [
+        OptLevel::O0,
+        OptLevel::O1,
+        OptLevel::O2,
+        OptLevel::O3,
+        OptLevel::Os,
+        OptLevel::Oz,
+    ];
+
+    /// Returns the name of the optimization level, as written after `-O`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            OptLevel::O0 => "0",
+            OptLevel::O1 => "1",
+            OptLevel::O2 => "2",
+            OptLevel::O3 => "3",
+            OptLevel::Os => "s",
+            OptLevel::Oz => "z",
+        }
+    }
+
+    /// Returns the optimization level with a name, if there is one.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the optimization level, such as `2` or `s`.
+    pub fn from_name(name: &str) -> Option<OptLevel> {
+        OptLevel::ALL
+            .iter()
+            .copied()
+            .find(|level| level.name() == name)
+    }
+
+    fn llvm_level(&self) -> OptimizationLevel {
+        match self {
+            OptLevel::O0 => OptimizationLevel::None,
+            OptLevel::O1 => OptimizationLevel::Less,
+            OptLevel::O2 | OptLevel::Os | OptLevel::Oz => OptimizationLevel::Default,
+            OptLevel::O3 => OptimizationLevel::Aggressive,
+        }
+    }
+
+    fn size_level(&self) -> u32 {
+        match self {
+            OptLevel::Os => 1,
+            OptLevel::Oz => 2,
+            _ => 0,
+        }
+    }
+
+    // The thresholds are the ones clang uses. Functions are not inlined below `O2`.
+    fn inline_threshold(&self) -> Option<u32> {
+        match self {
+            OptLevel::O0 | OptLevel::O1 => None,
+            OptLevel::O2 => Some(225),
+            OptLevel::O3 => Some(275),
+            OptLevel::Os => Some(75),
+            OptLevel::Oz => Some(25),
+        }
+    }
+
+    fn pass_manager_builder(&self) -> PassManagerBuilder {
+        let pass_manager_builder = PassManagerBuilder::create();
+        pass_manager_builder.set_optimization_level(self.llvm_level());
+        pass_manager_builder.set_size_level(self.size_level());
+        if let Some(threshold) = self.inline_threshold() {
+            pass_manager_builder.set_inliner_with_threshold(threshold);
+        }
+
+        pass_manager_builder
+    }
+}
+
+/// Struct that describes the machine that the code is generated for.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TargetOptions {
+    // The target triple, such as `aarch64-unknown-linux-gnu`, or the triple of the host if `None`.
+    pub triple: Option<String>,
+    // The CPU, such as `cortex-a72`, where `native` is the CPU of the host.
+    pub cpu: String,
+    // The features of the CPU, such as `+avx2,-sse4.1`.
+    pub features: String,
+}
+
+impl Default for TargetOptions {
+    fn default() -> Self {
+        Self {
+            triple: None,
+            cpu: "generic".to_string(),
+            features: String::new(),
+        }
+    }
 }
 
 pub struct Config<'a> {
-    pub writing_to_file: bool,
-    pub output_file_path: &'a str,
+    // The artifacts to write and the paths of their files. Only the
+    // artifacts that are produced by LLVM are written by `compile`.
+    pub outputs: Vec<(Emit, &'a Path)>,
+    // Whether the object file will be linked into a shared library,
+    // which requires position independent code.
+    pub position_independent: bool,
+    pub opt_level: OptLevel,
+    pub target: TargetOptions,
+    // Whether the module describes its source in DWARF, so that it can be debugged.
+    pub debug_info: bool,
 }
 
+/// Compiles a program into an LLVM module and returns its IR. The module is
+/// only optimized and written to the outputs of the config if there is one.
+///
+/// # Arguments
+/// * `program` - The program to compile.
+/// * `module_name` - The name of the module.
+/// * `interner` - The interner of the names in the program.
+/// * `config` - The optimization level and the outputs of the module.
 pub fn compile<'a>(
     program: &TypedProgram<'a>,
     module_name: &str,
@@ -70,60 +355,266 @@ pub fn compile<'a>(
     let module = context.create_module(module_name);
     let builder = context.create_builder();
 
+    let target_machine = match &config {
+        Some(config) => Some(create_target_machine(
+            &config.target,
+            config.opt_level,
+            config.position_independent,
+        )?),
+        None => None,
+    };
+
     let mut value_env = Environment::default();
-    CodeGenerator::new(&context, &module, &builder, interner, &mut value_env)
-        .generate_program(program)?;
+    let mut code_generator =
+        CodeGenerator::new(&context, &module, &builder, interner, &mut value_env);
+    if let (Some(config), Some(target_machine)) = (&config, &target_machine) {
+        if config.debug_info {
+            let pointer_size = target_machine.get_target_data().get_pointer_byte_size(None);
+            code_generator = code_generator.with_debug_info(DebugInfo::new(
+                &context,
+                &module,
+                program.file_name().unwrap_or(module_name),
+                config.opt_level != OptLevel::O0,
+                u64::from(pointer_size) * 8,
+            ));
+        }
+    }
+    code_generator.generate_program(program)?;
 
-    let pass_manager_builder = PassManagerBuilder::create();
-    pass_manager_builder.set_optimization_level(OptimizationLevel::Default);
-    let pass_manager = PassManager::create(());
-    pass_manager_builder.populate_module_pass_manager(&pass_manager);
+    if let (Some(config), Some(target_machine)) = (config, target_machine) {
+        set_target(&module, &target_machine);
+        optimize(&module, &target_machine, config.opt_level);
+        write_outputs(&module, &target_machine, &config.outputs)?;
+    }
+
+    Ok(module.print_to_string().to_string())
+}
 
-    pass_manager.add_promote_memory_to_register_pass();
-    pass_manager.add_instruction_combining_pass();
-    pass_manager.add_reassociate_pass();
-    pass_manager.add_gvn_pass();
-    pass_manager.add_cfg_simplification_pass();
+/// Links the bitcode of several files into a single module, optimizes the
+/// module as a whole and writes it to the outputs of the config. Making the
+/// functions private lets LLVM remove the ones that are inlined everywhere.
+///
+/// # Arguments
+/// * `bitcode_files` - The bitcode files, which were written by `compile`.
+/// * `module_name` - The name of the linked module.
+/// * `config` - The optimization level and the outputs of the linked module.
+/// * `internalize` - Whether every function except `main` is made private to the module.
+pub fn link_time_optimize<'a>(
+    bitcode_files: &[&Path],
+    module_name: &str,
+    config: Config<'a>,
+    internalize: bool,
+) -> Result<(), Vec<Error<'a>>> {
+    let context = Context::create();
+    let module = context.create_module(module_name);
+    for path in bitcode_files {
+        let link_failure = |message: String| {
+            vec![Error::LinkFailure {
+                path: path.display().to_string(),
+                message,
+            }]
+        };
+        let other = Module::parse_bitcode_from_path(path, &context)
+            .map_err(|error| link_failure(error.to_string()))?;
+        module
+            .link_in_module(other)
+            .map_err(|error| link_failure(error.to_string()))?;
+    }
+
+    let target_machine = create_target_machine(
+        &config.target,
+        config.opt_level,
+        config.position_independent,
+    )?;
+    set_target(&module, &target_machine);
+    if internalize {
+        internalize_functions(&module);
+    }
+
+    // LLVM ignores the argument that asks it to internalize the functions,
+    // which is why `internalize_functions` does it instead.
+    let pass_manager = PassManager::create(());
+    target_machine.add_analysis_passes(&pass_manager);
+    config
+        .opt_level
+        .pass_manager_builder()
+        .populate_lto_pass_manager(&pass_manager, false, true);
     pass_manager.run_on(&module);
 
-    if let Some(config) = config {
-        let target_triple = TargetMachine::get_default_triple();
-        let init_config = InitializationConfig {
+    write_outputs(&module, &target_machine, &config.outputs)
+}
+
+/// Makes every function that is defined in a module, except `main`, private to
+/// the module, so that the functions that are inlined everywhere can be removed.
+///
+/// # Arguments
+/// * `module` - The linked module.
+fn internalize_functions(module: &Module) {
+    let mut function = module.get_first_function();
+    while let Some(current) = function {
+        if current.count_basic_blocks() > 0 && current.get_name().to_bytes() != b"main" {
+            current.set_linkage(Linkage::Internal);
+        }
+        function = current.get_next_function();
+    }
+}
+
+/// Checks that LLVM can generate code for a target, so that an invalid
+/// target is reported once instead of for every file that is compiled.
+///
+/// # Arguments
+/// * `target` - The target to check.
+pub fn check_target<'a>(target: &TargetOptions) -> Result<(), Vec<Error<'a>>> {
+    create_target_machine(target, OptLevel::O0, false).map(|_| ())
+}
+
+/// Creates the machine that generates the code for a target, and reports
+/// the target as invalid if LLVM does not support it.
+///
+/// # Arguments
+/// * `target` - The target to generate code for.
+/// * `opt_level` - The optimization level of the generated code.
+/// * `position_independent` - Whether the code must be position independent.
+fn create_target_machine<'a>(
+    target: &TargetOptions,
+    opt_level: OptLevel,
+    position_independent: bool,
+) -> Result<TargetMachine, Vec<Error<'a>>> {
+    let target_triple = match &target.triple {
+        Some(triple) => TargetTriple::create(triple),
+        None => TargetMachine::get_default_triple(),
+    };
+    let invalid_target = |message: String| {
+        vec![Error::InvalidTarget {
+            triple: target_triple.as_str().to_string_lossy().into_owned(),
+            message,
+        }]
+    };
+
+    let reloc_mode = if position_independent {
+        RelocMode::PIC
+    } else {
+        RelocMode::Default
+    };
+
+    // The targets are initialized once, since files may be
+    // compiled on several threads at the same time.
+    INITIALIZE_TARGETS.call_once(|| {
+        Target::initialize_all(&InitializationConfig {
             asm_parser: true,
             asm_printer: true,
             base: true,
             disassembler: true,
             info: true,
             machine_code: true,
+        })
+    });
+    let llvm_target =
+        Target::from_triple(&target_triple).map_err(|error| invalid_target(error.to_string()))?;
+
+    // The features that are given with `native` are added to the features of the host.
+    let (cpu, features) = if target.cpu == "native" {
+        let mut features = TargetMachine::get_host_cpu_features().to_string();
+        if !target.features.is_empty() {
+            features = format!("{},{}", features, target.features);
+        }
+
+        (TargetMachine::get_host_cpu_name().to_string(), features)
+    } else {
+        (target.cpu.clone(), target.features.clone())
+    };
+
+    llvm_target
+        .create_target_machine(
+            &target_triple,
+            &cpu,
+            &features,
+            opt_level.llvm_level(),
+            reloc_mode,
+            CodeModel::Default,
+        )
+        .ok_or_else(|| {
+            invalid_target(format!(
+                "LLVM could not generate code for the CPU `{}` with the features `{}`",
+                cpu, features
+            ))
+        })
+}
+
+/// Sets the triple and the data layout of a module to the ones of a machine.
+///
+/// # Arguments
+/// * `module` - The module that is compiled by the machine.
+/// * `target_machine` - The machine.
+fn set_target(module: &Module, target_machine: &TargetMachine) {
+    module.set_triple(&target_machine.get_triple());
+    module.set_data_layout(&target_machine.get_target_data().get_data_layout());
+}
+
+/// Runs the optimizations of a level on a module. Every function is simplified
+/// on its own before the module passes inline the functions into each other.
+///
+/// # Arguments
+/// * `module` - The module to optimize.
+/// * `target_machine` - The machine the module is compiled for.
+/// * `opt_level` - The optimization level.
+fn optimize(module: &Module, target_machine: &TargetMachine, opt_level: OptLevel) {
+    if opt_level == OptLevel::O0 {
+        return;
+    }
+
+    let pass_manager_builder = opt_level.pass_manager_builder();
+
+    let function_pass_manager = PassManager::create(module);
+    target_machine.add_analysis_passes(&function_pass_manager);
+    pass_manager_builder.populate_function_pass_manager(&function_pass_manager);
+    function_pass_manager.initialize();
+    let mut function = module.get_first_function();
+    while let Some(current) = function {
+        function_pass_manager.run_on(&current);
+        function = current.get_next_function();
+    }
+    function_pass_manager.finalize();
+
+    let pass_manager = PassManager::create(());
+    target_machine.add_analysis_passes(&pass_manager);
+    pass_manager_builder.populate_module_pass_manager(&pass_manager);
+    pass_manager.run_on(module);
+}
+
+fn write_outputs<'a>(
+    module: &Module,
+    target_machine: &TargetMachine,
+    outputs: &[(Emit, &Path)],
+) -> Result<(), Vec<Error<'a>>> {
+    for (emit, path) in outputs {
+        let written = match emit {
+            Emit::LlvmIr => module
+                .print_to_file(path)
+                .map_err(|error| error.to_string()),
+            Emit::LlvmBc => {
+                if module.write_bitcode_to_path(path) {
+                    Ok(())
+                } else {
+                    Err("LLVM could not write the bitcode".to_string())
+                }
+            }
+            Emit::Asm => target_machine
+                .write_to_file(module, FileType::Assembly, path)
+                .map_err(|error| error.to_string()),
+            Emit::Obj => target_machine
+                .write_to_file(module, FileType::Object, path)
+                .map_err(|error| error.to_string()),
+            Emit::Tokens | Emit::Ast | Emit::TypedAst => Ok(()),
         };
 
-        Target::initialize_all(&init_config);
-        let target = Target::from_triple(&target_triple).unwrap();
-        module.set_triple(&target_triple);
-        let target_machine = target
-            .create_target_machine(
-                &target_triple,
-                "generic",
-                "",
-                OptimizationLevel::Default,
-                RelocMode::Default,
-                CodeModel::Default,
-            )
-            .unwrap();
-
-        module.set_data_layout(&target_machine.get_target_data().get_data_layout());
-        target_machine.add_analysis_passes(&pass_manager);
-
-        if config.writing_to_file {
-            target_machine
-                .write_to_file(
-                    &module,
-                    FileType::Object,
-                    Path::new(config.output_file_path),
-                )
-                .unwrap();
+        if let Err(message) = written {
+            return Err(vec![Error::WriteFailure {
+                path: path.display().to_string(),
+                message,
+            }]);
         }
     }
 
-    Ok(module.print_to_string().to_string())
+    Ok(())
 }
@@ -2,61 +2,108 @@ use crate::{error::Span, semantic_analyzer::types::Type};
 
 use super::expression::Expression;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Program<'a> {
     pub extern_declarations: Vec<ExternDeclaration<'a>>,
+    pub constants: Vec<Constant<'a>>,
     pub functions: Vec<Function<'a>>,
 }
 
 impl<'a> Program<'a> {
     pub fn new(
         extern_declarations: Vec<ExternDeclaration<'a>>,
+        constants: Vec<Constant<'a>>,
         functions: Vec<Function<'a>>,
     ) -> Self {
         Self {
             extern_declarations,
+            constants,
             functions,
         }
     }
 }
 
-#[derive(Debug)]
+/// An attribute written before an item, such as `#allow(unused_variable)`.
+/// The names are only resolved by the linter, since the `Parser` does
+/// not have access to the `Interner`.
+#[derive(Debug, Clone)]
+pub struct Attribute<'a> {
+    pub span: Span<'a>,
+    pub name: (Span<'a>, usize),
+    pub arguments: Vec<(Span<'a>, usize)>,
+}
+
+/// A top-level constant, such as `const PI: Float = 3.14159`.
+/// The value is evaluated at compile time.
+#[derive(Debug, Clone)]
+pub struct Constant<'a> {
+    pub span: Span<'a>,
+    pub name: (Span<'a>, usize),
+    pub ty: (Type, Span<'a>),
+    pub value: Expression<'a>,
+    pub attributes: Vec<Attribute<'a>>,
+}
+
+#[derive(Debug, Clone)]
 pub struct Prototype<'a> {
     pub span: Span<'a>,
     pub name: usize,
     pub parameters: Vec<Parameter<'a>>,
     pub return_type: (Type, Span<'a>),
+    // Whether the function is marked with `export` and should be
+    // callable from C when compiled into a library.
+    pub export: bool,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ExternDeclaration<'a> {
     pub span: Span<'a>,
     pub name: usize,
     pub parameters: Vec<(Type, Span<'a>)>,
+    // Whether the declaration ends with `...` and accepts
+    // any number of arguments after the fixed parameters.
+    pub variadic: bool,
     pub return_type: (Type, Span<'a>),
+    pub attributes: Vec<Attribute<'a>>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Function<'a> {
     pub prototype: Prototype<'a>,
     pub body: Expression<'a>,
+    pub attributes: Vec<Attribute<'a>>,
 }
 
 impl<'a> Function<'a> {
-    pub fn new(prototype: Prototype<'a>, body: Expression<'a>) -> Self {
-        Self { prototype, body }
+    pub fn new(
+        prototype: Prototype<'a>,
+        body: Expression<'a>,
+        attributes: Vec<Attribute<'a>>,
+    ) -> Self {
+        Self {
+            prototype,
+            body,
+            attributes,
+        }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Parameter<'a> {
     pub span: Span<'a>,
     pub name: usize,
     pub ty: Type,
+    // The value used when an application does not provide this parameter.
+    pub default: Option<Expression<'a>>,
 }
 
 impl<'a> Parameter<'a> {
-    pub fn new(span: Span<'a>, name: usize, ty: Type) -> Self {
-        Self { span, name, ty }
+    pub fn new(span: Span<'a>, name: usize, ty: Type, default: Option<Expression<'a>>) -> Self {
+        Self {
+            span,
+            name,
+            ty,
+            default,
+        }
     }
 }
@@ -9,12 +9,13 @@ pub type Expression<'a> = (Span<'a>, ExpressionKind<'a>);
 /// by the `Expression`. The `ExpressionKind` should strive to only store types that
 /// are small in nature and any other types (i.e. String) should be stored in the
 /// `Interner`.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum ExpressionKind<'a> {
     Int(i64),
     Float(f64),
     Boolean(bool),
     Char(char),
+    Null,
     // The actual value for the `Identifier` are
     // stored in the `Interner` to reduce redundency in values. Instead,
     // the id's are stored in the variant.
@@ -23,49 +24,70 @@ pub enum ExpressionKind<'a> {
     Binary(Binary<'a>),
     If(If<'a>),
     Let(Let<'a>),
+    Store(Store<'a>),
     Block(Vec<Expression<'a>>),
     Application(Application<'a>),
     While(While<'a>),
     Return(Option<Box<Expression<'a>>>),
+    Unsafe(Box<Expression<'a>>),
+    // A placeholder for an expression that could not be parsed.
+    // The syntax error was already recorded by the `Parser`.
+    Error,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Identifier(pub usize);
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Unary<'a> {
     pub operation: UnaryOperation,
     pub expression: Box<Expression<'a>>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Binary<'a> {
     pub operation: BinaryOperation,
     pub left: Box<Expression<'a>>,
     pub right: Box<Expression<'a>>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct If<'a> {
     pub condition: Box<Expression<'a>>,
     pub then_branch: Box<Expression<'a>>,
     pub else_branch: Option<Box<Expression<'a>>>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Let<'a> {
     pub name: (Span<'a>, Identifier),
     pub given_type: Option<Type>,
     pub expression: Box<Expression<'a>>,
 }
 
-#[derive(Debug)]
+/// Represents a store through a pointer (`let *pointer = expression`).
+#[derive(Debug, Clone)]
+pub struct Store<'a> {
+    pub pointer: Box<Expression<'a>>,
+    pub expression: Box<Expression<'a>>,
+}
+
+#[derive(Debug, Clone)]
 pub struct Application<'a> {
     pub function_name: (Span<'a>, Identifier),
-    pub parameters: Vec<Expression<'a>>,
+    pub arguments: Vec<Argument<'a>>,
+}
+
+/// Represents a single argument passed to a function.
+/// Named arguments (`value: x`) keep the span and the id of the
+/// name so that they can be matched with the parameters of the function.
+#[derive(Debug, Clone)]
+pub struct Argument<'a> {
+    pub name: Option<(Span<'a>, Identifier)>,
+    pub value: Expression<'a>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct While<'a> {
     pub condition: Box<Expression<'a>>,
     pub expression: Box<Expression<'a>>,
@@ -80,6 +102,8 @@ pub enum UnaryOperation {
     Plus,
     Minus,
     Not,
+    AddressOf,
+    Dereference,
 }
 
 /// Enum that details the different binary operations
@@ -93,6 +117,7 @@ pub enum BinaryOperation {
     Multiply,
     Divide,
     Equals,
+    NotEquals,
     LessThan,
     GreaterThan,
     LessThanEquals,
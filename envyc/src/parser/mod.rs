@@ -5,27 +5,35 @@ use expression::{BinaryOperation, Expression, UnaryOperation};
 use parselets::LetParselet;
 
 use crate::{
-    error::{Error, Span},
+    error::{
+        fix::{Edit, Fix},
+        Error, Span,
+    },
     lexer::token::{Token, TokenKind},
     semantic_analyzer::types::Type,
 };
 
 use self::{
-    ast::{Function, Parameter, Program, Prototype},
+    ast::{Attribute, Constant, Function, Parameter, Program, Prototype},
+    expression::ExpressionKind,
     parselets::{
         infix_parselet::InfixParselet, precedence::Precedence, prefix_parselet::PrefixParselet,
         BinaryOperationParselet, BlockParselet, BooleanParselet, CharParselet, FloatParselet,
-        IdentifierParselet, IfParselet, IntParselet, ParenthesisParselet, PrefixOperationParselet,
-        ReturnParselet, WhileParselet,
+        IdentifierParselet, IfParselet, IntParselet, NullParselet, ParenthesisParselet,
+        PrefixOperationParselet, ReturnParselet, UnsafeParselet, WhileParselet,
     },
 };
 
 pub mod ast;
 pub mod expression;
 pub mod parselets;
+pub mod syntax_tree;
 pub mod typed_ast;
 pub mod typed_expression;
 
+/// The types of an external declaration and whether it is variadic.
+type TypesList<'a> = (Vec<(Type, Span<'a>)>, bool);
+
 /// Struct that transforms the vector of tokens into a vector of expressions.
 ///
 /// The `Parser` uses a mixture of the Pratt parsing technique and the
@@ -33,49 +41,132 @@ pub mod typed_expression;
 /// called parselets.
 pub struct Parser<'a, T: Iterator<Item = Token<'a>>> {
     tokens: Peekable<T>,
+    // The syntax errors that the parser recovered from.
+    errors: Vec<Error<'a>>,
+    // The span of the last consumed token, which is used to find
+    // the end of the line while recovering from a syntax error.
+    last_span: Option<Span<'a>>,
 }
 
 impl<'a, T: Iterator<Item = Token<'a>>> Parser<'a, T> {
     pub fn new(tokens: Peekable<T>) -> Self {
-        Self { tokens }
+        Self {
+            tokens,
+            errors: vec![],
+            last_span: None,
+        }
     }
 
     /// Walks through the tokens and constructs a program, or a vector
-    /// of functions.
+    /// of functions. Every syntax error in the tokens is returned.
     pub fn parse(&mut self) -> Result<Program<'a>, Vec<Error<'a>>> {
+        let (program, errors) = self.parse_with_recovery();
+        if !errors.is_empty() {
+            Err(errors)
+        } else {
+            Ok(program)
+        }
+    }
+
+    /// Walks through the tokens and constructs a program, recovering from
+    /// syntax errors instead of stopping at the first one.
+    ///
+    /// When an item can not be parsed, the tokens are skipped until the next
+    /// `define`, `export`, `extern`, `const` or attribute. Inside of a block, a broken
+    /// expression is skipped until the end of its line or the closing `}`, and it
+    /// is replaced with `ExpressionKind::Error`. The returned program can still be
+    /// type checked, since errors are not reported for these placeholders.
+    pub fn parse_with_recovery(&mut self) -> (Program<'a>, Vec<Error<'a>>) {
         let mut extern_declarations = vec![];
+        let mut constants = vec![];
         let mut functions = vec![];
-        let mut errors = vec![];
+        // The attributes that were parsed before the next item.
+        let mut attributes = vec![];
         while let Some(&(span, kind)) = self.tokens.peek() {
-            match kind {
-                TokenKind::Define => match self.parse_function(span) {
-                    Ok(function) => functions.push(function),
-                    Err(error) => errors.push(error),
-                },
-                TokenKind::Extern => match self.parse_extern_declaration(span) {
-                    Ok(extern_declaration) => extern_declarations.push(extern_declaration),
-                    Err(error) => errors.push(error),
-                },
-                _ => {
-                    errors.push(Error::ExpectedKind {
-                        span,
-                        expected_kinds: vec![TokenKind::Define, TokenKind::Extern],
-                        actual_kind: kind,
-                    });
+            let result = match kind {
+                TokenKind::Hash => self
+                    .parse_attribute(span)
+                    .map(|attribute| attributes.push(attribute)),
+                TokenKind::Define | TokenKind::Export => self
+                    .parse_function(span, mem::take(&mut attributes))
+                    .map(|function| functions.push(function)),
+                TokenKind::Extern => self
+                    .parse_extern_declaration(span, mem::take(&mut attributes))
+                    .map(|extern_declaration| extern_declarations.push(extern_declaration)),
+                TokenKind::Const => self
+                    .parse_constant(span, mem::take(&mut attributes))
+                    .map(|constant| constants.push(constant)),
+                _ => Err(Box::new(Error::ExpectedKind {
+                    span,
+                    expected_kinds: vec![
+                        TokenKind::Define,
+                        TokenKind::Export,
+                        TokenKind::Extern,
+                        TokenKind::Const,
+                        TokenKind::Hash,
+                    ],
+                    actual_kind: kind,
+                    suggestion: None,
+                    fix: None,
+                })),
+            };
 
-                    return Err(errors);
-                }
+            if let Err(error) = result {
+                self.errors.push(*error);
+                self.synchronize_item();
             }
         }
 
-        if !errors.is_empty() {
-            Err(errors)
-        } else {
-            Ok(Program::new(extern_declarations, functions))
+        if let Some(attribute) = attributes.last() {
+            self.errors
+                .push(Error::UnexpectedEndOfInput(attribute.span));
+        }
+
+        (
+            Program::new(extern_declarations, constants, functions),
+            mem::take(&mut self.errors),
+        )
+    }
+
+    /// Parses an attribute that is written before an item, such as
+    /// `#allow(unused_variable, unused_parameter)`.
+    ///
+    /// # Arguments
+    /// * `span` - The `Span` of the `#` that starts the attribute.
+    fn parse_attribute(&mut self, span: Span<'a>) -> Result<Attribute<'a>, Box<Error<'a>>> {
+        let (hash_span, _) = self.expect(TokenKind::Hash, span)?;
+        let name = match self.expect(TokenKind::Identifier(0), hash_span)? {
+            (name_span, TokenKind::Identifier(id)) => (name_span, id),
+            _ => unreachable!(),
+        };
+
+        let (mut last_span, _) = self.expect(TokenKind::LeftParenthesis, name.0)?;
+        let mut arguments = vec![];
+        while let Some(&(argument_span, TokenKind::Identifier(id))) = self.peek() {
+            self.consume(argument_span)?;
+            arguments.push((argument_span, id));
+            last_span = argument_span;
+            if let Some(&(comma_span, TokenKind::Comma)) = self.peek() {
+                self.consume(comma_span)?;
+                last_span = comma_span;
+            } else {
+                break;
+            }
         }
+
+        let (right_paren_span, _) = self.expect(TokenKind::RightParenthesis, last_span)?;
+        Ok(Attribute {
+            span: hash_span.combine(right_paren_span),
+            name,
+            arguments,
+        })
     }
 
-    fn parse_prototype(&mut self, span: Span<'a>) -> Result<(Span<'a>, Prototype<'a>), Error<'a>> {
+    fn parse_prototype(
+        &mut self,
+        span: Span<'a>,
+        export: bool,
+    ) -> Result<(Span<'a>, Prototype<'a>), Box<Error<'a>>> {
         if let (prototype_name_span, TokenKind::Identifier(id)) =
             self.expect(TokenKind::Identifier(0), span)?
         {
@@ -88,26 +179,7 @@ impl<'a, T: Iterator<Item = Token<'a>>> Parser<'a, T> {
                 .map_or(left_paren_span, |param| param.span);
             let (right_paren_span, _) = self.expect(TokenKind::RightParenthesis, last_span)?;
             let (type_colon_span, _) = self.expect(TokenKind::ColonColon, right_paren_span)?;
-            let return_type = match self.consume(type_colon_span)? {
-                (span, TokenKind::Void) => (Type::Void, span),
-                (span, TokenKind::Int) => (Type::Int, span),
-                (span, TokenKind::Float) => (Type::Float, span),
-                (span, TokenKind::Boolean) => (Type::Boolean, span),
-                (span, TokenKind::Char) => (Type::Char, span),
-                (span, actual_kind) => {
-                    return Err(Error::ExpectedKind {
-                        span,
-                        expected_kinds: vec![
-                            TokenKind::Void,
-                            TokenKind::Int,
-                            TokenKind::Float,
-                            TokenKind::Boolean,
-                            TokenKind::Char,
-                        ],
-                        actual_kind,
-                    })
-                }
-            };
+            let return_type = self.parse_type(type_colon_span)?;
 
             let return_type_span = return_type.1;
             let prototype = Prototype {
@@ -115,6 +187,7 @@ impl<'a, T: Iterator<Item = Token<'a>>> Parser<'a, T> {
                 name: id,
                 parameters,
                 return_type,
+                export,
             };
 
             Ok((return_type_span, prototype))
@@ -126,46 +199,30 @@ impl<'a, T: Iterator<Item = Token<'a>>> Parser<'a, T> {
     fn parse_extern_declaration(
         &mut self,
         span: Span<'a>,
-    ) -> Result<ExternDeclaration<'a>, Error<'a>> {
+        attributes: Vec<Attribute<'a>>,
+    ) -> Result<ExternDeclaration<'a>, Box<Error<'a>>> {
         let (extern_span, _) = self.expect(TokenKind::Extern, span)?;
         if let (prototype_name_span, TokenKind::Identifier(id)) =
             self.expect(TokenKind::Identifier(0), extern_span)?
         {
             let (left_paren_span, _) =
                 self.expect(TokenKind::LeftParenthesis, prototype_name_span)?;
-            let parameters = self.parse_types_list()?;
+            let (parameters, variadic) = self.parse_types_list(left_paren_span)?;
             let last_span = parameters
                 .iter()
                 .last()
                 .map_or(left_paren_span, |param| param.1);
             let (right_paren_span, _) = self.expect(TokenKind::RightParenthesis, last_span)?;
             let (type_colon_span, _) = self.expect(TokenKind::ColonColon, right_paren_span)?;
-            let return_type = match self.consume(type_colon_span)? {
-                (span, TokenKind::Void) => (Type::Void, span),
-                (span, TokenKind::Int) => (Type::Int, span),
-                (span, TokenKind::Float) => (Type::Float, span),
-                (span, TokenKind::Boolean) => (Type::Boolean, span),
-                (span, TokenKind::Char) => (Type::Char, span),
-                (span, actual_kind) => {
-                    return Err(Error::ExpectedKind {
-                        span,
-                        expected_kinds: vec![
-                            TokenKind::Void,
-                            TokenKind::Int,
-                            TokenKind::Float,
-                            TokenKind::Boolean,
-                            TokenKind::Char,
-                        ],
-                        actual_kind,
-                    })
-                }
-            };
+            let return_type = self.parse_type(type_colon_span)?;
 
             let extern_declaration = ExternDeclaration {
                 span: span.combine(return_type.1),
                 name: id,
                 parameters,
+                variadic,
                 return_type,
+                attributes,
             };
 
             Ok(extern_declaration)
@@ -174,43 +231,82 @@ impl<'a, T: Iterator<Item = Token<'a>>> Parser<'a, T> {
         }
     }
 
-    fn parse_function(&mut self, span: Span<'a>) -> Result<Function<'a>, Error<'a>> {
+    fn parse_constant(
+        &mut self,
+        span: Span<'a>,
+        attributes: Vec<Attribute<'a>>,
+    ) -> Result<Constant<'a>, Box<Error<'a>>> {
+        let (const_span, _) = self.expect(TokenKind::Const, span)?;
+        if let (name_span, TokenKind::Identifier(id)) =
+            self.expect(TokenKind::Identifier(0), const_span)?
+        {
+            let (colon_span, _) = self.expect(TokenKind::Colon, name_span)?;
+            let ty = self.parse_type(colon_span)?;
+            let (equal_span, _) = self.expect(TokenKind::EqualSign, ty.1)?;
+            let value = self
+                .parse_expression(0, equal_span)
+                .unwrap_or_else(|error| {
+                    // The constant is kept, so its references are not reported as undefined.
+                    self.recover_item(*error, equal_span)
+                });
+
+            Ok(Constant {
+                span: const_span.combine(value.0),
+                name: (name_span, id),
+                ty,
+                value,
+                attributes,
+            })
+        } else {
+            unreachable!()
+        }
+    }
+
+    fn parse_function(
+        &mut self,
+        span: Span<'a>,
+        attributes: Vec<Attribute<'a>>,
+    ) -> Result<Function<'a>, Box<Error<'a>>> {
+        let export = if let Some((_, TokenKind::Export)) = self.peek() {
+            self.consume(span)?;
+            true
+        } else {
+            false
+        };
+
         let (define_span, _) = self.expect(TokenKind::Define, span)?;
-        let (right_paren_span, prototype) = self.parse_prototype(define_span)?;
+        let (right_paren_span, prototype) = self.parse_prototype(define_span, export)?;
         let (eq_span, _) = self.expect(TokenKind::EqualSign, right_paren_span)?;
-        let body = self.parse_expression(0, eq_span)?;
-        Ok(Function::new(prototype, body))
+        let body = self.parse_expression(0, eq_span).unwrap_or_else(|error| {
+            // The function is kept, so its applications are not reported as undefined.
+            self.recover_item(*error, eq_span)
+        });
+
+        Ok(Function::new(prototype, body, attributes))
     }
 
-    fn parse_types_list(&mut self) -> Result<Vec<(Type, Span<'a>)>, Error<'a>> {
+    /// Parses the comma separated types of an external declaration.
+    /// The list may end with `...`, in which case the declaration is variadic.
+    ///
+    /// # Arguments
+    /// * `span` - The `Span` of the left parenthesis that opens the list.
+    fn parse_types_list(&mut self, span: Span<'a>) -> Result<TypesList<'a>, Box<Error<'a>>> {
         let mut types = vec![];
-        while let Some((_, kind)) = self.tokens.peek() {
-            if kind == &TokenKind::RightParenthesis {
-                break;
-            }
-
-            let (type_span, kind) = self.tokens.next().unwrap();
-            let ty = match kind {
-                TokenKind::Void => Type::Void,
-                TokenKind::Int => Type::Int,
-                TokenKind::Float => Type::Float,
-                TokenKind::Boolean => Type::Boolean,
-                TokenKind::Char => Type::Char,
-                _ => {
-                    return Err(Error::ExpectedKind {
-                        span: type_span,
-                        expected_kinds: vec![
-                            TokenKind::Void,
-                            TokenKind::Int,
-                            TokenKind::Float,
-                            TokenKind::Boolean,
-                            TokenKind::Char,
-                        ],
-                        actual_kind: kind,
-                    })
+        let mut variadic = false;
+        let mut last_span = span;
+        while let Some(&(ellipsis_span, kind)) = self.tokens.peek() {
+            match kind {
+                TokenKind::RightParenthesis => break,
+                TokenKind::Ellipsis => {
+                    self.consume(ellipsis_span)?;
+                    variadic = true;
+                    break;
                 }
-            };
+                _ => {}
+            }
 
+            let (ty, type_span) = self.parse_type(last_span)?;
+            last_span = type_span;
             types.push((ty, type_span));
             if let Some((_, TokenKind::Comma)) = self.tokens.peek() {
                 self.tokens.next();
@@ -219,10 +315,10 @@ impl<'a, T: Iterator<Item = Token<'a>>> Parser<'a, T> {
             }
         }
 
-        Ok(types)
+        Ok((types, variadic))
     }
 
-    fn parse_parameters(&mut self) -> Result<Vec<Parameter<'a>>, Error<'a>> {
+    fn parse_parameters(&mut self) -> Result<Vec<Parameter<'a>>, Box<Error<'a>>> {
         let mut parameters = vec![];
         while let Some((_, kind)) = self.tokens.peek() {
             if kind == &TokenKind::RightParenthesis {
@@ -233,38 +329,27 @@ impl<'a, T: Iterator<Item = Token<'a>>> Parser<'a, T> {
             let id = match kind {
                 TokenKind::Identifier(id) => id,
                 _ => {
-                    return Err(Error::ExpectedKind {
+                    return Err(Box::new(Error::ExpectedKind {
                         span: param_span,
                         expected_kinds: vec![TokenKind::Identifier(0)],
                         actual_kind: kind,
-                    })
+                        suggestion: None,
+                        fix: None,
+                    }))
                 }
             };
 
             let (colon_span, _) = self.expect(TokenKind::Colon, param_span)?;
-            let (type_span, kind) = self.consume(colon_span)?;
-            let ty = match kind {
-                TokenKind::Void => Type::Void,
-                TokenKind::Int => Type::Int,
-                TokenKind::Float => Type::Float,
-                TokenKind::Boolean => Type::Boolean,
-                TokenKind::Char => Type::Char,
-                _ => {
-                    return Err(Error::ExpectedKind {
-                        span: type_span,
-                        expected_kinds: vec![
-                            TokenKind::Void,
-                            TokenKind::Int,
-                            TokenKind::Float,
-                            TokenKind::Boolean,
-                            TokenKind::Char,
-                        ],
-                        actual_kind: kind,
-                    })
-                }
+            let (ty, type_span) = self.parse_type(colon_span)?;
+
+            let default = if let Some((_, TokenKind::EqualSign)) = self.tokens.peek() {
+                let (equal_span, _) = self.consume(type_span)?;
+                Some(self.parse_expression(0, equal_span)?)
+            } else {
+                None
             };
 
-            parameters.push(Parameter::new(param_span, id, ty));
+            parameters.push(Parameter::new(param_span, id, ty, default));
             if let Some((_, TokenKind::Comma)) = self.tokens.peek() {
                 self.tokens.next();
             } else {
@@ -275,6 +360,44 @@ impl<'a, T: Iterator<Item = Token<'a>>> Parser<'a, T> {
         Ok(parameters)
     }
 
+    /// Parses a type. Pointer types are written as `Ptr<T>` and may be nested,
+    /// for example `Ptr<Ptr<Char>>`.
+    ///
+    /// # Arguments
+    /// * `span` - The `Span` of the previous token.
+    fn parse_type(&mut self, span: Span<'a>) -> Result<(Type, Span<'a>), Box<Error<'a>>> {
+        match self.consume(span)? {
+            (span, TokenKind::Void) => Ok((Type::Void, span)),
+            (span, TokenKind::Int) => Ok((Type::Int, span)),
+            (span, TokenKind::Float) => Ok((Type::Float, span)),
+            (span, TokenKind::Boolean) => Ok((Type::Boolean, span)),
+            (span, TokenKind::Char) => Ok((Type::Char, span)),
+            (ptr_span, TokenKind::Ptr) => {
+                let (left_angle_span, _) = self.expect(TokenKind::LeftAngleBracket, ptr_span)?;
+                let (pointee, pointee_span) = self.parse_type(left_angle_span)?;
+                let right_angle_span = self.expect_right_angle_bracket(pointee_span)?;
+                match pointee.pointer_to() {
+                    Some(ty) => Ok((ty, ptr_span.combine(right_angle_span))),
+                    None => Err(Box::new(Error::IllegalType(pointee_span))),
+                }
+            }
+            (span, actual_kind) => Err(Box::new(Error::ExpectedKind {
+                span,
+                expected_kinds: vec![
+                    TokenKind::Void,
+                    TokenKind::Int,
+                    TokenKind::Float,
+                    TokenKind::Boolean,
+                    TokenKind::Char,
+                    TokenKind::Ptr,
+                ],
+                actual_kind,
+                suggestion: None,
+                fix: None,
+            })),
+        }
+    }
+
     /// Parses a single expression. This function follows the Pratt parsing technique
     /// to handle operator precedence and infix operations.
     ///
@@ -285,11 +408,34 @@ impl<'a, T: Iterator<Item = Token<'a>>> Parser<'a, T> {
         &mut self,
         precedence: usize,
         span: Span<'a>,
-    ) -> Result<Expression<'a>, Error<'a>> {
+    ) -> Result<Expression<'a>, Box<Error<'a>>> {
+        // A closing brace or the start of an item never begins an expression,
+        // so they are not consumed and the parser can recover from them.
+        if let Some(&(token_span, kind)) = self.peek() {
+            if kind == TokenKind::RightCurlyBrace || is_item_start(kind) {
+                return Err(Box::new(Error::ExpectedPrefixExpression {
+                    span: token_span,
+                    found_kind: kind,
+                }));
+            }
+        }
+
         let token = self.consume(span)?;
         let mut left = self.parse_prefix(token)?;
         while precedence < self.get_precedence() {
+            // A `+` or a `-` at the start of a line begins a new expression with a
+            // unary operation, so `let x = 5` followed by `-1` is not a subtraction.
+            if let Some(&(token_span, TokenKind::Plus | TokenKind::Minus)) = self.peek() {
+                if token_span.line_start > left.0.line_end {
+                    break;
+                }
+            }
+
             let token = self.consume(left.0)?;
+            if token.1 == TokenKind::Star && token.0.line_start > left.0.line_end {
+                return Err(Box::new(Error::AmbiguousStar(token.0)));
+            }
+
             left = self.parse_infix(left, token)?;
         }
 
@@ -302,7 +448,7 @@ impl<'a, T: Iterator<Item = Token<'a>>> Parser<'a, T> {
     ///
     /// # Arguments
     /// * `token` - The token to parse into a prefix expression.
-    fn parse_prefix(&mut self, token: Token<'a>) -> Result<Expression<'a>, Error<'a>> {
+    fn parse_prefix(&mut self, token: Token<'a>) -> Result<Expression<'a>, Box<Error<'a>>> {
         match token.1 {
             TokenKind::IntegerLiteral(_) => IntParselet.parse(self, token),
             TokenKind::FloatLiteral(_) => FloatParselet.parse(self, token),
@@ -319,16 +465,26 @@ impl<'a, T: Iterator<Item = Token<'a>>> Parser<'a, T> {
             }
             TokenKind::Not => PrefixOperationParselet::new(Precedence::Unary, UnaryOperation::Not)
                 .parse(self, token),
+            TokenKind::Ampersand => {
+                PrefixOperationParselet::new(Precedence::Unary, UnaryOperation::AddressOf)
+                    .parse(self, token)
+            }
+            TokenKind::Star => {
+                PrefixOperationParselet::new(Precedence::Unary, UnaryOperation::Dereference)
+                    .parse(self, token)
+            }
+            TokenKind::Null => NullParselet.parse(self, token),
+            TokenKind::Unsafe => UnsafeParselet.parse(self, token),
             TokenKind::If => IfParselet.parse(self, token),
             TokenKind::Let => LetParselet.parse(self, token),
             TokenKind::LeftCurlyBrace => BlockParselet.parse(self, token),
             TokenKind::While => WhileParselet.parse(self, token),
             TokenKind::LeftParenthesis => ParenthesisParselet.parse(self, token),
             TokenKind::Return => ReturnParselet.parse(self, token),
-            _ => Err(Error::ExpectedPrefixExpression {
+            _ => Err(Box::new(Error::ExpectedPrefixExpression {
                 span: token.0,
                 found_kind: token.1,
-            }),
+            })),
         }
     }
 
@@ -343,7 +499,7 @@ impl<'a, T: Iterator<Item = Token<'a>>> Parser<'a, T> {
         &mut self,
         left: Expression<'a>,
         token: Token<'a>,
-    ) -> Result<Expression<'a>, Error<'a>> {
+    ) -> Result<Expression<'a>, Box<Error<'a>>> {
         match token.1 {
             TokenKind::Plus => {
                 BinaryOperationParselet::new(Precedence::Addition, BinaryOperation::Plus, false)
@@ -369,6 +525,12 @@ impl<'a, T: Iterator<Item = Token<'a>>> Parser<'a, T> {
                 BinaryOperationParselet::new(Precedence::Comparison, BinaryOperation::Equals, false)
                     .parse(self, left, token)
             }
+            TokenKind::ExclamationEqualSign => BinaryOperationParselet::new(
+                Precedence::Comparison,
+                BinaryOperation::NotEquals,
+                false,
+            )
+            .parse(self, left, token),
             TokenKind::LeftAngleBracket => BinaryOperationParselet::new(
                 Precedence::Comparison,
                 BinaryOperation::LessThan,
@@ -413,6 +575,7 @@ impl<'a, T: Iterator<Item = Token<'a>>> Parser<'a, T> {
                 TokenKind::Plus | TokenKind::Minus => Precedence::Addition.into(),
                 TokenKind::Star | TokenKind::Slash => Precedence::Multiplication.into(),
                 TokenKind::EqualSign
+                | TokenKind::ExclamationEqualSign
                 | TokenKind::LeftAngleBracket
                 | TokenKind::RightAngleBracket
                 | TokenKind::LessThanEqualSign
@@ -437,32 +600,178 @@ impl<'a, T: Iterator<Item = Token<'a>>> Parser<'a, T> {
     ///
     /// # Arguments
     /// * `span` - The `Span` of the previous token.
-    fn consume(&mut self, span: Span<'a>) -> Result<Token<'a>, Error<'a>> {
+    fn consume(&mut self, span: Span<'a>) -> Result<Token<'a>, Box<Error<'a>>> {
         match self.tokens.next() {
-            Some(token) => Ok(token),
-            None => Err(Error::UnexpectedEndOfInput(span)),
+            Some(token) => {
+                self.last_span = Some(token.0);
+                Ok(token)
+            }
+            None => Err(Box::new(Error::UnexpectedEndOfInput(span))),
         }
     }
 
     /// Consumes the next token and then verifies that the kind of the
     /// token matches the expected_kind. This function results in an error
     /// if there are no more tokens remaining or if the kind of the current token
-    /// does not match the expected_kind.
+    /// does not match the expected_kind. A token of the wrong kind is not consumed,
+    /// so the parser can recover from it.
     ///
     /// # Arguments
     /// * `expected_kind` - The kind expected of the next token.
     /// * `span` - The `Span` of the previous token.
-    fn expect(&mut self, expected_kind: TokenKind, span: Span<'a>) -> Result<Token<'a>, Error<'a>> {
-        let token = self.consume(span)?;
+    fn expect(
+        &mut self,
+        expected_kind: TokenKind,
+        span: Span<'a>,
+    ) -> Result<Token<'a>, Box<Error<'a>>> {
+        match self.peek() {
+            Some(&(actual_span, actual_kind))
+                if mem::discriminant(&actual_kind) != mem::discriminant(&expected_kind) =>
+            {
+                Err(Box::new(Error::ExpectedKind {
+                    span: actual_span,
+                    expected_kinds: vec![expected_kind],
+                    actual_kind,
+                    suggestion: None,
+                    fix: confused_token_fix(expected_kind, actual_span, actual_kind),
+                }))
+            }
+            _ => self.consume(span),
+        }
+    }
 
-        if mem::discriminant(&token.1) == mem::discriminant(&expected_kind) {
-            Ok(token)
-        } else {
-            Err(Error::ExpectedKind {
-                span: token.0,
-                expected_kinds: vec![expected_kind],
-                actual_kind: token.1,
-            })
+    /// Consumes the `>` that closes a pointer type. The lexer reads a `>` that is
+    /// directly followed by `=` as `>=`, so in `p: Ptr<Int>=null` the token is
+    /// split and the `=` is left for the default value.
+    ///
+    /// # Arguments
+    /// * `span` - The `Span` of the previous token.
+    fn expect_right_angle_bracket(&mut self, span: Span<'a>) -> Result<Span<'a>, Box<Error<'a>>> {
+        match self.tokens.peek_mut() {
+            // `≥` is a single character, so only the written `>=` is split.
+            Some(token)
+                if token.1 == TokenKind::GreaterThanEqualSign
+                    && token.0.end - token.0.start == 2 =>
+            {
+                let (right_angle_span, equal_span) = token.0.split_first();
+                *token = (equal_span, TokenKind::EqualSign);
+                self.last_span = Some(right_angle_span);
+                Ok(right_angle_span)
+            }
+            _ => Ok(self.expect(TokenKind::RightAngleBracket, span)?.0),
         }
     }
+
+    /// Records a syntax error in the body of an item and skips the rest of the item.
+    /// Returns the placeholder expression that replaces the broken body.
+    ///
+    /// # Arguments
+    /// * `error` - The syntax error to record.
+    /// * `span` - The `Span` of the token before the body.
+    fn recover_item(&mut self, error: Error<'a>, span: Span<'a>) -> Expression<'a> {
+        self.errors.push(error);
+        self.synchronize_item();
+        (
+            span.combine(self.last_span.unwrap_or(span)),
+            ExpressionKind::Error,
+        )
+    }
+
+    /// Determines whether the parser can recover from a syntax error inside of
+    /// an expression. When there are no more tokens or the next item starts,
+    /// the rest of the expression is missing, so the error is left for the
+    /// top level to recover from.
+    fn can_recover_expression(&mut self) -> bool {
+        self.peek().is_some_and(|&(_, kind)| !is_item_start(kind))
+    }
+
+    /// Records a syntax error inside of a block and skips the rest of the broken
+    /// expression. The tokens are skipped until the end of the line, the closing `}`
+    /// of the block or the start of the next item. Returns the placeholder
+    /// expression that replaces the broken expression.
+    ///
+    /// # Arguments
+    /// * `error` - The syntax error to record.
+    /// * `span` - The `Span` of the first token of the broken expression.
+    fn recover_expression(&mut self, error: Error<'a>, span: Span<'a>) -> Expression<'a> {
+        self.errors.push(error);
+        let line = self.last_span.unwrap_or(span).line_end;
+        while let Some(&(token_span, kind)) = self.tokens.peek() {
+            if token_span.line_start > line
+                || kind == TokenKind::RightCurlyBrace
+                || is_item_start(kind)
+            {
+                break;
+            }
+
+            self.consume(token_span).ok();
+        }
+
+        (
+            span.combine(self.last_span.unwrap_or(span)),
+            ExpressionKind::Error,
+        )
+    }
+
+    /// Skips tokens until the start of the next item, or until there are no more tokens.
+    fn synchronize_item(&mut self) {
+        while let Some(&(span, kind)) = self.tokens.peek() {
+            if is_item_start(kind) {
+                break;
+            }
+
+            self.consume(span).ok();
+        }
+    }
+}
+
+/// Determines whether a token starts a top level item, which is where
+/// the parser continues after an item could not be parsed.
+///
+/// # Arguments
+/// * `kind` - The kind of the token.
+fn is_item_start(kind: TokenKind) -> bool {
+    matches!(
+        kind,
+        TokenKind::Define
+            | TokenKind::Export
+            | TokenKind::Extern
+            | TokenKind::Const
+            | TokenKind::Hash
+    )
+}
+
+/// Creates a fix for a token that is easily confused with the expected token,
+/// such as `:` instead of the `::` before the return type of a function. A type
+/// found where `::` was expected is a return type whose `::` is missing.
+///
+/// # Arguments
+/// * `expected_kind` - The kind of the expected token.
+/// * `actual_span` - The `Span` of the token found.
+/// * `actual_kind` - The kind of the token found.
+fn confused_token_fix<'a>(
+    expected_kind: TokenKind,
+    actual_span: Span<'a>,
+    actual_kind: TokenKind,
+) -> Option<Fix<'a>> {
+    let replacement = match (expected_kind, actual_kind) {
+        (TokenKind::ColonColon, TokenKind::Colon)
+        | (TokenKind::Colon, TokenKind::ColonColon)
+        | (TokenKind::EqualSign, TokenKind::ColonEqualSign) => expected_kind.to_string(),
+        (
+            TokenKind::ColonColon,
+            TokenKind::Void
+            | TokenKind::Int
+            | TokenKind::Float
+            | TokenKind::Boolean
+            | TokenKind::Char
+            | TokenKind::Ptr,
+        ) => format!("{} {}", expected_kind, actual_kind),
+        _ => return None,
+    };
+
+    Some(Fix::machine_applicable(
+        format!("replace `{}` with `{}`", actual_kind, replacement),
+        vec![Edit::new(actual_span, replacement)],
+    ))
 }
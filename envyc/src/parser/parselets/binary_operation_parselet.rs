@@ -35,7 +35,7 @@ impl<'a> InfixParselet<'a> for BinaryOperationParselet {
         parser: &mut Parser<'a, impl Iterator<Item = Token<'a>>>,
         left: Expression<'a>,
         token: Token<'a>,
-    ) -> Result<Expression<'a>, Error<'a>> {
+    ) -> Result<Expression<'a>, Box<Error<'a>>> {
         let right = parser.parse_expression(
             self.precedence - if self.is_right_associative { 1 } else { 0 },
             token.0,
@@ -3,7 +3,7 @@ use crate::{
     lexer::token::{Token, TokenKind},
     parser::{
         expression::{Expression, ExpressionKind},
-        Parser,
+        is_item_start, Parser,
     },
 };
 
@@ -15,7 +15,7 @@ impl<'a> PrefixParselet<'a> for BlockParselet {
         &self,
         parser: &mut Parser<'a, impl Iterator<Item = Token<'a>>>,
         token: Token<'a>,
-    ) -> Result<Expression<'a>, Error<'a>> {
+    ) -> Result<Expression<'a>, Box<Error<'a>>> {
         let mut expressions = vec![];
         let mut last_span = token.0;
         loop {
@@ -24,7 +24,29 @@ impl<'a> PrefixParselet<'a> for BlockParselet {
                 break;
             }
 
-            let expression = parser.parse_expression(0, last_span)?;
+            let (expression_span, kind) = match parser.peek() {
+                Some(&token) => token,
+                None => return Err(Box::new(Error::UnexpectedEndOfInput(last_span))),
+            };
+
+            // A block can not contain an item, so the closing brace is missing.
+            if is_item_start(kind) {
+                return Err(Box::new(Error::ExpectedKind {
+                    span: expression_span,
+                    expected_kinds: vec![TokenKind::RightCurlyBrace],
+                    actual_kind: kind,
+                    suggestion: None,
+                    fix: None,
+                }));
+            }
+
+            let expression = match parser.parse_expression(0, last_span) {
+                Ok(expression) => expression,
+                Err(error) if parser.can_recover_expression() => {
+                    parser.recover_expression(*error, expression_span)
+                }
+                Err(error) => return Err(error),
+            };
             last_span = expression.0;
             expressions.push(expression);
         }
@@ -15,7 +15,7 @@ macro_rules! get {
             $expression
         } else {
             unreachable!()
-        };
+        }
     };
 }
 
@@ -25,7 +25,7 @@ impl<'a> PrefixParselet<'a> for FloatParselet {
         &self,
         _: &mut Parser<'a, impl Iterator<Item = Token<'a>>>,
         token: Token<'a>,
-    ) -> Result<Expression<'a>, Error<'a>> {
+    ) -> Result<Expression<'a>, Box<Error<'a>>> {
         let value = get!(token, TokenKind::FloatLiteral(value), value);
         Ok((token.0, ExpressionKind::Float(value)))
     }
@@ -2,7 +2,7 @@ use crate::{
     error::Error,
     lexer::token::{Token, TokenKind},
     parser::{
-        expression::{Application, Expression, ExpressionKind, Identifier},
+        expression::{Application, Argument, Expression, ExpressionKind, Identifier},
         Parser,
     },
 };
@@ -15,7 +15,7 @@ macro_rules! get {
             $expression
         } else {
             unreachable!()
-        };
+        }
     };
 }
 
@@ -25,18 +25,40 @@ impl<'a> PrefixParselet<'a> for IdentifierParselet {
         &self,
         parser: &mut Parser<'a, impl Iterator<Item = Token<'a>>>,
         token: Token<'a>,
-    ) -> Result<Expression<'a>, Error<'a>> {
+    ) -> Result<Expression<'a>, Box<Error<'a>>> {
         let id = get!(token, TokenKind::Identifier(id), id);
         if let Some((_, TokenKind::LeftParenthesis)) = parser.peek() {
             let (left_parenthesis_span, _) = parser.consume(token.0)?;
-            let mut parameters = Vec::new();
+            let mut arguments = Vec::new();
             if let Some((_, TokenKind::RightParenthesis)) = parser.peek() {
                 parser.consume(token.0)?;
             } else {
                 let mut last_span = left_parenthesis_span;
                 loop {
+                    // Only a name that is written on its own can be followed by a colon,
+                    // so an argument such as `(x): 1` is rejected.
+                    let first_span = parser.peek().map(|(span, _)| *span);
                     let expression = parser.parse_expression(0, last_span)?;
-                    parameters.push(expression);
+                    let argument = match (expression, parser.peek()) {
+                        (
+                            (name_span, ExpressionKind::Identifier(name)),
+                            Some((_, TokenKind::Colon)),
+                        ) if first_span == Some(name_span) => {
+                            let (colon_span, _) = parser.consume(name_span)?;
+                            let value = parser.parse_expression(0, colon_span)?;
+                            Argument {
+                                name: Some((name_span, name)),
+                                value,
+                            }
+                        }
+                        (expression, _) => Argument {
+                            name: None,
+                            value: expression,
+                        },
+                    };
+
+                    last_span = argument.value.0;
+                    arguments.push(argument);
 
                     match parser.peek() {
                         Some((_, TokenKind::RightParenthesis)) => break,
@@ -45,13 +67,15 @@ impl<'a> PrefixParselet<'a> for IdentifierParselet {
                             last_span = comma_span;
                         }
                         Some((span, kind)) => {
-                            return Err(Error::ExpectedKind {
+                            return Err(Box::new(Error::ExpectedKind {
                                 span: *span,
                                 expected_kinds: vec![TokenKind::RightParenthesis, TokenKind::Comma],
                                 actual_kind: *kind,
-                            })
+                                suggestion: None,
+                                fix: None,
+                            }))
                         }
-                        None => return Err(Error::UnexpectedEndOfInput(last_span)),
+                        None => return Err(Box::new(Error::UnexpectedEndOfInput(last_span))),
                     }
                 }
 
@@ -62,7 +86,7 @@ impl<'a> PrefixParselet<'a> for IdentifierParselet {
                 token.0,
                 ExpressionKind::Application(Application {
                     function_name: (token.0, Identifier(id)),
-                    parameters,
+                    arguments,
                 }),
             ))
         } else {
@@ -15,7 +15,7 @@ impl<'a> PrefixParselet<'a> for IfParselet {
         &self,
         parser: &mut Parser<'a, impl Iterator<Item = Token<'a>>>,
         token: Token<'a>,
-    ) -> Result<Expression<'a>, Error<'a>> {
+    ) -> Result<Expression<'a>, Box<Error<'a>>> {
         let condition = parser.parse_expression(0, token.0)?;
         let (then_span, _) = parser.expect(TokenKind::Then, condition.0)?;
         let then_branch = parser.parse_expression(0, then_span)?;
@@ -23,7 +23,7 @@ pub trait InfixParselet<'a> {
         parser: &mut Parser<'a, impl Iterator<Item = Token<'a>>>,
         left: Expression<'a>,
         token: Token<'a>,
-    ) -> Result<Expression<'a>, Error<'a>>;
+    ) -> Result<Expression<'a>, Box<Error<'a>>>;
 
     /// This method gets the precedence of the infix parselet.
     /// This is used to determine whether to continue parsing the
@@ -1,3 +1,5 @@
+use std::convert::TryFrom;
+
 use crate::{
     error::Error,
     lexer::token::{Token, TokenKind},
@@ -15,7 +17,7 @@ macro_rules! get {
             $expression
         } else {
             unreachable!()
-        };
+        }
     };
 }
 
@@ -25,8 +27,11 @@ impl<'a> PrefixParselet<'a> for IntParselet {
         &self,
         _: &mut Parser<'a, impl Iterator<Item = Token<'a>>>,
         token: Token<'a>,
-    ) -> Result<Expression<'a>, Error<'a>> {
+    ) -> Result<Expression<'a>, Box<Error<'a>>> {
         let value = get!(token, TokenKind::IntegerLiteral(value), value);
-        Ok((token.0, ExpressionKind::Int(value)))
+        match i64::try_from(value) {
+            Ok(value) => Ok((token.0, ExpressionKind::Int(value))),
+            Err(_) => Err(Box::new(Error::IntegerOverflow(token.0))),
+        }
     }
 }
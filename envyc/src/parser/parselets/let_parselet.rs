@@ -2,13 +2,12 @@ use crate::{
     error::Error,
     lexer::token::{Token, TokenKind},
     parser::{
-        expression::{Expression, ExpressionKind, Identifier, Let},
+        expression::{Expression, ExpressionKind, Identifier, Let, Store},
         Parser,
     },
-    semantic_analyzer::types::Type,
 };
 
-use super::prefix_parselet::PrefixParselet;
+use super::{precedence::Precedence, prefix_parselet::PrefixParselet};
 
 macro_rules! get {
     ($token: ident, $pattern: pat, $expression: expr) => {
@@ -16,7 +15,7 @@ macro_rules! get {
             $expression
         } else {
             unreachable!()
-        };
+        }
     };
 }
 
@@ -26,32 +25,18 @@ impl<'a> PrefixParselet<'a> for LetParselet {
         &self,
         parser: &mut Parser<'a, impl Iterator<Item = Token<'a>>>,
         token: Token<'a>,
-    ) -> Result<Expression<'a>, Error<'a>> {
+    ) -> Result<Expression<'a>, Box<Error<'a>>> {
+        if let Some((_, TokenKind::Star)) = parser.peek() {
+            return parse_store(parser, token);
+        }
+
         let identifier = parser.expect(TokenKind::Identifier(0), token.0)?;
         let id = get!(identifier, TokenKind::Identifier(id), id);
         let (given_type, type_span) = {
             if let Some((_, TokenKind::Colon)) = parser.peek() {
                 let (colon_span, _) = parser.consume(identifier.0)?;
-                match parser.consume(colon_span)? {
-                    (span, TokenKind::Void) => (Some(Type::Void), Some(span)),
-                    (span, TokenKind::Int) => (Some(Type::Int), Some(span)),
-                    (span, TokenKind::Float) => (Some(Type::Float), Some(span)),
-                    (span, TokenKind::Boolean) => (Some(Type::Boolean), Some(span)),
-                    (span, TokenKind::Char) => (Some(Type::Char), Some(span)),
-                    (span, actual_kind) => {
-                        return Err(Error::ExpectedKind {
-                            span,
-                            expected_kinds: vec![
-                                TokenKind::Void,
-                                TokenKind::Int,
-                                TokenKind::Float,
-                                TokenKind::Boolean,
-                                TokenKind::Char,
-                            ],
-                            actual_kind,
-                        })
-                    }
-                }
+                let (ty, span) = parser.parse_type(colon_span)?;
+                (Some(ty), Some(span))
             } else {
                 (None, None)
             }
@@ -64,7 +49,15 @@ impl<'a> PrefixParselet<'a> for LetParselet {
         };
 
         let (equal_span, _) = parser.expect(TokenKind::EqualSign, last_span)?;
-        let expression = parser.parse_expression(0, equal_span)?;
+        let value_span = parser.peek().map_or(equal_span, |token| token.0);
+        let expression = match parser.parse_expression(0, equal_span) {
+            Ok(expression) => expression,
+            // The variable is still defined, so its uses are not reported as undefined.
+            Err(error) if parser.can_recover_expression() => {
+                parser.recover_expression(*error, value_span)
+            }
+            Err(error) => return Err(error),
+        };
 
         Ok((
             token.0.combine(expression.0),
@@ -76,3 +69,23 @@ impl<'a> PrefixParselet<'a> for LetParselet {
         ))
     }
 }
+
+/// Parses a store through a pointer (`let *pointer = expression`).
+/// The pointer binds like a unary operand, so `let *p = v` stores through `p`.
+fn parse_store<'a>(
+    parser: &mut Parser<'a, impl Iterator<Item = Token<'a>>>,
+    token: Token<'a>,
+) -> Result<Expression<'a>, Box<Error<'a>>> {
+    let (star_span, _) = parser.consume(token.0)?;
+    let pointer = parser.parse_expression(Precedence::Unary.into(), star_span)?;
+    let (equal_span, _) = parser.expect(TokenKind::EqualSign, pointer.0)?;
+    let expression = parser.parse_expression(0, equal_span)?;
+
+    Ok((
+        token.0.combine(expression.0),
+        ExpressionKind::Store(Store {
+            pointer: Box::new(pointer),
+            expression: Box::new(expression),
+        }),
+    ))
+}
@@ -8,11 +8,13 @@ pub mod if_parselet;
 pub mod infix_parselet;
 pub mod int_parselet;
 pub mod let_parselet;
+pub mod null_parselet;
 pub mod parenthesis_parselet;
 pub mod precedence;
 pub mod prefix_operation_parselet;
 pub mod prefix_parselet;
 pub mod return_parselet;
+pub mod unsafe_parselet;
 pub mod while_parselet;
 
 pub use binary_operation_parselet::BinaryOperationParselet;
@@ -24,7 +26,9 @@ pub use identifier_parselet::IdentifierParselet;
 pub use if_parselet::IfParselet;
 pub use int_parselet::IntParselet;
 pub use let_parselet::LetParselet;
+pub use null_parselet::NullParselet;
 pub use parenthesis_parselet::ParenthesisParselet;
 pub use prefix_operation_parselet::PrefixOperationParselet;
 pub use return_parselet::ReturnParselet;
+pub use unsafe_parselet::UnsafeParselet;
 pub use while_parselet::WhileParselet;
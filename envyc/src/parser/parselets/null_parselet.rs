@@ -0,0 +1,21 @@
+use crate::{
+    error::Error,
+    lexer::token::Token,
+    parser::{
+        expression::{Expression, ExpressionKind},
+        Parser,
+    },
+};
+
+use super::prefix_parselet::PrefixParselet;
+
+pub struct NullParselet;
+impl<'a> PrefixParselet<'a> for NullParselet {
+    fn parse(
+        &self,
+        _: &mut Parser<'a, impl Iterator<Item = Token<'a>>>,
+        token: Token<'a>,
+    ) -> Result<Expression<'a>, Box<Error<'a>>> {
+        Ok((token.0, ExpressionKind::Null))
+    }
+}
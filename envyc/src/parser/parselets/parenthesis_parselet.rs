@@ -12,7 +12,7 @@ impl<'a> PrefixParselet<'a> for ParenthesisParselet {
         &self,
         parser: &mut Parser<'a, impl Iterator<Item = Token<'a>>>,
         token: Token<'a>,
-    ) -> Result<Expression<'a>, Error<'a>> {
+    ) -> Result<Expression<'a>, Box<Error<'a>>> {
         let expression = parser.parse_expression(0, token.0)?;
         let (right_parenthesis_span, _) =
             parser.expect(TokenKind::RightParenthesis, expression.0)?;
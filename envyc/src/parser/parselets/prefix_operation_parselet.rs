@@ -1,6 +1,8 @@
+use std::convert::TryFrom;
+
 use crate::{
     error::Error,
-    lexer::token::Token,
+    lexer::token::{Token, TokenKind},
     parser::{
         expression::{Expression, ExpressionKind, Unary, UnaryOperation},
         Parser,
@@ -28,7 +30,13 @@ impl<'a> PrefixParselet<'a> for PrefixOperationParselet {
         &self,
         parser: &mut Parser<'a, impl Iterator<Item = Token<'a>>>,
         token: Token<'a>,
-    ) -> Result<Expression<'a>, Error<'a>> {
+    ) -> Result<Expression<'a>, Box<Error<'a>>> {
+        if let UnaryOperation::Minus = self.operation {
+            if let Some(literal) = negative_literal(parser, token)? {
+                return Ok(literal);
+            }
+        }
+
         let operand = parser.parse_expression(self.precedence, token.0)?;
         let span = token.0.combine(operand.0);
         let kind = ExpressionKind::Unary(Unary {
@@ -39,3 +47,32 @@ impl<'a> PrefixParselet<'a> for PrefixOperationParselet {
         Ok((span, kind))
     }
 }
+
+/// Parses a minus sign that is followed by a number literal into a negative literal.
+/// The lexer never includes the sign in a literal, so `-9223372036854775808` can only
+/// be written as an `Int` by negating the literal here.
+///
+/// # Arguments
+/// * `parser` - The `Parser` whose next token may be a number literal.
+/// * `minus` - The minus sign token.
+fn negative_literal<'a>(
+    parser: &mut Parser<'a, impl Iterator<Item = Token<'a>>>,
+    minus: Token<'a>,
+) -> Result<Option<Expression<'a>>, Box<Error<'a>>> {
+    let literal = match parser.peek() {
+        Some((_, TokenKind::IntegerLiteral(_))) | Some((_, TokenKind::FloatLiteral(_))) => {
+            parser.consume(minus.0)?
+        }
+        _ => return Ok(None),
+    };
+
+    let span = minus.0.combine(literal.0);
+    match literal.1 {
+        TokenKind::IntegerLiteral(value) => match i64::try_from(-i128::from(value)) {
+            Ok(value) => Ok(Some((span, ExpressionKind::Int(value)))),
+            Err(_) => Err(Box::new(Error::IntegerOverflow(span))),
+        },
+        TokenKind::FloatLiteral(value) => Ok(Some((span, ExpressionKind::Float(-value)))),
+        _ => unreachable!(),
+    }
+}
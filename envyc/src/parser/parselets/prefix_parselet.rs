@@ -19,5 +19,5 @@ pub trait PrefixParselet<'a> {
         &self,
         parser: &mut Parser<'a, impl Iterator<Item = Token<'a>>>,
         token: Token<'a>,
-    ) -> Result<Expression<'a>, Error<'a>>;
+    ) -> Result<Expression<'a>, Box<Error<'a>>>;
 }
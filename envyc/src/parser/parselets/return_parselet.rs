@@ -15,7 +15,7 @@ impl<'a> PrefixParselet<'a> for ReturnParselet {
         &self,
         parser: &mut Parser<'a, impl Iterator<Item = Token<'a>>>,
         token: Token<'a>,
-    ) -> Result<Expression<'a>, Error<'a>> {
+    ) -> Result<Expression<'a>, Box<Error<'a>>> {
         let mut expression = None;
         match parser.peek() {
             Some((_, TokenKind::SemiColon)) => {
@@ -0,0 +1,29 @@
+use crate::{
+    error::Error,
+    lexer::token::{Token, TokenKind},
+    parser::{
+        expression::{Expression, ExpressionKind},
+        Parser,
+    },
+};
+
+use super::{prefix_parselet::PrefixParselet, BlockParselet};
+
+/// Parses an `unsafe` block. The body must be a block so that
+/// the region where pointers may be used is clearly delimited.
+pub struct UnsafeParselet;
+impl<'a> PrefixParselet<'a> for UnsafeParselet {
+    fn parse(
+        &self,
+        parser: &mut Parser<'a, impl Iterator<Item = Token<'a>>>,
+        token: Token<'a>,
+    ) -> Result<Expression<'a>, Box<Error<'a>>> {
+        let left_brace = parser.expect(TokenKind::LeftCurlyBrace, token.0)?;
+        let block = BlockParselet.parse(parser, left_brace)?;
+
+        Ok((
+            token.0.combine(block.0),
+            ExpressionKind::Unsafe(Box::new(block)),
+        ))
+    }
+}
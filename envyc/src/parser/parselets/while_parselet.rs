@@ -15,7 +15,7 @@ impl<'a> PrefixParselet<'a> for WhileParselet {
         &self,
         parser: &mut Parser<'a, impl Iterator<Item = Token<'a>>>,
         token: Token<'a>,
-    ) -> Result<Expression<'a>, Error<'a>> {
+    ) -> Result<Expression<'a>, Box<Error<'a>>> {
         let condition = parser.parse_expression(0, token.0)?;
         let expression = parser.parse_expression(0, condition.0)?;
 
@@ -0,0 +1,355 @@
+use std::fmt::Display;
+
+use crate::lexer::token::{Token, TokenKind};
+
+use super::{
+    ast::{Attribute, Function, Program},
+    expression::{Expression, ExpressionKind},
+};
+
+/// Enum that details the different kinds of nodes in a `SyntaxTree`.
+/// The kinds mirror the items of the `Program` and the kinds of expressions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SyntaxKind {
+    Root,
+    Attribute,
+    Function,
+    Prototype,
+    Parameter,
+    ExternDeclaration,
+    Constant,
+    Literal,
+    Identifier,
+    Unary,
+    Binary,
+    If,
+    Let,
+    Store,
+    Block,
+    Application,
+    // A named argument of an application, such as `min: 0`.
+    Argument,
+    While,
+    Return,
+    Unsafe,
+    Error,
+}
+
+/// A child of a `SyntaxNode`, which is either another node or a single token.
+#[derive(Debug)]
+pub enum SyntaxElement<'a> {
+    Node(SyntaxNode<'a>),
+    Token(Token<'a>),
+}
+
+/// Struct that represents a node of a `SyntaxTree`. The children of a node
+/// are stored in the order in which they are written in the source.
+#[derive(Debug)]
+pub struct SyntaxNode<'a> {
+    pub kind: SyntaxKind,
+    pub children: Vec<SyntaxElement<'a>>,
+}
+
+/// Struct that stores every token of a file, including the whitespace and the
+/// comments that are removed by `filter_tokens`, in a tree that follows the
+/// structure of the `Program`. The tree is lossless: writing out the text of
+/// its tokens in order reproduces the source exactly.
+///
+/// A token belongs to the innermost node whose tokens surround it, so the
+/// whitespace and comments between the expressions of a block belong to the
+/// block, while the whitespace between two items belongs to the root.
+#[derive(Debug)]
+pub struct SyntaxTree<'a> {
+    source: &'a str,
+    root: SyntaxNode<'a>,
+}
+
+impl<'a> SyntaxTree<'a> {
+    /// Creates a new `SyntaxTree`.
+    ///
+    /// # Arguments
+    /// * `source` - The source the tokens were lexed from.
+    /// * `tokens` - Every token of the source, as returned by `lex`.
+    /// * `program` - The `Program` parsed from the tokens.
+    pub fn new(source: &'a str, tokens: Vec<Token<'a>>, program: &Program<'a>) -> Self {
+        let outline = Outline::program(&tokens, program);
+        let mut tokens = tokens.into_iter().peekable();
+        let root = build(&outline, &mut tokens);
+
+        Self { source, root }
+    }
+
+    /// Returns the node that contains every other node.
+    pub fn root(&self) -> &SyntaxNode<'a> {
+        &self.root
+    }
+
+    /// Returns the text of a token in the source.
+    ///
+    /// # Arguments
+    /// * `token` - A token of the tree.
+    pub fn text(&self, token: &Token<'a>) -> &'a str {
+        &self.source[token.0.start..token.0.end]
+    }
+}
+
+impl Display for SyntaxTree<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fn write_node(
+            tree: &SyntaxTree,
+            node: &SyntaxNode,
+            f: &mut std::fmt::Formatter<'_>,
+        ) -> std::fmt::Result {
+            for child in &node.children {
+                match child {
+                    SyntaxElement::Node(node) => write_node(tree, node, f)?,
+                    SyntaxElement::Token(token) => write!(f, "{}", tree.text(token))?,
+                }
+            }
+
+            Ok(())
+        }
+
+        write_node(self, &self.root, f)
+    }
+}
+
+/// Struct that describes the byte offsets a node covers before
+/// the tokens are distributed among the nodes.
+struct Outline {
+    kind: SyntaxKind,
+    start: usize,
+    end: usize,
+    children: Vec<Outline>,
+}
+
+impl Outline {
+    /// Creates a new `Outline` that covers its own span and every child.
+    ///
+    /// # Arguments
+    /// * `kind` - The kind of the node.
+    /// * `(start, end)` - The byte offsets of the tokens that belong to the node itself.
+    /// * `children` - The outlines of the children, in the order they are written.
+    fn new(kind: SyntaxKind, (start, end): (usize, usize), children: Vec<Outline>) -> Self {
+        let start = children
+            .iter()
+            .map(|child| child.start)
+            .fold(start, usize::min);
+        let end = children.iter().map(|child| child.end).fold(end, usize::max);
+        Self {
+            kind,
+            start,
+            end,
+            children,
+        }
+    }
+
+    fn program(tokens: &[Token], program: &Program) -> Self {
+        let mut items = vec![];
+        for extern_declaration in &program.extern_declarations {
+            items.extend(attributes(&extern_declaration.attributes));
+            let span = extern_declaration.span;
+            items.push(Self::new(
+                SyntaxKind::ExternDeclaration,
+                (span.start, span.end),
+                vec![],
+            ));
+        }
+        for constant in &program.constants {
+            items.extend(attributes(&constant.attributes));
+            let span = constant.span;
+            let value = Self::expression(tokens, &constant.value);
+            items.push(Self::new(
+                SyntaxKind::Constant,
+                (span.start, span.end),
+                vec![value],
+            ));
+        }
+        for function in &program.functions {
+            items.extend(attributes(&function.attributes));
+            items.push(Self::function(tokens, function));
+        }
+
+        items.sort_by_key(|item| item.start);
+        Self::new(SyntaxKind::Root, (0, 0), items)
+    }
+
+    fn function(tokens: &[Token], function: &Function) -> Self {
+        let prototype = &function.prototype;
+        let parameters = prototype
+            .parameters
+            .iter()
+            .map(|parameter| {
+                let default = parameter
+                    .default
+                    .iter()
+                    .map(|default| Self::expression(tokens, default))
+                    .collect();
+                let span = parameter.span;
+                Self::new(SyntaxKind::Parameter, (span.start, span.end), default)
+            })
+            .collect();
+
+        // The span of the prototype starts at `define`, so the
+        // `export` before it is found in the tokens instead.
+        let mut start = prototype.span.start;
+        if prototype.export {
+            if let Some((span, TokenKind::Export)) = tokens
+                .iter()
+                .rev()
+                .find(|(span, kind)| !kind.is_trivia() && span.end <= start)
+            {
+                start = span.start;
+            }
+        }
+
+        let prototype = Self::new(
+            SyntaxKind::Prototype,
+            (prototype.span.start, prototype.span.end),
+            parameters,
+        );
+        let body = Self::expression(tokens, &function.body);
+        Self::new(SyntaxKind::Function, (start, start), vec![prototype, body])
+    }
+
+    fn expression(tokens: &[Token], expression: &Expression) -> Self {
+        let (span, kind) = expression;
+        let mut end = span.end;
+        let expression = |expression: &Expression| Self::expression(tokens, expression);
+        let (kind, children) = match kind {
+            ExpressionKind::Int(_)
+            | ExpressionKind::Float(_)
+            | ExpressionKind::Boolean(_)
+            | ExpressionKind::Char(_)
+            | ExpressionKind::Null => (SyntaxKind::Literal, vec![]),
+            ExpressionKind::Identifier(_) => (SyntaxKind::Identifier, vec![]),
+            ExpressionKind::Unary(unary) => {
+                (SyntaxKind::Unary, vec![expression(&unary.expression)])
+            }
+            ExpressionKind::Binary(binary) => (
+                SyntaxKind::Binary,
+                vec![expression(&binary.left), expression(&binary.right)],
+            ),
+            ExpressionKind::If(if_expression) => {
+                let mut children = vec![
+                    expression(&if_expression.condition),
+                    expression(&if_expression.then_branch),
+                ];
+                children.extend(if_expression.else_branch.iter().map(|e| expression(e)));
+                (SyntaxKind::If, children)
+            }
+            ExpressionKind::Let(let_expression) => (
+                SyntaxKind::Let,
+                vec![expression(&let_expression.expression)],
+            ),
+            ExpressionKind::Store(store) => (
+                SyntaxKind::Store,
+                vec![expression(&store.pointer), expression(&store.expression)],
+            ),
+            ExpressionKind::Block(expressions) => (
+                SyntaxKind::Block,
+                expressions.iter().map(expression).collect(),
+            ),
+            ExpressionKind::Application(application) => {
+                // The span of an application only covers the name of the
+                // function, so it is extended to the closing parenthesis.
+                end = closing_parenthesis(tokens, span.end).unwrap_or(end);
+                let arguments = application
+                    .arguments
+                    .iter()
+                    .map(|argument| match &argument.name {
+                        Some((name_span, _)) => Self::new(
+                            SyntaxKind::Argument,
+                            (name_span.start, name_span.end),
+                            vec![expression(&argument.value)],
+                        ),
+                        None => expression(&argument.value),
+                    })
+                    .collect();
+                (SyntaxKind::Application, arguments)
+            }
+            ExpressionKind::While(while_expression) => (
+                SyntaxKind::While,
+                vec![
+                    expression(&while_expression.condition),
+                    expression(&while_expression.expression),
+                ],
+            ),
+            ExpressionKind::Return(returned) => (
+                SyntaxKind::Return,
+                returned.iter().map(|e| expression(e)).collect(),
+            ),
+            ExpressionKind::Unsafe(block) => (SyntaxKind::Unsafe, vec![expression(block)]),
+            ExpressionKind::Error => (SyntaxKind::Error, vec![]),
+        };
+
+        Self::new(kind, (span.start, end), children)
+    }
+}
+
+/// Creates the outlines of the attributes of an item.
+///
+/// # Arguments
+/// * `attributes` - The attributes written before the item.
+fn attributes(attributes: &[Attribute]) -> Vec<Outline> {
+    attributes
+        .iter()
+        .map(|attribute| {
+            let span = attribute.span;
+            Outline::new(SyntaxKind::Attribute, (span.start, span.end), vec![])
+        })
+        .collect()
+}
+
+/// Finds the end of the parenthesis that closes the first parenthesis after an offset.
+///
+/// # Arguments
+/// * `tokens` - Every token of the source.
+/// * `offset` - The byte offset after which the parenthesis is opened.
+fn closing_parenthesis(tokens: &[Token], offset: usize) -> Option<usize> {
+    let mut depth = 0;
+    for (span, kind) in tokens.iter().filter(|(span, _)| span.start >= offset) {
+        match kind {
+            TokenKind::LeftParenthesis => depth += 1,
+            TokenKind::RightParenthesis if depth == 1 => return Some(span.end),
+            TokenKind::RightParenthesis => depth -= 1,
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Creates a `SyntaxNode` from an outline, taking every token that lies within it.
+///
+/// # Arguments
+/// * `outline` - The `Outline` of the node.
+/// * `tokens` - The tokens that were not yet added to a node.
+fn build<'a>(
+    outline: &Outline,
+    tokens: &mut std::iter::Peekable<impl Iterator<Item = Token<'a>>>,
+) -> SyntaxNode<'a> {
+    let mut children = vec![];
+    let mut outlines = outline.children.iter().peekable();
+    while let Some(&(span, _)) = tokens.peek() {
+        if outline.kind != SyntaxKind::Root && span.start >= outline.end {
+            break;
+        }
+
+        match outlines.peek() {
+            Some(child) if span.start >= child.end => {
+                outlines.next();
+            }
+            Some(child) if span.start >= child.start => {
+                children.push(SyntaxElement::Node(build(child, tokens)));
+                outlines.next();
+            }
+            _ => children.push(SyntaxElement::Token(tokens.next().unwrap())),
+        }
+    }
+
+    SyntaxNode {
+        kind: outline.kind,
+        children,
+    }
+}
@@ -1,31 +1,65 @@
-use crate::{error::Span, semantic_analyzer::types::Type};
+use crate::{
+    error::Span,
+    semantic_analyzer::{constant_evaluator::ConstantValue, types::Type},
+};
 
-use super::typed_expression::TypedExpression;
+use super::{ast::Attribute, typed_expression::TypedExpression};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct TypedProgram<'a> {
     pub extern_declarations: Vec<TypedExternDeclaration<'a>>,
+    pub constants: Vec<TypedConstant<'a>>,
     pub functions: Vec<TypedFunction<'a>>,
 }
 
 impl<'a> TypedProgram<'a> {
     pub fn new(
         extern_declarations: Vec<TypedExternDeclaration<'a>>,
+        constants: Vec<TypedConstant<'a>>,
         functions: Vec<TypedFunction<'a>>,
     ) -> Self {
         Self {
             extern_declarations,
+            constants,
             functions,
         }
     }
+
+    /// Returns the name of the file the program was parsed from, which is
+    /// unknown if the file is empty.
+    pub fn file_name(&self) -> Option<&'a str> {
+        self.functions
+            .iter()
+            .map(|function| function.prototype.span)
+            .chain(
+                self.extern_declarations
+                    .iter()
+                    .map(|extern_declaration| extern_declaration.span),
+            )
+            .chain(self.constants.iter().map(|constant| constant.span))
+            .map(|span| span.file_name)
+            .next()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TypedConstant<'a> {
+    pub span: Span<'a>,
+    pub name: (Span<'a>, usize),
+    pub ty: Type,
+    pub expression: TypedExpression<'a>,
+    // The result of evaluating the expression at compile time.
+    pub value: ConstantValue,
+    pub attributes: Vec<Attribute<'a>>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct TypedPrototype<'a> {
     pub span: Span<'a>,
     pub name: usize,
     pub parameters: Vec<TypedParameter<'a>>,
     pub return_type: Type,
+    pub export: bool,
 }
 
 impl<'a> TypedPrototype<'a> {
@@ -34,37 +68,50 @@ impl<'a> TypedPrototype<'a> {
         name: usize,
         parameters: Vec<TypedParameter<'a>>,
         return_type: Type,
+        export: bool,
     ) -> Self {
         Self {
             span,
             name,
             parameters,
             return_type,
+            export,
         }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct TypedExternDeclaration<'a> {
     pub span: Span<'a>,
     pub name: usize,
     pub parameters: Vec<(Type, Span<'a>)>,
+    pub variadic: bool,
     pub return_type: (Type, Span<'a>),
+    pub attributes: Vec<Attribute<'a>>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct TypedFunction<'a> {
     pub prototype: TypedPrototype<'a>,
     pub body: TypedExpression<'a>,
+    pub attributes: Vec<Attribute<'a>>,
 }
 
 impl<'a> TypedFunction<'a> {
-    pub fn new(prototype: TypedPrototype<'a>, body: TypedExpression<'a>) -> Self {
-        Self { prototype, body }
+    pub fn new(
+        prototype: TypedPrototype<'a>,
+        body: TypedExpression<'a>,
+        attributes: Vec<Attribute<'a>>,
+    ) -> Self {
+        Self {
+            prototype,
+            body,
+            attributes,
+        }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct TypedParameter<'a> {
     pub span: Span<'a>,
     pub ty: Type,
@@ -1,4 +1,7 @@
-use crate::{error::Span, semantic_analyzer::types::Type};
+use crate::{
+    error::Span,
+    semantic_analyzer::types::{PointeeType, Type},
+};
 
 use super::expression::{BinaryOperation, UnaryOperation};
 
@@ -11,12 +14,13 @@ pub type TypedExpression<'a> = (Span<'a>, TypedExpressionKind<'a>);
 /// by the `Expression`. The `ExpressionKind` should strive to only store types that
 /// are small in nature and any other types (i.e. String) should be stored in the
 /// `Interner`.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum TypedExpressionKind<'a> {
     Int(i64),
     Float(f64),
     Boolean(bool),
     Char(char),
+    Null,
     // The actual value for the `Identifier` are
     // stored in the `Interner` to reduce redundency in values. Instead,
     // the id's are stored in the variant.
@@ -25,10 +29,15 @@ pub enum TypedExpressionKind<'a> {
     Binary(TypedBinary<'a>),
     If(TypedIf<'a>),
     Let(TypedLet<'a>),
+    Store(TypedStore<'a>),
     Block(Vec<TypedExpression<'a>>),
     Application(TypedApplication<'a>),
     While(TypedWhile<'a>),
     Return(Option<Box<TypedExpression<'a>>>),
+    Unsafe(Box<TypedExpression<'a>>),
+    // A placeholder for an expression that could not be parsed or whose
+    // operands could not be parsed. It is never compiled.
+    Error,
 }
 
 impl<'a> TypedExpressionKind<'a> {
@@ -38,35 +47,39 @@ impl<'a> TypedExpressionKind<'a> {
             TypedExpressionKind::Float(_) => Type::Float,
             TypedExpressionKind::Boolean(_) => Type::Boolean,
             TypedExpressionKind::Char(_) => Type::Char,
+            TypedExpressionKind::Null => Type::Pointer(PointeeType::Void, 1),
             TypedExpressionKind::Identifier(ref inner) => inner.ty,
             TypedExpressionKind::Unary(ref inner) => inner.ty,
             TypedExpressionKind::Binary(ref inner) => inner.ty,
             TypedExpressionKind::If(ref inner) => inner.ty,
             TypedExpressionKind::Let(ref inner) => inner.ty,
+            TypedExpressionKind::Store(_) => Type::Void,
             TypedExpressionKind::Block(ref expressions) => expressions
                 .last()
                 .map_or(Type::Void, |(_, ref kind)| kind.get_type()),
             TypedExpressionKind::Application(ref inner) => inner.ty,
             TypedExpressionKind::While(_) => Type::Void,
             TypedExpressionKind::Return(_) => Type::Never,
+            TypedExpressionKind::Unsafe(ref inner) => inner.1.get_type(),
+            TypedExpressionKind::Error => Type::Never,
         }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct TypedIdentifier {
     pub id: usize,
     pub ty: Type,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct TypedUnary<'a> {
     pub operation: UnaryOperation,
     pub expression: Box<TypedExpression<'a>>,
     pub ty: Type,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct TypedBinary<'a> {
     pub operation: BinaryOperation,
     pub left: Box<TypedExpression<'a>>,
@@ -74,7 +87,7 @@ pub struct TypedBinary<'a> {
     pub ty: Type,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct TypedIf<'a> {
     pub condition: Box<TypedExpression<'a>>,
     pub then_branch: Box<TypedExpression<'a>>,
@@ -82,7 +95,7 @@ pub struct TypedIf<'a> {
     pub ty: Type,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct TypedLet<'a> {
     pub name: (Span<'a>, TypedIdentifier),
     pub given_type: Option<Type>,
@@ -90,14 +103,25 @@ pub struct TypedLet<'a> {
     pub ty: Type,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+pub struct TypedStore<'a> {
+    pub pointer: Box<TypedExpression<'a>>,
+    pub expression: Box<TypedExpression<'a>>,
+    // The type of the value that is stored, which is the pointee of the pointer.
+    pub ty: Type,
+}
+
+#[derive(Debug, Clone)]
 pub struct TypedApplication<'a> {
     pub function_name: (Span<'a>, usize),
     pub parameters: Vec<TypedExpression<'a>>,
+    // The indices of the parameters in the order that they are evaluated, which is
+    // the order the arguments were written in, followed by the default values.
+    pub evaluation_order: Vec<usize>,
     pub ty: Type,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct TypedWhile<'a> {
     pub condition: Box<TypedExpression<'a>>,
     pub expression: Box<TypedExpression<'a>>,
@@ -0,0 +1,495 @@
+use crate::{
+    interner::Interner,
+    lexer::token::{Token, TokenKind},
+    parser::{
+        ast::{Attribute, Program},
+        expression::{BinaryOperation, Expression, ExpressionKind, UnaryOperation},
+        typed_ast::TypedProgram,
+        typed_expression::{TypedExpression, TypedExpressionKind},
+    },
+    semantic_analyzer::types::Type,
+};
+
+/// Writes every token of a file on its own line, together with the location of
+/// the token. Identifiers and literals are followed by their value, and
+/// whitespace is skipped.
+///
+/// # Arguments
+/// * `tokens` - The tokens of the file.
+/// * `interner` - The `Interner` that stores the names of the identifiers.
+pub fn print_tokens(tokens: &[Token], interner: &Interner<String>) -> String {
+    let mut output = String::new();
+    for (span, kind) in tokens {
+        let value = match kind {
+            TokenKind::Whitespace(_) => continue,
+            TokenKind::Identifier(id) => format!(" `{}`", interner.get(*id)),
+            TokenKind::IntegerLiteral(value) => format!(" {}", value),
+            TokenKind::FloatLiteral(value) => format!(" {:?}", value),
+            TokenKind::BooleanLiteral(value) => format!(" {}", value),
+            TokenKind::CharLiteral(value) => format!(" {:?}", value),
+            _ => String::new(),
+        };
+
+        output.push_str(&format!(
+            "{}:{}-{}:{} {}{}\n",
+            span.line_start, span.column_start, span.line_end, span.column_end, kind, value
+        ));
+    }
+
+    output
+}
+
+/// Struct that writes a `Program` or a `TypedProgram` in the syntax of Envious,
+/// to inspect what the parser and the type checker produced.
+///
+/// Unlike the `Formatter`, which keeps the source as it was written, the printer
+/// writes every unary and binary expression within parentheses, so the output
+/// shows how the expressions were grouped. In a typed program, every expression
+/// whose type is not evident is followed by its type, such as `(square(4): Int)`,
+/// and every `let` is written with the type of its variable.
+pub struct Printer<'i> {
+    interner: &'i Interner<String>,
+    indent_width: usize,
+    output: String,
+    // The current level of indentation.
+    indent: usize,
+}
+
+impl<'i> Printer<'i> {
+    /// Creates a new `Printer`.
+    ///
+    /// # Arguments
+    /// * `interner` - The `Interner` that stores the names of the program.
+    /// * `indent_width` - The number of spaces used for every level of indentation.
+    pub fn new(interner: &'i Interner<String>, indent_width: usize) -> Self {
+        Self {
+            interner,
+            indent_width,
+            output: String::new(),
+            indent: 0,
+        }
+    }
+
+    /// Writes a program that was parsed but not checked.
+    ///
+    /// # Arguments
+    /// * `program` - The `Program` to write.
+    pub fn print_program(mut self, program: &Program) -> String {
+        for declaration in &program.extern_declarations {
+            self.print_attributes(&declaration.attributes);
+            let parameters = declaration.parameters.iter().map(|(ty, _)| *ty);
+            self.print_extern_declaration(
+                declaration.name,
+                parameters.collect(),
+                declaration.variadic,
+                declaration.return_type.0,
+            );
+        }
+
+        for constant in &program.constants {
+            self.print_attributes(&constant.attributes);
+            self.output.push_str(&format!(
+                "const {}: {} = ",
+                self.name(constant.name.1),
+                constant.ty.0
+            ));
+            self.print_expression(&constant.value);
+            self.output.push_str("\n\n");
+        }
+
+        for function in &program.functions {
+            self.print_attributes(&function.attributes);
+            let prototype = &function.prototype;
+            if prototype.export {
+                self.output.push_str("export ");
+            }
+
+            self.output
+                .push_str(&format!("define {}(", self.name(prototype.name)));
+            for (index, parameter) in prototype.parameters.iter().enumerate() {
+                if index > 0 {
+                    self.output.push_str(", ");
+                }
+
+                self.output
+                    .push_str(&format!("{}: {}", self.name(parameter.name), parameter.ty));
+                if let Some(default) = &parameter.default {
+                    self.output.push_str(" = ");
+                    self.print_expression(default);
+                }
+            }
+
+            self.output
+                .push_str(&format!(") :: {} = ", prototype.return_type.0));
+            self.print_expression(&function.body);
+            self.output.push_str("\n\n");
+        }
+
+        self.finish()
+    }
+
+    /// Writes a program that was checked, together with the types of its expressions.
+    ///
+    /// # Arguments
+    /// * `program` - The `TypedProgram` to write.
+    pub fn print_typed_program(mut self, program: &TypedProgram) -> String {
+        for declaration in &program.extern_declarations {
+            self.print_attributes(&declaration.attributes);
+            let parameters = declaration.parameters.iter().map(|(ty, _)| *ty);
+            self.print_extern_declaration(
+                declaration.name,
+                parameters.collect(),
+                declaration.variadic,
+                declaration.return_type.0,
+            );
+        }
+
+        for constant in &program.constants {
+            self.print_attributes(&constant.attributes);
+            self.output.push_str(&format!(
+                "const {}: {} = ",
+                self.name(constant.name.1),
+                constant.ty
+            ));
+            self.print_typed_expression(&constant.expression);
+            self.output.push_str("\n\n");
+        }
+
+        // The default arguments were inserted into the applications by the
+        // type checker, so the parameters are written without them.
+        for function in &program.functions {
+            self.print_attributes(&function.attributes);
+            let prototype = &function.prototype;
+            let parameters = prototype
+                .parameters
+                .iter()
+                .map(|parameter| format!("{}: {}", self.name(parameter.name), parameter.ty))
+                .collect::<Vec<_>>();
+            self.output.push_str(&format!(
+                "{}define {}({}) :: {} = ",
+                if prototype.export { "export " } else { "" },
+                self.name(prototype.name),
+                parameters.join(", "),
+                prototype.return_type
+            ));
+            self.print_typed_expression(&function.body);
+            self.output.push_str("\n\n");
+        }
+
+        self.finish()
+    }
+
+    /// Returns the output, which ends with a single line break unless it is empty.
+    fn finish(mut self) -> String {
+        while self.output.ends_with("\n\n") {
+            self.output.pop();
+        }
+
+        self.output
+    }
+
+    /// Writes an external declaration, which is the same in both kinds of programs.
+    ///
+    /// # Arguments
+    /// * `name` - The id of the name of the declaration.
+    /// * `parameters` - The types of the parameters.
+    /// * `variadic` - Whether the function accepts more arguments after the parameters.
+    /// * `return_type` - The return type of the function.
+    fn print_extern_declaration(
+        &mut self,
+        name: usize,
+        parameters: Vec<Type>,
+        variadic: bool,
+        return_type: Type,
+    ) {
+        let parameters = parameters
+            .iter()
+            .map(Type::to_string)
+            .chain(variadic.then(|| "...".to_string()))
+            .collect::<Vec<_>>();
+        self.output.push_str(&format!(
+            "extern {}({}) :: {}\n\n",
+            self.name(name),
+            parameters.join(", "),
+            return_type
+        ));
+    }
+
+    /// Writes the attributes of an item, each on its own line.
+    ///
+    /// # Arguments
+    /// * `attributes` - The attributes of the item.
+    fn print_attributes(&mut self, attributes: &[Attribute]) {
+        for attribute in attributes {
+            let arguments = attribute
+                .arguments
+                .iter()
+                .map(|(_, argument)| self.name(*argument).as_str())
+                .collect::<Vec<_>>();
+            self.output.push_str(&format!(
+                "#{}({})\n",
+                self.name(attribute.name.1),
+                arguments.join(", ")
+            ));
+        }
+    }
+
+    /// Writes an expression of a program that was not checked.
+    ///
+    /// # Arguments
+    /// * `expression` - The `Expression` to write.
+    fn print_expression(&mut self, expression: &Expression) {
+        match &expression.1 {
+            ExpressionKind::Int(value) => self.output.push_str(&value.to_string()),
+            ExpressionKind::Float(value) => self.output.push_str(&format!("{:?}", value)),
+            ExpressionKind::Boolean(value) => self.output.push_str(&value.to_string()),
+            ExpressionKind::Char(value) => self.output.push_str(&format!("{:?}", value)),
+            ExpressionKind::Null => self.output.push_str("null"),
+            ExpressionKind::Identifier(identifier) => {
+                let name = self.name(identifier.0);
+                self.output.push_str(name);
+            }
+            ExpressionKind::Unary(unary) => {
+                self.output
+                    .push_str(&format!("({}", unary_operator(unary.operation)));
+                self.print_expression(&unary.expression);
+                self.output.push(')');
+            }
+            ExpressionKind::Binary(binary) => {
+                self.output.push('(');
+                self.print_expression(&binary.left);
+                self.output
+                    .push_str(&format!(" {} ", binary_operator(binary.operation)));
+                self.print_expression(&binary.right);
+                self.output.push(')');
+            }
+            ExpressionKind::If(if_expression) => {
+                self.output.push_str("if ");
+                self.print_expression(&if_expression.condition);
+                self.output.push_str(" then ");
+                self.print_expression(&if_expression.then_branch);
+                if let Some(else_branch) = &if_expression.else_branch {
+                    self.output.push_str(" else ");
+                    self.print_expression(else_branch);
+                }
+            }
+            ExpressionKind::Let(let_expression) => {
+                let name = self.name(let_expression.name.1 .0);
+                self.output.push_str(&format!("let {}", name));
+                if let Some(given_type) = let_expression.given_type {
+                    self.output.push_str(&format!(": {}", given_type));
+                }
+
+                self.output.push_str(" = ");
+                self.print_expression(&let_expression.expression);
+            }
+            ExpressionKind::Store(store) => {
+                self.output.push_str("let *");
+                self.print_expression(&store.pointer);
+                self.output.push_str(" = ");
+                self.print_expression(&store.expression);
+            }
+            ExpressionKind::Block(expressions) => {
+                self.print_block(expressions, Self::print_expression)
+            }
+            ExpressionKind::Application(application) => {
+                let name = self.name(application.function_name.1 .0);
+                self.output.push_str(&format!("{}(", name));
+                for (index, argument) in application.arguments.iter().enumerate() {
+                    if index > 0 {
+                        self.output.push_str(", ");
+                    }
+
+                    if let Some((_, name)) = &argument.name {
+                        let name = self.name(name.0);
+                        self.output.push_str(&format!("{}: ", name));
+                    }
+
+                    self.print_expression(&argument.value);
+                }
+
+                self.output.push(')');
+            }
+            ExpressionKind::While(while_expression) => {
+                self.output.push_str("while ");
+                self.print_expression(&while_expression.condition);
+                self.output.push(' ');
+                self.print_expression(&while_expression.expression);
+            }
+            ExpressionKind::Return(expression) => {
+                self.output.push_str("return");
+                if let Some(expression) = expression {
+                    self.output.push(' ');
+                    self.print_expression(expression);
+                }
+            }
+            ExpressionKind::Unsafe(expression) => {
+                self.output.push_str("unsafe ");
+                self.print_expression(expression);
+            }
+            ExpressionKind::Error => self.output.push_str("<error>"),
+        }
+    }
+
+    /// Writes an expression of a program that was checked. Literals, blocks and
+    /// expressions that do not produce a value are written without their type.
+    ///
+    /// # Arguments
+    /// * `expression` - The `TypedExpression` to write.
+    fn print_typed_expression(&mut self, expression: &TypedExpression) {
+        let ty = expression.1.get_type();
+        match &expression.1 {
+            TypedExpressionKind::Int(value) => self.output.push_str(&value.to_string()),
+            TypedExpressionKind::Float(value) => self.output.push_str(&format!("{:?}", value)),
+            TypedExpressionKind::Boolean(value) => self.output.push_str(&value.to_string()),
+            TypedExpressionKind::Char(value) => self.output.push_str(&format!("{:?}", value)),
+            TypedExpressionKind::Null => self.output.push_str("null"),
+            TypedExpressionKind::Identifier(identifier) => {
+                let name = self.name(identifier.id);
+                self.output.push_str(&format!("({}: {})", name, ty));
+            }
+            TypedExpressionKind::Unary(unary) => {
+                self.output
+                    .push_str(&format!("({}", unary_operator(unary.operation)));
+                self.print_typed_expression(&unary.expression);
+                self.output.push_str(&format!(": {})", ty));
+            }
+            TypedExpressionKind::Binary(binary) => {
+                self.output.push('(');
+                self.print_typed_expression(&binary.left);
+                self.output
+                    .push_str(&format!(" {} ", binary_operator(binary.operation)));
+                self.print_typed_expression(&binary.right);
+                self.output.push_str(&format!(": {})", ty));
+            }
+            TypedExpressionKind::If(if_expression) => {
+                self.output.push_str("(if ");
+                self.print_typed_expression(&if_expression.condition);
+                self.output.push_str(" then ");
+                self.print_typed_expression(&if_expression.then_branch);
+                if let Some(else_branch) = &if_expression.else_branch {
+                    self.output.push_str(" else ");
+                    self.print_typed_expression(else_branch);
+                }
+
+                self.output.push_str(&format!(": {})", ty));
+            }
+            TypedExpressionKind::Let(let_expression) => {
+                let name = self.name(let_expression.name.1.id);
+                self.output
+                    .push_str(&format!("let {}: {} = ", name, let_expression.name.1.ty));
+                self.print_typed_expression(&let_expression.expression);
+            }
+            TypedExpressionKind::Store(store) => {
+                self.output.push_str("let *");
+                self.print_typed_expression(&store.pointer);
+                self.output.push_str(" = ");
+                self.print_typed_expression(&store.expression);
+            }
+            TypedExpressionKind::Block(expressions) => {
+                self.print_block(expressions, Self::print_typed_expression)
+            }
+            TypedExpressionKind::Application(application) => {
+                let name = self.name(application.function_name.1);
+                self.output.push_str(&format!("({}(", name));
+                for (index, parameter) in application.parameters.iter().enumerate() {
+                    if index > 0 {
+                        self.output.push_str(", ");
+                    }
+
+                    self.print_typed_expression(parameter);
+                }
+
+                self.output.push_str(&format!("): {})", ty));
+            }
+            TypedExpressionKind::While(while_expression) => {
+                self.output.push_str("while ");
+                self.print_typed_expression(&while_expression.condition);
+                self.output.push(' ');
+                self.print_typed_expression(&while_expression.expression);
+            }
+            TypedExpressionKind::Return(expression) => {
+                self.output.push_str("return");
+                if let Some(expression) = expression {
+                    self.output.push(' ');
+                    self.print_typed_expression(expression);
+                }
+            }
+            TypedExpressionKind::Unsafe(expression) => {
+                self.output.push_str("unsafe ");
+                self.print_typed_expression(expression);
+            }
+            TypedExpressionKind::Error => self.output.push_str("<error>"),
+        }
+    }
+
+    /// Writes the expressions of a block on their own lines, one level of
+    /// indentation deeper than the block.
+    ///
+    /// # Arguments
+    /// * `expressions` - The expressions of the block.
+    /// * `print` - The function that writes each expression.
+    fn print_block<E>(&mut self, expressions: &[E], print: fn(&mut Self, &E)) {
+        if expressions.is_empty() {
+            self.output.push_str("{}");
+            return;
+        }
+
+        self.output.push_str("{\n");
+        self.indent += 1;
+        for expression in expressions {
+            self.output
+                .push_str(&" ".repeat(self.indent * self.indent_width));
+            print(self, expression);
+            self.output.push('\n');
+        }
+
+        self.indent -= 1;
+        self.output
+            .push_str(&" ".repeat(self.indent * self.indent_width));
+        self.output.push('}');
+    }
+
+    /// Returns the name that was interned with an id.
+    ///
+    /// # Arguments
+    /// * `id` - The id of the name.
+    fn name(&self, id: usize) -> &'i String {
+        self.interner.get(id)
+    }
+}
+
+/// Returns how a unary operation is written.
+///
+/// # Arguments
+/// * `operation` - The operation to write.
+fn unary_operator(operation: UnaryOperation) -> &'static str {
+    match operation {
+        UnaryOperation::Plus => "+",
+        UnaryOperation::Minus => "-",
+        UnaryOperation::Not => "not ",
+        UnaryOperation::AddressOf => "&",
+        UnaryOperation::Dereference => "*",
+    }
+}
+
+/// Returns how a binary operation is written.
+///
+/// # Arguments
+/// * `operation` - The operation to write.
+fn binary_operator(operation: BinaryOperation) -> &'static str {
+    match operation {
+        BinaryOperation::Plus => "+",
+        BinaryOperation::Minus => "-",
+        BinaryOperation::Multiply => "*",
+        BinaryOperation::Divide => "/",
+        BinaryOperation::Equals => "=",
+        BinaryOperation::NotEquals => "!=",
+        BinaryOperation::LessThan => "<",
+        BinaryOperation::GreaterThan => ">",
+        BinaryOperation::LessThanEquals => "<=",
+        BinaryOperation::GreaterThanEquals => ">=",
+        BinaryOperation::Or => "or",
+        BinaryOperation::And => "and",
+    }
+}
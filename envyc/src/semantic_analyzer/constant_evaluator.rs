@@ -0,0 +1,395 @@
+use std::collections::HashMap;
+
+use crate::{
+    error::{Error, Span},
+    parser::{
+        expression::{BinaryOperation, UnaryOperation},
+        typed_ast::TypedFunction,
+        typed_expression::{TypedApplication, TypedBinary, TypedExpression, TypedExpressionKind},
+    },
+};
+
+// The maximum number of nested expressions during the evaluation of a constant,
+// which is only reached by recursive functions. Every nested expression is a
+// frame on the stack, so the limit keeps the evaluation from overflowing it.
+const DEPTH_LIMIT: usize = 256;
+// The maximum number of expressions evaluated for a single constant.
+// This prevents infinite loops from hanging the compiler.
+const STEP_LIMIT: usize = 1_000_000;
+
+/// Enum that represents the value of an expression that
+/// was evaluated at compile time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConstantValue {
+    Void,
+    Int(i64),
+    Float(f64),
+    Boolean(bool),
+    Char(char),
+}
+
+/// Enum that describes why the evaluation of an expression stopped early.
+/// A `return` unwinds to the closest function application, while an error
+/// unwinds through the entire evaluation. Reaching an expression that could
+/// not be parsed also unwinds through the entire evaluation, but its syntax
+/// error was already reported. The error is boxed to keep the results small,
+/// since every level of a recursive evaluation returns one.
+enum Interrupt<'a> {
+    Return(ConstantValue),
+    Error(Box<Error<'a>>),
+    Invalid,
+}
+
+impl<'a> From<Error<'a>> for Interrupt<'a> {
+    fn from(error: Error<'a>) -> Self {
+        Interrupt::Error(Box::new(error))
+    }
+}
+
+impl<'a> From<Box<Error<'a>>> for Interrupt<'a> {
+    fn from(error: Box<Error<'a>>) -> Self {
+        Interrupt::Error(error)
+    }
+}
+
+type EvaluationResult<'a> = Result<ConstantValue, Interrupt<'a>>;
+
+/// Struct that evaluates constants at compile time.
+///
+/// The evaluator walks the typed expressions directly. It supports arithmetic,
+/// comparisons, `if`, `while`, `let` and applications of functions defined in
+/// Envious. External functions and pointers can not be evaluated, since their
+/// behavior is only known at run time.
+pub struct ConstantEvaluator<'a, 'b> {
+    // The expressions of the constants, which are evaluated on demand.
+    constants: HashMap<usize, &'b TypedExpression<'a>>,
+    functions: HashMap<usize, &'b TypedFunction<'a>>,
+    // The values of the constants that were already evaluated.
+    values: HashMap<usize, ConstantValue>,
+    // The constants currently being evaluated, used to find cycles.
+    evaluating: Vec<usize>,
+    steps: usize,
+    // The number of expressions that are currently being evaluated.
+    depth: usize,
+}
+
+impl<'a, 'b> ConstantEvaluator<'a, 'b> {
+    pub fn new(
+        constants: HashMap<usize, &'b TypedExpression<'a>>,
+        functions: &'b [TypedFunction<'a>],
+    ) -> Self {
+        Self {
+            constants,
+            functions: functions
+                .iter()
+                .map(|function| (function.prototype.name, function))
+                .collect(),
+            values: HashMap::new(),
+            evaluating: vec![],
+            steps: 0,
+            depth: 0,
+        }
+    }
+
+    /// Evaluates the constant with the given name. Other constants that
+    /// are referenced are evaluated first, regardless of their order in the file.
+    /// Returns `None` if the value depends on an expression that could not be parsed.
+    ///
+    /// # Arguments
+    /// * `name` - The id of the constant.
+    /// * `span` - The `Span` where the constant is referenced.
+    pub fn evaluate_constant(
+        &mut self,
+        name: usize,
+        span: Span<'a>,
+    ) -> Result<Option<ConstantValue>, Box<Error<'a>>> {
+        match self.evaluate_constant_value(name, span) {
+            Ok(value) => Ok(Some(value)),
+            Err(Interrupt::Error(error)) => Err(error),
+            Err(Interrupt::Invalid) => Ok(None),
+            // A `return` outside of a function is reported as a non constant expression.
+            Err(Interrupt::Return(_)) => unreachable!(),
+        }
+    }
+
+    /// Evaluates an expression outside of any constant, such as a condition
+    /// inspected by the linter. Returns `None` if the expression can not be
+    /// evaluated at compile time or if its evaluation fails.
+    ///
+    /// # Arguments
+    /// * `expression` - The expression to evaluate.
+    pub fn evaluate_expression(
+        &mut self,
+        expression: &TypedExpression<'a>,
+    ) -> Option<ConstantValue> {
+        self.steps = 0;
+        self.evaluate(expression, &mut HashMap::new()).ok()
+    }
+
+    fn evaluate_constant_value(&mut self, name: usize, span: Span<'a>) -> EvaluationResult<'a> {
+        if let Some(value) = self.values.get(&name) {
+            return Ok(*value);
+        }
+
+        if self.evaluating.contains(&name) {
+            return Err(Error::CyclicConstant(span).into());
+        }
+
+        let expression = self.constants[&name];
+        if self.evaluating.is_empty() {
+            self.steps = 0;
+        }
+
+        self.evaluating.push(name);
+        let mut locals = HashMap::new();
+        let result = match self.evaluate(expression, &mut locals) {
+            Err(Interrupt::Return(_)) => Err(Error::NonConstantExpression(expression.0).into()),
+            result => result,
+        };
+
+        self.evaluating.pop();
+        let value = result?;
+        self.values.insert(name, value);
+        Ok(value)
+    }
+
+    /// Evaluates a single expression. Like the code generator, the local variables
+    /// of a function live in a single flat scope, so a `let` inside of a block
+    /// updates a variable defined outside of it.
+    ///
+    /// # Arguments
+    /// * `expression` - The expression to evaluate.
+    /// * `locals` - The local variables of the current function application.
+    fn evaluate(
+        &mut self,
+        expression: &TypedExpression<'a>,
+        locals: &mut HashMap<usize, ConstantValue>,
+    ) -> EvaluationResult<'a> {
+        self.steps += 1;
+        if self.steps > STEP_LIMIT {
+            return Err(Error::ConstantEvaluationLimit(expression.0).into());
+        }
+
+        self.depth += 1;
+        let result = self.evaluate_kind(expression, locals);
+        self.depth -= 1;
+        result
+    }
+
+    fn evaluate_kind(
+        &mut self,
+        expression: &TypedExpression<'a>,
+        locals: &mut HashMap<usize, ConstantValue>,
+    ) -> EvaluationResult<'a> {
+        match &expression.1 {
+            TypedExpressionKind::Int(value) => Ok(ConstantValue::Int(*value)),
+            TypedExpressionKind::Float(value) => Ok(ConstantValue::Float(*value)),
+            TypedExpressionKind::Boolean(value) => Ok(ConstantValue::Boolean(*value)),
+            TypedExpressionKind::Char(value) => Ok(ConstantValue::Char(*value)),
+            TypedExpressionKind::Identifier(identifier) => {
+                if let Some(value) = locals.get(&identifier.id) {
+                    Ok(*value)
+                } else if self.constants.contains_key(&identifier.id) {
+                    self.evaluate_constant_value(identifier.id, expression.0)
+                } else {
+                    Err(Error::NonConstantExpression(expression.0).into())
+                }
+            }
+            TypedExpressionKind::Unary(unary) => {
+                let value = self.evaluate(&unary.expression, locals)?;
+                evaluate_unary(expression.0, unary.operation, value)
+            }
+            TypedExpressionKind::Binary(binary) => {
+                self.evaluate_binary(expression.0, binary, locals)
+            }
+            TypedExpressionKind::If(typed_if) => {
+                match self.evaluate(&typed_if.condition, locals)? {
+                    ConstantValue::Boolean(true) => {
+                        let value = self.evaluate(&typed_if.then_branch, locals)?;
+                        if typed_if.else_branch.is_some() {
+                            Ok(value)
+                        } else {
+                            Ok(ConstantValue::Void)
+                        }
+                    }
+                    ConstantValue::Boolean(false) => match typed_if.else_branch {
+                        Some(ref else_branch) => self.evaluate(else_branch, locals),
+                        None => Ok(ConstantValue::Void),
+                    },
+                    _ => unreachable!(),
+                }
+            }
+            TypedExpressionKind::Let(typed_let) => {
+                let value = self.evaluate(&typed_let.expression, locals)?;
+                locals.insert(typed_let.name.1.id, value);
+                Ok(ConstantValue::Void)
+            }
+            TypedExpressionKind::Block(expressions) => {
+                let mut value = ConstantValue::Void;
+                for expression in expressions {
+                    value = self.evaluate(expression, locals)?;
+                }
+
+                Ok(value)
+            }
+            TypedExpressionKind::Application(application) => {
+                self.evaluate_application(expression.0, application, locals)
+            }
+            TypedExpressionKind::While(typed_while) => {
+                while let ConstantValue::Boolean(true) =
+                    self.evaluate(&typed_while.condition, locals)?
+                {
+                    self.evaluate(&typed_while.expression, locals)?;
+                }
+
+                Ok(ConstantValue::Void)
+            }
+            TypedExpressionKind::Return(Some(value)) => {
+                let value = self.evaluate(value, locals)?;
+                Err(Interrupt::Return(value))
+            }
+            TypedExpressionKind::Return(None) => Err(Interrupt::Return(ConstantValue::Void)),
+            TypedExpressionKind::Null
+            | TypedExpressionKind::Store(_)
+            | TypedExpressionKind::Unsafe(_) => {
+                Err(Error::NonConstantExpression(expression.0).into())
+            }
+            TypedExpressionKind::Error => Err(Interrupt::Invalid),
+        }
+    }
+
+    fn evaluate_binary(
+        &mut self,
+        span: Span<'a>,
+        binary: &TypedBinary<'a>,
+        locals: &mut HashMap<usize, ConstantValue>,
+    ) -> EvaluationResult<'a> {
+        let left = self.evaluate(&binary.left, locals)?;
+        let right = self.evaluate(&binary.right, locals)?;
+        let value = match (binary.operation, left, right) {
+            (operation, ConstantValue::Int(left), ConstantValue::Int(right)) => {
+                let result = match operation {
+                    BinaryOperation::Plus => left.checked_add(right),
+                    BinaryOperation::Minus => left.checked_sub(right),
+                    BinaryOperation::Multiply => left.checked_mul(right),
+                    BinaryOperation::Divide if right == 0 => {
+                        return Err(Error::DivisionByZero(binary.right.0).into())
+                    }
+                    BinaryOperation::Divide => left.checked_div(right),
+                    comparison => return Ok(compare(comparison, left, right)),
+                };
+
+                ConstantValue::Int(result.ok_or(Error::ConstantOverflow(span))?)
+            }
+            (operation, ConstantValue::Float(left), ConstantValue::Float(right)) => {
+                let result = match operation {
+                    BinaryOperation::Plus => left + right,
+                    BinaryOperation::Minus => left - right,
+                    BinaryOperation::Multiply => left * right,
+                    BinaryOperation::Divide if right == 0.0 => {
+                        return Err(Error::DivisionByZero(binary.right.0).into())
+                    }
+                    BinaryOperation::Divide => left / right,
+                    comparison => return Ok(compare(comparison, left, right)),
+                };
+
+                if result.is_infinite() && left.is_finite() && right.is_finite() {
+                    return Err(Error::ConstantOverflow(span).into());
+                }
+
+                ConstantValue::Float(result)
+            }
+            (BinaryOperation::Plus, ConstantValue::Char(left), ConstantValue::Char(right)) => {
+                // Chars only hold ASCII characters, so their sum must be one too.
+                let sum = left as u32 + right as u32;
+                let sum = match char::from_u32(sum).filter(char::is_ascii) {
+                    Some(sum) => sum,
+                    None => return Err(Error::ConstantOverflow(span).into()),
+                };
+
+                ConstantValue::Char(sum)
+            }
+            (operation, ConstantValue::Char(left), ConstantValue::Char(right)) => {
+                compare(operation, left, right)
+            }
+            (operation, ConstantValue::Boolean(left), ConstantValue::Boolean(right)) => {
+                match operation {
+                    BinaryOperation::Or => ConstantValue::Boolean(left || right),
+                    BinaryOperation::And => ConstantValue::Boolean(left && right),
+                    comparison => compare(comparison, left, right),
+                }
+            }
+            _ => return Err(Error::NonConstantExpression(span).into()),
+        };
+
+        Ok(value)
+    }
+
+    fn evaluate_application(
+        &mut self,
+        span: Span<'a>,
+        application: &TypedApplication<'a>,
+        locals: &mut HashMap<usize, ConstantValue>,
+    ) -> EvaluationResult<'a> {
+        let function = match self.functions.get(&application.function_name.1) {
+            Some(function) => *function,
+            None => return Err(Error::NonConstantExpression(span).into()),
+        };
+
+        let mut values = vec![ConstantValue::Void; application.parameters.len()];
+        for &index in &application.evaluation_order {
+            values[index] = self.evaluate(&application.parameters[index], locals)?;
+        }
+
+        let mut function_locals = HashMap::new();
+        for (parameter, value) in function.prototype.parameters.iter().zip(values) {
+            function_locals.insert(parameter.name, value);
+        }
+
+        if self.depth >= DEPTH_LIMIT {
+            return Err(Error::ConstantEvaluationLimit(span).into());
+        }
+
+        match self.evaluate(&function.body, &mut function_locals) {
+            Ok(value) | Err(Interrupt::Return(value)) => Ok(value),
+            Err(error) => Err(error),
+        }
+    }
+}
+
+fn evaluate_unary<'a>(
+    span: Span<'a>,
+    operation: UnaryOperation,
+    value: ConstantValue,
+) -> EvaluationResult<'a> {
+    match (operation, value) {
+        (UnaryOperation::Plus, value) => Ok(value),
+        (UnaryOperation::Minus, ConstantValue::Int(value)) => value
+            .checked_neg()
+            .map(ConstantValue::Int)
+            .ok_or_else(|| Error::ConstantOverflow(span).into()),
+        (UnaryOperation::Minus, ConstantValue::Float(value)) => Ok(ConstantValue::Float(-value)),
+        (UnaryOperation::Not, ConstantValue::Boolean(value)) => Ok(ConstantValue::Boolean(!value)),
+        _ => Err(Error::NonConstantExpression(span).into()),
+    }
+}
+
+/// Applies a comparison operation to two values of the same type.
+///
+/// # Arguments
+/// * `operation` - The comparison to apply.
+/// * `left` - The left operand.
+/// * `right` - The right operand.
+fn compare<T: PartialOrd>(operation: BinaryOperation, left: T, right: T) -> ConstantValue {
+    let result = match operation {
+        BinaryOperation::Equals => left == right,
+        BinaryOperation::NotEquals => left != right,
+        BinaryOperation::LessThan => left < right,
+        BinaryOperation::GreaterThan => left > right,
+        BinaryOperation::LessThanEquals => left <= right,
+        BinaryOperation::GreaterThanEquals => left >= right,
+        _ => unreachable!(),
+    };
+
+    ConstantValue::Boolean(result)
+}
@@ -0,0 +1,359 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    error::{
+        warning::{Lint, LintLevel, LintLevels, Warning},
+        Error, Span,
+    },
+    interner::Interner,
+    parser::{
+        ast::Attribute,
+        expression::UnaryOperation,
+        typed_ast::{TypedParameter, TypedProgram},
+        typed_expression::{TypedExpression, TypedExpressionKind},
+    },
+    semantic_analyzer::types::Type,
+};
+
+use super::constant_evaluator::{ConstantEvaluator, ConstantValue};
+
+/// Struct that records how the variables of a single item are used.
+#[derive(Default)]
+struct Usage<'a> {
+    // The ids of every variable and parameter that is read.
+    reads: HashSet<usize>,
+    // The first `let` of every variable, in the order they appear.
+    definitions: Vec<(usize, Span<'a>)>,
+    // Every `let` of the item, which is used to find assignments to parameters.
+    assignments: Vec<(usize, Span<'a>)>,
+}
+
+/// Struct that finds code which is valid but most likely a mistake.
+///
+/// The linter runs after the type checker, so it only inspects valid programs.
+/// Like the code generator, the local variables of a function live in a single
+/// flat scope, so a variable is used if it is read anywhere in its function.
+/// The default values of parameters are not linted.
+pub struct Linter<'a, 'b> {
+    interner: &'b Interner<String>,
+    levels: &'b LintLevels,
+    evaluator: ConstantEvaluator<'a, 'b>,
+    // The ids of the constants, whose values are known while compiling.
+    constants: HashSet<usize>,
+    // The spans of the prototypes of the functions, which contain their default values.
+    prototypes: HashMap<usize, Span<'a>>,
+    // The ids of the parameters of the function being linted.
+    parameters: HashSet<usize>,
+    warnings: Vec<(Warning<'a>, LintLevel)>,
+    errors: Vec<Error<'a>>,
+}
+
+impl<'a, 'b> Linter<'a, 'b> {
+    pub fn new(
+        program: &'b TypedProgram<'a>,
+        interner: &'b Interner<String>,
+        levels: &'b LintLevels,
+    ) -> Self {
+        Self {
+            interner,
+            levels,
+            evaluator: ConstantEvaluator::new(
+                program
+                    .constants
+                    .iter()
+                    .map(|constant| (constant.name.1, &constant.expression))
+                    .collect(),
+                &program.functions,
+            ),
+            constants: program
+                .constants
+                .iter()
+                .map(|constant| constant.name.1)
+                .collect(),
+            prototypes: program
+                .functions
+                .iter()
+                .map(|function| (function.prototype.name, function.prototype.span))
+                .collect(),
+            parameters: HashSet::new(),
+            warnings: vec![],
+            errors: vec![],
+        }
+    }
+
+    /// Lints every item of the program. Returns the warnings together with the
+    /// level of their lint, or the errors of attributes that could not be resolved.
+    ///
+    /// # Arguments
+    /// * `program` - The `TypedProgram` to lint.
+    pub fn lint_program(
+        mut self,
+        program: &TypedProgram<'a>,
+    ) -> Result<Vec<(Warning<'a>, LintLevel)>, Vec<Error<'a>>> {
+        for extern_declaration in &program.extern_declarations {
+            self.item_levels(&extern_declaration.attributes);
+        }
+
+        for constant in &program.constants {
+            let mut levels = self.item_levels(&constant.attributes);
+            // Every condition in the value of a constant is known while compiling.
+            levels.set(Lint::ConstantCondition, LintLevel::Allow);
+            self.parameters.clear();
+            let mut usage = Usage::default();
+            self.lint_expression(&constant.expression, &levels, &mut usage);
+            self.lint_variables(&usage, &[], &levels);
+        }
+
+        for function in &program.functions {
+            let levels = self.item_levels(&function.attributes);
+            let parameters = &function.prototype.parameters;
+            self.parameters = parameters.iter().map(|parameter| parameter.name).collect();
+            let mut usage = Usage::default();
+            self.lint_expression(&function.body, &levels, &mut usage);
+            self.lint_variables(&usage, parameters, &levels);
+        }
+
+        if !self.errors.is_empty() {
+            Err(self.errors)
+        } else {
+            Ok(self.warnings)
+        }
+    }
+
+    /// Applies the `#allow` attributes of an item to the levels given to the linter.
+    ///
+    /// # Arguments
+    /// * `attributes` - The attributes of the item.
+    fn item_levels(&mut self, attributes: &[Attribute<'a>]) -> LintLevels {
+        let mut levels = self.levels.clone();
+        for attribute in attributes {
+            let (name_span, name) = attribute.name;
+            if self.interner.get(name) != "allow" {
+                self.errors.push(Error::UnknownAttribute(name_span));
+                continue;
+            }
+
+            for &(argument_span, argument) in &attribute.arguments {
+                match self.interner.get(argument).as_str() {
+                    "warnings" => levels.set_all(LintLevel::Allow),
+                    name => match Lint::from_name(name) {
+                        Some(lint) => levels.set(lint, LintLevel::Allow),
+                        None => self.errors.push(Error::UnknownLint(argument_span)),
+                    },
+                }
+            }
+        }
+
+        levels
+    }
+
+    /// Records the warning unless its lint is allowed.
+    ///
+    /// # Arguments
+    /// * `warning` - The warning to record.
+    /// * `levels` - The levels of the lints in the current item.
+    fn warn(&mut self, warning: Warning<'a>, levels: &LintLevels) {
+        let level = levels.get(warning.lint());
+        if level != LintLevel::Allow {
+            self.warnings.push((warning, level));
+        }
+    }
+
+    /// Lints an expression and records how it uses variables.
+    ///
+    /// # Arguments
+    /// * `expression` - The expression to lint.
+    /// * `levels` - The levels of the lints in the current item.
+    /// * `usage` - The usage of the variables in the current item.
+    fn lint_expression(
+        &mut self,
+        expression: &TypedExpression<'a>,
+        levels: &LintLevels,
+        usage: &mut Usage<'a>,
+    ) {
+        match &expression.1 {
+            TypedExpressionKind::Identifier(identifier) => {
+                usage.reads.insert(identifier.id);
+            }
+            TypedExpressionKind::Unary(unary) => {
+                self.lint_expression(&unary.expression, levels, usage)
+            }
+            TypedExpressionKind::Binary(binary) => {
+                self.lint_expression(&binary.left, levels, usage);
+                self.lint_expression(&binary.right, levels, usage);
+            }
+            TypedExpressionKind::If(typed_if) => {
+                self.lint_condition(&typed_if.condition, false, levels);
+                self.lint_expression(&typed_if.condition, levels, usage);
+                self.lint_expression(&typed_if.then_branch, levels, usage);
+                if let Some(ref else_branch) = typed_if.else_branch {
+                    self.lint_expression(else_branch, levels, usage);
+                }
+            }
+            TypedExpressionKind::Let(typed_let) => {
+                let (name_span, ref identifier) = typed_let.name;
+                if !usage.definitions.iter().any(|(id, _)| *id == identifier.id) {
+                    usage.definitions.push((identifier.id, name_span));
+                }
+
+                usage.assignments.push((identifier.id, name_span));
+                self.lint_expression(&typed_let.expression, levels, usage);
+            }
+            TypedExpressionKind::Store(store) => {
+                self.lint_expression(&store.pointer, levels, usage);
+                self.lint_expression(&store.expression, levels, usage);
+            }
+            TypedExpressionKind::Block(expressions) => {
+                let diverging = expressions
+                    .iter()
+                    .position(|expression| expression.1.get_type() == Type::Never);
+                if let Some(index) = diverging.filter(|index| index + 1 < expressions.len()) {
+                    let first = expressions[index + 1].0;
+                    let last = expressions[expressions.len() - 1].0;
+                    self.warn(
+                        Warning::UnreachableCode {
+                            span: first.combine(last),
+                            diverging_span: expressions[index].0,
+                        },
+                        levels,
+                    );
+                }
+
+                for expression in expressions {
+                    self.lint_expression(expression, levels, usage);
+                }
+            }
+            TypedExpressionKind::Application(application) => {
+                let prototype = self.prototypes.get(&application.function_name.1).copied();
+                for parameter in &application.parameters {
+                    // Default values are copied into every application, but they are
+                    // written in the prototype of the function, outside of this item.
+                    if !prototype.is_some_and(|prototype| prototype.contains(&parameter.0)) {
+                        self.lint_expression(parameter, levels, usage);
+                    }
+                }
+            }
+            TypedExpressionKind::While(typed_while) => {
+                self.lint_condition(&typed_while.condition, true, levels);
+                self.lint_expression(&typed_while.condition, levels, usage);
+                self.lint_expression(&typed_while.expression, levels, usage);
+            }
+            TypedExpressionKind::Return(Some(value)) => self.lint_expression(value, levels, usage),
+            TypedExpressionKind::Unsafe(inner) => self.lint_expression(inner, levels, usage),
+            TypedExpressionKind::Int(_)
+            | TypedExpressionKind::Float(_)
+            | TypedExpressionKind::Boolean(_)
+            | TypedExpressionKind::Char(_)
+            | TypedExpressionKind::Null
+            | TypedExpressionKind::Return(None)
+            | TypedExpressionKind::Error => {}
+        }
+    }
+
+    /// Reports the condition of an `if` or `while` expression if its value is
+    /// known while compiling. `while true` is not reported, since it is the
+    /// only way to write a loop that is left with `return`.
+    ///
+    /// # Arguments
+    /// * `condition` - The condition to inspect.
+    /// * `is_while` - Whether the condition belongs to a `while` expression.
+    /// * `levels` - The levels of the lints in the current item.
+    fn lint_condition(
+        &mut self,
+        condition: &TypedExpression<'a>,
+        is_while: bool,
+        levels: &LintLevels,
+    ) {
+        if !self.is_constant(condition) {
+            return;
+        }
+
+        if let Some(ConstantValue::Boolean(value)) = self.evaluator.evaluate_expression(condition) {
+            if !(is_while && value) {
+                self.warn(
+                    Warning::ConstantCondition {
+                        span: condition.0,
+                        value,
+                    },
+                    levels,
+                );
+            }
+        }
+    }
+
+    /// Determines whether an expression only consists of literals, constants and
+    /// operators. Applications are never constant, even if the function could be
+    /// evaluated, since their result may change when the function does.
+    ///
+    /// # Arguments
+    /// * `expression` - The expression to inspect.
+    fn is_constant(&self, expression: &TypedExpression<'a>) -> bool {
+        match &expression.1 {
+            TypedExpressionKind::Int(_)
+            | TypedExpressionKind::Float(_)
+            | TypedExpressionKind::Boolean(_)
+            | TypedExpressionKind::Char(_) => true,
+            TypedExpressionKind::Identifier(identifier) => {
+                self.constants.contains(&identifier.id) && !self.parameters.contains(&identifier.id)
+            }
+            TypedExpressionKind::Unary(unary) => {
+                !matches!(
+                    unary.operation,
+                    UnaryOperation::AddressOf | UnaryOperation::Dereference
+                ) && self.is_constant(&unary.expression)
+            }
+            TypedExpressionKind::Binary(binary) => {
+                self.is_constant(&binary.left) && self.is_constant(&binary.right)
+            }
+            _ => false,
+        }
+    }
+
+    /// Reports the parameters and variables that are never read, and the
+    /// `let` expressions that assign to a parameter.
+    ///
+    /// # Arguments
+    /// * `usage` - The usage of the variables in the item.
+    /// * `parameters` - The parameters of the item.
+    /// * `levels` - The levels of the lints in the item.
+    fn lint_variables(
+        &mut self,
+        usage: &Usage<'a>,
+        parameters: &[TypedParameter<'a>],
+        levels: &LintLevels,
+    ) {
+        for parameter in parameters {
+            if !usage.reads.contains(&parameter.name) && !self.is_ignored(parameter.name) {
+                self.warn(Warning::UnusedParameter(parameter.span), levels);
+            }
+        }
+
+        for &(id, span) in &usage.definitions {
+            if !self.parameters.contains(&id) && !usage.reads.contains(&id) && !self.is_ignored(id)
+            {
+                self.warn(Warning::UnusedVariable(span), levels);
+            }
+        }
+
+        for &(id, span) in &usage.assignments {
+            if let Some(parameter) = parameters.iter().find(|parameter| parameter.name == id) {
+                self.warn(
+                    Warning::ShadowedParameter {
+                        span,
+                        parameter_span: parameter.span,
+                    },
+                    levels,
+                );
+            }
+        }
+    }
+
+    /// Determines whether a variable is never reported as unused,
+    /// which is the case when its name starts with `_`.
+    ///
+    /// # Arguments
+    /// * `id` - The id of the name of the variable.
+    fn is_ignored(&self, id: usize) -> bool {
+        self.interner.get(id).starts_with('_')
+    }
+}
@@ -1,2 +1,4 @@
+pub mod constant_evaluator;
+pub mod lint;
 pub mod type_check;
 pub mod types;
@@ -1,24 +1,32 @@
 use crate::{
     environment::Environment,
-    error::{Error, Span},
-    function_table::FunctionTable,
+    error::{
+        fix::{Edit, Fix},
+        suggestion::Suggestion,
+        Error, Span,
+    },
+    function_table::{FunctionParameter, FunctionSignature, FunctionTable},
     parser::{
         ast::{ExternDeclaration, Function, Parameter, Program},
         expression::{
             Application, Binary, BinaryOperation, Expression, ExpressionKind, Identifier, If, Let,
-            Unary, UnaryOperation, While,
+            Store, Unary, UnaryOperation, While,
         },
         typed_ast::{
-            TypedExternDeclaration, TypedFunction, TypedParameter, TypedProgram, TypedPrototype,
+            TypedConstant, TypedExternDeclaration, TypedFunction, TypedParameter, TypedProgram,
+            TypedPrototype,
         },
         typed_expression::{
             TypedApplication, TypedBinary, TypedExpression, TypedExpressionKind, TypedIdentifier,
-            TypedIf, TypedLet, TypedUnary, TypedWhile,
+            TypedIf, TypedLet, TypedStore, TypedUnary, TypedWhile,
         },
     },
 };
 
-use super::types::Type;
+use super::{
+    constant_evaluator::{ConstantEvaluator, ConstantValue},
+    types::{PointeeType, Type},
+};
 
 pub trait TypeCheck<'a> {
     type Output;
@@ -27,7 +35,7 @@ pub trait TypeCheck<'a> {
     fn check(
         self,
         env: &mut Environment<Type>,
-        function_table: &mut FunctionTable,
+        function_table: &mut FunctionTable<'a>,
     ) -> Result<Self::Output, Self::Error>;
 }
 
@@ -39,7 +47,7 @@ pub trait TypeCheckSpan<'a> {
         self,
         span: Span<'a>,
         env: &mut Environment<Type>,
-        function_table: &mut FunctionTable,
+        function_table: &mut FunctionTable<'a>,
     ) -> Result<Self::Output, Self::Error>;
 }
 
@@ -50,7 +58,7 @@ pub trait TypeCheckFunction<'a> {
     fn check(
         self,
         env: &mut Environment<Type>,
-        function_table: &mut FunctionTable,
+        function_table: &mut FunctionTable<'a>,
         current_function: usize,
     ) -> Result<Self::Output, Self::Error>;
 }
@@ -63,26 +71,26 @@ pub trait TypeCheckSpanFunction<'a> {
         self,
         span: Span<'a>,
         env: &mut Environment<Type>,
-        function_table: &mut FunctionTable,
+        function_table: &mut FunctionTable<'a>,
         current_function: usize,
     ) -> Result<Self::Output, Self::Error>;
 }
 
-impl<'a, T: TypeCheck<'a>> TypeCheck<'a> for Vec<T> {
+impl<'a, T: TypeCheck<'a, Error = Box<Error<'a>>>> TypeCheck<'a> for Vec<T> {
     type Output = Vec<T::Output>;
-    type Error = Vec<T::Error>;
+    type Error = Vec<Error<'a>>;
 
     fn check(
         self,
         env: &mut Environment<Type>,
-        function_table: &mut FunctionTable,
+        function_table: &mut FunctionTable<'a>,
     ) -> Result<Self::Output, Self::Error> {
         let mut results = vec![];
         let mut errors = vec![];
         for value in self {
             match value.check(env, function_table) {
                 Ok(result) => results.push(result),
-                Err(error) => errors.push(error),
+                Err(error) => errors.push(*error),
             }
         }
 
@@ -94,14 +102,14 @@ impl<'a, T: TypeCheck<'a>> TypeCheck<'a> for Vec<T> {
     }
 }
 
-impl<'a, T: TypeCheckFunction<'a>> TypeCheckFunction<'a> for Vec<T> {
+impl<'a, T: TypeCheckFunction<'a, Error = Box<Error<'a>>>> TypeCheckFunction<'a> for Vec<T> {
     type Output = Vec<T::Output>;
-    type Error = Vec<T::Error>;
+    type Error = Vec<Error<'a>>;
 
     fn check(
         self,
         env: &mut Environment<Type>,
-        function_table: &mut FunctionTable,
+        function_table: &mut FunctionTable<'a>,
         current_function: usize,
     ) -> Result<Self::Output, Self::Error> {
         let mut results = vec![];
@@ -109,7 +117,7 @@ impl<'a, T: TypeCheckFunction<'a>> TypeCheckFunction<'a> for Vec<T> {
         for value in self {
             match value.check(env, function_table, current_function) {
                 Ok(result) => results.push(result),
-                Err(error) => errors.push(error),
+                Err(error) => errors.push(*error),
             }
         }
 
@@ -128,42 +136,181 @@ impl<'a> TypeCheck<'a> for Program<'a> {
     fn check(
         self,
         env: &mut Environment<Type>,
-        function_table: &mut FunctionTable,
+        function_table: &mut FunctionTable<'a>,
     ) -> Result<Self::Output, Self::Error> {
-        let extern_declarations = self.extern_declarations.check(env, function_table)?;
-        for function in &self.functions {
-            let function_name = function.prototype.name;
-            let function_return_type = function.prototype.return_type.0;
-            let parameter_types = function
-                .prototype
-                .parameters
-                .iter()
-                .map(|parameter| parameter.ty)
-                .collect::<Vec<_>>();
-            env.define(function_name, function_return_type);
-            function_table.add_function_definition(function_name, parameter_types);
+        check_program(
+            self,
+            env,
+            function_table,
+            |function, env, function_table| function.check(env, function_table),
+        )
+    }
+}
+
+/// Checks the types of a program. The functions are checked by a callback once
+/// every function, constant and default value is known, which allows a function
+/// that did not change to be taken from a cache instead of being checked again.
+///
+/// # Arguments
+/// * `program` - The `Program` to check.
+/// * `env` - The `Environment` that stores the types of the variables.
+/// * `function_table` - The `FunctionTable` that stores the known functions.
+/// * `check_function` - The callback that checks a single `Function`.
+pub fn check_program<'a>(
+    program: Program<'a>,
+    env: &mut Environment<Type>,
+    function_table: &mut FunctionTable<'a>,
+    mut check_function: impl FnMut(
+        Function<'a>,
+        &mut Environment<Type>,
+        &mut FunctionTable<'a>,
+    ) -> Result<TypedFunction<'a>, Box<Error<'a>>>,
+) -> Result<TypedProgram<'a>, Vec<Error<'a>>> {
+    let extern_declarations = program.extern_declarations.check(env, function_table)?;
+    for function in &program.functions {
+        let function_name = function.prototype.name;
+        let function_return_type = function.prototype.return_type.0;
+        let parameters = function
+            .prototype
+            .parameters
+            .iter()
+            .map(|parameter| {
+                FunctionParameter::new(parameter.span, Some(parameter.name), parameter.ty)
+            })
+            .collect::<Vec<_>>();
+        env.define(function_name, function_return_type);
+        function_table.add_function_definition(function_name, parameters, false);
+    }
+
+    // The constants are defined before any value is checked, so
+    // they can be referenced regardless of their order in the file.
+    let mut errors = vec![];
+    for constant in &program.constants {
+        let (name_span, constant_name) = constant.name;
+        if !matches!(
+            constant.ty.0,
+            Type::Int | Type::Float | Type::Boolean | Type::Char
+        ) {
+            errors.push(Error::IllegalType(constant.ty.1));
         }
 
-        Ok(TypedProgram {
-            extern_declarations,
-            functions: self.functions.check(env, function_table)?,
-        })
+        // The constant is defined even if its type is illegal, so that the
+        // items which reference it do not report that it is unknown.
+        env.define(constant_name, constant.ty.0);
+        function_table.add_constant(constant_name, name_span);
     }
+
+    let mut functions = program.functions;
+    for function in &mut functions {
+        let function_name = function.prototype.name;
+        for (index, parameter) in function.prototype.parameters.iter_mut().enumerate() {
+            if let Some(default) = parameter.default.take() {
+                let default_span = default.0;
+                let typed_default =
+                    match check_value(default, parameter.ty, env, function_table, function_name) {
+                        Ok(typed_default) => typed_default,
+                        Err(error) => {
+                            // The applications that leave out the parameter are still
+                            // checked, so the default gets a placeholder value.
+                            errors.push(*error);
+                            (default_span, TypedExpressionKind::Error)
+                        }
+                    };
+                function_table.set_parameter_default(function_name, index, typed_default);
+            }
+        }
+    }
+
+    let mut checked_constants = vec![];
+    for constant in program.constants {
+        let (_, constant_name) = constant.name;
+        let ty = constant.ty.0;
+        match check_value(constant.value, ty, env, function_table, constant_name) {
+            Ok(expression) => checked_constants.push((
+                constant.span,
+                constant.name,
+                ty,
+                expression,
+                constant.attributes,
+            )),
+            Err(error) => errors.push(*error),
+        }
+    }
+
+    // The functions are checked even if a constant or a default value is wrong,
+    // since their types are known from their declarations.
+    let mut typed_functions = vec![];
+    for function in functions {
+        // A scope is not removed when an error is found within it, so the
+        // scopes are restored to keep the names of one function out of the next.
+        let depth = env.depth();
+        match check_function(function, env, function_table) {
+            Ok(typed_function) => typed_functions.push(typed_function),
+            Err(error) => errors.push(*error),
+        }
+        env.truncate(depth);
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    let functions = typed_functions;
+    let mut evaluator = ConstantEvaluator::new(
+        checked_constants
+            .iter()
+            .map(|(_, (_, name), _, expression, _)| (*name, expression))
+            .collect(),
+        &functions,
+    );
+
+    // A constant whose value depends on an expression that could not be parsed
+    // gets a placeholder value, like the expression itself. Its syntax error was
+    // already reported by the parser, and programs with syntax errors are never
+    // compiled, so an empty list of errors is never returned.
+    let mut constant_values = vec![];
+    for (_, (name_span, name), _, _, _) in &checked_constants {
+        match evaluator.evaluate_constant(*name, *name_span) {
+            Ok(value) => constant_values.push(value.unwrap_or(ConstantValue::Void)),
+            Err(error) => errors.push(*error),
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    let constants = checked_constants
+        .into_iter()
+        .zip(constant_values)
+        .map(
+            |((span, name, ty, expression, attributes), value)| TypedConstant {
+                span,
+                name,
+                ty,
+                expression,
+                value,
+                attributes,
+            },
+        )
+        .collect();
+
+    Ok(TypedProgram::new(extern_declarations, constants, functions))
 }
 
 impl<'a> TypeCheck<'a> for ExternDeclaration<'a> {
     type Output = TypedExternDeclaration<'a>;
-    type Error = Error<'a>;
+    type Error = Box<Error<'a>>;
 
     fn check(
         self,
         env: &mut Environment<Type>,
-        function_table: &mut FunctionTable,
+        function_table: &mut FunctionTable<'a>,
     ) -> Result<Self::Output, Self::Error> {
         let mut parameters = vec![];
         for parameter in self.parameters {
             if parameter.0 == Type::Void {
-                return Err(Error::IllegalType(parameter.1));
+                return Err(Box::new(Error::IllegalType(parameter.1)));
             } else {
                 parameters.push((parameter.0, parameter.1));
             }
@@ -172,32 +319,38 @@ impl<'a> TypeCheck<'a> for ExternDeclaration<'a> {
         env.define(self.name, self.return_type.0);
         function_table.add_function_definition(
             self.name,
-            parameters.iter().map(|parameter| parameter.0).collect(),
+            parameters
+                .iter()
+                .map(|parameter| FunctionParameter::new(parameter.1, None, parameter.0))
+                .collect(),
+            self.variadic,
         );
 
         Ok(TypedExternDeclaration {
             span: self.span,
             name: self.name,
             parameters,
+            variadic: self.variadic,
             return_type: self.return_type,
+            attributes: self.attributes,
         })
     }
 }
 
 impl<'a> TypeCheck<'a> for Function<'a> {
     type Output = TypedFunction<'a>;
-    type Error = Error<'a>;
+    type Error = Box<Error<'a>>;
 
     fn check(
         self,
         env: &mut Environment<Type>,
-        function_table: &mut FunctionTable,
+        function_table: &mut FunctionTable<'a>,
     ) -> Result<Self::Output, Self::Error> {
         env.new_scope();
         let mut typed_params = vec![];
         for parameter in self.prototype.parameters {
             if parameter.ty == Type::Void {
-                return Err(Error::IllegalType(parameter.span));
+                return Err(Box::new(Error::IllegalType(parameter.span)));
             } else {
                 env.define(parameter.name, parameter.ty);
                 typed_params.push(TypedParameter::new(
@@ -210,12 +363,13 @@ impl<'a> TypeCheck<'a> for Function<'a> {
 
         let typed_body = self.body.check(env, function_table, self.prototype.name)?;
         let return_type = get_type(&typed_body.1);
-        if self.prototype.return_type.0 != return_type {
-            return Err(Error::TypeMismatch {
+        if !return_type.coerces_to(self.prototype.return_type.0) {
+            return Err(Box::new(Error::TypeMismatch {
                 span: typed_body.0,
                 expected_type: self.prototype.return_type.0,
                 actual_type: return_type,
-            });
+                fix: float_literal_fix(&typed_body, self.prototype.return_type.0),
+            }));
         }
 
         let typed_function = TypedFunction::new(
@@ -223,9 +377,11 @@ impl<'a> TypeCheck<'a> for Function<'a> {
                 self.prototype.span,
                 self.prototype.name,
                 typed_params,
-                return_type,
+                self.prototype.return_type.0,
+                self.prototype.export,
             ),
             typed_body,
+            self.attributes,
         );
         env.remove_top_scope();
         Ok(typed_function)
@@ -234,12 +390,12 @@ impl<'a> TypeCheck<'a> for Function<'a> {
 
 impl<'a> TypeCheck<'a> for Parameter<'a> {
     type Output = TypedParameter<'a>;
-    type Error = Error<'a>;
+    type Error = Box<Error<'a>>;
 
     fn check(
         self,
         _: &mut Environment<Type>,
-        _: &mut FunctionTable,
+        _: &mut FunctionTable<'a>,
     ) -> Result<Self::Output, Self::Error> {
         Ok(TypedParameter::new(self.span, self.ty, self.name))
     }
@@ -247,12 +403,12 @@ impl<'a> TypeCheck<'a> for Parameter<'a> {
 
 impl<'a> TypeCheckFunction<'a> for Expression<'a> {
     type Output = TypedExpression<'a>;
-    type Error = Error<'a>;
+    type Error = Box<Error<'a>>;
 
     fn check(
         self,
         env: &mut Environment<Type>,
-        function_table: &mut FunctionTable,
+        function_table: &mut FunctionTable<'a>,
         current_function: usize,
     ) -> Result<Self::Output, Self::Error> {
         match self.1 {
@@ -260,6 +416,10 @@ impl<'a> TypeCheckFunction<'a> for Expression<'a> {
             ExpressionKind::Float(value) => Ok((self.0, TypedExpressionKind::Float(value))),
             ExpressionKind::Boolean(value) => Ok((self.0, TypedExpressionKind::Boolean(value))),
             ExpressionKind::Char(value) => Ok((self.0, TypedExpressionKind::Char(value))),
+            ExpressionKind::Null if !env.is_unsafe() => {
+                Err(Box::new(Error::UnsafeOperation(self.0)))
+            }
+            ExpressionKind::Null => Ok((self.0, TypedExpressionKind::Null)),
             ExpressionKind::Identifier(inner) => inner.check_span(self.0, env, function_table),
             ExpressionKind::Unary(inner) => {
                 inner.check_span(self.0, env, function_table, current_function)
@@ -273,6 +433,9 @@ impl<'a> TypeCheckFunction<'a> for Expression<'a> {
             ExpressionKind::Let(inner) => {
                 inner.check_span(self.0, env, function_table, current_function)
             }
+            ExpressionKind::Store(inner) => {
+                inner.check_span(self.0, env, function_table, current_function)
+            }
             ExpressionKind::Block(expressions) => {
                 env.new_scope();
                 match expressions.check(env, function_table, current_function) {
@@ -280,7 +443,7 @@ impl<'a> TypeCheckFunction<'a> for Expression<'a> {
                         env.remove_top_scope();
                         Ok((self.0, TypedExpressionKind::Block(typed_expressions)))
                     }
-                    Err(errors) => Err(errors.into_iter().next().unwrap()),
+                    Err(errors) => Err(Box::new(errors.into_iter().next().unwrap())),
                 }
             }
             ExpressionKind::Application(inner) => {
@@ -294,12 +457,13 @@ impl<'a> TypeCheckFunction<'a> for Expression<'a> {
                 let typed_expression = expression.check(env, function_table, current_function)?;
                 let expression_type = get_type(&typed_expression.1);
                 let function_return_type = env.get(current_function).unwrap();
-                if function_return_type != expression_type {
-                    Err(Error::TypeMismatch {
+                if !expression_type.coerces_to(function_return_type) {
+                    Err(Box::new(Error::TypeMismatch {
                         span: typed_expression.0,
                         expected_type: function_return_type,
                         actual_type: expression_type,
-                    })
+                        fix: float_literal_fix(&typed_expression, function_return_type),
+                    }))
                 } else {
                     Ok((
                         self.0,
@@ -307,51 +471,87 @@ impl<'a> TypeCheckFunction<'a> for Expression<'a> {
                     ))
                 }
             }
+            ExpressionKind::Unsafe(expression) => {
+                env.new_unsafe_scope();
+                let typed_expression = expression.check(env, function_table, current_function);
+                env.remove_top_scope();
+                Ok((
+                    self.0,
+                    TypedExpressionKind::Unsafe(Box::new(typed_expression?)),
+                ))
+            }
+            ExpressionKind::Error => Ok((self.0, TypedExpressionKind::Error)),
         }
     }
 }
 
 impl<'a> TypeCheckSpan<'a> for Identifier {
     type Output = TypedExpression<'a>;
-    type Error = Error<'a>;
+    type Error = Box<Error<'a>>;
 
     fn check_span(
         self,
         span: Span<'a>,
         env: &mut Environment<Type>,
-        _: &mut FunctionTable,
+        _: &mut FunctionTable<'a>,
     ) -> Result<Self::Output, Self::Error> {
         match env.get(self.0) {
             Some(ty) => Ok((
                 span,
                 TypedExpressionKind::Identifier(TypedIdentifier { id: self.0, ty }),
             )),
-            None => Err(Error::UndefinedVariable(span)),
+            None => Err(Box::new(Error::UndefinedVariable {
+                span,
+                suggestion: Suggestion::Unresolved {
+                    name: self.0,
+                    candidates: env.names(),
+                },
+            })),
         }
     }
 }
 
 impl<'a> TypeCheckSpanFunction<'a> for Unary<'a> {
     type Output = TypedExpression<'a>;
-    type Error = Error<'a>;
+    type Error = Box<Error<'a>>;
 
     fn check_span(
         self,
         span: Span<'a>,
         env: &mut Environment<Type>,
-        function_table: &mut FunctionTable,
+        function_table: &mut FunctionTable<'a>,
         current_function: usize,
     ) -> Result<Self::Output, Self::Error> {
+        if let UnaryOperation::AddressOf | UnaryOperation::Dereference = self.operation {
+            if !env.is_unsafe() {
+                return Err(Box::new(Error::UnsafeOperation(span)));
+            }
+        }
+
+        // Only variables live in memory, so they are the only expressions
+        // whose address can be taken.
+        if let UnaryOperation::AddressOf = self.operation {
+            if !matches!(self.expression.1, ExpressionKind::Identifier(_)) {
+                return Err(Box::new(Error::IllegalAddressOf(self.expression.0)));
+            }
+        }
+
         let typed_expression = self
             .expression
             .check(env, function_table, current_function)?;
         let expression_type = get_type(&typed_expression.1);
+        if expression_type == Type::Never {
+            return Ok((span, TypedExpressionKind::Error));
+        }
+
         let operation_ty = match (self.operation, expression_type) {
             (UnaryOperation::Plus, Type::Int) => Some(Type::Int),
             (UnaryOperation::Plus, Type::Float) => Some(Type::Float),
             (UnaryOperation::Minus, Type::Int) => Some(Type::Int),
             (UnaryOperation::Minus, Type::Float) => Some(Type::Float),
             (UnaryOperation::Not, Type::Boolean) => Some(Type::Boolean),
+            (UnaryOperation::AddressOf, ty) => ty.pointer_to(),
+            (UnaryOperation::Dereference, ty) => ty.pointee(),
             _ => None,
         };
 
@@ -365,10 +565,10 @@ impl<'a> TypeCheckSpanFunction<'a> for Unary<'a> {
                 }),
             ))
         } else {
-            let error = Error::UnsupportedOperation {
+            let error = Box::new(Error::UnsupportedOperation {
                 operation_span: span,
                 operands: vec![(typed_expression.0, expression_type)],
-            };
+            });
 
             Err(error)
         }
@@ -377,19 +577,23 @@ impl<'a> TypeCheckSpanFunction<'a> for Unary<'a> {
 
 impl<'a> TypeCheckSpanFunction<'a> for Binary<'a> {
     type Output = TypedExpression<'a>;
-    type Error = Error<'a>;
+    type Error = Box<Error<'a>>;
 
     fn check_span(
         self,
         span: Span<'a>,
         env: &mut Environment<Type>,
-        function_table: &mut FunctionTable,
+        function_table: &mut FunctionTable<'a>,
         current_function: usize,
     ) -> Result<Self::Output, Self::Error> {
         let typed_left = self.left.check(env, function_table, current_function)?;
         let typed_right = self.right.check(env, function_table, current_function)?;
         let left_type = get_type(&typed_left.1);
         let right_type = get_type(&typed_right.1);
+        if left_type == Type::Never || right_type == Type::Never {
+            return Ok((span, TypedExpressionKind::Error));
+        }
+
         let result_type = match (self.operation, left_type, right_type) {
             (BinaryOperation::Plus, Type::Int, Type::Int) => Some(Type::Int),
             (BinaryOperation::Plus, Type::Float, Type::Float) => Some(Type::Float),
@@ -408,6 +612,10 @@ impl<'a> TypeCheckSpanFunction<'a> for Binary<'a> {
             | (BinaryOperation::Equals, Type::Float, Type::Float)
             | (BinaryOperation::Equals, Type::Char, Type::Char)
             | (BinaryOperation::Equals, Type::Boolean, Type::Boolean)
+            | (BinaryOperation::NotEquals, Type::Int, Type::Int)
+            | (BinaryOperation::NotEquals, Type::Float, Type::Float)
+            | (BinaryOperation::NotEquals, Type::Char, Type::Char)
+            | (BinaryOperation::NotEquals, Type::Boolean, Type::Boolean)
             | (BinaryOperation::LessThan, Type::Int, Type::Int)
             | (BinaryOperation::LessThan, Type::Float, Type::Float)
             | (BinaryOperation::LessThan, Type::Char, Type::Char)
@@ -419,9 +627,25 @@ impl<'a> TypeCheckSpanFunction<'a> for Binary<'a> {
             | (BinaryOperation::GreaterThanEquals, Type::Float, Type::Float)
             | (BinaryOperation::Or, Type::Boolean, Type::Boolean)
             | (BinaryOperation::And, Type::Boolean, Type::Boolean) => Some(Type::Boolean),
+
+            // Pointers can be compared with pointers of the same type or with `null`.
+            (BinaryOperation::Equals, left @ Type::Pointer(..), right @ Type::Pointer(..))
+            | (BinaryOperation::NotEquals, left @ Type::Pointer(..), right @ Type::Pointer(..))
+                if left.coerces_to(right) || right.coerces_to(left) =>
+            {
+                Some(Type::Boolean)
+            }
             _ => None,
         };
 
+        // Comparing pointers is a pointer operation, so it is only allowed in
+        // `unsafe` blocks like `&`, `*` and `null`.
+        if let (Some(_), Type::Pointer(..)) = (result_type, left_type) {
+            if !env.is_unsafe() {
+                return Err(Box::new(Error::UnsafeOperation(span)));
+            }
+        }
+
         if let Some(result_type) = result_type {
             Ok((
                 span,
@@ -433,10 +657,10 @@ impl<'a> TypeCheckSpanFunction<'a> for Binary<'a> {
                 }),
             ))
         } else {
-            let error = Error::UnsupportedOperation {
+            let error = Box::new(Error::UnsupportedOperation {
                 operation_span: span,
                 operands: vec![(typed_left.0, left_type), (typed_right.0, right_type)],
-            };
+            });
 
             Err(error)
         }
@@ -445,25 +669,26 @@ impl<'a> TypeCheckSpanFunction<'a> for Binary<'a> {
 
 impl<'a> TypeCheckSpanFunction<'a> for If<'a> {
     type Output = TypedExpression<'a>;
-    type Error = Error<'a>;
+    type Error = Box<Error<'a>>;
 
     fn check_span(
         self,
         span: Span<'a>,
         env: &mut Environment<Type>,
-        function_table: &mut FunctionTable,
+        function_table: &mut FunctionTable<'a>,
         current_function: usize,
     ) -> Result<Self::Output, Self::Error> {
         let typed_condition = self
             .condition
             .check(env, function_table, current_function)?;
         let condition_type = get_type(&typed_condition.1);
-        if condition_type != Type::Boolean {
-            return Err(Error::TypeMismatch {
+        if !condition_type.coerces_to(Type::Boolean) {
+            return Err(Box::new(Error::TypeMismatch {
                 span: typed_condition.0,
                 expected_type: Type::Boolean,
                 actual_type: condition_type,
-            });
+                fix: None,
+            }));
         }
 
         let typed_then = self
@@ -474,23 +699,32 @@ impl<'a> TypeCheckSpanFunction<'a> for If<'a> {
             let typed_else = else_branch.check(env, function_table, current_function)?;
             let else_type = get_type(&typed_else.1);
 
-            if then_type == else_type {
+            if then_type.coerces_to(else_type) || else_type.coerces_to(then_type) {
+                // A branch that could not be parsed takes the type of the other branch.
+                let ty = if then_type == Type::Never {
+                    else_type
+                } else {
+                    then_type
+                };
+
                 Ok((
                     span,
                     TypedExpressionKind::If(TypedIf {
                         condition: Box::new(typed_condition),
                         then_branch: Box::new(typed_then),
                         else_branch: Some(Box::new(typed_else)),
-                        ty: then_type,
+                        ty,
                     }),
                 ))
             } else {
-                Err(Error::ConflictingType {
+                Err(Box::new(Error::ConflictingType {
                     first_span: typed_then.0,
                     first_type: then_type,
                     second_span: typed_else.0,
                     second_type: else_type,
-                })
+                    fix: float_literal_fix(&typed_then, else_type)
+                        .or_else(|| float_literal_fix(&typed_else, then_type)),
+                }))
             }
         } else {
             Ok((
@@ -508,39 +742,57 @@ impl<'a> TypeCheckSpanFunction<'a> for If<'a> {
 
 impl<'a> TypeCheckSpanFunction<'a> for Let<'a> {
     type Output = TypedExpression<'a>;
-    type Error = Error<'a>;
+    type Error = Box<Error<'a>>;
 
     fn check_span(
         self,
         span: Span<'a>,
         env: &mut Environment<Type>,
-        function_table: &mut FunctionTable,
+        function_table: &mut FunctionTable<'a>,
         current_function: usize,
     ) -> Result<Self::Output, Self::Error> {
+        if let Some(constant_span) = function_table.get_constant(self.name.1 .0) {
+            return Err(Box::new(Error::AssignmentToConstant {
+                span: self.name.0,
+                constant_span,
+            }));
+        }
+
         let typed_expression = self
             .expression
             .check(env, function_table, current_function)?;
         let expression_type = get_type(&typed_expression.1);
         if let Some(given_type) = self.given_type {
-            if expression_type != given_type {
-                return Err(Error::ConflictingType {
+            if !expression_type.coerces_to(given_type) {
+                return Err(Box::new(Error::ConflictingType {
                     first_span: self.name.0,
                     first_type: given_type,
                     second_span: typed_expression.0,
                     second_type: expression_type,
-                });
+                    fix: float_literal_fix(&typed_expression, given_type),
+                }));
             }
         } else if let Some(previously_defined_type) = env.get(self.name.1 .0) {
-            if expression_type != previously_defined_type {
-                return Err(Error::ConflictingPreviousType {
+            if !expression_type.coerces_to(previously_defined_type)
+                && previously_defined_type != Type::Never
+            {
+                return Err(Box::new(Error::ConflictingPreviousType {
                     name_span: self.name.0,
                     previous_type: previously_defined_type,
                     second_span: typed_expression.0,
                     second_type: expression_type,
-                });
+                }));
             }
         }
 
+        let expression_type = match (self.given_type, env.get(self.name.1 .0)) {
+            (Some(given_type), _) => given_type,
+            // A value that could not be parsed keeps the previous type of the variable.
+            (None, Some(previously_defined_type)) if expression_type == Type::Never => {
+                previously_defined_type
+            }
+            (None, _) => expression_type,
+        };
         let (identifier_span, Identifier(identifier_id)) = self.name;
         let typed_name = (
             identifier_span,
@@ -563,41 +815,101 @@ impl<'a> TypeCheckSpanFunction<'a> for Let<'a> {
     }
 }
 
-impl<'a> TypeCheckSpanFunction<'a> for Application<'a> {
+impl<'a> TypeCheckSpanFunction<'a> for Store<'a> {
     type Output = TypedExpression<'a>;
-    type Error = Error<'a>;
+    type Error = Box<Error<'a>>;
 
     fn check_span(
         self,
         span: Span<'a>,
         env: &mut Environment<Type>,
-        function_table: &mut FunctionTable,
+        function_table: &mut FunctionTable<'a>,
         current_function: usize,
     ) -> Result<Self::Output, Self::Error> {
-        let mut parameters = Vec::new();
-        for parameter in self.parameters {
-            let typed_value = parameter.check(env, function_table, current_function)?;
-            parameters.push(typed_value);
+        if !env.is_unsafe() {
+            return Err(Box::new(Error::UnsafeOperation(span)));
         }
 
-        let (function_span, Identifier(function_name)) = self.function_name;
-        let defined_types = function_table.get_function_definition(function_name, function_span)?;
-        if parameters.len() != defined_types.len() {
-            return Err(Error::ParameterMismatch {
-                span,
-                expected_parameter_count: defined_types.len(),
-                actual_parameter_count: parameters.len(),
-            });
+        let typed_pointer = self.pointer.check(env, function_table, current_function)?;
+        let typed_expression = self
+            .expression
+            .check(env, function_table, current_function)?;
+        let pointer_type = get_type(&typed_pointer.1);
+        let expression_type = get_type(&typed_expression.1);
+        if pointer_type == Type::Never || expression_type == Type::Never {
+            return Ok((span, TypedExpressionKind::Error));
+        }
+
+        let pointee_type = match pointer_type.pointee() {
+            Some(pointee_type) => pointee_type,
+            None => {
+                return Err(Box::new(Error::UnsupportedOperation {
+                    operation_span: span,
+                    operands: vec![(typed_pointer.0, pointer_type)],
+                }))
+            }
+        };
+        if !expression_type.coerces_to(pointee_type) {
+            return Err(Box::new(Error::TypeMismatch {
+                span: typed_expression.0,
+                expected_type: pointee_type,
+                actual_type: expression_type,
+                fix: float_literal_fix(&typed_expression, pointee_type),
+            }));
         }
 
-        for (&defined_parameter_type, actual_parameter) in defined_types.iter().zip(&parameters) {
+        Ok((
+            span,
+            TypedExpressionKind::Store(TypedStore {
+                pointer: Box::new(typed_pointer),
+                expression: Box::new(typed_expression),
+                ty: pointee_type,
+            }),
+        ))
+    }
+}
+
+impl<'a> TypeCheckSpanFunction<'a> for Application<'a> {
+    type Output = TypedExpression<'a>;
+    type Error = Box<Error<'a>>;
+
+    fn check_span(
+        self,
+        span: Span<'a>,
+        env: &mut Environment<Type>,
+        function_table: &mut FunctionTable<'a>,
+        current_function: usize,
+    ) -> Result<Self::Output, Self::Error> {
+        let mut arguments = Vec::new();
+        for argument in self.arguments {
+            let typed_value = argument
+                .value
+                .check(env, function_table, current_function)?;
+            arguments.push((argument.name, typed_value));
+        }
+
+        let (function_span, Identifier(function_name)) = self.function_name;
+        let signature = function_table.get_function_definition(function_name, function_span)?;
+        let (parameters, evaluation_order) =
+            order_arguments(span, function_span, signature, arguments)?;
+        for (defined_parameter, actual_parameter) in signature.parameters.iter().zip(&parameters) {
             let actual_parameter_type = get_type(&actual_parameter.1);
-            if defined_parameter_type != actual_parameter_type {
-                return Err(Error::TypeMismatch {
+            if !actual_parameter_type.coerces_to(defined_parameter.ty) {
+                return Err(Box::new(Error::TypeMismatch {
                     span: actual_parameter.0,
-                    expected_type: defined_parameter_type,
+                    expected_type: defined_parameter.ty,
                     actual_type: actual_parameter_type,
-                });
+                    fix: float_literal_fix(actual_parameter, defined_parameter.ty),
+                }));
+            }
+        }
+
+        // The variadic part of the arguments is not constrained by the declaration,
+        // but it must still produce a value that can be passed to the function.
+        for variadic_parameter in parameters.iter().skip(signature.parameters.len()) {
+            let variadic_parameter_type = get_type(&variadic_parameter.1);
+            if variadic_parameter_type == Type::Void {
+                return Err(Box::new(Error::IllegalType(variadic_parameter.0)));
             }
         }
 
@@ -607,6 +919,7 @@ impl<'a> TypeCheckSpanFunction<'a> for Application<'a> {
             TypedExpressionKind::Application(TypedApplication {
                 function_name: (function_span, function_name),
                 parameters,
+                evaluation_order,
                 ty: return_type,
             }),
         ))
@@ -615,25 +928,26 @@ impl<'a> TypeCheckSpanFunction<'a> for Application<'a> {
 
 impl<'a> TypeCheckSpanFunction<'a> for While<'a> {
     type Output = TypedExpression<'a>;
-    type Error = Error<'a>;
+    type Error = Box<Error<'a>>;
 
     fn check_span(
         self,
         span: Span<'a>,
         env: &mut Environment<Type>,
-        function_table: &mut FunctionTable,
+        function_table: &mut FunctionTable<'a>,
         current_function: usize,
     ) -> Result<Self::Output, Self::Error> {
         let typed_condition = self
             .condition
             .check(env, function_table, current_function)?;
         let condition_type = get_type(&typed_condition.1);
-        if condition_type != Type::Boolean {
-            return Err(Error::TypeMismatch {
+        if !condition_type.coerces_to(Type::Boolean) {
+            return Err(Box::new(Error::TypeMismatch {
                 span: typed_condition.0,
                 expected_type: Type::Boolean,
                 actual_type: condition_type,
-            });
+                fix: None,
+            }));
         }
 
         let typed_expression = self
@@ -649,17 +963,212 @@ impl<'a> TypeCheckSpanFunction<'a> for While<'a> {
     }
 }
 
+/// Type checks a value that is declared outside of a function body, such as
+/// the default value of a parameter or the value of a constant. These values are
+/// checked in a fresh scope, so they can not refer to the parameters of a function.
+///
+/// # Arguments
+/// * `value` - The value to check.
+/// * `expected_type` - The declared `Type` of the value.
+/// * `current_function` - The id of the function or constant that declares the value.
+fn check_value<'a>(
+    value: Expression<'a>,
+    expected_type: Type,
+    env: &mut Environment<Type>,
+    function_table: &mut FunctionTable<'a>,
+    current_function: usize,
+) -> Result<TypedExpression<'a>, Box<Error<'a>>> {
+    env.new_scope();
+    let typed_value = value.check(env, function_table, current_function);
+    env.remove_top_scope();
+    let typed_value = typed_value?;
+    let value_type = get_type(&typed_value.1);
+    if !value_type.coerces_to(expected_type) {
+        return Err(Box::new(Error::TypeMismatch {
+            span: typed_value.0,
+            expected_type,
+            actual_type: value_type,
+            fix: float_literal_fix(&typed_value, expected_type),
+        }));
+    }
+
+    Ok(typed_value)
+}
+
+/// Matches the arguments of an application with the parameters of the function.
+/// Positional arguments are assigned in order, named arguments are assigned by
+/// name and the remaining parameters are filled with their default values.
+/// The returned arguments are in the same order as the parameters, followed by
+/// any extra positional arguments given to a variadic function. They are returned
+/// with the order they are evaluated in, so that the side effects of the arguments
+/// happen in the order they were written, even if they are named out of order.
+///
+/// # Arguments
+/// * `span` - The `Span` of the application.
+/// * `function_span` - The `Span` of the name of the function.
+/// * `signature` - The signature of the function being applied.
+/// * `arguments` - The typed arguments with their optional names.
+fn order_arguments<'a>(
+    span: Span<'a>,
+    function_span: Span<'a>,
+    signature: &FunctionSignature<'a>,
+    arguments: Vec<(Option<(Span<'a>, Identifier)>, TypedExpression<'a>)>,
+) -> Result<(Vec<TypedExpression<'a>>, Vec<usize>), Box<Error<'a>>> {
+    let parameters = &signature.parameters;
+    let argument_count = arguments.len();
+    let argument_spans = arguments
+        .iter()
+        .map(|(name, value)| (name.is_some(), value.0))
+        .collect::<Vec<_>>();
+    let mut ordered: Vec<Option<(Span<'a>, TypedExpression<'a>)>> =
+        parameters.iter().map(|_| None).collect();
+    let mut variadic_arguments = vec![];
+    let mut evaluation_order = vec![];
+    let mut seen_named_argument = None;
+    for (position, (name, value)) in arguments.into_iter().enumerate() {
+        let (argument_span, index) = match name {
+            Some((name_span, Identifier(name))) => {
+                seen_named_argument = Some(name_span);
+                let index = parameters
+                    .iter()
+                    .position(|parameter| parameter.name == Some(name))
+                    .ok_or(Error::UnknownArgumentName {
+                        span: name_span,
+                        function_span,
+                    })?;
+                (name_span, index)
+            }
+            None => {
+                if let Some(named_span) = seen_named_argument {
+                    return Err(Box::new(Error::PositionalAfterNamedArgument {
+                        span: value.0,
+                        named_span,
+                    }));
+                }
+
+                if position >= parameters.len() && signature.variadic {
+                    evaluation_order.push(position);
+                    variadic_arguments.push(value);
+                    continue;
+                } else if position >= parameters.len() {
+                    return Err(Box::new(Error::ParameterMismatch {
+                        span,
+                        expected_parameter_count: parameters.len(),
+                        actual_parameter_count: argument_count,
+                        fix: extra_arguments_fix(&argument_spans, position),
+                    }));
+                }
+
+                (value.0, position)
+            }
+        };
+
+        if let Some((previous_span, _)) = &ordered[index] {
+            return Err(Box::new(Error::DuplicateArgument {
+                span: argument_span,
+                previous_span: *previous_span,
+            }));
+        }
+
+        evaluation_order.push(index);
+        ordered[index] = Some((argument_span, value));
+    }
+
+    // The default values are evaluated after every argument that was written.
+    evaluation_order.extend((0..parameters.len()).filter(|index| ordered[*index].is_none()));
+
+    let mut ordered = ordered
+        .into_iter()
+        .zip(parameters)
+        .map(
+            |(argument, parameter)| match (argument, &parameter.default) {
+                (Some((_, value)), _) => Ok(value),
+                (None, Some(default)) => Ok(default.clone()),
+                (None, None) => Err(Box::new(Error::MissingArgument {
+                    span,
+                    parameter_span: parameter.span,
+                })),
+            },
+        )
+        .collect::<Result<Vec<_>, _>>()?;
+
+    ordered.append(&mut variadic_arguments);
+    Ok((ordered, evaluation_order))
+}
+
+/// Creates a fix that writes an integer literal as a float literal, since
+/// an `Int` is never converted into a `Float` on its own.
+///
+/// # Arguments
+/// * `expression` - The expression whose type does not match.
+/// * `expected_type` - The `Type` the expression should have.
+fn float_literal_fix<'a>(expression: &TypedExpression<'a>, expected_type: Type) -> Option<Fix<'a>> {
+    match expression.1 {
+        TypedExpressionKind::Int(value) if expected_type == Type::Float => {
+            let replacement = format!("{}.0", value);
+            Some(Fix::machine_applicable(
+                format!("use the float literal `{}`", replacement),
+                vec![Edit::new(expression.0, replacement)],
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// Creates a fix that removes the positional arguments that the function does
+/// not accept, together with the commas that separate them. No fix is created
+/// if a named argument follows them, since the arguments may only be out of order.
+///
+/// # Arguments
+/// * `arguments` - Whether each argument is named, and the `Span` of its value.
+/// * `first_extra` - The position of the first argument that is not accepted.
+fn extra_arguments_fix<'a>(arguments: &[(bool, Span<'a>)], first_extra: usize) -> Option<Fix<'a>> {
+    if arguments[first_extra..].iter().any(|(named, _)| *named) {
+        return None;
+    }
+
+    let (_, last) = arguments[arguments.len() - 1];
+    // The removed code starts after the last accepted argument, so its comma is removed too.
+    let start = match first_extra.checked_sub(1) {
+        Some(previous) => {
+            let (_, previous) = arguments[previous];
+            Span::new(
+                previous.file_name,
+                previous.end,
+                previous.end,
+                previous.line_end,
+                previous.column_end + 1,
+                previous.line_end,
+                previous.column_end + 1,
+            )
+        }
+        None => arguments[first_extra].1,
+    };
+
+    let count = arguments.len() - first_extra;
+    Some(Fix::machine_applicable(
+        format!(
+            "remove the {} extra argument{}",
+            count,
+            if count == 1 { "" } else { "s" }
+        ),
+        vec![Edit::new(start.combine(last), "")],
+    ))
+}
+
 fn get_type(typed_expression_kind: &TypedExpressionKind) -> Type {
     match typed_expression_kind {
         TypedExpressionKind::Int(_) => Type::Int,
         TypedExpressionKind::Float(_) => Type::Float,
         TypedExpressionKind::Boolean(_) => Type::Boolean,
         TypedExpressionKind::Char(_) => Type::Char,
+        TypedExpressionKind::Null => Type::Pointer(PointeeType::Void, 1),
         TypedExpressionKind::Identifier(ref inner) => inner.ty,
         TypedExpressionKind::Unary(ref inner) => inner.ty,
         TypedExpressionKind::Binary(ref inner) => inner.ty,
         TypedExpressionKind::If(ref inner) => inner.ty,
         TypedExpressionKind::Let(ref inner) => inner.ty,
+        TypedExpressionKind::Store(_) => Type::Void,
         TypedExpressionKind::Block(ref expressions) => expressions
             .iter()
             .last()
@@ -669,5 +1178,7 @@ fn get_type(typed_expression_kind: &TypedExpressionKind) -> Type {
         TypedExpressionKind::While(_) => Type::Void,
         TypedExpressionKind::Return(None) => Type::Void,
         TypedExpressionKind::Return(Some(ref inner)) => get_type(&inner.1),
+        TypedExpressionKind::Unsafe(ref inner) => get_type(&inner.1),
+        TypedExpressionKind::Error => Type::Never,
     }
 }
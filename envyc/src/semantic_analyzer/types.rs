@@ -12,19 +12,84 @@ pub enum Type {
     Boolean,
     Char,
     Never,
+    // A raw pointer. The pointee is the innermost type and the
+    // depth is the number of pointers wrapping it, so `Ptr<Ptr<Int>>`
+    // is represented as `Pointer(PointeeType::Int, 2)`.
+    Pointer(PointeeType, usize),
+}
+
+/// Enum that represents the innermost type of a pointer.
+/// `Void` is used for opaque pointers that can not be dereferenced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointeeType {
+    Void,
+    Int,
+    Float,
+    Boolean,
+    Char,
+}
+
+impl Type {
+    /// Returns the type of a pointer to a value of this type.
+    /// `Never` can not be pointed to.
+    pub fn pointer_to(self) -> Option<Type> {
+        match self {
+            Type::Void => Some(Type::Pointer(PointeeType::Void, 1)),
+            Type::Int => Some(Type::Pointer(PointeeType::Int, 1)),
+            Type::Float => Some(Type::Pointer(PointeeType::Float, 1)),
+            Type::Boolean => Some(Type::Pointer(PointeeType::Boolean, 1)),
+            Type::Char => Some(Type::Pointer(PointeeType::Char, 1)),
+            Type::Pointer(pointee, depth) => Some(Type::Pointer(pointee, depth + 1)),
+            Type::Never => None,
+        }
+    }
+
+    /// Returns the type of the value this pointer refers to.
+    /// Opaque pointers and values that are not pointers can not be dereferenced.
+    pub fn pointee(self) -> Option<Type> {
+        match self {
+            Type::Pointer(pointee, depth) if depth > 1 => Some(Type::Pointer(pointee, depth - 1)),
+            Type::Pointer(PointeeType::Int, _) => Some(Type::Int),
+            Type::Pointer(PointeeType::Float, _) => Some(Type::Float),
+            Type::Pointer(PointeeType::Boolean, _) => Some(Type::Boolean),
+            Type::Pointer(PointeeType::Char, _) => Some(Type::Char),
+            _ => None,
+        }
+    }
+
+    /// Determines whether a value of this type can be used where the expected
+    /// type is required. This only differs from equality for `Ptr<Void>`,
+    /// which converts to any other pointer, similar to `void *` in C, and for
+    /// `Never`, the type of expressions that could not be parsed, which converts
+    /// to any type so that a syntax error does not cause further type errors.
+    ///
+    /// # Arguments
+    /// * `expected` - The `Type` that is required.
+    pub fn coerces_to(self, expected: Type) -> bool {
+        match (self, expected) {
+            (Type::Never, _) => true,
+            (Type::Pointer(PointeeType::Void, 1), Type::Pointer(..)) => true,
+            (actual, expected) => actual == expected,
+        }
+    }
 }
 
 impl PartialEq for Type {
     fn eq(&self, other: &Self) -> bool {
-        matches!(
-            (self, other),
-            (Type::Void, Type::Void)
-                | (Type::Never, Type::Never)
-                | (Type::Int, Type::Int)
-                | (Type::Float, Type::Float)
-                | (Type::Boolean, Type::Boolean)
-                | (Type::Char, Type::Char)
-        )
+        match (self, other) {
+            (Type::Pointer(pointee, depth), Type::Pointer(other_pointee, other_depth)) => {
+                pointee == other_pointee && depth == other_depth
+            }
+            _ => matches!(
+                (self, other),
+                (Type::Void, Type::Void)
+                    | (Type::Never, Type::Never)
+                    | (Type::Int, Type::Int)
+                    | (Type::Float, Type::Float)
+                    | (Type::Boolean, Type::Boolean)
+                    | (Type::Char, Type::Char)
+            ),
+        }
     }
 }
 
@@ -37,6 +102,27 @@ impl Display for Type {
             Type::Boolean => write!(f, "Boolean"),
             Type::Char => write!(f, "Char"),
             Type::Never => write!(f, "Never"),
+            Type::Pointer(pointee, depth) => {
+                write!(
+                    f,
+                    "{}{}{}",
+                    "Ptr<".repeat(*depth),
+                    pointee,
+                    ">".repeat(*depth)
+                )
+            }
+        }
+    }
+}
+
+impl Display for PointeeType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PointeeType::Void => write!(f, "Void"),
+            PointeeType::Int => write!(f, "Int"),
+            PointeeType::Float => write!(f, "Float"),
+            PointeeType::Boolean => write!(f, "Boolean"),
+            PointeeType::Char => write!(f, "Char"),
         }
     }
 }
@@ -0,0 +1,182 @@
+use envyc::{
+    environment::Environment,
+    error::{Error, Span},
+    filter_tokens,
+    function_table::FunctionTable,
+    interner::Interner,
+    lex, parse,
+    parser::typed_expression::TypedExpressionKind,
+    printer::Printer,
+    type_check,
+};
+
+const FUNCTIONS: &str = "define clamp(min: Int, value: Int, max: Int = 10) :: Int = value
+define scale(value: Float, factor: Float = 2.0, offset: Float = 0.0) :: Float = value * factor + offset
+";
+
+/// Type checks the functions above together with a function that returns
+/// an application, and returns the printed function. The errors are
+/// returned with their code and the source of their spans.
+fn check_application(
+    return_type: &str,
+    application: &str,
+) -> Result<String, Vec<(&'static str, String, String)>> {
+    let source = format!(
+        "{}define test() :: {} = {}\n",
+        FUNCTIONS, return_type, application
+    );
+    let mut interner = Interner::default();
+    let tokens = lex("test.envy", source.as_bytes(), &mut interner).unwrap();
+    let program = parse(filter_tokens(tokens), &interner).expect("source should parse");
+    let text = |span: Span| source[span.start..span.end].to_string();
+    match type_check(
+        program,
+        &mut Environment::default(),
+        &mut FunctionTable::default(),
+        &interner,
+    ) {
+        Ok(typed_program) => {
+            let printed = Printer::new(&interner, 4).print_typed_program(&typed_program);
+            Ok(printed.lines().last().unwrap().to_string())
+        }
+        Err(errors) => Err(errors
+            .iter()
+            .map(|error| {
+                let (span, other_span) = match error {
+                    Error::UnknownArgumentName {
+                        span,
+                        function_span,
+                    } => (*span, *function_span),
+                    Error::DuplicateArgument {
+                        span,
+                        previous_span,
+                    } => (*span, *previous_span),
+                    Error::PositionalAfterNamedArgument { span, named_span } => {
+                        (*span, *named_span)
+                    }
+                    Error::MissingArgument {
+                        span,
+                        parameter_span,
+                    } => (*span, *parameter_span),
+                    other => panic!("unexpected error {:?}", other),
+                };
+                (error.code(), text(span), text(other_span))
+            })
+            .collect()),
+    }
+}
+
+#[test]
+fn named_arguments_are_ordered_like_the_parameters() {
+    let printed = check_application("Int", "clamp(value: 5, max: 8, min: 1)").unwrap();
+    assert_eq!(printed, "define test() :: Int = (clamp(1, 5, 8): Int)");
+}
+
+#[test]
+fn named_arguments_follow_positional_arguments() {
+    let printed = check_application("Int", "clamp(1, max: 8, value: 5)").unwrap();
+    assert_eq!(printed, "define test() :: Int = (clamp(1, 5, 8): Int)");
+}
+
+#[test]
+fn missing_arguments_use_the_default_values() {
+    let printed = check_application("Float", "scale(3.0, offset: 1.0)").unwrap();
+    assert_eq!(
+        printed,
+        "define test() :: Float = (scale(3.0, 2.0, 1.0): Float)"
+    );
+}
+
+#[test]
+fn defaults_are_typed_expressions() {
+    let mut interner = Interner::default();
+    let source = "define f(x: Int = 1 + 2) :: Int = x\ndefine main() :: Int = { f() }\n";
+    let tokens = lex("test.envy", source.as_bytes(), &mut interner).unwrap();
+    let program = parse(filter_tokens(tokens), &interner).unwrap();
+    let typed_program = type_check(
+        program,
+        &mut Environment::default(),
+        &mut FunctionTable::default(),
+        &interner,
+    )
+    .unwrap();
+    let main = &typed_program.functions[1];
+    let application = match &main.body.1 {
+        TypedExpressionKind::Block(expressions) => &expressions[0].1,
+        other => panic!("unexpected body {:?}", other),
+    };
+    match application {
+        TypedExpressionKind::Application(application) => {
+            assert!(matches!(
+                application.parameters[0].1,
+                TypedExpressionKind::Binary(_)
+            ));
+        }
+        other => panic!("unexpected expression {:?}", other),
+    }
+}
+
+#[test]
+fn arguments_are_evaluated_in_the_order_they_are_written() {
+    let mut interner = Interner::default();
+    let source = "define clamp(min: Int, value: Int, max: Int = 10) :: Int = value
+define main() :: Int = { clamp(value: 5, min: 1) }
+";
+    let tokens = lex("test.envy", source.as_bytes(), &mut interner).unwrap();
+    let program = parse(filter_tokens(tokens), &interner).unwrap();
+    let typed_program = type_check(
+        program,
+        &mut Environment::default(),
+        &mut FunctionTable::default(),
+        &interner,
+    )
+    .unwrap();
+    let main = &typed_program.functions[1];
+    match &main.body.1 {
+        TypedExpressionKind::Block(expressions) => match &expressions[0].1 {
+            TypedExpressionKind::Application(application) => {
+                assert_eq!(application.evaluation_order, vec![1, 0, 2]);
+            }
+            other => panic!("unexpected expression {:?}", other),
+        },
+        other => panic!("unexpected body {:?}", other),
+    }
+}
+
+#[test]
+fn only_names_are_followed_by_a_colon() {
+    let mut interner = Interner::default();
+    let source = format!("{}define test() :: Int = clamp((min): 1, 5)\n", FUNCTIONS);
+    let tokens = lex("test.envy", source.as_bytes(), &mut interner).unwrap();
+    assert!(parse(filter_tokens(tokens), &interner).is_err());
+}
+
+#[test]
+fn unknown_argument_names_are_reported() {
+    let errors = check_application("Int", "clamp(1, 5, maximum: 8)").unwrap_err();
+    assert_eq!(
+        errors,
+        vec![("E0015", "maximum".to_string(), "clamp".to_string())]
+    );
+}
+
+#[test]
+fn duplicate_arguments_are_reported() {
+    let errors = check_application("Int", "clamp(1, 5, min: 2)").unwrap_err();
+    assert_eq!(errors, vec![("E0016", "min".to_string(), "1".to_string())]);
+}
+
+#[test]
+fn positional_arguments_after_named_arguments_are_reported() {
+    let errors = check_application("Int", "clamp(min: 1, 5)").unwrap_err();
+    assert_eq!(errors, vec![("E0017", "5".to_string(), "min".to_string())]);
+}
+
+#[test]
+fn missing_arguments_without_defaults_are_reported() {
+    let errors = check_application("Int", "clamp(max: 8, min: 1)").unwrap_err();
+    assert_eq!(
+        errors,
+        vec![("E0018", "clamp".to_string(), "value".to_string())]
+    );
+}
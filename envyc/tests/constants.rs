@@ -0,0 +1,167 @@
+use envyc::{
+    environment::Environment, error::Error, filter_tokens, function_table::FunctionTable,
+    interner::Interner, lex, parse, semantic_analyzer::constant_evaluator::ConstantValue,
+    type_check,
+};
+
+/// Type checks a program and returns the value of each constant. The errors
+/// are returned with their code and the byte offsets of their spans.
+fn evaluate(source: &str) -> Result<Vec<ConstantValue>, Vec<(&'static str, usize, usize)>> {
+    let mut interner = Interner::default();
+    let tokens = lex("test.envy", source.as_bytes(), &mut interner).unwrap();
+    let program = parse(filter_tokens(tokens), &interner).expect("source should parse");
+    match type_check(
+        program,
+        &mut Environment::default(),
+        &mut FunctionTable::default(),
+        &interner,
+    ) {
+        Ok(typed_program) => Ok(typed_program
+            .constants
+            .iter()
+            .map(|constant| constant.value)
+            .collect()),
+        Err(errors) => Err(errors
+            .iter()
+            .map(|error| {
+                let span = match error {
+                    Error::DivisionByZero(span)
+                    | Error::ConstantOverflow(span)
+                    | Error::NonConstantExpression(span)
+                    | Error::CyclicConstant(span)
+                    | Error::ConstantEvaluationLimit(span) => *span,
+                    Error::AssignmentToConstant { span, .. } => *span,
+                    other => panic!("unexpected error {:?}", other),
+                };
+                (error.code(), span.start, span.end)
+            })
+            .collect()),
+    }
+}
+
+/// Returns the byte offsets of the `occurrence`th (starting at 0) match of `text` in `source`.
+fn find(source: &str, text: &str, occurrence: usize) -> (usize, usize) {
+    let (start, _) = source.match_indices(text).nth(occurrence).unwrap();
+    (start, start + text.len())
+}
+
+/// Type checks a program that is expected to fail with a single error, and
+/// returns its code and the source of its span.
+fn single_error(source: &str) -> (&'static str, (usize, usize)) {
+    let errors = evaluate(source).unwrap_err();
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    let (code, start, end) = errors[0];
+    (code, (start, end))
+}
+
+#[test]
+fn constants_are_evaluated_in_any_order() {
+    let source = "const TAU: Float = 2.0 * PI
+const PI: Float = 3.5
+define square(x: Int) :: Int = x * x
+const AREA: Int = if square(3) > 8 then square(3) else 0
+";
+    assert_eq!(
+        evaluate(source).unwrap(),
+        vec![
+            ConstantValue::Float(7.0),
+            ConstantValue::Float(3.5),
+            ConstantValue::Int(9)
+        ]
+    );
+}
+
+#[test]
+fn division_by_zero_is_reported_at_the_divisor() {
+    let source = "const ZERO: Int = 0\nconst X: Int = 1 / ZERO\n";
+    assert_eq!(single_error(source), ("E0021", find(source, "ZERO", 1)));
+
+    let source = "const X: Float = 1.0 / 0.0\n";
+    assert_eq!(single_error(source), ("E0021", find(source, "0.0", 0)));
+}
+
+#[test]
+fn overflows_are_reported_at_the_operation() {
+    let source = "const X: Int = 9223372036854775807 + 1\n";
+    assert_eq!(
+        single_error(source),
+        ("E0022", find(source, "9223372036854775807 + 1", 0))
+    );
+
+    let source = "const X: Float = 1.0e308 * 10.0\n";
+    assert_eq!(
+        single_error(source),
+        ("E0022", find(source, "1.0e308 * 10.0", 0))
+    );
+
+    let source =
+        "define negate(x: Int) :: Int = -x\nconst X: Int = negate(-9223372036854775807 - 1)\n";
+    assert_eq!(single_error(source), ("E0022", find(source, "-x", 0)));
+}
+
+#[test]
+fn sums_of_chars_must_be_ascii() {
+    let source = "const X: Char = '\\u{1}' + '~'\n";
+    assert_eq!(
+        evaluate(source).unwrap(),
+        vec![ConstantValue::Char('\u{7f}')]
+    );
+
+    let source = "const X: Char = 'a' + 'a'\n";
+    assert_eq!(
+        single_error(source),
+        ("E0022", find(source, "'a' + 'a'", 0))
+    );
+}
+
+#[test]
+fn cyclic_constants_are_reported_at_the_reference() {
+    let source = "const A: Int = B + 1\nconst B: Int = A\n";
+    assert_eq!(
+        evaluate(source).unwrap_err(),
+        vec![
+            ("E0024", find(source, "A", 1).0, find(source, "A", 1).1),
+            ("E0024", find(source, "B", 0).0, find(source, "B", 0).1),
+        ]
+    );
+
+    let source = "const A: Int = A\n";
+    assert_eq!(single_error(source), ("E0024", find(source, "A", 1)));
+}
+
+#[test]
+fn deep_recursion_stops_at_the_call_depth_limit() {
+    let source = "define down(n: Int) :: Int = down(n + 1)\nconst X: Int = down(0)\n";
+    assert_eq!(single_error(source), ("E0025", find(source, "down", 1)));
+
+    // Each application evaluates several nested expressions, which must not
+    // overflow the stack before the limit is reached.
+    let source = "define down(n: Int) :: Int = if n >= 0 then {\n    let m = n + 1\n    { 1 } + { down(m) }\n} else 0\nconst X: Int = down(0)\n";
+    assert_eq!(single_error(source), ("E0025", find(source, "down", 1)));
+}
+
+#[test]
+fn infinite_loops_stop_at_the_step_limit() {
+    let source = "define spin() :: Int = {\n    while true {}\n    0\n}\nconst X: Int = spin()\n";
+    let (code, (start, end)) = single_error(source);
+    assert_eq!(code, "E0025");
+    // The limit is reached somewhere in the loop.
+    let (loop_start, loop_end) = find(source, "while true {}", 0);
+    assert!(
+        loop_start <= start && end <= loop_end,
+        "{}",
+        &source[start..end]
+    );
+}
+
+#[test]
+fn constants_can_not_call_external_functions() {
+    let source = "extern rand() :: Int\nconst X: Int = rand()\n";
+    assert_eq!(single_error(source), ("E0023", find(source, "rand", 1)));
+}
+
+#[test]
+fn assignments_to_constants_are_reported_at_the_name() {
+    let source = "const LIMIT: Int = 1\ndefine f() :: Int = {\n    let LIMIT = 2\n    LIMIT\n}\n";
+    assert_eq!(single_error(source), ("E0026", find(source, "LIMIT", 1)));
+}
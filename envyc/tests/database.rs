@@ -0,0 +1,176 @@
+use std::{fs, hash::Hasher};
+
+use envyc::database::{
+    cache::BuildCache,
+    fingerprint::{Fingerprint, FingerprintHasher},
+    Database, Query,
+};
+
+const FILE: &str = "test.envy";
+
+const SOURCE: &str = "const LIMIT: Int = 10
+
+define square(x: Int) :: Int = x * x
+
+define area(width: Int) :: Int = {
+    let size = square(width)
+    size + LIMIT
+}
+
+define main() :: Int = 0
+";
+
+/// Returns the names of the functions that were checked since the last call.
+fn checked_functions(database: &mut Database) -> Vec<String> {
+    let mut names = vec![];
+    for query in database.take_executed() {
+        if let Query::CheckFunction(_, name) = query {
+            names.push(database.interner().get(name).clone());
+        }
+    }
+
+    names
+}
+
+/// Checks an edited source from scratch and returns the debug output of the
+/// result, which contains every span of the typed program. The original source
+/// is lexed first, so that the names are interned in the same order.
+fn check_from_scratch(source: &str) -> String {
+    let mut database = Database::default();
+    database.set_source(FILE, SOURCE);
+    database.lex(FILE).unwrap();
+    database.set_source(FILE, source);
+    format!("{:?}", database.type_check(FILE))
+}
+
+#[test]
+fn unchanged_files_are_not_checked_again() {
+    let mut database = Database::default();
+    database.set_source(FILE, SOURCE);
+    assert!(database.type_check(FILE).is_ok());
+    assert_eq!(
+        checked_functions(&mut database),
+        vec!["square", "area", "main"]
+    );
+
+    let revision = database.revision();
+    database.set_source(FILE, SOURCE);
+    assert!(database.type_check(FILE).is_ok());
+    assert_eq!(database.revision(), revision);
+    assert!(database.take_executed().is_empty());
+}
+
+#[test]
+fn editing_a_body_only_checks_that_function() {
+    let mut database = Database::default();
+    database.set_source(FILE, SOURCE);
+    database.type_check(FILE).unwrap();
+    database.take_executed();
+
+    // The new line moves every function after `area`.
+    let edited = SOURCE.replace(
+        "size + LIMIT",
+        "let doubled = size * 2\n    doubled + LIMIT",
+    );
+    database.set_source(FILE, &edited);
+    let result = database.type_check(FILE);
+    assert_eq!(
+        database.take_executed(),
+        vec![
+            Query::Lex(FILE),
+            Query::Parse(FILE),
+            Query::TypeCheck(FILE),
+            Query::CheckFunction(FILE, database.interner_mut().insert("area".to_string())),
+        ]
+    );
+    assert_eq!(format!("{:?}", result), check_from_scratch(&edited));
+}
+
+#[test]
+fn changing_a_signature_checks_its_dependents() {
+    let mut database = Database::default();
+    database.set_source(FILE, SOURCE);
+    database.type_check(FILE).unwrap();
+    database.take_executed();
+
+    let edited = SOURCE.replace(
+        "define square(x: Int) :: Int",
+        "define square(x: Int, y: Int = 1) :: Int",
+    );
+    database.set_source(FILE, &edited);
+    let result = database.type_check(FILE);
+    assert_eq!(checked_functions(&mut database), vec!["square", "area"]);
+    assert_eq!(format!("{:?}", result), check_from_scratch(&edited));
+
+    let edited = edited.replace("const LIMIT: Int = 10", "const LIMIT: Float = 10.0");
+    database.set_source(FILE, &edited);
+    assert!(database.type_check(FILE).is_err());
+    assert_eq!(checked_functions(&mut database), vec!["area"]);
+}
+
+#[test]
+fn functions_with_errors_are_checked_again() {
+    let mut database = Database::default();
+    let broken = SOURCE.replace("x * x", "x * true");
+    database.set_source(FILE, &broken);
+    assert!(database.type_check(FILE).is_err());
+    database.take_executed();
+
+    database.set_source(FILE, SOURCE);
+    assert!(database.type_check(FILE).is_ok());
+    assert_eq!(checked_functions(&mut database), vec!["square"]);
+}
+
+#[test]
+fn adding_a_constant_checks_the_functions_that_assign_its_name() {
+    for body in ["let count = 1.0\n    0", "let count = 2\n    count"] {
+        let mut database = Database::default();
+        let source = SOURCE.replace(
+            "define main() :: Int = 0",
+            &format!("define main() :: Int = {{\n    {}\n}}", body),
+        );
+        database.set_source(FILE, &source);
+        assert!(database.type_check(FILE).is_ok(), "{}", body);
+        database.take_executed();
+
+        // `main` does not change, but `count` now names a constant.
+        let edited = format!("const count: Int = 1\n{}", source);
+        database.set_source(FILE, &edited);
+        let result = database.type_check(FILE);
+        assert!(result.is_err(), "{}", body);
+        assert_eq!(checked_functions(&mut database), vec!["main"], "{}", body);
+        assert_eq!(format!("{:?}", result), check_from_scratch(&edited));
+    }
+}
+
+#[test]
+fn fingerprints_use_fnv_1a() {
+    let mut hasher = FingerprintHasher::default();
+    hasher.write(b"a");
+    assert_eq!(hasher.finish(), 0xaf63_dc4c_8601_ec8c);
+    assert_eq!(Fingerprint::of(SOURCE), Fingerprint::of(SOURCE));
+    assert_ne!(Fingerprint::of(SOURCE), Fingerprint::of(""));
+}
+
+#[test]
+fn builds_are_cached_until_their_outputs_change() {
+    let directory = std::env::temp_dir().join(format!("envious-cache-{}", std::process::id()));
+    fs::create_dir_all(&directory).unwrap();
+    let output = directory.join("test.o");
+    fs::write(&output, "object").unwrap();
+
+    let cache = BuildCache::new(&directory);
+    let key = Fingerprint::of(SOURCE);
+    assert!(cache.get(FILE, key).is_none());
+
+    cache
+        .insert(FILE, key, std::slice::from_ref(&output), true)
+        .unwrap();
+    assert!(cache.get(FILE, key).unwrap().contains_main);
+    assert!(cache.get(FILE, Fingerprint::of("")).is_none());
+    assert!(cache.get("other.envy", key).is_none());
+
+    fs::write(&output, "changed").unwrap();
+    assert!(cache.get(FILE, key).is_none());
+    fs::remove_dir_all(&directory).unwrap();
+}
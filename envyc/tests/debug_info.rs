@@ -0,0 +1,58 @@
+use envyc::{
+    compile, environment::Environment, filter_tokens, function_table::FunctionTable,
+    interner::Interner, lex, parse, type_check, Config, OptLevel, TargetOptions,
+};
+
+const SOURCE: &str = "define square(number: Int) :: Int = {
+    let result = number * number
+    result
+}
+";
+
+/// Compiles `SOURCE` and returns the IR of its module.
+fn compile_ir(debug_info: bool) -> String {
+    let mut interner = Interner::default();
+    let tokens = lex("src/square.envy", SOURCE.as_bytes(), &mut interner).unwrap();
+    let program = parse(filter_tokens(tokens), &interner).unwrap();
+    let typed_program = type_check(
+        program,
+        &mut Environment::default(),
+        &mut FunctionTable::default(),
+        &interner,
+    )
+    .unwrap();
+
+    let config = Config {
+        outputs: vec![],
+        position_independent: false,
+        opt_level: OptLevel::O0,
+        target: TargetOptions::default(),
+        debug_info,
+    };
+    compile(&typed_program, "square", &mut interner, Some(config)).expect("source should compile")
+}
+
+#[test]
+fn functions_and_variables_are_described() {
+    let ir = compile_ir(true);
+    assert!(
+        ir.contains("!DIFile(filename: \"square.envy\", directory: \"src\")"),
+        "{}",
+        ir
+    );
+    assert!(ir.contains("!DISubprogram(name: \"square\""), "{}", ir);
+    assert!(
+        ir.contains("!DILocalVariable(name: \"number\", arg: 1"),
+        "{}",
+        ir
+    );
+    assert!(ir.contains("!DILocalVariable(name: \"result\""), "{}", ir);
+    assert!(ir.contains("!DILocation(line: 2"), "{}", ir);
+}
+
+#[test]
+fn modules_have_no_debug_information_by_default() {
+    let ir = compile_ir(false);
+    assert!(!ir.contains("!DISubprogram"), "{}", ir);
+    assert!(!ir.contains("!llvm.dbg.cu"), "{}", ir);
+}
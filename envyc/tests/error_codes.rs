@@ -0,0 +1,190 @@
+use std::collections::HashSet;
+
+use envyc::{
+    error::{explanations::explain, suggestion::Suggestion, Error, Span},
+    lexer::token::TokenKind,
+    semantic_analyzer::types::Type,
+};
+
+/// Creates one error of every kind. The match in `kind_name` has no wildcard,
+/// so adding a variant to `Error` fails to compile until it is added here.
+fn every_error<'a>() -> Vec<Error<'a>> {
+    let span = Span::new("test.envy", 0, 1, 1, 1, 1, 1);
+    vec![
+        Error::IntegerOverflow(span),
+        Error::FloatOverflow(span),
+        Error::UnterminatedChar(span),
+        Error::InvalidEscape(span),
+        Error::CharOutOfRange(span),
+        Error::UnrecognizedCharacter(span),
+        Error::MalformedNumber(span),
+        Error::UnexpectedEndOfInput(span),
+        Error::ExpectedPrefixExpression {
+            span,
+            found_kind: TokenKind::EqualSign,
+        },
+        Error::ExpectedKind {
+            span,
+            expected_kinds: vec![TokenKind::EqualSign],
+            actual_kind: TokenKind::Colon,
+            suggestion: None,
+            fix: None,
+        },
+        Error::AmbiguousStar(span),
+        Error::UnsupportedOperation {
+            operation_span: span,
+            operands: vec![(span, Type::Boolean)],
+        },
+        Error::TypeMismatch {
+            span,
+            expected_type: Type::Int,
+            actual_type: Type::Float,
+            fix: None,
+        },
+        Error::ConflictingType {
+            first_span: span,
+            first_type: Type::Int,
+            second_span: span,
+            second_type: Type::Float,
+            fix: None,
+        },
+        Error::ConflictingPreviousType {
+            name_span: span,
+            previous_type: Type::Int,
+            second_span: span,
+            second_type: Type::Char,
+        },
+        Error::IllegalType(span),
+        Error::UndefinedVariable {
+            span,
+            suggestion: Suggestion::Resolved(None),
+        },
+        Error::ParameterMismatch {
+            span,
+            expected_parameter_count: 2,
+            actual_parameter_count: 3,
+            fix: None,
+        },
+        Error::UnknownArgumentName {
+            span,
+            function_span: span,
+        },
+        Error::DuplicateArgument {
+            span,
+            previous_span: span,
+        },
+        Error::PositionalAfterNamedArgument {
+            span,
+            named_span: span,
+        },
+        Error::MissingArgument {
+            span,
+            parameter_span: span,
+        },
+        Error::UnsafeOperation(span),
+        Error::IllegalAddressOf(span),
+        Error::DivisionByZero(span),
+        Error::ConstantOverflow(span),
+        Error::NonConstantExpression(span),
+        Error::CyclicConstant(span),
+        Error::ConstantEvaluationLimit(span),
+        Error::AssignmentToConstant {
+            span,
+            constant_span: span,
+        },
+        Error::UnknownFunction {
+            span,
+            suggestion: Suggestion::Resolved(None),
+        },
+        Error::UnknownAttribute(span),
+        Error::UnknownLint(span),
+        Error::ExpectedFunction,
+        Error::LLVMFunctionFailure,
+        Error::WriteFailure {
+            path: "test.o".to_string(),
+            message: "No such file or directory".to_string(),
+        },
+        Error::LinkFailure {
+            path: "test.bc".to_string(),
+            message: "Invalid bitcode signature".to_string(),
+        },
+        Error::InvalidTarget {
+            triple: "sparc-unknown-plan9".to_string(),
+            message: "No available targets are compatible with triple".to_string(),
+        },
+    ]
+}
+
+fn kind_name(error: &Error) -> &'static str {
+    match error {
+        Error::IntegerOverflow(_) => "IntegerOverflow",
+        Error::FloatOverflow(_) => "FloatOverflow",
+        Error::UnterminatedChar(_) => "UnterminatedChar",
+        Error::InvalidEscape(_) => "InvalidEscape",
+        Error::CharOutOfRange(_) => "CharOutOfRange",
+        Error::UnrecognizedCharacter(_) => "UnrecognizedCharacter",
+        Error::MalformedNumber(_) => "MalformedNumber",
+        Error::UnexpectedEndOfInput(_) => "UnexpectedEndOfInput",
+        Error::ExpectedPrefixExpression { .. } => "ExpectedPrefixExpression",
+        Error::ExpectedKind { .. } => "ExpectedKind",
+        Error::AmbiguousStar(_) => "AmbiguousStar",
+        Error::UnsupportedOperation { .. } => "UnsupportedOperation",
+        Error::TypeMismatch { .. } => "TypeMismatch",
+        Error::ConflictingType { .. } => "ConflictingType",
+        Error::ConflictingPreviousType { .. } => "ConflictingPreviousType",
+        Error::IllegalType(_) => "IllegalType",
+        Error::UndefinedVariable { .. } => "UndefinedVariable",
+        Error::ParameterMismatch { .. } => "ParameterMismatch",
+        Error::UnknownArgumentName { .. } => "UnknownArgumentName",
+        Error::DuplicateArgument { .. } => "DuplicateArgument",
+        Error::PositionalAfterNamedArgument { .. } => "PositionalAfterNamedArgument",
+        Error::MissingArgument { .. } => "MissingArgument",
+        Error::UnsafeOperation(_) => "UnsafeOperation",
+        Error::IllegalAddressOf(_) => "IllegalAddressOf",
+        Error::DivisionByZero(_) => "DivisionByZero",
+        Error::ConstantOverflow(_) => "ConstantOverflow",
+        Error::NonConstantExpression(_) => "NonConstantExpression",
+        Error::CyclicConstant(_) => "CyclicConstant",
+        Error::ConstantEvaluationLimit(_) => "ConstantEvaluationLimit",
+        Error::AssignmentToConstant { .. } => "AssignmentToConstant",
+        Error::UnknownFunction { .. } => "UnknownFunction",
+        Error::UnknownAttribute(_) => "UnknownAttribute",
+        Error::UnknownLint(_) => "UnknownLint",
+        Error::ExpectedFunction => "ExpectedFunction",
+        Error::LLVMFunctionFailure => "LLVMFunctionFailure",
+        Error::WriteFailure { .. } => "WriteFailure",
+        Error::LinkFailure { .. } => "LinkFailure",
+        Error::InvalidTarget { .. } => "InvalidTarget",
+    }
+}
+
+#[test]
+fn every_error_has_an_explanation() {
+    for error in every_error() {
+        assert!(
+            explain(error.code()).is_some(),
+            "`{}` ({}) has no explanation",
+            kind_name(&error),
+            error.code()
+        );
+    }
+}
+
+#[test]
+fn error_codes_are_unique() {
+    let mut codes = HashSet::new();
+    for error in every_error() {
+        assert!(
+            codes.insert(error.code()),
+            "`{}` reuses the code {}",
+            kind_name(&error),
+            error.code()
+        );
+    }
+}
+
+#[test]
+fn unknown_codes_have_no_explanation() {
+    assert!(explain("E9999").is_none());
+    assert!(explain("").is_none());
+}
@@ -0,0 +1,183 @@
+use envyc::{
+    environment::Environment,
+    error::{
+        reporter::{ErrorFormat, ErrorReporter},
+        Error,
+    },
+    filter_tokens,
+    function_table::FunctionTable,
+    interner::Interner,
+    lex, parse, type_check,
+};
+use serde_json::{json, Value};
+
+const SOURCE: &str = "define f() :: Float = 1\ndefine g() :: Int = x\n";
+
+/// Type checks `SOURCE` as the file `file_name` and returns its errors.
+///
+/// # Arguments
+/// * `file_name` - The name of the file.
+fn errors(file_name: &str) -> Vec<Error<'_>> {
+    let mut interner = Interner::default();
+    let tokens = lex(file_name, SOURCE.as_bytes(), &mut interner).unwrap();
+    let program = parse(filter_tokens(tokens), &interner).expect("source should parse");
+    type_check(
+        program,
+        &mut Environment::default(),
+        &mut FunctionTable::default(),
+        &interner,
+    )
+    .expect_err("source should not type check")
+}
+
+/// Reports the errors of `SOURCE` as the file `file_name` in the SARIF format
+/// and returns the parsed log.
+///
+/// # Arguments
+/// * `file_name` - The name of the file.
+fn sarif_log(file_name: &str) -> Value {
+    let reporter = ErrorReporter::new(vec![(file_name, SOURCE)]).with_format(ErrorFormat::Sarif);
+    for error in errors(file_name) {
+        assert!(reporter.report(&error, false).is_empty());
+    }
+    serde_json::from_str(&reporter.sarif_log()).unwrap()
+}
+
+#[test]
+fn json_errors_are_written_one_per_line() {
+    let reporter = ErrorReporter::new(vec![("main.envy", SOURCE)]).with_format(ErrorFormat::Json);
+    let output = errors("main.envy")
+        .iter()
+        .map(|error| String::from_utf8(reporter.report(error, false)).unwrap())
+        .collect::<String>();
+    let lines = output.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 2, "{}", output);
+
+    let span = json!({
+        "file_name": "main.envy",
+        "start": 22,
+        "end": 23,
+        "line_start": 1,
+        "column_start": 23,
+        "line_end": 1,
+        "column_end": 23,
+    });
+    assert_eq!(
+        serde_json::from_str::<Value>(lines[0]).unwrap(),
+        json!({
+            "code": "E0009",
+            "severity": "error",
+            "message": "type mismatch",
+            "labels": [{
+                "style": "primary",
+                "span": span,
+                "message": "expected `Float` but found `Int`",
+            }],
+            "notes": [],
+            "fixes": [{
+                "message": "use the float literal `1.0`",
+                "applicability": "machine-applicable",
+                "edits": [{ "span": span, "replacement": "1.0" }],
+            }],
+        })
+    );
+
+    let unknown = serde_json::from_str::<Value>(lines[1]).unwrap();
+    assert_eq!(unknown["code"], "E0013");
+    assert_eq!(unknown["labels"][0]["span"]["line_start"], 2);
+    assert_eq!(unknown["labels"][0]["span"]["column_start"], 21);
+}
+
+#[test]
+fn sarif_logs_contain_every_error() {
+    let log = sarif_log("main.envy");
+    assert_eq!(log["version"], "2.1.0");
+    assert_eq!(
+        log["$schema"],
+        "https://json.schemastore.org/sarif-2.1.0.json"
+    );
+
+    let run = &log["runs"][0];
+    assert_eq!(run["tool"]["driver"]["name"], "envious");
+    assert_eq!(
+        run["tool"]["driver"]["rules"],
+        json!([{ "id": "E0009" }, { "id": "E0013" }])
+    );
+
+    let results = run["results"].as_array().unwrap();
+    assert_eq!(results.len(), 2);
+    let region = json!({
+        "startLine": 1,
+        "startColumn": 23,
+        "endLine": 1,
+        "endColumn": 24,
+        "byteOffset": 22,
+        "byteLength": 1,
+    });
+    let artifact_location = json!({ "uri": "main.envy", "uriBaseId": "%SRCROOT%" });
+    assert_eq!(
+        results[0],
+        json!({
+            "ruleId": "E0009",
+            "level": "error",
+            "message": { "text": "type mismatch" },
+            "locations": [{
+                "physicalLocation": {
+                    "artifactLocation": artifact_location,
+                    "region": region,
+                },
+                "message": { "text": "expected `Float` but found `Int`" },
+            }],
+            "relatedLocations": [],
+            "fixes": [{
+                "description": { "text": "use the float literal `1.0`" },
+                "artifactChanges": [{
+                    "artifactLocation": artifact_location,
+                    "replacements": [{
+                        "deletedRegion": region,
+                        "insertedContent": { "text": "1.0" },
+                    }],
+                }],
+            }],
+            "properties": { "notes": [] },
+        })
+    );
+    assert_eq!(results[1]["ruleId"], "E0013");
+}
+
+#[test]
+fn relative_paths_are_resolved_against_the_source_root() {
+    let log = sarif_log("src/main file.envy");
+    let run = &log["runs"][0];
+    let location = &run["results"][0]["locations"][0]["physicalLocation"]["artifactLocation"];
+    assert_eq!(
+        *location,
+        json!({ "uri": "src/main%20file.envy", "uriBaseId": "%SRCROOT%" })
+    );
+
+    // The source root is the directory the compiler runs in.
+    let root = run["originalUriBaseIds"]["%SRCROOT%"]["uri"]
+        .as_str()
+        .unwrap();
+    let directory = std::env::current_dir().unwrap();
+    assert!(root.starts_with("file:///"), "{}", root);
+    assert!(root.ends_with('/'), "{}", root);
+    assert!(
+        root.contains(directory.file_name().unwrap().to_str().unwrap()),
+        "{}",
+        root
+    );
+}
+
+#[test]
+fn absolute_paths_are_file_uris() {
+    let file_name = std::env::temp_dir().join("my project").join("main.envy");
+    let file_name = file_name.to_str().unwrap();
+    let log = sarif_log(file_name);
+    let location =
+        &log["runs"][0]["results"][0]["locations"][0]["physicalLocation"]["artifactLocation"];
+    let uri = location["uri"].as_str().unwrap();
+    assert!(uri.starts_with("file:///"), "{}", uri);
+    assert!(uri.ends_with("/my%20project/main.envy"), "{}", uri);
+    assert!(location.get("uriBaseId").is_none());
+}
@@ -0,0 +1,168 @@
+use envyc::{
+    environment::Environment,
+    error::{
+        fix::{apply_fixes, Applicability, Edit, Fix},
+        Span,
+    },
+    filter_tokens,
+    function_table::FunctionTable,
+    interner::Interner,
+    lex, parse_with_recovery, type_check,
+};
+
+/// Checks a program once and applies the machine-applicable fixes of its errors,
+/// like a single pass of `envious fix`. Returns the fixed source and the number
+/// of fixes that were applied.
+fn fix(source: &str) -> (String, usize) {
+    let mut interner = Interner::default();
+    let tokens = lex("test.envy", source.as_bytes(), &mut interner).expect("source should lex");
+    let (program, mut errors) = parse_with_recovery(filter_tokens(tokens), &interner);
+    if let Err(type_errors) = type_check(
+        program,
+        &mut Environment::default(),
+        &mut FunctionTable::default(),
+        &interner,
+    ) {
+        errors.extend(type_errors);
+    }
+
+    let fixes = errors
+        .iter()
+        .filter_map(|error| error.fix())
+        .filter(|fix| fix.applicability == Applicability::MachineApplicable)
+        .collect::<Vec<_>>();
+    apply_fixes(source, &fixes)
+}
+
+/// Creates an edit that replaces the bytes from `start` to `end`. Only the byte
+/// offsets of the span are used when fixes are applied.
+fn edit(start: usize, end: usize, replacement: &str) -> Edit<'static> {
+    Edit::new(Span::new("test.envy", start, end, 1, 1, 1, 1), replacement)
+}
+
+#[test]
+fn integers_become_float_literals() {
+    assert_eq!(
+        fix("define f() :: Float = 1\n"),
+        ("define f() :: Float = 1.0\n".to_string(), 1)
+    );
+    assert_eq!(
+        fix("define f(x: Float) :: Float = x\nconst Y: Float = f(12)\n"),
+        (
+            "define f(x: Float) :: Float = x\nconst Y: Float = f(12.0)\n".to_string(),
+            1
+        )
+    );
+}
+
+#[test]
+fn edits_use_byte_offsets() {
+    // The characters before the edit take more than one byte each.
+    assert_eq!(
+        fix("// café ☕\ndefine f() :: Float = 12\n"),
+        ("// café ☕\ndefine f() :: Float = 12.0\n".to_string(), 1)
+    );
+}
+
+#[test]
+fn confused_tokens_are_replaced() {
+    let cases = [
+        (
+            "define f(x: Int) : Int = x\n",
+            "define f(x: Int) :: Int = x\n",
+        ),
+        (
+            "define f(x :: Int) :: Int = x\n",
+            "define f(x : Int) :: Int = x\n",
+        ),
+        ("const X: Int := 1\n", "const X: Int = 1\n"),
+        ("define f() Int = 1\n", "define f() :: Int = 1\n"),
+    ];
+    for (source, expected) in cases {
+        assert_eq!(fix(source), (expected.to_string(), 1), "{}", source);
+    }
+}
+
+#[test]
+fn extra_arguments_are_removed_with_their_commas() {
+    let cases = [
+        ("add(1, 2, 3)", "add(1, 2)"),
+        ("add(1, 2, 3, 4)", "add(1, 2)"),
+        ("add(1, 2 , 3)", "add(1, 2)"),
+        ("zero(1, 2)", "zero()"),
+    ];
+    for (application, expected) in cases {
+        let program = |application| {
+            format!(
+                "define add(a: Int, b: Int) :: Int = a + b\ndefine zero() :: Int = 0\nconst X: Int = {}\n",
+                application
+            )
+        };
+        assert_eq!(
+            fix(&program(application)),
+            (program(expected), 1),
+            "{}",
+            application
+        );
+    }
+
+    // A named argument after the extra arguments means they may only be out of order.
+    let source = "define add(a: Int, b: Int) :: Int = a + b\nconst X: Int = add(1, 2, 3, b: 4)\n";
+    assert_eq!(fix(source), (source.to_string(), 0));
+}
+
+#[test]
+fn every_fix_of_a_pass_is_applied() {
+    let source = "define f() :: Float = 1
+define g() : Int = 2
+define h(x: Float) :: Float = x
+const Y: Float = h(3, 4)
+";
+    let expected = "define f() :: Float = 1.0
+define g() :: Int = 2
+define h(x: Float) :: Float = x
+const Y: Float = h(3)
+";
+    assert_eq!(fix(source), (expected.to_string(), 3));
+}
+
+#[test]
+fn edits_are_applied_in_any_order() {
+    let fixes = [
+        Fix::machine_applicable("second", vec![edit(6, 7, "two")]),
+        Fix::machine_applicable("first", vec![edit(0, 1, "one"), edit(3, 3, "!")]),
+    ];
+    assert_eq!(
+        apply_fixes("a, b, c", &fixes),
+        ("one, !b, two".to_string(), 2)
+    );
+}
+
+#[test]
+fn overlapping_fixes_are_dropped() {
+    let source = "0123456789";
+    let fixes = [
+        Fix::machine_applicable("first", vec![edit(2, 5, "x")]),
+        // Overlaps with the first fix.
+        Fix::machine_applicable("overlapping", vec![edit(4, 6, "y")]),
+        // Only one edit overlaps, but the edits of a fix are applied together.
+        Fix::machine_applicable("partly overlapping", vec![edit(0, 1, "z"), edit(3, 4, "z")]),
+        // Touches the first fix without overlapping it.
+        Fix::machine_applicable("adjacent", vec![edit(5, 7, "w")]),
+    ];
+    assert_eq!(apply_fixes(source, &fixes), ("01xw789".to_string(), 2));
+
+    // An edit that contains another edit overlaps with it.
+    let fixes = [
+        Fix::machine_applicable("inner", vec![edit(4, 5, "x")]),
+        Fix::machine_applicable("outer", vec![edit(2, 8, "y")]),
+    ];
+    assert_eq!(apply_fixes(source, &fixes), ("0123x56789".to_string(), 1));
+}
+
+#[test]
+fn sources_without_fixes_are_unchanged() {
+    let source = "define f() :: Int = 1\n";
+    assert_eq!(fix(source), (source.to_string(), 0));
+    assert_eq!(apply_fixes(source, &[]), (source.to_string(), 0));
+}
@@ -0,0 +1,141 @@
+use std::{fs, path::Path};
+
+use envyc::{
+    filter_tokens, format_source, formatter::FormatConfig, interner::Interner, lex, parse,
+    parser::syntax_tree::SyntaxTree,
+};
+
+fn format(source: &str) -> String {
+    format_source("test.envy", source, FormatConfig::default()).expect("source should parse")
+}
+
+fn standard_library() -> Vec<(String, String)> {
+    let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("../standard_library");
+    let mut files = vec![];
+    for entry in fs::read_dir(directory).unwrap() {
+        let path = entry.unwrap().path();
+        if path
+            .extension()
+            .is_some_and(|extension| extension == "envy")
+        {
+            let source = fs::read_to_string(&path).unwrap();
+            files.push((path.display().to_string(), source));
+        }
+    }
+
+    assert!(!files.is_empty());
+    files
+}
+
+#[test]
+fn syntax_tree_is_lossless() {
+    let mut sources = standard_library()
+        .into_iter()
+        .map(|(_, source)| source)
+        .collect::<Vec<_>>();
+    sources.push(MESSY.to_string());
+
+    for source in sources {
+        let mut interner = Interner::default();
+        let tokens = lex("test.envy", source.as_bytes(), &mut interner).unwrap();
+        let program = parse(filter_tokens(tokens.clone()), &interner).unwrap();
+        let tree = SyntaxTree::new(&source, tokens, &program);
+        assert_eq!(tree.to_string(), source);
+    }
+}
+
+#[test]
+fn standard_library_is_formatted() {
+    for (path, source) in standard_library() {
+        assert_eq!(format(&source), source, "`{}` is not formatted", path);
+    }
+}
+
+const MESSY: &str = "
+// Helpers for the examples.
+extern printi( Int,... )::Void
+#allow( unused_parameter )
+const LIMIT:Int=-10
+
+
+export define   clamp(value:Int,low:Int=0,high:Int=LIMIT)::Int=if value<low then low else if value>high then high else value
+define main()::Int={ let total:Ptr<Ptr<Int>> =null   // unused
+let x=clamp(3,high: -x*2)
+
+
+  printi(x, 1)
+  while x>0 {x=x-1}
+  // done
+  if not x=0 then {}else{ unsafe{let  * total=null
+return 1} }
+  0 }";
+
+const FORMATTED: &str = "// Helpers for the examples.
+extern printi(Int, ...) :: Void
+#allow(unused_parameter)
+const LIMIT: Int = -10
+
+export define clamp(value: Int, low: Int = 0, high: Int = LIMIT) :: Int = if value < low then low else if value > high then high else value
+define main() :: Int = {
+    let total: Ptr<Ptr<Int>> = null // unused
+    let x = clamp(3, high: -x * 2)
+
+    printi(x, 1)
+    while x > 0 {
+        x = x - 1
+    }
+    // done
+    if not x = 0 then {} else {
+        unsafe {
+            let *total = null
+            return 1
+        }
+    }
+    0
+}
+";
+
+#[test]
+fn code_is_formatted_in_the_canonical_layout() {
+    assert_eq!(format(MESSY), FORMATTED);
+}
+
+#[test]
+fn formatting_is_idempotent() {
+    assert_eq!(format(FORMATTED), FORMATTED);
+    for (_, source) in standard_library() {
+        let formatted = format(&source);
+        assert_eq!(format(&formatted), formatted);
+    }
+}
+
+#[test]
+fn comments_within_expressions_are_kept() {
+    let source = "define f() :: Int = 1 + // one\n2 *\n// two\n3\n";
+    let formatted = format(source);
+    assert_eq!(
+        formatted,
+        "define f() :: Int = 1 + // one\n    2 *\n    // two\n    3\n"
+    );
+    assert_eq!(format(&formatted), formatted);
+}
+
+#[test]
+fn indent_width_is_configurable() {
+    let config = FormatConfig { indent_width: 2 };
+    let formatted = format_source("test.envy", "define f() :: Int = { { 1 } }", config).unwrap();
+    assert_eq!(formatted, "define f() :: Int = {\n  {\n    1\n  }\n}\n");
+}
+
+#[test]
+fn syntax_errors_are_reported() {
+    let result = format_source("test.envy", "define f( :: Int = 1", FormatConfig::default());
+    assert!(result.is_err());
+}
+
+#[test]
+fn default_values_of_pointer_types_are_spaced() {
+    let formatted = format("define first(p: Ptr<Int>=null) :: Int = 0\n");
+    assert_eq!(formatted, "define first(p: Ptr<Int> = null) :: Int = 0\n");
+    assert_eq!(format(&formatted), formatted);
+}
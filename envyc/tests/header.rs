@@ -0,0 +1,59 @@
+use envyc::{
+    environment::Environment, filter_tokens, function_table::FunctionTable, generate_header,
+    interner::Interner, lex, parse, type_check,
+};
+
+/// Type checks a program and returns the header of its exported functions.
+fn header(source: &str) -> String {
+    let mut interner = Interner::default();
+    let tokens = lex("test.envy", source.as_bytes(), &mut interner).unwrap();
+    let program = parse(filter_tokens(tokens), &interner).unwrap();
+    let typed_program = type_check(
+        program,
+        &mut Environment::default(),
+        &mut FunctionTable::default(),
+        &interner,
+    )
+    .expect("source should type check");
+    generate_header(&typed_program, "my-math", &interner)
+}
+
+#[test]
+fn headers_declare_the_exported_functions() {
+    let source = "export define scale(value: Float, flag: Boolean) :: Float = value
+define hidden(x: Int) :: Int = x
+export define first(text: Ptr<Char>, data: Ptr<Ptr<Void>>) :: Char = 'a'
+export define nothing() :: Void = {}
+";
+    assert_eq!(
+        header(source),
+        "#ifndef ENVIOUS_MY_MATH_H
+#define ENVIOUS_MY_MATH_H
+
+#include <stdbool.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern \"C\" {
+#endif
+
+double scale(double value, bool flag);
+char first(char* text, void** data);
+void nothing(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* ENVIOUS_MY_MATH_H */
+"
+    );
+}
+
+#[test]
+fn parameters_named_after_keywords_are_renamed() {
+    let source = "export define pick(int: Int, char: Char, default: Int, class: Int, bool: Boolean) :: Int = int\n";
+    assert!(header(source).contains(
+        "int64_t pick(int64_t int_, char char_, int64_t default_, int64_t class_, bool bool_);"
+    ));
+}
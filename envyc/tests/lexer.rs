@@ -0,0 +1,197 @@
+use envyc::{error::Error, filter_tokens, interner::Interner, lex, lexer::token::TokenKind};
+
+fn kinds(source: &str, interner: &mut Interner<String>) -> Vec<TokenKind> {
+    let tokens = lex("test.envy", source.as_bytes(), interner).expect("source should lex");
+    filter_tokens(tokens).map(|(_, kind)| kind).collect()
+}
+
+#[test]
+fn unicode_identifiers_are_lexed() {
+    let mut interner = Interner::default();
+    let kinds = kinds("let Δx = α_1", &mut interner);
+    assert_eq!(kinds.len(), 4);
+    match (kinds[1], kinds[3]) {
+        (TokenKind::Identifier(first), TokenKind::Identifier(second)) => {
+            assert_eq!(interner.get(first), "Δx");
+            assert_eq!(interner.get(second), "α_1");
+        }
+        _ => panic!("expected identifiers, found {:?}", kinds),
+    }
+}
+
+#[test]
+fn math_symbols_are_operator_aliases() {
+    let mut interner = Interner::default();
+    assert_eq!(
+        kinds("≤ ≥ ≠ ¬ ∧ ∨ × ÷", &mut interner),
+        vec![
+            TokenKind::LessThanEqualSign,
+            TokenKind::GreaterThanEqualSign,
+            TokenKind::ExclamationEqualSign,
+            TokenKind::Not,
+            TokenKind::And,
+            TokenKind::Or,
+            TokenKind::Star,
+            TokenKind::Slash,
+        ]
+    );
+}
+
+#[test]
+fn maps_to_is_not_an_operator() {
+    let mut interner = Interner::default();
+    let errors = lex("test.envy", "x ↦ x".as_bytes(), &mut interner).unwrap_err();
+    assert!(matches!(
+        errors.as_slice(),
+        [Error::UnrecognizedCharacter(span)] if (span.start, span.end) == (2, 5)
+    ));
+}
+
+#[test]
+fn columns_count_characters() {
+    let mut interner = Interner::default();
+    let errors = lex("test.envy", "é + ∑".as_bytes(), &mut interner).unwrap_err();
+    match errors.as_slice() {
+        [Error::UnrecognizedCharacter(span)] => {
+            assert_eq!((span.start, span.end), (5, 8));
+            assert_eq!((span.column_start, span.column_end), (5, 5));
+        }
+        errors => panic!(
+            "expected a single unrecognized character, found {:?}",
+            errors
+        ),
+    }
+}
+
+#[test]
+fn numbers_can_use_other_bases_and_separators() {
+    let mut interner = Interner::default();
+    assert_eq!(
+        kinds("0xFF 0o17 0b1010 1_000_000", &mut interner),
+        vec![
+            TokenKind::IntegerLiteral(255),
+            TokenKind::IntegerLiteral(15),
+            TokenKind::IntegerLiteral(10),
+            TokenKind::IntegerLiteral(1_000_000),
+        ]
+    );
+    assert_eq!(
+        kinds("1.5e-3 2E10 inf", &mut interner),
+        vec![
+            TokenKind::FloatLiteral(1.5e-3),
+            TokenKind::FloatLiteral(2e10),
+            TokenKind::FloatLiteral(f64::INFINITY),
+        ]
+    );
+}
+
+#[test]
+fn minus_is_never_part_of_a_number() {
+    let mut interner = Interner::default();
+    assert_eq!(
+        kinds("a-1", &mut interner)[1..],
+        [TokenKind::Minus, TokenKind::IntegerLiteral(1)]
+    );
+}
+
+#[test]
+fn numbers_end_at_the_first_byte_outside_of_the_base() {
+    let mut interner = Interner::default();
+    assert_eq!(
+        kinds("if x then 1else 2", &mut interner)[3..],
+        [
+            TokenKind::IntegerLiteral(1),
+            TokenKind::Else,
+            TokenKind::IntegerLiteral(2)
+        ]
+    );
+    assert_eq!(
+        kinds("0b102", &mut interner),
+        vec![TokenKind::IntegerLiteral(2), TokenKind::IntegerLiteral(2)]
+    );
+    assert!(matches!(
+        kinds("1e", &mut interner).as_slice(),
+        [TokenKind::IntegerLiteral(1), TokenKind::Identifier(_)]
+    ));
+}
+
+#[test]
+fn separators_outside_of_digits_are_malformed() {
+    let mut interner = Interner::default();
+    for source in &["0x", "1_", "0x_1", "1__0", "1_.5"] {
+        match lex("test.envy", source.as_bytes(), &mut interner) {
+            Err(errors) => assert!(matches!(errors.as_slice(), [Error::MalformedNumber(_)])),
+            Ok(tokens) => panic!("expected `{}` to be malformed, found {:?}", source, tokens),
+        }
+    }
+}
+
+#[test]
+fn chars_can_be_escaped() {
+    let mut interner = Interner::default();
+    assert_eq!(
+        kinds(
+            r"'\n' '\t' '\\' '\'' '\0' '\u{7e}' '\u{7f}' '~'",
+            &mut interner
+        ),
+        vec![
+            TokenKind::CharLiteral('\n'),
+            TokenKind::CharLiteral('\t'),
+            TokenKind::CharLiteral('\\'),
+            TokenKind::CharLiteral('\''),
+            TokenKind::CharLiteral('\0'),
+            TokenKind::CharLiteral('~'),
+            TokenKind::CharLiteral('\u{7f}'),
+            TokenKind::CharLiteral('~'),
+        ]
+    );
+}
+
+#[test]
+fn invalid_chars_are_reported_once() {
+    let mut interner = Interner::default();
+    let errors = lex(
+        "test.envy",
+        r"'\q' '\u{zz}' '→' 'é' '\u{80}' '\u{ff}'".as_bytes(),
+        &mut interner,
+    )
+    .unwrap_err();
+    assert!(matches!(
+        errors.as_slice(),
+        [
+            Error::InvalidEscape(_),
+            Error::InvalidEscape(_),
+            Error::CharOutOfRange(_),
+            Error::CharOutOfRange(_),
+            Error::CharOutOfRange(_),
+            Error::CharOutOfRange(_)
+        ]
+    ));
+}
+
+#[test]
+fn chars_are_limited_to_ascii() {
+    let mut interner = Interner::default();
+    let source = r"'é' '\u{e9}'";
+    let errors = lex("test.envy", source.as_bytes(), &mut interner).unwrap_err();
+    let spans = errors
+        .iter()
+        .map(|error| match error {
+            Error::CharOutOfRange(span) => &source[span.start..span.end],
+            other => panic!("unexpected error {:?}", other),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(spans, vec!["'é'", r"'\u{e9}'"]);
+}
+
+#[test]
+fn comments_are_trivia() {
+    let mut interner = Interner::default();
+    let tokens = lex("test.envy", "a / b // c / d\ne".as_bytes(), &mut interner).unwrap();
+    let comment = tokens
+        .iter()
+        .find(|(_, kind)| *kind == TokenKind::Comment)
+        .expect("expected a comment");
+    assert_eq!((comment.0.start, comment.0.end), (6, 14));
+    assert_eq!(kinds("a / b // c / d\ne", &mut interner).len(), 4);
+}
@@ -0,0 +1,205 @@
+use envyc::{
+    environment::Environment,
+    error::warning::{Lint, LintLevel, LintLevels, Warning},
+    filter_tokens,
+    function_table::FunctionTable,
+    interner::Interner,
+    lex, lint, parse, type_check,
+};
+
+/// Lints a program and returns the name of the lint of each warning, the
+/// source of its span and its level, or the codes of the errors.
+///
+/// # Arguments
+/// * `source` - The source of the program.
+/// * `levels` - The `LintLevels` given on the command line.
+fn lint_with(
+    source: &str,
+    levels: &LintLevels,
+) -> Result<Vec<(&'static str, String, LintLevel)>, Vec<&'static str>> {
+    let mut interner = Interner::default();
+    let tokens = lex("test.envy", source.as_bytes(), &mut interner).unwrap();
+    let program = parse(filter_tokens(tokens), &interner).expect("source should parse");
+    let typed_program = type_check(
+        program,
+        &mut Environment::default(),
+        &mut FunctionTable::default(),
+        &interner,
+    )
+    .expect("source should type check");
+
+    match lint(&typed_program, &interner, levels) {
+        Ok(warnings) => Ok(warnings
+            .iter()
+            .map(|(warning, level)| {
+                let span = match warning {
+                    Warning::UnusedVariable(span) | Warning::UnusedParameter(span) => *span,
+                    Warning::UnreachableCode { span, .. }
+                    | Warning::ShadowedParameter { span, .. }
+                    | Warning::ConstantCondition { span, .. } => *span,
+                };
+                (
+                    warning.lint().name(),
+                    source[span.start..span.end].to_string(),
+                    *level,
+                )
+            })
+            .collect()),
+        Err(errors) => Err(errors.iter().map(|error| error.code()).collect()),
+    }
+}
+
+/// Lints a program with the default levels and returns the name of the lint of
+/// each warning together with the source of its span.
+fn warnings(source: &str) -> Vec<(&'static str, String)> {
+    lint_with(source, &LintLevels::default())
+        .expect("source should lint")
+        .into_iter()
+        .map(|(name, span, level)| {
+            assert_eq!(level, LintLevel::Warn);
+            (name, span)
+        })
+        .collect()
+}
+
+#[test]
+fn unused_variables_are_reported_once() {
+    let source = "define f() :: Int = {
+    let unused = 1
+    let unused = 2
+    let _ignored = 3
+    let used = 4
+    used
+}
+";
+    assert_eq!(
+        warnings(source),
+        vec![("unused_variable", "unused".to_string())]
+    );
+}
+
+#[test]
+fn unused_parameters_are_reported() {
+    let source = "define f(unused: Int, used: Int, _ignored: Int) :: Int = used\n";
+    assert_eq!(
+        warnings(source),
+        vec![("unused_parameter", "unused".to_string())]
+    );
+}
+
+#[test]
+fn code_after_return_is_unreachable() {
+    let source = "define f(x: Int) :: Int = {
+    return x
+    let y = x + 1
+    y
+}
+";
+    assert_eq!(
+        warnings(source),
+        vec![("unreachable_code", "let y = x + 1\n    y".to_string())]
+    );
+
+    // A `return` at the end of a block is not followed by anything.
+    assert!(warnings("define f(x: Int) :: Int = {\n    return x\n}\n").is_empty());
+}
+
+#[test]
+fn assignments_to_parameters_are_reported() {
+    let source = "define f(x: Int) :: Int = {
+    let x = x + 1
+    x
+}
+";
+    assert_eq!(
+        warnings(source),
+        vec![("shadowed_parameter", "x".to_string())]
+    );
+}
+
+#[test]
+fn constant_conditions_are_reported() {
+    let source = "const DEBUG: Boolean = false
+define f(x: Int) :: Int = {
+    if DEBUG then 1 else 2
+    if 1 > 2 then 3 else 4
+    while false {}
+    while true {
+        return x
+    }
+    if x > 0 then x else 0
+}
+";
+    assert_eq!(
+        warnings(source),
+        vec![
+            ("constant_condition", "DEBUG".to_string()),
+            ("constant_condition", "1 > 2".to_string()),
+            ("constant_condition", "false".to_string()),
+        ]
+    );
+
+    // Applications are never constant, even if they could be evaluated.
+    let source = "define yes() :: Boolean = true
+define f() :: Int = if yes() then 1 else 2
+";
+    assert!(warnings(source).is_empty());
+}
+
+#[test]
+fn allow_attributes_silence_lints_of_their_item() {
+    let source = "#allow(unused_variable)
+define f() :: Int = {
+    let x = 1
+    0
+}
+define g() :: Int = {
+    let y = 1
+    0
+}
+#allow(warnings)
+define h(unused: Int) :: Int = {
+    let z = 1
+    if true then 0 else 1
+}
+";
+    assert_eq!(warnings(source), vec![("unused_variable", "y".to_string())]);
+}
+
+#[test]
+fn unknown_attributes_and_lints_are_errors() {
+    assert_eq!(
+        lint_with(
+            "#allow(unused_variables)\ndefine f() :: Int = 0\n",
+            &LintLevels::default()
+        ),
+        Err(vec!["E0031"])
+    );
+    assert_eq!(
+        lint_with(
+            "#inline(always)\ndefine f() :: Int = 0\n",
+            &LintLevels::default()
+        ),
+        Err(vec!["E0030"])
+    );
+}
+
+#[test]
+fn levels_change_how_lints_are_reported() {
+    let source = "define f(unused: Int) :: Int = {
+    let x = 1
+    0
+}
+";
+    let mut levels = LintLevels::default();
+    levels.set_all(LintLevel::Deny);
+    levels.set(Lint::UnusedParameter, LintLevel::Allow);
+    assert_eq!(
+        lint_with(source, &levels),
+        Ok(vec![("unused_variable", "x".to_string(), LintLevel::Deny)])
+    );
+
+    // An `#allow` attribute silences a lint even if it is denied.
+    let source = format!("#allow(unused_variable)\n{}", source);
+    assert_eq!(lint_with(&source, &levels), Ok(vec![]));
+}
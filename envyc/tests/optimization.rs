@@ -0,0 +1,197 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use envyc::{
+    compile, environment::Environment, filter_tokens, function_table::FunctionTable,
+    interner::Interner, lex, link_time_optimize, parse, type_check, Config, Emit, OptLevel,
+    TargetOptions,
+};
+
+const SQUARE: &str = "define square(x: Int) :: Int = x * x
+define sixteen() :: Int = square(4)
+";
+
+// `mix` is too large to be inlined when optimizing for size, but small
+// enough to be inlined with the thresholds of `O2` and `O3`.
+const MIX: &str = "define mix(x: Int) :: Int = {
+    let a = x * 3 + 7
+    let b = a * a - x
+    let c = b / 5 + a * 11
+    let d = c * c + b * 13
+    let e = d / 7 - c * 17 + a
+    let f = e * e + d * 19 - b
+    let g = f / 3 + e * 23 - c
+    let h = g * g - f * 29 + d
+    let i = h / 11 + g * 31 - e
+    i * i - h * 37 + f
+}
+define apply(y: Int) :: Int = mix(y)
+";
+
+/// Compiles a source with an optimization level, writes the given outputs,
+/// and returns the IR of the optimized module.
+///
+/// # Arguments
+/// * `source` - The source of the file.
+/// * `name` - The name of the module.
+/// * `opt_level` - The optimization level.
+/// * `outputs` - The artifacts to write.
+fn compile_ir(
+    source: &str,
+    name: &str,
+    opt_level: OptLevel,
+    outputs: Vec<(Emit, &Path)>,
+) -> String {
+    let mut interner = Interner::default();
+    let tokens = lex(name, source.as_bytes(), &mut interner).unwrap();
+    let program = parse(filter_tokens(tokens), &interner).unwrap();
+    let typed_program = type_check(
+        program,
+        &mut Environment::default(),
+        &mut FunctionTable::default(),
+        &interner,
+    )
+    .unwrap();
+
+    let config = Config {
+        outputs,
+        position_independent: false,
+        opt_level,
+        target: TargetOptions::default(),
+        debug_info: false,
+    };
+    compile(&typed_program, name, &mut interner, Some(config)).expect("source should compile")
+}
+
+const LIBRARY: &str = "define twice(x: Int) :: Int = x * 2
+export define quadruple(x: Int) :: Int = twice(twice(x))
+";
+
+const MAIN: &str = "extern quadruple(Int) :: Int
+extern report(Int) :: Void
+define main() :: Void = report(quadruple(3))
+";
+
+/// Compiles the library and the main file into bitcode, optimizes them together
+/// with an optimization level and returns the IR of the linked module.
+///
+/// # Arguments
+/// * `name` - The name of the test, which is unique to it.
+/// * `opt_level` - The optimization level.
+/// * `internalize` - Whether every function except `main` is made private.
+fn link_time_optimized_ir(name: &str, opt_level: OptLevel, internalize: bool) -> String {
+    let directory = std::env::temp_dir().join(format!("envious-{}-{}", name, std::process::id()));
+    fs::create_dir_all(&directory).unwrap();
+    let library_path = directory.join("library.bc");
+    let main_path = directory.join("main.bc");
+    compile_ir(
+        LIBRARY,
+        "library",
+        opt_level,
+        vec![(Emit::LlvmBc, &library_path)],
+    );
+    compile_ir(MAIN, "main", opt_level, vec![(Emit::LlvmBc, &main_path)]);
+
+    let ir_path = directory.join("linked.ll");
+    let config = Config {
+        outputs: vec![(Emit::LlvmIr, &ir_path)],
+        position_independent: false,
+        opt_level,
+        target: TargetOptions::default(),
+        debug_info: false,
+    };
+    link_time_optimize(&[&library_path, &main_path], "linked", config, internalize)
+        .expect("bitcode should link");
+
+    let ir = fs::read_to_string(&ir_path).unwrap();
+    fs::remove_dir_all(directory).unwrap();
+    ir
+}
+
+#[test]
+fn files_are_optimized_by_default() {
+    assert_eq!(OptLevel::default(), OptLevel::O2);
+    let ir = compile_ir(SQUARE, "square", OptLevel::default(), vec![]);
+    assert!(ir.contains("ret i64 16"), "{}", ir);
+}
+
+#[test]
+fn levels_are_named_as_in_the_arguments() {
+    for level in OptLevel::ALL {
+        assert_eq!(OptLevel::from_name(level.name()), Some(level));
+    }
+    assert_eq!(OptLevel::from_name("s"), Some(OptLevel::Os));
+    assert_eq!(OptLevel::from_name("4"), None);
+}
+
+#[test]
+fn only_optimized_levels_simplify_functions() {
+    let ir = compile_ir(SQUARE, "square", OptLevel::O0, vec![]);
+    assert!(ir.contains("alloca i64"), "{}", ir);
+    assert!(ir.contains("call i64 @square(i64 4)"), "{}", ir);
+
+    // `O1` promotes the variables into registers, but does not inline.
+    let ir = compile_ir(SQUARE, "square", OptLevel::O1, vec![]);
+    assert!(!ir.contains("alloca"), "{}", ir);
+    assert!(ir.contains("call i64 @square(i64 4)"), "{}", ir);
+
+    for level in [OptLevel::O2, OptLevel::O3, OptLevel::Os, OptLevel::Oz] {
+        let ir = compile_ir(SQUARE, "square", level, vec![]);
+        assert!(ir.contains("ret i64 16"), "{:?}: {}", level, ir);
+    }
+}
+
+#[test]
+fn the_inliner_threshold_depends_on_the_level() {
+    for (level, inlined) in [
+        (OptLevel::O1, false),
+        (OptLevel::O2, true),
+        (OptLevel::O3, true),
+        (OptLevel::Os, false),
+        (OptLevel::Oz, false),
+    ] {
+        let ir = compile_ir(MIX, "mix", level, vec![]);
+        assert_eq!(
+            !ir.contains("call i64 @mix"),
+            inlined,
+            "{:?}: {}",
+            level,
+            ir
+        );
+    }
+}
+
+#[test]
+fn outputs_are_written_after_optimizing() {
+    let directory =
+        std::env::temp_dir().join(format!("envious-optimization-{}", std::process::id()));
+    fs::create_dir_all(&directory).unwrap();
+    let ir_path: PathBuf = directory.join("square.ll");
+    let ir = compile_ir(
+        SQUARE,
+        "square",
+        OptLevel::O3,
+        vec![(Emit::LlvmIr, &ir_path)],
+    );
+    assert_eq!(fs::read_to_string(&ir_path).unwrap(), ir);
+    fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn link_time_optimization_inlines_functions_across_files() {
+    let ir = link_time_optimized_ir("lto-internalize", OptLevel::O2, true);
+    assert!(ir.contains("call void @report(i64 12)"), "{}", ir);
+    // Every function except `main` is private, so the inlined ones are removed.
+    assert!(ir.contains("define hidden void @main()"), "{}", ir);
+    assert!(!ir.contains("@quadruple"), "{}", ir);
+    assert!(!ir.contains("@twice"), "{}", ir);
+}
+
+#[test]
+fn libraries_keep_their_functions_after_link_time_optimization() {
+    let ir = link_time_optimized_ir("lto-library", OptLevel::O2, false);
+    assert!(ir.contains("call void @report(i64 12)"), "{}", ir);
+    assert!(ir.contains("define i64 @quadruple(i64 %x)"), "{}", ir);
+}
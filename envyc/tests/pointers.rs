@@ -0,0 +1,210 @@
+use envyc::{
+    compile, environment::Environment, error::Error, filter_tokens, function_table::FunctionTable,
+    interner::Interner, lex, parse, type_check,
+};
+
+/// Type checks a program and returns the code of each error together with
+/// the source of its span.
+fn check(source: &str) -> Vec<(&'static str, String)> {
+    let mut interner = Interner::default();
+    let tokens = lex("test.envy", source.as_bytes(), &mut interner).unwrap();
+    let program = parse(filter_tokens(tokens), &interner).expect("source should parse");
+    let errors = match type_check(
+        program,
+        &mut Environment::default(),
+        &mut FunctionTable::default(),
+        &interner,
+    ) {
+        Ok(_) => return vec![],
+        Err(errors) => errors,
+    };
+
+    errors
+        .iter()
+        .map(|error| {
+            let span = match error {
+                Error::UnsafeOperation(span) | Error::IllegalAddressOf(span) => *span,
+                Error::TypeMismatch { span, .. } => *span,
+                Error::UnsupportedOperation { operation_span, .. } => *operation_span,
+                other => panic!("unexpected error {:?}", other),
+            };
+            (error.code(), source[span.start..span.end].to_string())
+        })
+        .collect()
+}
+
+/// Parses a program and returns the code of each syntax error together with
+/// the source of its span.
+fn syntax_errors(source: &str) -> Vec<(&'static str, String)> {
+    let mut interner = Interner::default();
+    let tokens = lex("test.envy", source.as_bytes(), &mut interner).unwrap();
+    match parse(filter_tokens(tokens), &interner) {
+        Ok(_) => vec![],
+        Err(errors) => errors
+            .iter()
+            .map(|error| {
+                let span = match error {
+                    Error::AmbiguousStar(span) => *span,
+                    other => panic!("unexpected error {:?}", other),
+                };
+                (error.code(), source[span.start..span.end].to_string())
+            })
+            .collect(),
+    }
+}
+
+/// Compiles a program and returns the IR of the module.
+fn compile_ir(source: &str) -> String {
+    let mut interner = Interner::default();
+    let tokens = lex("test.envy", source.as_bytes(), &mut interner).unwrap();
+    let program = parse(filter_tokens(tokens), &interner).unwrap();
+    let typed_program = type_check(
+        program,
+        &mut Environment::default(),
+        &mut FunctionTable::default(),
+        &interner,
+    )
+    .expect("source should type check");
+    compile(&typed_program, "test", &mut interner, None).expect("source should compile")
+}
+
+#[test]
+fn pointer_types_are_compiled_to_llvm_pointers() {
+    let ir = compile_ir("define first(p: Ptr<Ptr<Char>>) :: Ptr<Char> = unsafe { *p }\n");
+    assert!(ir.contains("define hidden i8* @first(i8** %p)"), "{}", ir);
+    assert!(ir.contains("%deref = load i8*, i8** %p2"), "{}", ir);
+}
+
+#[test]
+fn void_pointers_coerce_to_every_pointer() {
+    let ir = compile_ir(
+        "define cast(p: Ptr<Void>) :: Ptr<Int> = p
+define nothing() :: Ptr<Char> = unsafe { null }
+",
+    );
+    assert!(ir.contains("%ptrcast = bitcast i8* %p2 to i64*"), "{}", ir);
+    assert!(ir.contains("phi i8* [ null, %entry ]"), "{}", ir);
+
+    // Only `Ptr<Void>` is opaque, other pointers do not convert to each other.
+    assert_eq!(
+        check("define cast(p: Ptr<Char>) :: Ptr<Int> = p\n"),
+        vec![("E0009", "p".to_string())]
+    );
+}
+
+#[test]
+fn pointer_operations_are_only_allowed_in_unsafe_blocks() {
+    let cases = [
+        ("define read(p: Ptr<Int>) :: Int = *p\n", "*p"),
+        ("define address() :: Ptr<Int> = { let x = 1\n&x }\n", "&x"),
+        ("define nothing() :: Ptr<Int> = null\n", "null"),
+        (
+            "define same(p: Ptr<Int>, q: Ptr<Int>) :: Boolean = p = q\n",
+            "p = q",
+        ),
+        (
+            "define different(p: Ptr<Int>) :: Boolean = p != null\n",
+            "null",
+        ),
+        (
+            "define write(p: Ptr<Int>) :: Void = let *p = 1\n",
+            "let *p = 1",
+        ),
+    ];
+    for (source, span) in cases {
+        assert_eq!(
+            check(source),
+            vec![("E0019", span.to_string())],
+            "{}",
+            source
+        );
+    }
+}
+
+#[test]
+fn pointer_operations_are_allowed_in_unsafe_blocks() {
+    let source = "define read(p: Ptr<Int>) :: Int = unsafe { *p }
+define address() :: Ptr<Int> = { let x = 1\nunsafe { &x } }
+define same(p: Ptr<Int>, q: Ptr<Int>) :: Boolean = unsafe { p = q }
+define is_null(p: Ptr<Int>) :: Boolean = unsafe { p != null }
+define write(p: Ptr<Int>) :: Void = unsafe { let *p = 1 }
+";
+    assert!(check(source).is_empty());
+}
+
+#[test]
+fn comparing_pointers_compares_their_addresses() {
+    let ir = compile_ir("define is_null(p: Ptr<Int>) :: Boolean = unsafe { p = null }\n");
+    assert!(
+        ir.contains("%ptrtoint = ptrtoint i64* %p2 to i64"),
+        "{}",
+        ir
+    );
+    assert!(ir.contains("%ptrcmp = icmp eq i64 %ptrtoint, 0"), "{}", ir);
+}
+
+#[test]
+fn values_are_stored_through_pointers() {
+    let ir =
+        compile_ir("define write(p: Ptr<Int>, value: Int) :: Void = unsafe { let *p = value }\n");
+    assert!(ir.contains("store i64 %value4, i64* %p3"), "{}", ir);
+
+    let ir = compile_ir("define clear(p: Ptr<Ptr<Int>>) :: Void = unsafe { let *p = null }\n");
+    assert!(ir.contains("store i64* null, i64** %p2"), "{}", ir);
+
+    // The pointer of a store binds like the operand of a unary operation,
+    // so `let **p = 0` stores through the pointer that `p` points to.
+    let ir = compile_ir("define reset(p: Ptr<Ptr<Int>>) :: Void = unsafe { let **p = 0 }\n");
+    assert!(ir.contains("%deref = load i64*, i64** %p2"), "{}", ir);
+    assert!(ir.contains("store i64 0, i64* %deref"), "{}", ir);
+}
+
+#[test]
+fn stores_are_type_checked() {
+    assert_eq!(
+        check("define write(p: Ptr<Int>) :: Void = unsafe { let *p = 1.0 }\n"),
+        vec![("E0009", "1.0".to_string())]
+    );
+    assert_eq!(
+        check("define write(p: Int) :: Void = unsafe { let *p = 1 }\n"),
+        vec![("E0008", "let *p = 1".to_string())]
+    );
+    assert_eq!(
+        check("define write(p: Ptr<Void>) :: Void = unsafe { let *p = 1 }\n"),
+        vec![("E0008", "let *p = 1".to_string())]
+    );
+}
+
+#[test]
+fn a_star_at_the_start_of_a_line_is_ambiguous() {
+    let source = "define read(x: Int, p: Ptr<Int>) :: Int = unsafe {
+    let a = x
+    *p
+}
+";
+    assert_eq!(syntax_errors(source), vec![("E0038", "*".to_string())]);
+}
+
+#[test]
+fn unambiguous_stars_are_parsed() {
+    let source = "define read(x: Int, p: Ptr<Int>) :: Int = unsafe {
+    let a = x
+    { *p }
+}
+define area(width: Int, height: Int) :: Int = width *
+    height
+";
+    assert!(syntax_errors(source).is_empty());
+    assert!(check(source).is_empty());
+}
+
+#[test]
+fn default_values_may_follow_pointer_types_without_a_space() {
+    let source = "define first(p: Ptr<Int>=null, q: Ptr<Ptr<Int>>=null) :: Int = 0\n";
+    assert!(syntax_errors(source).is_empty());
+    // The defaults are parsed, and `null` is only allowed in unsafe blocks.
+    assert_eq!(
+        check(source),
+        vec![("E0019", "null".to_string()), ("E0019", "null".to_string())]
+    );
+}
@@ -0,0 +1,80 @@
+use std::{fs, path::Path};
+
+use envyc::{
+    environment::Environment,
+    filter_tokens,
+    function_table::FunctionTable,
+    interner::Interner,
+    lex, parse,
+    printer::{print_tokens, Printer},
+    type_check,
+};
+
+fn print_ast(source: &str) -> String {
+    let mut interner = Interner::default();
+    let tokens = lex("test.envy", source.as_bytes(), &mut interner).unwrap();
+    let program = parse(filter_tokens(tokens), &interner).expect("source should parse");
+    Printer::new(&interner, 4).print_program(&program)
+}
+
+fn print_typed_ast(source: &str) -> String {
+    let mut interner = Interner::default();
+    let tokens = lex("test.envy", source.as_bytes(), &mut interner).unwrap();
+    let program = parse(filter_tokens(tokens), &interner).unwrap();
+    let typed_program = type_check(
+        program,
+        &mut Environment::default(),
+        &mut FunctionTable::default(),
+        &interner,
+    )
+    .expect("source should type check");
+    Printer::new(&interner, 4).print_typed_program(&typed_program)
+}
+
+#[test]
+fn tokens_have_their_spans() {
+    let mut interner = Interner::default();
+    let tokens = lex("test.envy", b"let x = 1", &mut interner).unwrap();
+    let printed = print_tokens(&tokens, &interner);
+    let lines = printed.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 4);
+    assert!(lines[0].starts_with("1:1-1:3 "), "{}", lines[0]);
+    assert!(lines[1].contains("`x`"), "{}", lines[1]);
+}
+
+#[test]
+fn printed_ast_parses_to_the_same_ast() {
+    let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("../standard_library");
+    for entry in fs::read_dir(directory).unwrap() {
+        let path = entry.unwrap().path();
+        if path
+            .extension()
+            .is_some_and(|extension| extension == "envy")
+        {
+            let printed = print_ast(&fs::read_to_string(&path).unwrap());
+            assert_eq!(print_ast(&printed), printed, "`{}`", path.display());
+        }
+    }
+}
+
+#[test]
+fn printed_ast_makes_precedence_explicit() {
+    let printed = print_ast("define f(a: Int) :: Int = a + a * 2 - -a\n");
+    assert_eq!(
+        printed,
+        "define f(a: Int) :: Int = ((a + (a * 2)) - (-a))\n"
+    );
+}
+
+#[test]
+fn typed_ast_has_the_types_of_expressions() {
+    let printed = print_typed_ast("define f(a: Int) :: Int = a * 2\n");
+    assert_eq!(printed, "define f(a: Int) :: Int = ((a: Int) * 2: Int)\n");
+}
+
+#[test]
+fn a_sign_at_the_start_of_a_line_starts_an_expression() {
+    let printed = print_ast("define f() :: Int = { let x = 5\n    -1 }\n");
+    assert_eq!(printed, "define f() :: Int = {\n    let x = 5\n    -1\n}\n");
+    assert_eq!(print_ast(&printed), printed);
+}
@@ -0,0 +1,186 @@
+use envyc::{
+    environment::Environment, error::Error, filter_tokens, function_table::FunctionTable,
+    interner::Interner, lex, parse_with_recovery, type_check,
+};
+
+/// The code of an error together with the source of its span.
+type Reported = (&'static str, String);
+
+/// Parses a program while recovering from syntax errors and type checks the
+/// result. Returns the codes and the source of the spans of the syntax errors
+/// and of the type errors.
+fn check(source: &str) -> (Vec<Reported>, Vec<Reported>) {
+    let mut interner = Interner::default();
+    let tokens = lex("test.envy", source.as_bytes(), &mut interner).expect("source should lex");
+    let (program, syntax_errors) = parse_with_recovery(filter_tokens(tokens), &interner);
+    let type_errors = match type_check(
+        program,
+        &mut Environment::default(),
+        &mut FunctionTable::default(),
+        &interner,
+    ) {
+        Ok(_) => vec![],
+        Err(errors) => {
+            assert!(!errors.is_empty(), "type_check returned no errors");
+            errors
+        }
+    };
+
+    let describe = |errors: Vec<Error>| {
+        errors
+            .iter()
+            .map(|error| {
+                let span = match error {
+                    Error::UnexpectedEndOfInput(span) | Error::IllegalType(span) => *span,
+                    Error::ExpectedPrefixExpression { span, .. }
+                    | Error::ExpectedKind { span, .. }
+                    | Error::TypeMismatch { span, .. } => *span,
+                    Error::ConflictingType { second_span, .. } => *second_span,
+                    Error::UnsupportedOperation { operation_span, .. } => *operation_span,
+                    other => panic!("unexpected error {:?}", other),
+                };
+                (error.code(), source[span.start..span.end].to_string())
+            })
+            .collect::<Vec<_>>()
+    };
+
+    (describe(syntax_errors), describe(type_errors))
+}
+
+#[test]
+fn every_broken_item_is_reported() {
+    let source = "define a() :: Int = 1 +
+define b() :: Int = )
+const C: Int =
+define d() :: Int = 1
+";
+    let (syntax_errors, type_errors) = check(source);
+    assert_eq!(
+        syntax_errors,
+        vec![
+            ("E0006", "define".to_string()),
+            ("E0006", ")".to_string()),
+            ("E0006", "define".to_string()),
+        ]
+    );
+    assert!(type_errors.is_empty(), "{:?}", type_errors);
+}
+
+#[test]
+fn every_broken_expression_of_a_block_is_reported() {
+    let source = "define f() :: Int = {
+    let x = )
+    let y = 2 / / 3
+    let z = ,
+    x
+}
+";
+    let (syntax_errors, type_errors) = check(source);
+    assert_eq!(
+        syntax_errors,
+        vec![
+            ("E0006", ")".to_string()),
+            ("E0006", "/".to_string()),
+            ("E0006", ",".to_string()),
+        ]
+    );
+    assert!(type_errors.is_empty(), "{:?}", type_errors);
+}
+
+#[test]
+fn type_errors_next_to_syntax_errors_are_reported() {
+    // A placeholder converts to any type, but only the expressions that use
+    // the broken expression are exempt from type errors.
+    let source = "define broken() :: Int = {
+    let x = )
+    let y: Int = 1.0
+    x + 1
+}
+define wrong() :: Boolean = 1 + true
+";
+    let (syntax_errors, type_errors) = check(source);
+    assert_eq!(syntax_errors, vec![("E0006", ")".to_string())]);
+    assert_eq!(
+        type_errors,
+        vec![
+            ("E0010", "1.0".to_string()),
+            ("E0008", "1 + true".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn functions_are_checked_next_to_wrong_constants_and_defaults() {
+    let source = "const A: Int = 1.0
+const P: Ptr<Int> = 0
+define f(x: Int = true) :: Int = x
+define g() :: Int = f() + A
+define h() :: Float = 1
+";
+    let (syntax_errors, type_errors) = check(source);
+    assert!(syntax_errors.is_empty(), "{:?}", syntax_errors);
+    // `g` uses the wrong constant and leaves out the wrong default without errors.
+    assert_eq!(
+        type_errors,
+        vec![
+            ("E0012", "Ptr<Int>".to_string()),
+            ("E0009", "true".to_string()),
+            ("E0009", "1.0".to_string()),
+            ("E0009", "0".to_string()),
+            ("E0009", "1".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn constants_that_depend_on_syntax_errors_are_not_reported_twice() {
+    let source = "const A: Int = 1 +
+const B: Int = A * 2
+";
+    let (syntax_errors, type_errors) = check(source);
+    assert_eq!(syntax_errors, vec![("E0006", "const".to_string())]);
+    assert!(type_errors.is_empty(), "{:?}", type_errors);
+
+    // The other constants are still checked and evaluated.
+    let source = "const A: Int = 1 +
+const B: Int = A * 2
+const C: Int = 1.0
+";
+    let (syntax_errors, type_errors) = check(source);
+    assert_eq!(syntax_errors, vec![("E0006", "const".to_string())]);
+    assert_eq!(type_errors, vec![("E0009", "1.0".to_string())]);
+}
+
+#[test]
+fn recovery_terminates_on_every_prefix_of_a_program() {
+    let source = "#allow(unused_variable)
+const LIMIT: Int = 10
+extern printi(Int, ...) :: Void
+export define clamp(value: Int, high: Int = LIMIT) :: Int = if value > high then high else value
+define main() :: Int = {
+    let x: Int = clamp(3, high: 2 * 4)
+    while x > 0 { let x = x - 1 }
+    unsafe { let p = &x }
+    return x
+}
+";
+    for (end, _) in source.char_indices() {
+        let prefix = &source[..end];
+        let mut interner = Interner::default();
+        let tokens = match lex("test.envy", prefix.as_bytes(), &mut interner) {
+            Ok(tokens) => tokens,
+            Err(_) => continue,
+        };
+
+        let (program, _) = parse_with_recovery(filter_tokens(tokens), &interner);
+        let checked = type_check(
+            program,
+            &mut Environment::default(),
+            &mut FunctionTable::default(),
+            &interner,
+        );
+        if let Err(errors) = checked {
+            assert!(!errors.is_empty(), "no errors for {:?}", prefix);
+        }
+    }
+}
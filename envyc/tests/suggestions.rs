@@ -0,0 +1,24 @@
+use envyc::error::suggestion::closest;
+
+#[test]
+fn typos_suggest_the_closest_name() {
+    let candidates = ["count", "counter", "total"];
+    assert_eq!(closest("coutn", candidates.iter().copied()), Some("count"));
+    assert_eq!(closest("totl", candidates.iter().copied()), Some("total"));
+}
+
+#[test]
+fn prefixes_suggest_keywords() {
+    let keywords = ["Void", "Int", "Float", "Boolean", "Char", "Ptr"];
+    assert_eq!(closest("Integer", keywords.iter().copied()), Some("Int"));
+    assert_eq!(
+        closest("defin", ["define", "extern"].iter().copied()),
+        Some("define")
+    );
+}
+
+#[test]
+fn unrelated_names_are_not_suggested() {
+    assert_eq!(closest("x", ["y", "z"].iter().copied()), None);
+    assert_eq!(closest("width", ["height", "depth"].iter().copied()), None);
+}
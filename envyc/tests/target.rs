@@ -0,0 +1,87 @@
+use std::{fs, path::PathBuf};
+
+use envyc::{
+    check_target, compile, environment::Environment, filter_tokens, function_table::FunctionTable,
+    interner::Interner, lex, parse, type_check, Config, Emit, OptLevel, TargetOptions,
+};
+
+const SOURCE: &str = "define square(number: Int) :: Int = number * number\n";
+
+// The values of `e_machine` in the ELF header.
+const EM_X86_64: u16 = 62;
+const EM_AARCH64: u16 = 183;
+
+fn target(triple: &str) -> TargetOptions {
+    TargetOptions {
+        triple: Some(triple.to_string()),
+        ..TargetOptions::default()
+    }
+}
+
+/// Compiles `SOURCE` into an object file for a target and returns its contents.
+fn compile_object(name: &str, target: TargetOptions) -> Vec<u8> {
+    let directory = std::env::temp_dir().join(format!("envious-target-{}", std::process::id()));
+    fs::create_dir_all(&directory).unwrap();
+    let object_path: PathBuf = directory.join(format!("{}.o", name));
+
+    let mut interner = Interner::default();
+    let tokens = lex("test.envy", SOURCE.as_bytes(), &mut interner).unwrap();
+    let program = parse(filter_tokens(tokens), &interner).unwrap();
+    let typed_program = type_check(
+        program,
+        &mut Environment::default(),
+        &mut FunctionTable::default(),
+        &interner,
+    )
+    .unwrap();
+
+    let config = Config {
+        outputs: vec![(Emit::Obj, &object_path)],
+        position_independent: false,
+        opt_level: OptLevel::O2,
+        target,
+        debug_info: false,
+    };
+    compile(&typed_program, name, &mut interner, Some(config)).expect("source should compile");
+
+    let object = fs::read(&object_path).unwrap();
+    fs::remove_file(&object_path).unwrap();
+    object
+}
+
+fn elf_machine(object: &[u8]) -> u16 {
+    assert_eq!(&object[..4], b"\x7fELF", "the object is not an ELF file");
+    u16::from_le_bytes([object[18], object[19]])
+}
+
+#[test]
+fn compiles_for_x86_64_linux() {
+    let object = compile_object("x86_64", target("x86_64-unknown-linux-gnu"));
+    assert_eq!(elf_machine(&object), EM_X86_64);
+}
+
+#[test]
+fn compiles_for_aarch64_linux() {
+    let object = compile_object("aarch64", target("aarch64-unknown-linux-gnu"));
+    assert_eq!(elf_machine(&object), EM_AARCH64);
+}
+
+#[test]
+fn compiles_for_a_cpu_with_features() {
+    let object = compile_object(
+        "skylake",
+        TargetOptions {
+            triple: Some("x86_64-unknown-linux-gnu".to_string()),
+            cpu: "skylake".to_string(),
+            features: "+avx2".to_string(),
+        },
+    );
+    assert_eq!(elf_machine(&object), EM_X86_64);
+}
+
+#[test]
+fn unknown_triples_are_invalid_targets() {
+    let errors = check_target(&target("potato-unknown-nowhere")).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].code(), "E0037");
+}
@@ -0,0 +1,93 @@
+use envyc::{
+    compile, environment::Environment, error::Error, filter_tokens, function_table::FunctionTable,
+    interner::Interner, lex, parse, type_check,
+};
+
+const PRINTF: &str = "extern printf(Ptr<Char>, ...) :: Int\n";
+
+/// Type checks a function after the declaration of `printf` and returns the codes of its errors.
+fn check(function: &str) -> Vec<&'static str> {
+    let source = format!("{}{}\n", PRINTF, function);
+    let mut interner = Interner::default();
+    let tokens = lex("test.envy", source.as_bytes(), &mut interner).unwrap();
+    let program = parse(filter_tokens(tokens), &interner).expect("source should parse");
+    type_check(
+        program,
+        &mut Environment::default(),
+        &mut FunctionTable::default(),
+        &interner,
+    )
+    .err()
+    .unwrap_or_default()
+    .iter()
+    .map(Error::code)
+    .collect()
+}
+
+/// Compiles a function after the declaration of `printf` and returns the IR of the module.
+fn compile_ir(function: &str) -> String {
+    let source = format!("{}{}\n", PRINTF, function);
+    let mut interner = Interner::default();
+    let tokens = lex("test.envy", source.as_bytes(), &mut interner).unwrap();
+    let program = parse(filter_tokens(tokens), &interner).unwrap();
+    let typed_program = type_check(
+        program,
+        &mut Environment::default(),
+        &mut FunctionTable::default(),
+        &interner,
+    )
+    .expect("source should type check");
+    compile(&typed_program, "test", &mut interner, None).expect("source should compile")
+}
+
+#[test]
+fn variadic_externs_are_declared_as_variadic() {
+    let ir = compile_ir("define hello(format: Ptr<Char>) :: Int = printf(format)");
+    assert!(ir.contains("declare i64 @printf(i8*, ...)"), "{}", ir);
+}
+
+#[test]
+fn variadic_arguments_get_the_default_argument_promotions() {
+    let ir = compile_ir(
+        "define show(format: Ptr<Char>, flag: Boolean, letter: Char, count: Int, ratio: Float) :: Int = printf(format, flag, letter, count, ratio)",
+    );
+    assert!(
+        ir.contains("%boolpromote = zext i1 %flag7 to i32"),
+        "{}",
+        ir
+    );
+    assert!(
+        ir.contains("%charpromote = sext i8 %letter8 to i32"),
+        "{}",
+        ir
+    );
+    assert!(
+        ir.contains(
+            "@printf(i8* %format6, i32 %boolpromote, i32 %charpromote, i64 %count9, double %ratio10)"
+        ),
+        "{}",
+        ir
+    );
+}
+
+#[test]
+fn only_the_fixed_parameters_are_checked() {
+    assert!(check("define ok(format: Ptr<Char>) :: Int = printf(format, 1, 2.0, 'c')").is_empty());
+
+    // The format is missing, or is an `Int` instead of a `Ptr<Char>`.
+    assert_eq!(check("define missing() :: Int = printf()"), vec!["E0018"]);
+    assert_eq!(check("define wrong() :: Int = printf(1)"), vec!["E0009"]);
+}
+
+#[test]
+fn c_pointer_syntax_is_not_a_type() {
+    // `Char*` in C is written `Ptr<Char>` in Envious.
+    let mut interner = Interner::default();
+    let tokens = lex(
+        "test.envy",
+        b"extern printf(Char*, ...) :: Int\n",
+        &mut interner,
+    )
+    .unwrap();
+    assert!(parse(filter_tokens(tokens), &interner).is_err());
+}